@@ -22,6 +22,7 @@
     APIBackend
     tweezer_devices
     emulator_devices
+    interaction_models
 
 """
 
@@ -30,5 +31,6 @@ from .api_devices import *  # type: ignore
 from .pragma_operations import *  # type: ignore
 from .qryd_devices import *  # type: ignore
 from .tweezer_devices import *  # type: ignore
+from .interaction_models import *  # type: ignore
 
 devices = qryd_devices  # type: ignore
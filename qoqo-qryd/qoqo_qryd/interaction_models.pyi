@@ -0,0 +1,125 @@
+# This is an auto generated file containing only the documentation.
+# You can find the full implementation on this page:
+# https://github.com/HQSquantumsimulations/qoqo_qryd
+
+"""
+Interaction models for tweezer devices.
+
+.. autosummary::
+   :toctree: generated/
+
+   CutoffInteractionModel
+   BlockadeInteractionModel
+
+"""
+
+from typing import Optional, Dict, Tuple
+
+class CutoffInteractionModel:
+    """
+    Interaction model with a hard distance cut-off and a gate time increasing with the square of the distance.
+
+    Only supports PhaseShiftedControlledZ and PhaseShiftedControlledPhase gates.
+
+    Args:
+        cutoff (float): The distance in micrometers above which two-qubit gates are not possible.
+        gate_time_coefficient (float): The gate time per squared distance.
+    """
+
+    def __init__(self, cutoff: float, gate_time_coefficient: float):
+        return
+
+    def two_qubit_gate_time(self, hqslang: str, distance: float) -> Optional[float]:
+        """
+        Returns the gate time of a two-qubit gate between tweezers at a given distance.
+
+        Args:
+            hqslang (str): The hqslang name of the two-qubit gate.
+            distance (float): The distance between the tweezers in micrometers.
+
+        Returns:
+            Optional[float]: The gate time, None if the gate is not available at the given distance.
+        """
+
+class BlockadeInteractionModel:
+    """
+    Interaction model based on the Rydberg blockade with a van der Waals interaction C6 / r^6.
+
+    A gate is available if the tweezers are within the blockade radius. The imperfect blockade
+    lengthens the gate time and reduces the fidelity with the leakage (Ω / V(r))^2.
+
+    Args:
+        c6_coefficient (float): The C6 coefficient of the van der Waals interaction in MHz µm^6.
+        rabi_frequency (float): The Rabi frequency of the Rydberg excitation in MHz.
+        gate_times (Dict[str, float]): The gate time of each supported two- and three-qubit gate in the limit of perfect blockade.
+        gate_fidelity (float): The gate fidelity in the limit of perfect blockade.
+
+    Raises:
+        ValueError: A parameter is out of range.
+    """
+
+    def __init__(
+        self,
+        c6_coefficient: float,
+        rabi_frequency: float,
+        gate_times: Dict[str, float],
+        gate_fidelity: float,
+    ):
+        return
+
+    def set_blockade_radius(self, blockade_radius: float):
+        """
+        Sets a blockade radius overriding the radius derived from C6 and the Rabi frequency.
+
+        Args:
+            blockade_radius (float): The blockade radius in µm.
+
+        Raises:
+            ValueError: The blockade radius is not positive.
+        """
+
+    def blockade_radius(self) -> float:
+        """
+        Returns the blockade radius.
+
+        Returns:
+            float: The set blockade radius, or (C6 / Ω)^(1/6) if none is set.
+        """
+
+    def two_qubit_gate_time(self, hqslang: str, distance: float) -> Optional[float]:
+        """
+        Returns the gate time of a two-qubit gate between tweezers at a given distance.
+
+        Args:
+            hqslang (str): The hqslang name of the two-qubit gate.
+            distance (float): The distance between the tweezers in micrometers.
+
+        Returns:
+            Optional[float]: The gate time, None if the gate is not available at the given distance.
+        """
+
+    def two_qubit_gate_fidelity(self, hqslang: str, distance: float) -> Optional[float]:
+        """
+        Returns the fidelity of a two-qubit gate between tweezers at a given distance.
+
+        Args:
+            hqslang (str): The hqslang name of the two-qubit gate.
+            distance (float): The distance between the tweezers in micrometers.
+
+        Returns:
+            Optional[float]: The gate fidelity, None if the gate is not available at the given distance.
+        """
+
+    def three_qubit_gate_time(
+        self, hqslang: str, distances: Tuple[float, float, float]
+    ) -> Optional[float]:
+        """
+        Returns the gate time of a three-qubit gate between tweezers at given distances.
+
+        Args:
+            hqslang (str): The hqslang name of the three-qubit gate.
+            distances (Tuple[float, float, float]): The distances between the three pairs of tweezers in micrometers.
+
+        Returns:
+            Optional[float]: The gate time, None if the gate is not available at the given distances.
+        """
@@ -19,7 +19,8 @@ qoqo is the HQS python package to represent quantum circuits.
 
 """
 
-from typing import Optional, List, Tuple, Dict, Union
+from typing import Any, Optional, List, Tuple, Dict, Union
+import numpy as np
 from qoqo import Circuit
 from qoqo.measurements import (
     ClassicalRegister,
@@ -46,6 +47,13 @@ class SimulatorBackend:
     and is compatible with running single circuits, running and evaluating measurements
     and running QuantumPrograms on simulated QRyd devices.
 
+    With a seeded device, the simulations are reproducible. The circuits of a measurement are run
+    with distinct seeds derived from the device seed, sequentially or in parallel with
+    `set_number_threads`, and their shots can be split into batches with `set_shot_batch_size`.
+
+    Clifford circuits on large devices can be simulated with a stabilizer tableau instead of a
+    state vector, see `set_simulation_mode`.
+
     Args:
         device (Union[QRydDevice,TweezerDevice]): The device providing information about the available operations.
 
@@ -107,6 +115,305 @@ class SimulatorBackend:
             ValueError: Input cannot be deserialized to SimulatorBackend.
         """
 
+    def set_noise_model(self, gate_error_rates: Optional[Dict[str, float]] = None):
+        """
+        Enable noisy simulations with the decoherence rates of the device and the given gate errors.
+
+        After each gate, the decoherence rates of the tweezer each of its qubits occupies act
+        for the gate time, followed by the depolarising rate of the gate.
+
+        Args:
+            gate_error_rates (Optional[Dict[str, float]]): The depolarising rates added to the qubits of a gate, by gate name.
+        """
+
+    def remove_noise_model(self):
+        """
+        Disable noisy simulations.
+        """
+
+    def is_noisy(self) -> bool:
+        """
+        Return whether the simulations are noisy.
+
+        Returns:
+            bool: Whether a noise model is set.
+        """
+
+    def noisy_circuit(self, circuit: Circuit) -> Circuit:
+        """
+        Return the circuit run in noisy simulations, with the noise operations after each gate.
+
+        Args:
+            circuit (Circuit): The circuit to add the noise to.
+
+        Returns:
+            Circuit: The circuit with the noise and crosstalk operations, unchanged without noise model and crosstalk model.
+
+        Raises:
+            TypeError: Circuit argument cannot be converted to qoqo Circuit
+            ValueError: A gate is not available on the device or a PragmaChangeDevice operation failed.
+        """
+
+    def check_circuit(self, circuit: Circuit) -> List[Dict[str, Any]]:
+        """
+        Check every operation of a circuit against the device without running it.
+
+        The following rules are checked, following the PragmaChangeDevice operations of the circuit:
+        QubitNotInTweezer: the qubits of each gate are held in a tweezer.
+        UnavailableGate: each gate is available on the tweezers of its qubits.
+        QubitOutOfRange: the qubits of each operation are within the number of qubits of the simulator.
+        FailedDeviceChange: each PragmaChangeDevice operation can be applied to the device.
+
+        Runs of circuits with violations fail with an error listing all of them.
+
+        Args:
+            circuit (Circuit): The circuit to check.
+
+        Returns:
+            List[Dict[str, Any]]: The violations, in the order of the circuit. Each violation contains
+                the "index" of the operation, its "hqslang" name, its "qubits", the "tweezers" of the
+                qubits (None for qubits not in a tweezer), the violated "rule" and a "message".
+
+        Raises:
+            TypeError: Circuit argument cannot be converted to qoqo Circuit
+        """
+
+    def state_vector(
+        self, circuit: Circuit, tweezer_basis: Optional[bool] = None
+    ) -> Tuple[np.ndarray, List[int]]:
+        """
+        Return the state vector of the qubits after a circuit.
+
+        The circuit is run once, with the noise and the crosstalk of the backend. Measurements
+        collapse the state, atom losses and readout errors do not affect it.
+
+        Args:
+            circuit (Circuit): The circuit to run.
+            tweezer_basis (Optional[bool]): Whether bit i of the basis state indices is the qubit in the i-th occupied tweezer after the shifts of the circuit instead of qubit i. Defaults to False.
+
+        Returns:
+            Tuple[np.ndarray, List[int]]: The state vector and the qubit or tweezer of each bit of the basis state indices.
+
+        Raises:
+            TypeError: Circuit argument cannot be converted to qoqo Circuit
+            ValueError: The circuit does not act on any qubit, a qubit is not in a tweezer, the simulation is noisy or running the circuit failed.
+        """
+
+    def density_matrix(
+        self, circuit: Circuit, tweezer_basis: Optional[bool] = None
+    ) -> Tuple[np.ndarray, List[int]]:
+        """
+        Return the density matrix of the qubits after a circuit.
+
+        The circuit is run once, with the noise and the crosstalk of the backend. Measurements
+        collapse the state, atom losses and readout errors do not affect it.
+
+        Args:
+            circuit (Circuit): The circuit to run.
+            tweezer_basis (Optional[bool]): Whether bit i of the basis state indices is the qubit in the i-th occupied tweezer after the shifts of the circuit instead of qubit i. Defaults to False.
+
+        Returns:
+            Tuple[np.ndarray, List[int]]: The density matrix and the qubit or tweezer of each bit of the basis state indices.
+
+        Raises:
+            TypeError: Circuit argument cannot be converted to qoqo Circuit
+            ValueError: The circuit does not act on any qubit, a qubit is not in a tweezer or running the circuit failed.
+        """
+
+    def state_snapshots(
+        self,
+        circuit: Circuit,
+        density_matrix: Optional[bool] = None,
+        tweezer_basis: Optional[bool] = None,
+    ) -> List[Tuple[int, np.ndarray, List[int]]]:
+        """
+        Return the state of the qubits after each operation of a circuit.
+
+        The snapshots are taken in a single run of the circuit, the noise and the crosstalk
+        following an operation are part of its snapshot.
+
+        Args:
+            circuit (Circuit): The circuit to run.
+            density_matrix (Optional[bool]): Whether to return density matrices instead of state vectors. Defaults to False.
+            tweezer_basis (Optional[bool]): Whether bit i of the basis state indices is the qubit in the i-th occupied tweezer at each snapshot instead of qubit i. Defaults to False.
+
+        Returns:
+            List[Tuple[int, np.ndarray, List[int]]]: The number of applied operations, the state and the qubit or tweezer of each bit of the basis state indices for each snapshot.
+
+        Raises:
+            TypeError: Circuit argument cannot be converted to qoqo Circuit
+            ValueError: The circuit does not act on any qubit, a qubit is not in a tweezer, the state vectors of a noisy simulation are requested or running the circuit failed.
+        """
+
+    def set_atom_loss_model(
+        self,
+        shift_loss_rate: float,
+        measurement_loss_probability: float,
+        idle_loss_rate: float,
+        lost_register: Optional[str] = None,
+    ):
+        """
+        Enable the loss of atoms during shifts, measurements and idle times.
+
+        Lost qubits read out as false in every later measurement. For each shot, the lost qubits
+        are reported in an additional bit register with one entry per qubit.
+
+        Args:
+            shift_loss_rate (float): The loss rate per unit of shift distance.
+            measurement_loss_probability (float): The probability of losing a qubit when it is measured.
+            idle_loss_rate (float): The loss rate per unit of time a qubit is idle.
+            lost_register (Optional[str]): The name of the register of lost qubits. Defaults to "lost_qubits".
+
+        Raises:
+            ValueError: A rate is negative or the probability is not between 0 and 1.
+        """
+
+    def remove_atom_loss_model(self):
+        """
+        Disable the loss of atoms.
+        """
+
+    def has_atom_loss(self) -> bool:
+        """
+        Return whether atoms can be lost in the simulations.
+
+        Returns:
+            bool: Whether an atom loss model is set.
+        """
+
+    def set_readout_errors(self, readout_errors: bool):
+        """
+        Set whether the readout errors of the device are applied to the measured bits.
+
+        Each measured bit is flipped with the probability given by the readout confusion matrix
+        of the tweezer the qubit occupies.
+
+        Args:
+            readout_errors (bool): Whether the readout errors are applied.
+        """
+
+    def has_readout_errors(self) -> bool:
+        """
+        Return whether the readout errors of the device are applied to the measured bits.
+
+        Returns:
+            bool: Whether the readout errors are applied.
+        """
+
+    def set_crosstalk_model(self, strength: float, range: Optional[float] = None):
+        """
+        Enable the Rydberg blockade crosstalk during entangling gates.
+
+        During each entangling gate, the spectator qubits close to the qubits of the gate acquire
+        a phase when both the gate qubit and the spectator are in state |1>.
+
+        Args:
+            strength (float): The phase acquired by a spectator qubit during an entangling gate.
+            range (Optional[float]): The maximal distance in micrometers between the tweezers of a gate qubit and a spectator. Defaults to the two-qubit connectivity of the device.
+
+        Raises:
+            ValueError: The strength is not finite or the range is not finite and positive.
+        """
+
+    def remove_crosstalk_model(self):
+        """
+        Disable the crosstalk during entangling gates.
+        """
+
+    def has_crosstalk(self) -> bool:
+        """
+        Return whether the simulations include crosstalk during entangling gates.
+
+        Returns:
+            bool: Whether a crosstalk model is set.
+        """
+
+    def set_number_threads(self, number_threads: int):
+        """
+        Set the number of threads running the circuits of a measurement.
+
+        Every circuit, or batch of shots of a circuit, is run with the same seed as in sequential
+        runs and the registers are merged in order, so the results are identical to sequential
+        runs. The QuEST simulations are serialized, as QuEST keeps its random number generator
+        in global state, so several threads only speed up stabilizer simulations, see
+        `set_simulation_mode`.
+
+        Args:
+            number_threads (int): The number of threads, 0 for the available parallelism of the machine and 1 for sequential runs.
+        """
+
+    def number_threads(self) -> int:
+        """
+        Return the number of threads running the circuits of a measurement.
+
+        Returns:
+            int: The number of threads, 1 for sequential runs.
+        """
+
+    def set_shot_batch_size(self, shot_batch_size: int):
+        """
+        Set the maximal number of shots run at once.
+
+        The shots of each circuit are split into batches run with their own seeds, in parallel
+        with several threads, see `set_number_threads`. The float and complex registers are
+        returned once per batch.
+
+        Args:
+            shot_batch_size (int): The maximal number of shots of a batch.
+        """
+
+    def shot_batch_size(self) -> Optional[int]:
+        """
+        Return the maximal number of shots run at once.
+
+        Returns:
+            Optional[int]: The maximal number of shots of a batch, None to run all the shots of a circuit at once.
+        """
+
+    def set_simulation_mode(self, simulation_mode: str):
+        """
+        Set the simulation mode of the circuits.
+
+        The stabilizer simulation supports Clifford gates, measurements and active resets on any
+        number of qubits, with the device checks, atom losses and readout errors of state vector
+        simulations. Noise models are not supported. The automatic mode uses the stabilizer
+        simulation for noise-free Clifford circuits and the state vector simulation otherwise,
+        failing for other circuits on backends with more than 30 qubits.
+
+        Args:
+            simulation_mode (str): The simulation mode, state_vector, stabilizer or automatic.
+
+        Raises:
+            ValueError: The simulation mode is unknown.
+        """
+
+    def simulation_mode(self) -> str:
+        """
+        Return the simulation mode of the circuits.
+
+        Returns:
+            str: The simulation mode, state_vector, stabilizer or automatic.
+        """
+
+    def post_select_shots(
+        self,
+        bit_registers: Dict[str, List[List[bool]]],
+        qubits: Optional[List[int]] = None,
+    ) -> Dict[str, List[List[bool]]]:
+        """
+        Post-select the shots in which none of the given qubits has been lost.
+
+        Args:
+            bit_registers (Dict[str, List[List[bool]]]): The bit registers of a run, including the register of lost qubits.
+            qubits (Optional[List[int]]): The qubits that must not be lost. Defaults to all qubits.
+
+        Returns:
+            Dict[str, List[List[bool]]]: The bit registers of the selected shots, without the register of lost qubits.
+
+        Raises:
+            ValueError: The register of lost qubits is missing or a register has a different number of shots.
+        """
+
     def run_circuit(self, circuit: Circuit) -> Tuple[
         Dict[str, List[List[bool]]],
         Dict[str, List[List[float]]],
@@ -242,6 +549,30 @@ class APIBackend:
 
         """
 
+    @staticmethod
+    def mitigate_counts(
+        counts: Dict[str, int],
+        number_qubits: int,
+        confusion_matrices: List[Tuple[float, float]],
+        method: Optional[str] = None,
+    ) -> Dict[str, float]:
+        """
+        Mitigate the readout errors of the counts of a WebAPI job result.
+
+        Args:
+            counts (Dict[str, int]): The counts of the job result, as in `get_job_result()["data"]["counts"]`.
+            number_qubits (int): The number of measured qubits.
+            confusion_matrices (List[Tuple[float, float]]): The probabilities of reading out 1 for state |0>
+                and 0 for state |1> of each measured qubit, see `TweezerDevice.readout_confusion_matrices`.
+            method (Optional[str]): "inversion" or "least_squares". Defaults to "inversion".
+
+        Returns:
+            Dict[str, float]: The mitigated probabilities, keyed by bitstrings whose i-th character is the result of qubit i.
+
+        Raises:
+            ValueError: A count cannot be parsed, a probability is not in [0, 1], the method is unknown or the mitigation failed.
+        """
+
     def delete_job(self, job_location: str):
         """
         Delete a posted WebAPI job
@@ -628,3 +959,73 @@ class pragma_operations:
         Args:
             new_layout (str): The name of the new layout.
         """
+
+
+def download_device_snapshot(
+    path: str,
+    device_name: Optional[str] = None,
+    access_token: Optional[str] = None,
+    mock_port: Optional[str] = None,
+    dev: Optional[bool] = None,
+    api_version: Optional[str] = None,
+):
+    """
+    Downloads a device from the QRyd WebAPI and writes it to a snapshot file.
+
+    The snapshot stores the device together with its name, the API version and the download time.
+    It can be loaded without network access with `device_from_snapshot`.
+
+    This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
+
+    Args:
+        path (str): The path of the snapshot file to write. An existing file is overwritten.
+        device_name (Optional[str]): The name of the device to download. Defaults to "qryd_emulator".
+        access_token (Optional[str]): An access_token is required to access QRYD hardware and emulators.
+                            The access_token can either be given as an argument here
+                                or set via the environmental variable `$QRYD_API_TOKEN`.
+        mock_port (Optional[str]): Server port to be used for testing purposes.
+        dev (Optional[bool]): The boolean to set the dev header to.
+        api_version (Optional[str]): The version of the QRYD API to use. Defaults to "v1_1".
+
+    Raises:
+        RoqoqoBackendError
+    """
+
+def device_from_snapshot(path: str, seed: Optional[int] = None):
+    """
+    Creates a new TweezerDevice or EmulatorDevice instance from a device snapshot file.
+
+    The offline counterpart of `device_from_api`.
+
+    Args:
+        path (str): The path of the snapshot file, as written by `download_device_snapshot`.
+        seed (Optional[int]): Optionally overwrite seed value from the stored device instance.
+
+    Returns:
+        Union[TweezerDevice, EmulatorDevice]: Either the TweezerDevice or EmulatorDevice instance
+            depending on the stored information.
+
+    Raises:
+        ValueError: The file could not be read or does not contain a valid snapshot.
+    """
+
+def mitigate_bit_register(
+    register: List[List[bool]],
+    confusion_matrices: List[Tuple[float, float]],
+    method: Optional[str] = None,
+) -> Dict[str, float]:
+    """
+    Mitigates the readout errors of the shots of a bit register.
+
+    Args:
+        register (List[List[bool]]): The measured shots, with one entry per bit of the register.
+        confusion_matrices (List[Tuple[float, float]]): The probabilities of reading out 1 for state |0>
+            and 0 for state |1> of the qubit measured into each bit, see `TweezerDevice.readout_confusion_matrices`.
+        method (Optional[str]): "inversion" or "least_squares". Defaults to "inversion".
+
+    Returns:
+        Dict[str, float]: The mitigated probabilities, keyed by bitstrings whose i-th character is the i-th bit.
+
+    Raises:
+        ValueError: A probability is not in [0, 1], the method is unknown or the mitigation failed.
+    """
@@ -13,8 +13,11 @@ Tweezer devices for the QRyd platform.
 
 """
 
-from typing import Optional, List, Dict, Union, Sequence
+from typing import Any, Optional, List, Dict, Union, Sequence, Tuple
+import numpy as np
 from qoqo.devices import GenericDevice
+from qoqo.operations import PragmaChangeDevice
+from .interaction_models import CutoffInteractionModel, BlockadeInteractionModel
 
 class TweezerDevice:
     """
@@ -56,6 +59,7 @@ class TweezerDevice:
         seed: Optional[int],
         dev: Optional[bool],
         api_version: Optional[str],
+        cache_dir: Optional[str],
     ) -> TweezerDevice:
         """
         Creates a new TweezerDevice instance containing populated tweezer data.
@@ -71,6 +75,8 @@ class TweezerDevice:
             seed (Optional[int]): Optionally overwrite seed value from downloaded device instance.
             dev (Optional[bool]): The boolean to set the dev header to.
             api_version (Optional[str]): The version of the QRYD API to use. Defaults to "v1_1".
+            cache_dir (Optional[str]): The directory of the on-disk device cache to use.
+                                Defaults to the environmental variable `$QRYD_API_CACHE_DIR`.
 
         Returns:
             TweezerDevice: The new TweezerDevice instance with populated tweezer data.
@@ -79,6 +85,24 @@ class TweezerDevice:
             RoqoqoBackendError
         """
 
+    @staticmethod
+    def from_snapshot(path: str, seed: Optional[int]) -> TweezerDevice:
+        """
+        Creates a new TweezerDevice instance from a device snapshot file.
+
+        The offline counterpart of `from_api`.
+
+        Args:
+            path (str): The path of the snapshot file, as written by `download_device_snapshot`.
+            seed (Optional[int]): Optionally overwrite seed value from the stored device instance.
+
+        Returns:
+            TweezerDevice: The new TweezerDevice instance.
+
+        Raises:
+            ValueError: The file could not be read or does not contain a compatible device.
+        """
+
     def current_layout(self) -> str:
         """
         Get the name of the current layout.
@@ -115,7 +139,9 @@ class TweezerDevice:
         """
         Modifies the qubit -> tweezer mapping of the device.
 
-        If a qubit -> tweezer mapping is already present, it is overwritten.
+        If a qubit -> tweezer mapping is already present, it is overwritten and a qubit
+        previously occupying the tweezer is removed from the mapping. Use `insert_qubit`,
+        `move_qubit` and `remove_qubit` to report such conflicts instead.
 
         Args:
             qubit (int): The index of the qubit.
@@ -128,6 +154,68 @@ class TweezerDevice:
             ValueError: The tweezer is not present in the device.
         """
 
+    def insert_qubit(self, qubit: int, tweezer: int) -> None:
+        """
+        Places an unmapped qubit in a free tweezer of the current Layout.
+
+        Args:
+            qubit (int): The index of the qubit.
+            tweezer (int): The index of the tweezer.
+
+        Raises:
+            ValueError: The tweezer is not present in the device or occupied, or the qubit is already mapped.
+        """
+
+    def move_qubit(self, qubit: int, tweezer: int) -> int:
+        """
+        Moves a mapped qubit into a free tweezer of the current Layout.
+
+        Args:
+            qubit (int): The index of the qubit.
+            tweezer (int): The index of the target tweezer.
+
+        Returns:
+            int: The tweezer the qubit has been moved out of.
+
+        Raises:
+            ValueError: The tweezer is not present in the device or occupied, or the qubit is not mapped.
+        """
+
+    def remove_qubit(self, qubit: int) -> int:
+        """
+        Removes a qubit from the qubit -> tweezer mapping.
+
+        Args:
+            qubit (int): The index of the qubit.
+
+        Returns:
+            int: The tweezer the qubit occupied.
+
+        Raises:
+            ValueError: The qubit is not mapped.
+        """
+
+    def plan_shifts(
+        self, target: Dict[int, int], max_states: Optional[int] = None
+    ) -> List[PragmaChangeDevice]:
+        """
+        Plans the tweezer shifts moving the qubits of the device into a target arrangement.
+
+        Only the allowed tweezer shifts of the current Layout are used. The search finds the
+        smallest number of parallel PragmaShiftQubitsTweezers operations, whose shifts move
+        different qubits and are checked in order as in `change_device`.
+
+        Args:
+            target (Dict[int, int]): The target qubit -> tweezer mapping, containing the qubits of the device mapping.
+            max_states (Optional[int]): The maximal number of qubit arrangements to explore.
+
+        Returns:
+            List[PragmaChangeDevice]: The wrapped PragmaShiftQubitsTweezers operations to apply in order.
+
+        Raises:
+            ValueError: The target is invalid or not reachable, or the search exceeded max_states.
+        """
+
     def get_qubit_to_tweezer_mapping(self) -> Dict[int, int]:
         """
         Get the qubit -> tweezer mapping of the device.
@@ -137,6 +225,39 @@ class TweezerDevice:
             None: The mapping is empty.
         """
 
+    def qubit_at(self, tweezer: int) -> Optional[int]:
+        """
+        Get the qubit occupying the given tweezer.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+
+        Returns:
+            Optional[int]: The qubit in the tweezer, None if the tweezer is free.
+        """
+
+    def occupied_tweezers(self) -> List[int]:
+        """
+        Get the tweezers occupied by a qubit.
+
+        Returns:
+            list[int]: The occupied tweezers, in ascending order.
+        """
+
+    def free_tweezers(self, layout_name: Optional[str] = None) -> List[int]:
+        """
+        Get the tweezers of a Layout not occupied by a qubit.
+
+        Args:
+            layout_name (Optional[str]): The name of the Layout. Defaults to the current Layout.
+
+        Returns:
+            list[int]: The free tweezers, in ascending order.
+
+        Raises:
+            ValueError: The layout does not exist or no current layout is set.
+        """
+
     def get_available_gates_names(self, layout_name: Optional[str]) -> List[str]:
         """
         Get the names of the available gates in the given layout.
@@ -368,6 +489,266 @@ class TweezerDevice:
             int: The number of tweezer positions in the device.
         """
 
+    def validate(self) -> Dict[str, List[Dict[str, Any]]]:
+        """
+        Checks the self-consistency of all Layouts of the device.
+
+        The following rules are checked for each Layout:
+        AsymmetricTwoQubitGate: two-qubit gates are set for both orders of each tweezer pair, with the same gate time.
+        ShiftWithUnknownTweezer: allowed tweezer shifts only involve tweezers with gates.
+        TweezersPerRowMismatch: the tweezers per row, if set, add up to the number of tweezers.
+        UnconnectedThreeQubitGate: three-qubit gates only involve tweezer pairs connected by a two-qubit gate.
+        PositionOfUnknownTweezer: tweezer positions are only set for tweezers with gates.
+
+        Returns:
+            Dict[str, List[Dict[str, Any]]]: The issues of each Layout, by Layout name. Each issue
+                contains the violated "rule", the involved "tweezers" and a "message".
+        """
+
+    def get_tweezer_position(
+        self, tweezer: int, layout_name: Optional[str] = None
+    ) -> Tuple[float, float, float]:
+        """
+        Returns the position of a tweezer in a given Layout.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            Tuple[float, float, float]: The (x, y, z) coordinates of the tweezer in micrometers.
+
+        Raises:
+            ValueError: The layout does not exist or has no position for the tweezer.
+        """
+
+    def get_tweezer_decoherence_rates(
+        self, tweezer: int, layout_name: Optional[str] = None
+    ) -> Tuple[float, float, float]:
+        """
+        Returns the decoherence rates of a qubit held in a tweezer in a given Layout.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            Tuple[float, float, float]: The damping, dephasing and depolarising rates, zero if none are set.
+
+        Raises:
+            ValueError: The layout does not exist.
+        """
+
+    def qubit_decoherence_rates(self, qubit: int) -> np.ndarray:
+        """
+        Returns the decoherence rates matrix of the tweezer a qubit occupies in the current Layout.
+
+        Args:
+            qubit (int): The qubit.
+
+        Returns:
+            np.ndarray: The 3x3 decoherence rates matrix, zero for qubits without rates.
+        """
+
+    def single_qubit_gate_error(self, hqslang: str, qubit: int) -> float:
+        """
+        Returns the error rate of a single-qubit gate on a qubit in the current Layout.
+
+        Args:
+            hqslang (str): The hqslang name of the single-qubit gate.
+            qubit (int): The qubit the gate acts on.
+
+        Returns:
+            float: The error rate, zero if none is set.
+
+        Raises:
+            ValueError: The gate is not available on the qubit.
+        """
+
+    def two_qubit_gate_error(self, hqslang: str, control: int, target: int) -> float:
+        """
+        Returns the error rate of a two-qubit gate on a qubit couple in the current Layout.
+
+        Args:
+            hqslang (str): The hqslang name of the two-qubit gate.
+            control (int): The control qubit the gate acts on.
+            target (int): The target qubit the gate acts on.
+
+        Returns:
+            float: The error rate, zero if none is set.
+
+        Raises:
+            ValueError: The gate is not available on the qubits.
+        """
+
+    def three_qubit_gate_error(
+        self, hqslang: str, control_0: int, control_1: int, target: int
+    ) -> float:
+        """
+        Returns the error rate of a three-qubit gate on a qubit trio in the current Layout.
+
+        Args:
+            hqslang (str): The hqslang name of the three-qubit gate.
+            control_0 (int): The first control qubit the gate acts on.
+            control_1 (int): The second control qubit the gate acts on.
+            target (int): The target qubit the gate acts on.
+
+        Returns:
+            float: The error rate, zero if none is set.
+
+        Raises:
+            ValueError: The gate is not available on the qubits.
+        """
+
+    def readout_error(self, qubit: int) -> float:
+        """
+        Returns the readout error rate of a qubit in the current Layout.
+
+        Args:
+            qubit (int): The measured qubit.
+
+        Returns:
+            float: The readout error rate of the tweezer the qubit occupies, zero if none is set.
+
+        Raises:
+            ValueError: The qubit is not mapped to a tweezer.
+        """
+
+    def readout_confusion_matrix(self, qubit: int) -> Tuple[float, float]:
+        """
+        Returns the readout confusion matrix of a qubit in the current Layout.
+
+        Args:
+            qubit (int): The measured qubit.
+
+        Returns:
+            Tuple[float, float]: The probabilities of reading out 1 for state |0> and 0 for state |1>
+                in the tweezer the qubit occupies, zero if none are set.
+
+        Raises:
+            ValueError: The qubit is not mapped to a tweezer.
+        """
+
+    def readout_confusion_matrices(self, qubits: List[int]) -> List[Tuple[float, float]]:
+        """
+        Returns the readout confusion matrices of the qubits measured into a register, in register order.
+
+        Args:
+            qubits (List[int]): The qubit measured into each bit of the register.
+
+        Returns:
+            List[Tuple[float, float]]: The confusion matrix of each qubit, as for `readout_confusion_matrix`.
+
+        Raises:
+            ValueError: A qubit is not mapped to a tweezer.
+        """
+
+    def tweezer_error_score(self, tweezer: int, layout_name: Optional[str] = None) -> float:
+        """
+        Returns the error score of a tweezer in a given Layout.
+
+        The score is the sum of the largest single-qubit gate error rate of the tweezer, the
+        largest two-qubit gate error rate over the pairs of tweezers it belongs to and its
+        readout error rate.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            float: The error score of the tweezer, zero if no error rates are set.
+
+        Raises:
+            ValueError: The layout does not exist.
+        """
+
+    def tweezer_position_dimensions(self, layout_name: Optional[str] = None) -> int:
+        """
+        Returns the number of coordinates of the tweezer positions of a given Layout.
+
+        Args:
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            int: 2 for two-dimensional and 3 for three-dimensional positions.
+
+        Raises:
+            ValueError: The layout does not exist or has no tweezer positions.
+        """
+
+    def tweezer_distance(
+        self, tweezer0: int, tweezer1: int, layout_name: Optional[str] = None
+    ) -> float:
+        """
+        Returns the distance between two tweezers in a given Layout.
+
+        Args:
+            tweezer0 (int): The index of the first tweezer.
+            tweezer1 (int): The index of the second tweezer.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            float: The distance between the tweezers in micrometers.
+
+        Raises:
+            ValueError: The position of one of the tweezers is not set.
+        """
+
+    def qubit_distance(self, qubit0: int, qubit1: int) -> float:
+        """
+        Returns the distance between the tweezers two qubits are mapped to in the current Layout.
+
+        Args:
+            qubit0 (int): The index of the first qubit.
+            qubit1 (int): The index of the second qubit.
+
+        Returns:
+            float: The distance between the qubits in micrometers.
+
+        Raises:
+            ValueError: A qubit is not mapped or the position of its tweezer is not set.
+        """
+
+    def nearest_tweezers(
+        self, tweezer: int, number: int, layout_name: Optional[str] = None
+    ) -> List[Tuple[int, float]]:
+        """
+        Returns the tweezers closest to a given tweezer in a given Layout.
+
+        Only tweezers with a set position are considered.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            number (int): The maximal number of tweezers to return.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            List[Tuple[int, float]]: The closest tweezers with their distances, sorted by distance.
+
+        Raises:
+            ValueError: The position of the given tweezer is not set.
+        """
+
+    def tweezers_within_distance(
+        self, tweezer: int, max_distance: float, layout_name: Optional[str] = None
+    ) -> List[Tuple[int, float]]:
+        """
+        Returns the tweezers within a given distance of a given tweezer in a given Layout.
+
+        Only tweezers with a set position are considered.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            max_distance (float): The maximal distance in micrometers, inclusive.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            List[Tuple[int, float]]: The tweezers in range with their distances, sorted by distance.
+
+        Raises:
+            ValueError: The position of the given tweezer is not set.
+        """
+
     def two_qubit_edges(self) -> Sequence[(int, int)]:
         """
         Return the list of pairs of qubits linked by a native two-qubit-gate in the device.
@@ -408,6 +789,17 @@ class TweezerDevice:
         Returns the seed usized for the API.
         """
 
+    def is_offline_fallback(self) -> bool:
+        """
+        Returns whether the device is a cached copy, used because the QRyd WebAPI was unreachable.
+
+        Only set by `from_api` and `device_from_api` with the device cache enabled
+        via the environmental variable `$QRYD_API_CACHE_DIR`.
+
+        Returns:
+            bool: Whether the device definition may be outdated.
+        """
+
     def _enum_to_bincode(self) -> bytearray:
         """
         Return the bincode representation of the Enum variant of the Device.
@@ -475,6 +867,85 @@ class TweezerMutableDevice:
             name (str): The name that is assigned to the new Layout.
         """
 
+    def add_lattice_layout(
+        self,
+        name: str,
+        lattice: str,
+        rows: int,
+        columns: int,
+        spacing: float,
+        gate_times: Dict[str, float],
+        interaction_radius: Optional[float] = None,
+        row_shifts: Optional[bool] = None,
+    ):
+        """
+        Add a new Layout generated from a regular lattice of tweezers to the device.
+
+        Every tweezer supports the given single-qubit gates. Two-qubit gates are set for
+        every pair of tweezers within the interaction radius, three-qubit gates for every
+        trio of tweezers pairwise within the interaction radius.
+
+        Args:
+            name (str): The name that is assigned to the new Layout.
+            lattice (str): The lattice type, one of "square", "triangular", "hexagonal" or "kagome".
+            rows (int): The number of rows of tweezers.
+            columns (int): The number of tweezers per row. For kagome lattices, the number of tweezers in the full rows.
+            spacing (float): The distance between nearest neighbours in micrometers.
+            gate_times (Dict[str, float]): The gate time of each native gate supported by the Layout.
+            interaction_radius (Optional[float]): The maximal distance of interacting tweezers in micrometers. Defaults to the spacing.
+            row_shifts (Optional[bool]): Whether to allow shifting qubits along the rows. Defaults to False.
+
+        Raises:
+            ValueError: The name is already in use or the Layout could not be generated.
+        """
+
+    def add_layout_from_positions(
+        self,
+        name: str,
+        positions: List[List[List[float]]],
+        gate_times: Dict[str, float],
+        interaction_radius: float,
+        row_shifts: Optional[bool] = None,
+    ):
+        """
+        Add a new Layout generated from arbitrary tweezer positions to the device.
+
+        Every tweezer supports the given single-qubit gates. Two-qubit gates are set for
+        every pair of tweezers within the interaction radius, three-qubit gates for every
+        trio of tweezers pairwise within the interaction radius.
+
+        Args:
+            name (str): The name that is assigned to the new Layout.
+            positions (List[List[List[float]]]): The (x, y) or (x, y, z) coordinates of the tweezers in micrometers, row by row.
+            gate_times (Dict[str, float]): The gate time of each native gate supported by the Layout.
+            interaction_radius (float): The maximal distance of interacting tweezers in micrometers.
+            row_shifts (Optional[bool]): Whether to allow shifting qubits along the rows. Defaults to False.
+
+        Raises:
+            ValueError: The name is already in use or the Layout could not be generated.
+        """
+
+    def apply_interaction_model(
+        self,
+        model: Union[CutoffInteractionModel, BlockadeInteractionModel],
+        layout_name: Optional[str] = None,
+    ):
+        """
+        Derive the two- and three-qubit gate times of a Layout from an interaction model.
+
+        The model is evaluated for every pair and trio of tweezers with a set position and
+        every native two- and three-qubit gate. Two-qubit gate times are set for both orders
+        of each pair. The existing two- and three-qubit gate times of the Layout are replaced.
+
+        Args:
+            model (Union[CutoffInteractionModel, BlockadeInteractionModel]): The interaction model to evaluate.
+            layout_name (Optional[str]): The name of the Layout to set the gate times in. Defaults to the current Layout.
+
+        Raises:
+            TypeError: The model is not an interaction model.
+            ValueError: The layout does not exist.
+        """
+
     def switch_layout(self, layout_number: str, with_trivial_map: bool):
         """
         Switch to a different pre-defined Layout.
@@ -503,7 +974,9 @@ class TweezerMutableDevice:
         """
         Modifies the qubit -> tweezer mapping of the device.
 
-        If a qubit -> tweezer mapping is already present, it is overwritten.
+        If a qubit -> tweezer mapping is already present, it is overwritten and a qubit
+        previously occupying the tweezer is removed from the mapping. Use `insert_qubit`,
+        `move_qubit` and `remove_qubit` to report such conflicts instead.
 
         Args:
             qubit (int): The index of the qubit.
@@ -516,13 +989,108 @@ class TweezerMutableDevice:
             ValueError: The tweezer is not present in the device.
         """
 
-    def get_qubit_to_tweezer_mapping(self) -> Dict[int, int]:
+    def insert_qubit(self, qubit: int, tweezer: int) -> None:
+        """
+        Places an unmapped qubit in a free tweezer of the current Layout.
+
+        Args:
+            qubit (int): The index of the qubit.
+            tweezer (int): The index of the tweezer.
+
+        Raises:
+            ValueError: The tweezer is not present in the device or occupied, or the qubit is already mapped.
+        """
+
+    def move_qubit(self, qubit: int, tweezer: int) -> int:
+        """
+        Moves a mapped qubit into a free tweezer of the current Layout.
+
+        Args:
+            qubit (int): The index of the qubit.
+            tweezer (int): The index of the target tweezer.
+
+        Returns:
+            int: The tweezer the qubit has been moved out of.
+
+        Raises:
+            ValueError: The tweezer is not present in the device or occupied, or the qubit is not mapped.
+        """
+
+    def remove_qubit(self, qubit: int) -> int:
+        """
+        Removes a qubit from the qubit -> tweezer mapping.
+
+        Args:
+            qubit (int): The index of the qubit.
+
+        Returns:
+            int: The tweezer the qubit occupied.
+
+        Raises:
+            ValueError: The qubit is not mapped.
+        """
+
+    def plan_shifts(
+        self, target: Dict[int, int], max_states: Optional[int] = None
+    ) -> List[PragmaChangeDevice]:
+        """
+        Plans the tweezer shifts moving the qubits of the device into a target arrangement.
+
+        Only the allowed tweezer shifts of the current Layout are used. The search finds the
+        smallest number of parallel PragmaShiftQubitsTweezers operations, whose shifts move
+        different qubits and are checked in order as in `change_device`.
+
+        Args:
+            target (Dict[int, int]): The target qubit -> tweezer mapping, containing the qubits of the device mapping.
+            max_states (Optional[int]): The maximal number of qubit arrangements to explore.
+
+        Returns:
+            List[PragmaChangeDevice]: The wrapped PragmaShiftQubitsTweezers operations to apply in order.
+
+        Raises:
+            ValueError: The target is invalid or not reachable, or the search exceeded max_states.
+        """
+
+    def get_qubit_to_tweezer_mapping(self) -> Dict[int, int]:
+        """
+        Get the qubit -> tweezer mapping of the device.
+
+        Returns:
+            dict[int, int]: The qubit -> tweezer mapping.
+            None: The mapping is empty.
+        """
+
+    def qubit_at(self, tweezer: int) -> Optional[int]:
+        """
+        Get the qubit occupying the given tweezer.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+
+        Returns:
+            Optional[int]: The qubit in the tweezer, None if the tweezer is free.
+        """
+
+    def occupied_tweezers(self) -> List[int]:
+        """
+        Get the tweezers occupied by a qubit.
+
+        Returns:
+            list[int]: The occupied tweezers, in ascending order.
+        """
+
+    def free_tweezers(self, layout_name: Optional[str] = None) -> List[int]:
         """
-        Get the qubit -> tweezer mapping of the device.
+        Get the tweezers of a Layout not occupied by a qubit.
+
+        Args:
+            layout_name (Optional[str]): The name of the Layout. Defaults to the current Layout.
 
         Returns:
-            dict[int, int]: The qubit -> tweezer mapping.
-            None: The mapping is empty.
+            list[int]: The free tweezers, in ascending order.
+
+        Raises:
+            ValueError: The layout does not exist or no current layout is set.
         """
 
     def get_available_gates_names(self, layout_name: Optional[str]) -> List[str]:
@@ -757,6 +1325,266 @@ class TweezerMutableDevice:
             int: The number of tweezer positions in the device.
         """
 
+    def validate(self) -> Dict[str, List[Dict[str, Any]]]:
+        """
+        Checks the self-consistency of all Layouts of the device.
+
+        The following rules are checked for each Layout:
+        AsymmetricTwoQubitGate: two-qubit gates are set for both orders of each tweezer pair, with the same gate time.
+        ShiftWithUnknownTweezer: allowed tweezer shifts only involve tweezers with gates.
+        TweezersPerRowMismatch: the tweezers per row, if set, add up to the number of tweezers.
+        UnconnectedThreeQubitGate: three-qubit gates only involve tweezer pairs connected by a two-qubit gate.
+        PositionOfUnknownTweezer: tweezer positions are only set for tweezers with gates.
+
+        Returns:
+            Dict[str, List[Dict[str, Any]]]: The issues of each Layout, by Layout name. Each issue
+                contains the violated "rule", the involved "tweezers" and a "message".
+        """
+
+    def get_tweezer_position(
+        self, tweezer: int, layout_name: Optional[str] = None
+    ) -> Tuple[float, float, float]:
+        """
+        Returns the position of a tweezer in a given Layout.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            Tuple[float, float, float]: The (x, y, z) coordinates of the tweezer in micrometers.
+
+        Raises:
+            ValueError: The layout does not exist or has no position for the tweezer.
+        """
+
+    def get_tweezer_decoherence_rates(
+        self, tweezer: int, layout_name: Optional[str] = None
+    ) -> Tuple[float, float, float]:
+        """
+        Returns the decoherence rates of a qubit held in a tweezer in a given Layout.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            Tuple[float, float, float]: The damping, dephasing and depolarising rates, zero if none are set.
+
+        Raises:
+            ValueError: The layout does not exist.
+        """
+
+    def qubit_decoherence_rates(self, qubit: int) -> np.ndarray:
+        """
+        Returns the decoherence rates matrix of the tweezer a qubit occupies in the current Layout.
+
+        Args:
+            qubit (int): The qubit.
+
+        Returns:
+            np.ndarray: The 3x3 decoherence rates matrix, zero for qubits without rates.
+        """
+
+    def single_qubit_gate_error(self, hqslang: str, qubit: int) -> float:
+        """
+        Returns the error rate of a single-qubit gate on a qubit in the current Layout.
+
+        Args:
+            hqslang (str): The hqslang name of the single-qubit gate.
+            qubit (int): The qubit the gate acts on.
+
+        Returns:
+            float: The error rate, zero if none is set.
+
+        Raises:
+            ValueError: The gate is not available on the qubit.
+        """
+
+    def two_qubit_gate_error(self, hqslang: str, control: int, target: int) -> float:
+        """
+        Returns the error rate of a two-qubit gate on a qubit couple in the current Layout.
+
+        Args:
+            hqslang (str): The hqslang name of the two-qubit gate.
+            control (int): The control qubit the gate acts on.
+            target (int): The target qubit the gate acts on.
+
+        Returns:
+            float: The error rate, zero if none is set.
+
+        Raises:
+            ValueError: The gate is not available on the qubits.
+        """
+
+    def three_qubit_gate_error(
+        self, hqslang: str, control_0: int, control_1: int, target: int
+    ) -> float:
+        """
+        Returns the error rate of a three-qubit gate on a qubit trio in the current Layout.
+
+        Args:
+            hqslang (str): The hqslang name of the three-qubit gate.
+            control_0 (int): The first control qubit the gate acts on.
+            control_1 (int): The second control qubit the gate acts on.
+            target (int): The target qubit the gate acts on.
+
+        Returns:
+            float: The error rate, zero if none is set.
+
+        Raises:
+            ValueError: The gate is not available on the qubits.
+        """
+
+    def readout_error(self, qubit: int) -> float:
+        """
+        Returns the readout error rate of a qubit in the current Layout.
+
+        Args:
+            qubit (int): The measured qubit.
+
+        Returns:
+            float: The readout error rate of the tweezer the qubit occupies, zero if none is set.
+
+        Raises:
+            ValueError: The qubit is not mapped to a tweezer.
+        """
+
+    def readout_confusion_matrix(self, qubit: int) -> Tuple[float, float]:
+        """
+        Returns the readout confusion matrix of a qubit in the current Layout.
+
+        Args:
+            qubit (int): The measured qubit.
+
+        Returns:
+            Tuple[float, float]: The probabilities of reading out 1 for state |0> and 0 for state |1>
+                in the tweezer the qubit occupies, zero if none are set.
+
+        Raises:
+            ValueError: The qubit is not mapped to a tweezer.
+        """
+
+    def readout_confusion_matrices(self, qubits: List[int]) -> List[Tuple[float, float]]:
+        """
+        Returns the readout confusion matrices of the qubits measured into a register, in register order.
+
+        Args:
+            qubits (List[int]): The qubit measured into each bit of the register.
+
+        Returns:
+            List[Tuple[float, float]]: The confusion matrix of each qubit, as for `readout_confusion_matrix`.
+
+        Raises:
+            ValueError: A qubit is not mapped to a tweezer.
+        """
+
+    def tweezer_error_score(self, tweezer: int, layout_name: Optional[str] = None) -> float:
+        """
+        Returns the error score of a tweezer in a given Layout.
+
+        The score is the sum of the largest single-qubit gate error rate of the tweezer, the
+        largest two-qubit gate error rate over the pairs of tweezers it belongs to and its
+        readout error rate.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            float: The error score of the tweezer, zero if no error rates are set.
+
+        Raises:
+            ValueError: The layout does not exist.
+        """
+
+    def tweezer_position_dimensions(self, layout_name: Optional[str] = None) -> int:
+        """
+        Returns the number of coordinates of the tweezer positions of a given Layout.
+
+        Args:
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            int: 2 for two-dimensional and 3 for three-dimensional positions.
+
+        Raises:
+            ValueError: The layout does not exist or has no tweezer positions.
+        """
+
+    def tweezer_distance(
+        self, tweezer0: int, tweezer1: int, layout_name: Optional[str] = None
+    ) -> float:
+        """
+        Returns the distance between two tweezers in a given Layout.
+
+        Args:
+            tweezer0 (int): The index of the first tweezer.
+            tweezer1 (int): The index of the second tweezer.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            float: The distance between the tweezers in micrometers.
+
+        Raises:
+            ValueError: The position of one of the tweezers is not set.
+        """
+
+    def qubit_distance(self, qubit0: int, qubit1: int) -> float:
+        """
+        Returns the distance between the tweezers two qubits are mapped to in the current Layout.
+
+        Args:
+            qubit0 (int): The index of the first qubit.
+            qubit1 (int): The index of the second qubit.
+
+        Returns:
+            float: The distance between the qubits in micrometers.
+
+        Raises:
+            ValueError: A qubit is not mapped or the position of its tweezer is not set.
+        """
+
+    def nearest_tweezers(
+        self, tweezer: int, number: int, layout_name: Optional[str] = None
+    ) -> List[Tuple[int, float]]:
+        """
+        Returns the tweezers closest to a given tweezer in a given Layout.
+
+        Only tweezers with a set position are considered.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            number (int): The maximal number of tweezers to return.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            List[Tuple[int, float]]: The closest tweezers with their distances, sorted by distance.
+
+        Raises:
+            ValueError: The position of the given tweezer is not set.
+        """
+
+    def tweezers_within_distance(
+        self, tweezer: int, max_distance: float, layout_name: Optional[str] = None
+    ) -> List[Tuple[int, float]]:
+        """
+        Returns the tweezers within a given distance of a given tweezer in a given Layout.
+
+        Only tweezers with a set position are considered.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            max_distance (float): The maximal distance in micrometers, inclusive.
+            layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+
+        Returns:
+            List[Tuple[int, float]]: The tweezers in range with their distances, sorted by distance.
+
+        Raises:
+            ValueError: The position of the given tweezer is not set.
+        """
+
     def two_qubit_edges(self) -> Sequence[(int, int)]:
         """
         Return the list of pairs of qubits linked by a native two-qubit-gate in the device.
@@ -953,6 +1781,166 @@ class TweezerMutableDevice:
             ValueError: No layout name provided and no current layout set.
         """
 
+    def set_tweezer_position(
+        self, tweezer: int, position: List[float], layout_name: Optional[str] = None
+    ):
+        """
+        Set the position of a tweezer in a given Layout.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            position (List[float]): The (x, y) or (x, y, z) coordinates of the tweezer in micrometers.
+            layout_name (Optional[str]): The name of the Layout to set the position in. Defaults to the current Layout.
+
+        Raises:
+            ValueError: The tweezer is not present, the position is not valid or has a different
+                number of coordinates than the positions already set in the Layout.
+        """
+
+    def set_tweezer_decoherence_rates(
+        self,
+        tweezer: int,
+        damping: float,
+        dephasing: float,
+        depolarising: float,
+        layout_name: Optional[str] = None,
+    ):
+        """
+        Set the decoherence rates of a qubit held in a tweezer in a given Layout.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            damping (float): The damping rate, 1/T1.
+            dephasing (float): The pure dephasing rate.
+            depolarising (float): The depolarising rate.
+            layout_name (Optional[str]): The name of the Layout to set the rates in. Defaults to the current Layout.
+
+        Raises:
+            ValueError: The tweezer is not present or a rate is negative or not finite.
+        """
+
+    def set_tweezer_t1_t2(
+        self, tweezer: int, t1: float, t2: float, layout_name: Optional[str] = None
+    ):
+        """
+        Set the decoherence rates of a qubit held in a tweezer from its T1 and T2 times.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            t1 (float): The relaxation time T1.
+            t2 (float): The coherence time T2, at most 2 T1.
+            layout_name (Optional[str]): The name of the Layout to set the rates in. Defaults to the current Layout.
+
+        Raises:
+            ValueError: The tweezer is not present or the times are not valid.
+        """
+
+    def set_tweezer_single_qubit_gate_error(
+        self,
+        hqslang: str,
+        tweezer: int,
+        error_rate: float,
+        layout_name: Optional[str] = None,
+    ):
+        """
+        Set the error rate of a single-qubit gate for a tweezer in a given Layout.
+
+        Args:
+            hqslang (str): The hqslang name of a single-qubit gate.
+            tweezer (int): The index of the tweezer.
+            error_rate (float): The probability of an error during the gate.
+            layout_name (Optional[str]): The name of the Layout to set the error rate in.
+                Defaults to the current Layout.
+
+        Raises:
+            ValueError: The gate has no gate time for the tweezer or the error rate is not in [0, 1].
+        """
+
+    def set_tweezer_two_qubit_gate_error(
+        self,
+        hqslang: str,
+        tweezer0: int,
+        tweezer1: int,
+        error_rate: float,
+        layout_name: Optional[str] = None,
+    ):
+        """
+        Set the error rate of a two-qubit gate for a tweezer couple in a given Layout.
+
+        Args:
+            hqslang (str): The hqslang name of a two-qubit gate.
+            tweezer0 (int): The index of the first tweezer.
+            tweezer1 (int): The index of the second tweezer.
+            error_rate (float): The probability of an error during the gate.
+            layout_name (Optional[str]): The name of the Layout to set the error rate in.
+                Defaults to the current Layout.
+
+        Raises:
+            ValueError: The gate has no gate time for the tweezers or the error rate is not in [0, 1].
+        """
+
+    def set_tweezer_three_qubit_gate_error(
+        self,
+        hqslang: str,
+        tweezer0: int,
+        tweezer1: int,
+        tweezer2: int,
+        error_rate: float,
+        layout_name: Optional[str] = None,
+    ):
+        """
+        Set the error rate of a three-qubit gate for a tweezer trio in a given Layout.
+
+        Args:
+            hqslang (str): The hqslang name of a three-qubit gate.
+            tweezer0 (int): The index of the first tweezer.
+            tweezer1 (int): The index of the second tweezer.
+            tweezer2 (int): The index of the third tweezer.
+            error_rate (float): The probability of an error during the gate.
+            layout_name (Optional[str]): The name of the Layout to set the error rate in.
+                Defaults to the current Layout.
+
+        Raises:
+            ValueError: The gate has no gate time for the tweezers or the error rate is not in [0, 1].
+        """
+
+    def set_tweezer_readout_error(
+        self, tweezer: int, error_rate: float, layout_name: Optional[str] = None
+    ):
+        """
+        Set the readout error rate of a tweezer in a given Layout.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            error_rate (float): The probability of reading out the wrong state of the qubit held in the tweezer.
+            layout_name (Optional[str]): The name of the Layout to set the error rate in.
+                Defaults to the current Layout.
+
+        Raises:
+            ValueError: The tweezer is not present or the error rate is not in [0, 1].
+        """
+
+    def set_tweezer_readout_confusion_matrix(
+        self,
+        tweezer: int,
+        zero_to_one: float,
+        one_to_zero: float,
+        layout_name: Optional[str] = None,
+    ):
+        """
+        Set the readout confusion matrix of a tweezer in a given Layout.
+
+        Args:
+            tweezer (int): The index of the tweezer.
+            zero_to_one (float): The probability of reading out 1 for a qubit in state |0>.
+            one_to_zero (float): The probability of reading out 0 for a qubit in state |1>.
+            layout_name (Optional[str]): The name of the Layout to set the confusion matrix in.
+                Defaults to the current Layout.
+
+        Raises:
+            ValueError: The tweezer is not present or a probability is not in [0, 1].
+        """
+
     def set_allow_reset(self, allow_reset: bool):
         """
         Set whether the device allows PragmaActiveReset operations or not.
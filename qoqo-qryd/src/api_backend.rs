@@ -22,8 +22,8 @@ use qoqo::{convert_into_circuit, convert_into_quantum_program};
 use roqoqo::prelude::*;
 use roqoqo::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister};
 use roqoqo::Circuit;
-use roqoqo_qryd::APIBackend;
 use roqoqo_qryd::QRydAPIDevice;
+use roqoqo_qryd::{APIBackend, ResultCounts};
 use std::collections::HashMap;
 
 /// Qoqo backend interfacing QRydDemo WebAPI.
@@ -174,6 +174,39 @@ impl APIBackendWrapper {
         })
     }
 
+    /// Mitigate the readout errors of the counts of a WebAPI job result.
+    ///
+    /// Args:
+    ///     counts (Dict[str, int]): The counts of the job result, as in `get_job_result()["data"]["counts"]`.
+    ///     number_qubits (int): The number of measured qubits.
+    ///     confusion_matrices (List[Tuple[float, float]]): The probabilities of reading out 1 for state |0>
+    ///         and 0 for state |1> of each measured qubit, see `TweezerDevice.readout_confusion_matrices`.
+    ///     method (Optional[str]): "inversion" or "least_squares". Defaults to "inversion".
+    ///
+    /// Returns:
+    ///     Dict[str, float]: The mitigated probabilities, keyed by bitstrings whose i-th character is the result of qubit i.
+    ///
+    /// Raises:
+    ///     ValueError: A count cannot be parsed, a probability is not in [0, 1], the method is unknown or the mitigation failed.
+    #[staticmethod]
+    #[pyo3(text_signature = "(counts, number_qubits, confusion_matrices, method, /)")]
+    pub fn mitigate_counts(
+        counts: HashMap<String, u64>,
+        number_qubits: usize,
+        confusion_matrices: Vec<(f64, f64)>,
+        method: Option<String>,
+    ) -> PyResult<HashMap<String, f64>> {
+        let (confusion_matrices, method) =
+            crate::readout_mitigation_arguments(confusion_matrices, method)?;
+        APIBackend::mitigate_counts(
+            &ResultCounts { counts },
+            number_qubits,
+            &confusion_matrices,
+            method,
+        )
+        .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
     /// Delete a posted WebAPI job
     ///
     /// Args:
@@ -10,7 +10,7 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use bincode::{deserialize, serialize};
+use bincode::serialize;
 use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{IntoPyDict, PyByteArray};
@@ -100,6 +100,8 @@ impl EmulatorDeviceWrapper {
     ///     seed (Optional[int]): Optionally overwrite seed value from downloaded device instance.
     ///     dev (Optional[bool]): The boolean to set the dev header to.
     ///     api_version (Optional[str]): The version of the QRYD API to use. Defaults to "v1_1".
+    ///     cache_dir (Optional[str]): The directory of the on-disk device cache to use.
+    ///                         Defaults to the environmental variable `$QRYD_API_CACHE_DIR`.
     ///
     /// Returns
     ///     TweezerDevice: The new TweezerDevice instance with populated tweezer data.
@@ -108,7 +110,9 @@ impl EmulatorDeviceWrapper {
     ///     RoqoqoBackendError
     #[staticmethod]
     #[cfg(feature = "web-api")]
-    #[pyo3(text_signature = "(device_name, access_token, mock_port, seed, api_version, /)")]
+    #[pyo3(
+        text_signature = "(device_name, access_token, mock_port, seed, api_version, cache_dir, /)"
+    )]
     pub fn from_api(
         device_name: Option<String>,
         access_token: Option<String>,
@@ -116,10 +120,39 @@ impl EmulatorDeviceWrapper {
         seed: Option<usize>,
         dev: Option<bool>,
         api_version: Option<String>,
+        cache_dir: Option<String>,
     ) -> PyResult<Self> {
-        let internal =
-            EmulatorDevice::from_api(device_name, access_token, mock_port, seed, dev, api_version)
-                .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+        let internal = EmulatorDevice::from_api_with_options(
+            device_name,
+            access_token,
+            mock_port,
+            seed,
+            dev,
+            api_version,
+            &crate::download_options(cache_dir),
+        )
+        .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+        Ok(EmulatorDeviceWrapper { internal })
+    }
+
+    /// Creates a new EmulatorDevice instance from a device snapshot file.
+    ///
+    /// The offline counterpart of `from_api`.
+    ///
+    /// Args:
+    ///     path (str): The path of the snapshot file, as written by `download_device_snapshot`.
+    ///     seed (Optional[int]): Optionally overwrite seed value from the stored device instance.
+    ///
+    /// Returns:
+    ///     EmulatorDevice: The new EmulatorDevice instance.
+    ///
+    /// Raises:
+    ///     ValueError: The file could not be read or does not contain a compatible device.
+    #[staticmethod]
+    #[pyo3(text_signature = "(path, seed, /)")]
+    pub fn from_snapshot(path: String, seed: Option<usize>) -> PyResult<Self> {
+        let internal = EmulatorDevice::from_snapshot(path, seed)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
         Ok(EmulatorDeviceWrapper { internal })
     }
 
@@ -183,9 +216,8 @@ impl EmulatorDeviceWrapper {
         Python::with_gil(|py| -> Option<PyObject> {
             self.internal
                 .internal
-                .qubit_to_tweezer
-                .as_ref()
-                .map(|mapping| mapping.into_py_dict_bound(py).into())
+                .qubit_to_tweezer()
+                .map(|mapping| mapping.as_map().into_py_dict_bound(py).into())
         })
     }
 
@@ -460,7 +492,7 @@ impl EmulatorDeviceWrapper {
 
         Ok(EmulatorDeviceWrapper {
             internal: EmulatorDevice {
-                internal: deserialize(&bytes[..]).map_err(|_| {
+                internal: TweezerDevice::from_bincode(&bytes[..]).map_err(|_| {
                     PyValueError::new_err("Input cannot be deserialized to EmulatorDevice")
                 })?,
             },
@@ -501,7 +533,7 @@ impl EmulatorDeviceWrapper {
     fn from_json(input: &str) -> PyResult<EmulatorDeviceWrapper> {
         let tw: TweezerDevice = serde_json::from_str(input)
             .map_err(|_| PyValueError::new_err("Input cannot be deserialized to EmulatorDevice"))?;
-        if tw.available_gates.is_none() || tw.layout_register.is_some() {
+        if tw.available_gates.is_none() || tw.layout_register().is_some() {
             return Err(PyValueError::new_err(
                 "Trying to deserialize an incorrectly setup device into EmulatorDevice",
             ));
@@ -535,6 +567,17 @@ impl EmulatorDeviceWrapper {
     pub fn seed(&self) -> Option<usize> {
         self.internal.seed()
     }
+
+    /// Returns whether the device is a cached copy, used because the QRyd WebAPI was unreachable.
+    ///
+    /// Only set by `from_api` and `device_from_api` with the device cache enabled
+    /// via the environmental variable `$QRYD_API_CACHE_DIR`.
+    ///
+    /// Returns:
+    ///     bool: Whether the device definition may be outdated.
+    pub fn is_offline_fallback(&self) -> bool {
+        self.internal.is_offline_fallback()
+    }
 }
 
 /// Convert generic python object to [roqoqo_qryd::EmulatorDevice].
@@ -547,7 +590,10 @@ pub fn convert_into_device(input: &Bound<PyAny>) -> Result<EmulatorDevice, QoqoB
     let bytes = get_bytes
         .extract::<Vec<u8>>()
         .map_err(|_| QoqoBackendError::CannotExtractObject)?;
-    bincode::deserialize(&bytes[..]).map_err(|_| QoqoBackendError::CannotExtractObject)
+    Ok(EmulatorDevice {
+        internal: TweezerDevice::from_bincode(&bytes[..])
+            .map_err(|_| QoqoBackendError::CannotExtractObject)?,
+    })
 }
 
 /// Emulator devices for the QRyd platform.
@@ -0,0 +1,282 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Interaction models
+//!
+//! Models deriving the availability, gate time and fidelity of multi-qubit gates
+//! from the distances between tweezers.
+
+use std::collections::HashMap;
+
+use pyo3::exceptions::{PyNotImplementedError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use roqoqo_qryd::{BlockadeInteractionModel, CutoffInteractionModel, InteractionModel};
+
+/// Interaction model with a hard distance cut-off and a gate time increasing with the square of the distance.
+///
+/// Only supports PhaseShiftedControlledZ and PhaseShiftedControlledPhase gates.
+///
+/// Args:
+///     cutoff (float): The distance in micrometers above which two-qubit gates are not possible.
+///     gate_time_coefficient (float): The gate time per squared distance.
+#[pyclass(name = "CutoffInteractionModel", module = "qoqo_qryd")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CutoffInteractionModelWrapper {
+    /// Internal storage of [roqoqo_qryd::CutoffInteractionModel]
+    pub internal: CutoffInteractionModel,
+}
+
+#[pymethods]
+impl CutoffInteractionModelWrapper {
+    /// Creates a new CutoffInteractionModel.
+    ///
+    /// Args:
+    ///     cutoff (float): The distance in micrometers above which two-qubit gates are not possible.
+    ///     gate_time_coefficient (float): The gate time per squared distance.
+    ///
+    /// Returns:
+    ///     CutoffInteractionModel: The new CutoffInteractionModel instance.
+    #[new]
+    #[pyo3(text_signature = "(cutoff, gate_time_coefficient, /)")]
+    pub fn new(cutoff: f64, gate_time_coefficient: f64) -> Self {
+        Self {
+            internal: CutoffInteractionModel::new(cutoff, gate_time_coefficient),
+        }
+    }
+
+    /// Returns the gate time of a two-qubit gate between tweezers at a given distance.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of the two-qubit gate.
+    ///     distance (float): The distance between the tweezers in micrometers.
+    ///
+    /// Returns:
+    ///     Optional[float]: The gate time, None if the gate is not available at the given distance.
+    #[pyo3(text_signature = "(hqslang, distance, /)")]
+    pub fn two_qubit_gate_time(&self, hqslang: &str, distance: f64) -> Option<f64> {
+        self.internal.two_qubit_gate_time(hqslang, distance)
+    }
+
+    /// Return a string containing a printable representation of the model.
+    ///
+    /// Returns:
+    ///     str: The printable string representation of the model.
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.internal)
+    }
+
+    /// Return the __richcmp__ magic method to perform rich comparison operations on CutoffInteractionModel.
+    ///
+    /// Args:
+    ///     self: The CutoffInteractionModel object.
+    ///     other: The object to compare self to.
+    ///     op: Whether they should be equal or not.
+    ///
+    /// Returns:
+    ///     bool: Whether the two models compared evaluated to True or False.
+    fn __richcmp__(&self, other: Py<PyAny>, op: pyo3::class::basic::CompareOp) -> PyResult<bool> {
+        let other: CutoffInteractionModelWrapper =
+            Python::with_gil(|py| -> PyResult<CutoffInteractionModelWrapper> {
+                other.extract(py)
+            })?;
+        match op {
+            pyo3::class::basic::CompareOp::Eq => Ok(self.internal == other.internal),
+            pyo3::class::basic::CompareOp::Ne => Ok(self.internal != other.internal),
+            _ => Err(PyNotImplementedError::new_err(
+                "Other comparison not implemented.",
+            )),
+        }
+    }
+}
+
+/// Interaction model based on the Rydberg blockade with a van der Waals interaction C6 / r^6.
+///
+/// A gate is available if the tweezers are within the blockade radius. The imperfect blockade
+/// lengthens the gate time and reduces the fidelity with the leakage (Ω / V(r))^2.
+///
+/// Args:
+///     c6_coefficient (float): The C6 coefficient of the van der Waals interaction in MHz µm^6.
+///     rabi_frequency (float): The Rabi frequency of the Rydberg excitation in MHz.
+///     gate_times (Dict[str, float]): The gate time of each supported two- and three-qubit gate in the limit of perfect blockade.
+///     gate_fidelity (float): The gate fidelity in the limit of perfect blockade.
+#[pyclass(name = "BlockadeInteractionModel", module = "qoqo_qryd")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockadeInteractionModelWrapper {
+    /// Internal storage of [roqoqo_qryd::BlockadeInteractionModel]
+    pub internal: BlockadeInteractionModel,
+}
+
+#[pymethods]
+impl BlockadeInteractionModelWrapper {
+    /// Creates a new BlockadeInteractionModel.
+    ///
+    /// Args:
+    ///     c6_coefficient (float): The C6 coefficient of the van der Waals interaction in MHz µm^6.
+    ///     rabi_frequency (float): The Rabi frequency of the Rydberg excitation in MHz.
+    ///     gate_times (Dict[str, float]): The gate time of each supported two- and three-qubit gate in the limit of perfect blockade.
+    ///     gate_fidelity (float): The gate fidelity in the limit of perfect blockade.
+    ///
+    /// Returns:
+    ///     BlockadeInteractionModel: The new BlockadeInteractionModel instance.
+    ///
+    /// Raises:
+    ///     ValueError: A parameter is out of range.
+    #[new]
+    #[pyo3(text_signature = "(c6_coefficient, rabi_frequency, gate_times, gate_fidelity, /)")]
+    pub fn new(
+        c6_coefficient: f64,
+        rabi_frequency: f64,
+        gate_times: HashMap<String, f64>,
+        gate_fidelity: f64,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            internal: BlockadeInteractionModel::new(
+                c6_coefficient,
+                rabi_frequency,
+                gate_times,
+                gate_fidelity,
+            )
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?,
+        })
+    }
+
+    /// Sets a blockade radius overriding the radius derived from C6 and the Rabi frequency.
+    ///
+    /// Args:
+    ///     blockade_radius (float): The blockade radius in µm.
+    ///
+    /// Raises:
+    ///     ValueError: The blockade radius is not positive.
+    #[pyo3(text_signature = "(blockade_radius, /)")]
+    pub fn set_blockade_radius(&mut self, blockade_radius: f64) -> PyResult<()> {
+        self.internal
+            .set_blockade_radius(blockade_radius)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Returns the blockade radius.
+    ///
+    /// Returns:
+    ///     float: The set blockade radius, or (C6 / Ω)^(1/6) if none is set.
+    pub fn blockade_radius(&self) -> f64 {
+        self.internal.blockade_radius()
+    }
+
+    /// Returns the gate time of a two-qubit gate between tweezers at a given distance.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of the two-qubit gate.
+    ///     distance (float): The distance between the tweezers in micrometers.
+    ///
+    /// Returns:
+    ///     Optional[float]: The gate time, None if the gate is not available at the given distance.
+    #[pyo3(text_signature = "(hqslang, distance, /)")]
+    pub fn two_qubit_gate_time(&self, hqslang: &str, distance: f64) -> Option<f64> {
+        self.internal.two_qubit_gate_time(hqslang, distance)
+    }
+
+    /// Returns the fidelity of a two-qubit gate between tweezers at a given distance.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of the two-qubit gate.
+    ///     distance (float): The distance between the tweezers in micrometers.
+    ///
+    /// Returns:
+    ///     Optional[float]: The gate fidelity, None if the gate is not available at the given distance.
+    #[pyo3(text_signature = "(hqslang, distance, /)")]
+    pub fn two_qubit_gate_fidelity(&self, hqslang: &str, distance: f64) -> Option<f64> {
+        self.internal.two_qubit_gate_fidelity(hqslang, distance)
+    }
+
+    /// Returns the gate time of a three-qubit gate between tweezers at given distances.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of the three-qubit gate.
+    ///     distances (Tuple[float, float, float]): The distances between the three pairs of tweezers in micrometers.
+    ///
+    /// Returns:
+    ///     Optional[float]: The gate time, None if the gate is not available at the given distances.
+    #[pyo3(text_signature = "(hqslang, distances, /)")]
+    pub fn three_qubit_gate_time(&self, hqslang: &str, distances: [f64; 3]) -> Option<f64> {
+        self.internal.three_qubit_gate_time(hqslang, distances)
+    }
+
+    /// Return a string containing a printable representation of the model.
+    ///
+    /// Returns:
+    ///     str: The printable string representation of the model.
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.internal)
+    }
+
+    /// Return the __richcmp__ magic method to perform rich comparison operations on BlockadeInteractionModel.
+    ///
+    /// Args:
+    ///     self: The BlockadeInteractionModel object.
+    ///     other: The object to compare self to.
+    ///     op: Whether they should be equal or not.
+    ///
+    /// Returns:
+    ///     bool: Whether the two models compared evaluated to True or False.
+    fn __richcmp__(&self, other: Py<PyAny>, op: pyo3::class::basic::CompareOp) -> PyResult<bool> {
+        let other: BlockadeInteractionModelWrapper =
+            Python::with_gil(|py| -> PyResult<BlockadeInteractionModelWrapper> {
+                other.extract(py)
+            })?;
+        match op {
+            pyo3::class::basic::CompareOp::Eq => Ok(self.internal == other.internal),
+            pyo3::class::basic::CompareOp::Ne => Ok(self.internal != other.internal),
+            _ => Err(PyNotImplementedError::new_err(
+                "Other comparison not implemented.",
+            )),
+        }
+    }
+}
+
+/// Extracts an interaction model from a python object.
+///
+/// Args:
+///     model: A CutoffInteractionModel or BlockadeInteractionModel.
+///
+/// Returns:
+///     Box<dyn InteractionModel>: The internal interaction model.
+///
+/// Raises:
+///     TypeError: The object is not an interaction model.
+pub(crate) fn extract_interaction_model(
+    model: &Bound<PyAny>,
+) -> PyResult<Box<dyn InteractionModel>> {
+    if let Ok(model) = model.extract::<CutoffInteractionModelWrapper>() {
+        Ok(Box::new(model.internal))
+    } else if let Ok(model) = model.extract::<BlockadeInteractionModelWrapper>() {
+        Ok(Box::new(model.internal))
+    } else {
+        Err(PyTypeError::new_err(
+            "Input is not a CutoffInteractionModel or BlockadeInteractionModel.",
+        ))
+    }
+}
+
+/// Interaction models for tweezer devices.
+///
+/// .. autosummary::
+///    :toctree: generated/
+///
+///    CutoffInteractionModel
+///    BlockadeInteractionModel
+///
+#[pymodule]
+pub fn interaction_models(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<CutoffInteractionModelWrapper>()?;
+    m.add_class::<BlockadeInteractionModelWrapper>()?;
+    Ok(())
+}
@@ -25,11 +25,11 @@
 //! * operations: roqoqo Pragma operations specific to QRyd devices that can change the topology of QRyd devices
 //! * simulator (optional): A QuEST based simulator for QRyd devices that checks the availability of the quantum operations on a chosen device during simulation
 
-#[cfg(feature = "web-api")]
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use pyo3::wrap_pymodule;
+use std::collections::HashMap;
 
 /// qoqo utilities for QRyd quantum computers.
 ///
@@ -48,6 +48,7 @@ use pyo3::wrap_pymodule;
 ///     APIBackend
 ///     tweezer_devices
 ///     emulator_devices
+///     interaction_models
 ///
 pub mod qryd_devices;
 pub use qryd_devices::*;
@@ -65,6 +66,11 @@ pub use tweezer_devices::*;
 pub mod emulator_devices;
 pub use emulator_devices::*;
 
+/// Interaction models for tweezer devices.
+///
+pub mod interaction_models;
+pub use interaction_models::*;
+
 #[cfg(feature = "simulator")]
 pub mod simulator_backend;
 #[cfg(feature = "simulator")]
@@ -86,6 +92,16 @@ pub use api_backend::APIBackendWrapper;
 pub mod api_devices;
 pub use api_devices::*;
 
+/// Returns the options of device downloads, with the cache directory overriding `$QRYD_API_CACHE_DIR`.
+#[cfg(feature = "web-api")]
+pub(crate) fn download_options(cache_dir: Option<String>) -> roqoqo_qryd::DownloadOptions {
+    let options = roqoqo_qryd::DownloadOptions::from_env();
+    match cache_dir {
+        Some(directory) => options.with_cache_dir(directory),
+        None => options,
+    }
+}
+
 /// Creates a new TweezerDevice instance containing populated tweezer data or EmulatorDevice instance.
 ///
 /// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
@@ -98,6 +114,8 @@ pub use api_devices::*;
 ///     seed (Optional[int]): Optionally overwrite seed value from downloaded device instance.
 ///     dev (Optional[bool]): The boolean to set the dev header to.
 ///     api_version (Optional[str]): The version of the QRYD API to use. Defaults to "v1_1".
+///     cache_dir (Optional[str]): The directory of the on-disk device cache to use.
+///                         Defaults to the environmental variable `$QRYD_API_CACHE_DIR`.
 ///
 /// Returns
 ///     Union[TweezerDevice, EmulatorDevice]: Either the TweezerDevice or EmulatorDevice instance
@@ -113,9 +131,17 @@ pub fn device_from_api(
     seed: Option<usize>,
     dev: Option<bool>,
     api_version: Option<String>,
+    cache_dir: Option<String>,
 ) -> PyResult<PyObject> {
     Python::with_gil(|py| -> PyResult<PyObject> {
-        match roqoqo_qryd::device_from_api(device_name, access_token, seed, dev, api_version) {
+        match roqoqo_qryd::device_from_api_with_options(
+            device_name,
+            access_token,
+            seed,
+            dev,
+            api_version,
+            &download_options(cache_dir),
+        ) {
             Ok(device) => match device {
                 roqoqo_qryd::CombinedDevice::Tweezer(tweezer_device) => Ok(TweezerDeviceWrapper {
                     internal: tweezer_device,
@@ -133,6 +159,120 @@ pub fn device_from_api(
     })
 }
 
+/// Downloads a device from the QRyd WebAPI and writes it to a snapshot file.
+///
+/// The snapshot stores the device together with its name, the API version and the download time.
+/// It can be loaded without network access with `device_from_snapshot`.
+///
+/// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
+///
+/// Args
+///     path (str): The path of the snapshot file to write. An existing file is overwritten.
+///     device_name (Optional[str]): The name of the device to download. Defaults to "qryd_emulator".
+///     access_token (Optional[str]): An access_token is required to access QRYD hardware and emulators.
+///                         The access_token can either be given as an argument here
+///                             or set via the environmental variable `$QRYD_API_TOKEN`.
+///     mock_port (Optional[str]): Server port to be used for testing purposes.
+///     dev (Optional[bool]): The boolean to set the dev header to.
+///     api_version (Optional[str]): The version of the QRYD API to use. Defaults to "v1_1".
+///
+/// Raises:
+///     RoqoqoBackendError
+#[cfg(feature = "web-api")]
+#[pyfunction]
+pub fn download_device_snapshot(
+    path: String,
+    device_name: Option<String>,
+    access_token: Option<String>,
+    mock_port: Option<String>,
+    dev: Option<bool>,
+    api_version: Option<String>,
+) -> PyResult<()> {
+    roqoqo_qryd::DeviceSnapshot::from_api(device_name, access_token, mock_port, dev, api_version)
+        .and_then(|snapshot| snapshot.save(path))
+        .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+}
+
+/// Creates a new TweezerDevice or EmulatorDevice instance from a device snapshot file.
+///
+/// The offline counterpart of `device_from_api`.
+///
+/// Args
+///     path (str): The path of the snapshot file, as written by `download_device_snapshot`.
+///     seed (Optional[int]): Optionally overwrite seed value from the stored device instance.
+///
+/// Returns
+///     Union[TweezerDevice, EmulatorDevice]: Either the TweezerDevice or EmulatorDevice instance
+///         depending on the stored information.
+///
+/// Raises:
+///     ValueError: The file could not be read or does not contain a valid snapshot.
+#[pyfunction]
+pub fn device_from_snapshot(path: String, seed: Option<usize>) -> PyResult<PyObject> {
+    let device = roqoqo_qryd::device_from_snapshot(path, seed)
+        .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+    Python::with_gil(|py| -> PyResult<PyObject> {
+        match device {
+            roqoqo_qryd::CombinedDevice::Tweezer(tweezer_device) => Ok(TweezerDeviceWrapper {
+                internal: tweezer_device,
+            }
+            .into_py(py)),
+            roqoqo_qryd::CombinedDevice::Emulator(emulator_device) => Ok(EmulatorDeviceWrapper {
+                internal: emulator_device,
+            }
+            .into_py(py)),
+        }
+    })
+}
+
+/// Converts the confusion matrices and mitigation method arguments of the readout mitigation functions.
+pub(crate) fn readout_mitigation_arguments(
+    confusion_matrices: Vec<(f64, f64)>,
+    method: Option<String>,
+) -> PyResult<(
+    Vec<roqoqo_qryd::ReadoutConfusionMatrix>,
+    roqoqo_qryd::ReadoutMitigationMethod,
+)> {
+    let confusion_matrices = confusion_matrices
+        .into_iter()
+        .map(|(zero_to_one, one_to_zero)| {
+            roqoqo_qryd::ReadoutConfusionMatrix::new(zero_to_one, one_to_zero)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+    let method = match method {
+        Some(method) => method
+            .parse()
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?,
+        None => roqoqo_qryd::ReadoutMitigationMethod::default(),
+    };
+    Ok((confusion_matrices, method))
+}
+
+/// Mitigates the readout errors of the shots of a bit register.
+///
+/// Args:
+///     register (List[List[bool]]): The measured shots, with one entry per bit of the register.
+///     confusion_matrices (List[Tuple[float, float]]): The probabilities of reading out 1 for state |0>
+///         and 0 for state |1> of the qubit measured into each bit, see `TweezerDevice.readout_confusion_matrices`.
+///     method (Optional[str]): "inversion" or "least_squares". Defaults to "inversion".
+///
+/// Returns:
+///     Dict[str, float]: The mitigated probabilities, keyed by bitstrings whose i-th character is the i-th bit.
+///
+/// Raises:
+///     ValueError: A probability is not in [0, 1], the method is unknown or the mitigation failed.
+#[pyfunction]
+pub fn mitigate_bit_register(
+    register: Vec<Vec<bool>>,
+    confusion_matrices: Vec<(f64, f64)>,
+    method: Option<String>,
+) -> PyResult<HashMap<String, f64>> {
+    let (confusion_matrices, method) = readout_mitigation_arguments(confusion_matrices, method)?;
+    roqoqo_qryd::mitigate_bit_register(&register, &confusion_matrices, method)
+        .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+}
+
 /// QRyd utilities for qoqo quantum computation toolkit.
 ///
 /// qoqo is the HQS python package to represent quantum circuits.
@@ -146,7 +286,11 @@ pub fn device_from_api(
 ///     qryd_devices
 ///     tweezer_devices
 ///     emulator_devices
+///     interaction_models
 ///     device_from_api
+///     download_device_snapshot
+///     device_from_snapshot
+///     mitigate_bit_register
 ///
 ///
 #[pymodule]
@@ -157,6 +301,10 @@ fn qoqo_qryd(_py: Python, module: &Bound<PyModule>) -> PyResult<()> {
     module.add_class::<APIBackendWrapper>()?;
     #[cfg(feature = "web-api")]
     module.add_function(wrap_pyfunction!(device_from_api, module)?)?;
+    #[cfg(feature = "web-api")]
+    module.add_function(wrap_pyfunction!(download_device_snapshot, module)?)?;
+    module.add_function(wrap_pyfunction!(device_from_snapshot, module)?)?;
+    module.add_function(wrap_pyfunction!(mitigate_bit_register, module)?)?;
     let wrapper = wrap_pymodule!(qryd_devices::qryd_devices);
     module.add_wrapped(wrapper)?;
     let wrapper = wrap_pymodule!(api_devices::api_devices);
@@ -168,6 +316,8 @@ fn qoqo_qryd(_py: Python, module: &Bound<PyModule>) -> PyResult<()> {
     module.add_wrapped(wrapper)?;
     let wrapper = wrap_pymodule!(emulator_devices::emulator_devices);
     module.add_wrapped(wrapper)?;
+    let wrapper = wrap_pymodule!(interaction_models::interaction_models);
+    module.add_wrapped(wrapper)?;
     // Adding nice imports corresponding to maturin example
     let system = PyModule::import_bound(_py, "sys")?;
     let binding = system.getattr("modules")?;
@@ -186,5 +336,9 @@ fn qoqo_qryd(_py: Python, module: &Bound<PyModule>) -> PyResult<()> {
         "qoqo_qryd.emulator_devices",
         module.getattr("emulator_devices")?,
     )?;
+    system_modules.set_item(
+        "qoqo_qryd.interaction_models",
+        module.getattr("interaction_models")?,
+    )?;
     Ok(())
 }
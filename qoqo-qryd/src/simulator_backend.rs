@@ -13,16 +13,22 @@
 //! Provides a QuEST based simulator for the QuEST quantum computer
 
 use crate::tweezer_devices::convert_into_device;
-use bincode::{deserialize, serialize};
+use bincode::serialize;
+use numpy::ToPyArray;
 use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyByteArray, PyType};
+use pyo3::types::{PyByteArray, PyDict, PyType};
 use qoqo::convert_into_circuit;
+use qoqo::CircuitWrapper;
 use qoqo::QoqoBackendError;
+use roqoqo::measurements::ClassicalRegister;
 use roqoqo::prelude::*;
 use roqoqo::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister};
 use roqoqo::Circuit;
-use roqoqo_qryd::SimulatorBackend;
+use roqoqo_qryd::{
+    post_select_shots, AtomLossModel, CrosstalkModel, SimulatedState, SimulationMode,
+    SimulatorBackend, SimulatorNoiseModel, StateBasis, StateSnapshot, DEFAULT_LOST_QUBITS_REGISTER,
+};
 use std::collections::HashMap;
 
 /// Local simulator backend for Rydberg devices.
@@ -39,6 +45,13 @@ use std::collections::HashMap;
 /// and is compatible with running single circuits, running and evaluating measurements
 /// and running QuantumPrograms on simulated QRyd devices.
 ///
+/// With a seeded device, the simulations are reproducible. The circuits of a measurement are run
+/// with distinct seeds derived from the device seed, sequentially or in parallel with
+/// `set_number_threads`, and their shots can be split into batches with `set_shot_batch_size`.
+///
+/// Clifford circuits on large devices can be simulated with a stabilizer tableau instead of a
+/// state vector, see `set_simulation_mode`.
+///
 /// Args:
 ///     device (Union[QRydDevice,TweezerDevice]): The device providing information about the available operations.
 ///
@@ -135,7 +148,7 @@ impl SimulatorBackendWrapper {
             .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
 
         Ok(SimulatorBackendWrapper {
-            internal: deserialize(&bytes[..]).map_err(|_| {
+            internal: SimulatorBackend::from_bincode(&bytes[..]).map_err(|_| {
                 PyValueError::new_err("Input cannot be deserialized to SimulatorBackend")
             })?,
         })
@@ -174,6 +187,417 @@ impl SimulatorBackendWrapper {
         })
     }
 
+    /// Enable noisy simulations with the decoherence rates of the device and the given gate errors.
+    ///
+    /// After each gate, the decoherence rates of the tweezer each of its qubits occupies act
+    /// for the gate time, followed by the depolarising rate of the gate.
+    ///
+    /// Args:
+    ///     gate_error_rates (Optional[Dict[str, float]]): The depolarising rates added to the qubits of a gate, by gate name.
+    #[pyo3(text_signature = "(gate_error_rates, /)")]
+    pub fn set_noise_model(&mut self, gate_error_rates: Option<HashMap<String, f64>>) {
+        self.internal.noise_model = Some(SimulatorNoiseModel {
+            gate_error_rates: gate_error_rates.unwrap_or_default(),
+        });
+    }
+
+    /// Disable noisy simulations.
+    pub fn remove_noise_model(&mut self) {
+        self.internal.noise_model = None;
+    }
+
+    /// Return whether the simulations are noisy.
+    ///
+    /// Returns:
+    ///     bool: Whether a noise model is set.
+    pub fn is_noisy(&self) -> bool {
+        self.internal.noise_model.is_some()
+    }
+
+    /// Return the circuit run in noisy simulations, with the noise operations after each gate.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit to add the noise to.
+    ///
+    /// Returns:
+    ///     Circuit: The circuit with the noise and crosstalk operations, unchanged without noise model and crosstalk model.
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    ///     ValueError: A gate is not available on the device or a PragmaChangeDevice operation failed.
+    #[pyo3(text_signature = "(circuit, /)")]
+    pub fn noisy_circuit(&self, circuit: &Bound<PyAny>) -> PyResult<CircuitWrapper> {
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {:?}",
+                err
+            ))
+        })?;
+        self.internal
+            .noisy_circuit(&circuit)
+            .map(|internal| CircuitWrapper { internal })
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Check every operation of a circuit against the device without running it.
+    ///
+    /// The following rules are checked, following the PragmaChangeDevice operations of the circuit:
+    /// QubitNotInTweezer: the qubits of each gate are held in a tweezer.
+    /// UnavailableGate: each gate is available on the tweezers of its qubits.
+    /// QubitOutOfRange: the qubits of each operation are within the number of qubits of the simulator.
+    /// FailedDeviceChange: each PragmaChangeDevice operation can be applied to the device.
+    ///
+    /// Runs of circuits with violations fail with an error listing all of them.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit to check.
+    ///
+    /// Returns:
+    ///     List[Dict[str, Any]]: The violations, in the order of the circuit. Each violation contains
+    ///         the "index" of the operation, its "hqslang" name, its "qubits", the "tweezers" of the
+    ///         qubits (None for qubits not in a tweezer), the violated "rule" and a "message".
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    #[pyo3(text_signature = "(circuit, /)")]
+    pub fn check_circuit(&self, circuit: &Bound<PyAny>) -> PyResult<Vec<PyObject>> {
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {:?}",
+                err
+            ))
+        })?;
+        Python::with_gil(|py| {
+            self.internal
+                .check_circuit(&circuit)
+                .iter()
+                .map(|violation| -> PyResult<PyObject> {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("index", violation.index)?;
+                    dict.set_item("hqslang", &violation.hqslang)?;
+                    dict.set_item("qubits", &violation.qubits)?;
+                    dict.set_item("tweezers", &violation.tweezers)?;
+                    dict.set_item("rule", violation.reason.rule())?;
+                    dict.set_item("message", violation.to_string())?;
+                    Ok(dict.into_py(py))
+                })
+                .collect()
+        })
+    }
+
+    /// Return the state vector of the qubits after a circuit.
+    ///
+    /// The circuit is run once, with the noise and the crosstalk of the backend. Measurements
+    /// collapse the state, atom losses and readout errors do not affect it.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit to run.
+    ///     tweezer_basis (Optional[bool]): Whether bit i of the basis state indices is the qubit in the i-th occupied tweezer after the shifts of the circuit instead of qubit i. Defaults to False.
+    ///
+    /// Returns:
+    ///     Tuple[np.ndarray, List[int]]: The state vector and the qubit or tweezer of each bit of the basis state indices.
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    ///     ValueError: The circuit does not act on any qubit, a qubit is not in a tweezer, the simulation is noisy or running the circuit failed.
+    #[pyo3(text_signature = "(circuit, tweezer_basis, /)")]
+    pub fn state_vector(
+        &self,
+        circuit: &Bound<PyAny>,
+        tweezer_basis: Option<bool>,
+    ) -> PyResult<(PyObject, Vec<usize>)> {
+        let snapshot = self.simulate_state(circuit, false, tweezer_basis)?;
+        Ok((state_to_pyarray(snapshot.state), snapshot.labels))
+    }
+
+    /// Return the density matrix of the qubits after a circuit.
+    ///
+    /// The circuit is run once, with the noise and the crosstalk of the backend. Measurements
+    /// collapse the state, atom losses and readout errors do not affect it.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit to run.
+    ///     tweezer_basis (Optional[bool]): Whether bit i of the basis state indices is the qubit in the i-th occupied tweezer after the shifts of the circuit instead of qubit i. Defaults to False.
+    ///
+    /// Returns:
+    ///     Tuple[np.ndarray, List[int]]: The density matrix and the qubit or tweezer of each bit of the basis state indices.
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    ///     ValueError: The circuit does not act on any qubit, a qubit is not in a tweezer or running the circuit failed.
+    #[pyo3(text_signature = "(circuit, tweezer_basis, /)")]
+    pub fn density_matrix(
+        &self,
+        circuit: &Bound<PyAny>,
+        tweezer_basis: Option<bool>,
+    ) -> PyResult<(PyObject, Vec<usize>)> {
+        let snapshot = self.simulate_state(circuit, true, tweezer_basis)?;
+        Ok((state_to_pyarray(snapshot.state), snapshot.labels))
+    }
+
+    /// Return the state of the qubits after each operation of a circuit.
+    ///
+    /// The snapshots are taken in a single run of the circuit, the noise and the crosstalk
+    /// following an operation are part of its snapshot.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit to run.
+    ///     density_matrix (Optional[bool]): Whether to return density matrices instead of state vectors. Defaults to False.
+    ///     tweezer_basis (Optional[bool]): Whether bit i of the basis state indices is the qubit in the i-th occupied tweezer at each snapshot instead of qubit i. Defaults to False.
+    ///
+    /// Returns:
+    ///     List[Tuple[int, np.ndarray, List[int]]]: The number of applied operations, the state and the qubit or tweezer of each bit of the basis state indices for each snapshot.
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    ///     ValueError: The circuit does not act on any qubit, a qubit is not in a tweezer, the state vectors of a noisy simulation are requested or running the circuit failed.
+    #[pyo3(text_signature = "(circuit, density_matrix, tweezer_basis, /)")]
+    pub fn state_snapshots(
+        &self,
+        circuit: &Bound<PyAny>,
+        density_matrix: Option<bool>,
+        tweezer_basis: Option<bool>,
+    ) -> PyResult<Vec<(usize, PyObject, Vec<usize>)>> {
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {:?}",
+                err
+            ))
+        })?;
+        let snapshots = self
+            .internal
+            .state_snapshots(
+                &circuit,
+                density_matrix.unwrap_or(false),
+                state_basis(tweezer_basis),
+            )
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+        Ok(snapshots
+            .into_iter()
+            .map(|snapshot| {
+                (
+                    snapshot.position,
+                    state_to_pyarray(snapshot.state),
+                    snapshot.labels,
+                )
+            })
+            .collect())
+    }
+
+    /// Enable the loss of atoms during shifts, measurements and idle times.
+    ///
+    /// Lost qubits read out as false in every later measurement. For each shot, the lost qubits
+    /// are reported in an additional bit register with one entry per qubit.
+    ///
+    /// Args:
+    ///     shift_loss_rate (float): The loss rate per unit of shift distance.
+    ///     measurement_loss_probability (float): The probability of losing a qubit when it is measured.
+    ///     idle_loss_rate (float): The loss rate per unit of time a qubit is idle.
+    ///     lost_register (Optional[str]): The name of the register of lost qubits. Defaults to "lost_qubits".
+    ///
+    /// Raises:
+    ///     ValueError: A rate is negative or the probability is not between 0 and 1.
+    #[pyo3(
+        text_signature = "(shift_loss_rate, measurement_loss_probability, idle_loss_rate, lost_register, /)"
+    )]
+    pub fn set_atom_loss_model(
+        &mut self,
+        shift_loss_rate: f64,
+        measurement_loss_probability: f64,
+        idle_loss_rate: f64,
+        lost_register: Option<String>,
+    ) -> PyResult<()> {
+        let model = AtomLossModel::new()
+            .with_shift_loss_rate(shift_loss_rate)
+            .with_measurement_loss_probability(measurement_loss_probability)
+            .with_idle_loss_rate(idle_loss_rate)
+            .with_lost_register(
+                lost_register
+                    .as_deref()
+                    .unwrap_or(DEFAULT_LOST_QUBITS_REGISTER),
+            );
+        model
+            .validate()
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+        self.internal.atom_loss_model = Some(model);
+        Ok(())
+    }
+
+    /// Disable the loss of atoms.
+    pub fn remove_atom_loss_model(&mut self) {
+        self.internal.atom_loss_model = None;
+    }
+
+    /// Return whether atoms can be lost in the simulations.
+    ///
+    /// Returns:
+    ///     bool: Whether an atom loss model is set.
+    pub fn has_atom_loss(&self) -> bool {
+        self.internal.atom_loss_model.is_some()
+    }
+
+    /// Set whether the readout errors of the device are applied to the measured bits.
+    ///
+    /// Each measured bit is flipped with the probability given by the readout confusion matrix
+    /// of the tweezer the qubit occupies.
+    ///
+    /// Args:
+    ///     readout_errors (bool): Whether the readout errors are applied.
+    #[pyo3(text_signature = "(readout_errors, /)")]
+    pub fn set_readout_errors(&mut self, readout_errors: bool) {
+        self.internal.readout_errors = readout_errors;
+    }
+
+    /// Return whether the readout errors of the device are applied to the measured bits.
+    ///
+    /// Returns:
+    ///     bool: Whether the readout errors are applied.
+    pub fn has_readout_errors(&self) -> bool {
+        self.internal.readout_errors
+    }
+
+    /// Enable the Rydberg blockade crosstalk during entangling gates.
+    ///
+    /// During each entangling gate, the spectator qubits close to the qubits of the gate acquire
+    /// a phase when both the gate qubit and the spectator are in state |1>.
+    ///
+    /// Args:
+    ///     strength (float): The phase acquired by a spectator qubit during an entangling gate.
+    ///     range (Optional[float]): The maximal distance in micrometers between the tweezers of a gate qubit and a spectator. Defaults to the two-qubit connectivity of the device.
+    ///
+    /// Raises:
+    ///     ValueError: The strength is not finite or the range is not finite and positive.
+    #[pyo3(text_signature = "(strength, range, /)")]
+    pub fn set_crosstalk_model(&mut self, strength: f64, range: Option<f64>) -> PyResult<()> {
+        let mut model = CrosstalkModel::new(strength);
+        if let Some(range) = range {
+            model = model.with_range(range);
+        }
+        model
+            .validate()
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+        self.internal.crosstalk_model = Some(model);
+        Ok(())
+    }
+
+    /// Disable the crosstalk during entangling gates.
+    pub fn remove_crosstalk_model(&mut self) {
+        self.internal.crosstalk_model = None;
+    }
+
+    /// Return whether the simulations include crosstalk during entangling gates.
+    ///
+    /// Returns:
+    ///     bool: Whether a crosstalk model is set.
+    pub fn has_crosstalk(&self) -> bool {
+        self.internal.crosstalk_model.is_some()
+    }
+
+    /// Set the number of threads running the circuits of a measurement.
+    ///
+    /// Every circuit, or batch of shots of a circuit, is run with the same seed as in sequential
+    /// runs and the registers are merged in order, so the results are identical to sequential
+    /// runs. The QuEST simulations are serialized, as QuEST keeps its random number generator
+    /// in global state, so several threads only speed up stabilizer simulations, see
+    /// `set_simulation_mode`.
+    ///
+    /// Args:
+    ///     number_threads (int): The number of threads, 0 for the available parallelism of the machine and 1 for sequential runs.
+    #[pyo3(text_signature = "(number_threads, /)")]
+    pub fn set_number_threads(&mut self, number_threads: usize) {
+        self.internal.number_threads = Some(number_threads);
+    }
+
+    /// Return the number of threads running the circuits of a measurement.
+    ///
+    /// Returns:
+    ///     int: The number of threads, 1 for sequential runs.
+    pub fn number_threads(&self) -> usize {
+        self.internal.threads()
+    }
+
+    /// Set the maximal number of shots run at once.
+    ///
+    /// The shots of each circuit are split into batches run with their own seeds, in parallel
+    /// with several threads, see `set_number_threads`. The float and complex registers are
+    /// returned once per batch.
+    ///
+    /// Args:
+    ///     shot_batch_size (int): The maximal number of shots of a batch.
+    #[pyo3(text_signature = "(shot_batch_size, /)")]
+    pub fn set_shot_batch_size(&mut self, shot_batch_size: usize) {
+        self.internal.shot_batch_size = Some(shot_batch_size);
+    }
+
+    /// Return the maximal number of shots run at once.
+    ///
+    /// Returns:
+    ///     Optional[int]: The maximal number of shots of a batch, None to run all the shots of a circuit at once.
+    pub fn shot_batch_size(&self) -> Option<usize> {
+        self.internal.shot_batch_size
+    }
+
+    /// Set the simulation mode of the circuits.
+    ///
+    /// The stabilizer simulation supports Clifford gates, measurements and active resets on any
+    /// number of qubits, with the device checks, atom losses and readout errors of state vector
+    /// simulations. Noise models are not supported. The automatic mode uses the stabilizer
+    /// simulation for noise-free Clifford circuits and the state vector simulation otherwise,
+    /// failing for other circuits on backends with more than 30 qubits.
+    ///
+    /// Args:
+    ///     simulation_mode (str): The simulation mode, state_vector, stabilizer or automatic.
+    ///
+    /// Raises:
+    ///     ValueError: The simulation mode is unknown.
+    #[pyo3(text_signature = "(simulation_mode, /)")]
+    pub fn set_simulation_mode(&mut self, simulation_mode: &str) -> PyResult<()> {
+        self.internal.simulation_mode = simulation_mode
+            .parse()
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+        Ok(())
+    }
+
+    /// Return the simulation mode of the circuits.
+    ///
+    /// Returns:
+    ///     str: The simulation mode, state_vector, stabilizer or automatic.
+    pub fn simulation_mode(&self) -> String {
+        match self.internal.simulation_mode {
+            SimulationMode::StateVector => "state_vector",
+            SimulationMode::Stabilizer => "stabilizer",
+            SimulationMode::Automatic => "automatic",
+        }
+        .to_string()
+    }
+
+    /// Post-select the shots in which none of the given qubits has been lost.
+    ///
+    /// Args:
+    ///     bit_registers (Dict[str, List[List[bool]]]): The bit registers of a run, including the register of lost qubits.
+    ///     qubits (Optional[List[int]]): The qubits that must not be lost. Defaults to all qubits.
+    ///
+    /// Returns:
+    ///     Dict[str, List[List[bool]]]: The bit registers of the selected shots, without the register of lost qubits.
+    ///
+    /// Raises:
+    ///     ValueError: The register of lost qubits is missing or a register has a different number of shots.
+    #[pyo3(text_signature = "(bit_registers, qubits, /)")]
+    pub fn post_select_shots(
+        &self,
+        bit_registers: HashMap<String, BitOutputRegister>,
+        qubits: Option<Vec<usize>>,
+    ) -> PyResult<HashMap<String, BitOutputRegister>> {
+        let lost_register = self
+            .internal
+            .atom_loss_model
+            .as_ref()
+            .map(|model| model.lost_register.as_str())
+            .unwrap_or(DEFAULT_LOST_QUBITS_REGISTER);
+        post_select_shots(&bit_registers, lost_register, qubits.as_deref())
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
     /// Run a circuit with the QRyd backend.
     ///
     /// A circuit is passed to the backend and executed.
@@ -283,39 +707,14 @@ impl SimulatorBackendWrapper {
             )
         }
 
-        let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
-        let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
-        let mut complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
-
-        for circuit in run_circuits {
-            let (tmp_bit_reg, tmp_float_reg, tmp_complex_reg) =
-                self.internal.run_circuit(&circuit).map_err(|err| {
-                    PyRuntimeError::new_err(format!("Running a circuit failed {:?}", err))
-                })?;
-
-            for (key, mut val) in tmp_bit_reg.into_iter() {
-                if let Some(x) = bit_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = bit_registers.insert(key, val);
-                }
-            }
-            for (key, mut val) in tmp_float_reg.into_iter() {
-                if let Some(x) = float_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = float_registers.insert(key, val);
-                }
-            }
-            for (key, mut val) in tmp_complex_reg.into_iter() {
-                if let Some(x) = complex_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = complex_registers.insert(key, val);
-                }
-            }
-        }
-        Ok((bit_registers, float_registers, complex_registers))
+        // The circuits are run as one measurement to use the seeds of the circuits of a measurement
+        let measurement = ClassicalRegister {
+            constant_circuit: None,
+            circuits: run_circuits,
+        };
+        self.internal
+            .run_measurement_registers(&measurement)
+            .map_err(|err| PyRuntimeError::new_err(format!("Running a circuit failed {:?}", err)))
     }
 
     /// Evaluates expectation values of a measurement with the backend.
@@ -357,6 +756,43 @@ impl SimulatorBackendWrapper {
     }
 }
 
+impl SimulatorBackendWrapper {
+    /// Returns the state of the qubits after a circuit.
+    fn simulate_state(
+        &self,
+        circuit: &Bound<PyAny>,
+        density_matrix: bool,
+        tweezer_basis: Option<bool>,
+    ) -> PyResult<StateSnapshot> {
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {:?}",
+                err
+            ))
+        })?;
+        self.internal
+            .simulate_state(&circuit, density_matrix, state_basis(tweezer_basis))
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+}
+
+/// Returns the basis of the simulated states.
+fn state_basis(tweezer_basis: Option<bool>) -> StateBasis {
+    if tweezer_basis.unwrap_or(false) {
+        StateBasis::Tweezers
+    } else {
+        StateBasis::Qubits
+    }
+}
+
+/// Converts a simulated state to a numpy array.
+fn state_to_pyarray(state: SimulatedState) -> PyObject {
+    Python::with_gil(|py| match state {
+        SimulatedState::StateVector(state) => state.to_pyarray_bound(py).into_any().unbind(),
+        SimulatedState::DensityMatrix(state) => state.to_pyarray_bound(py).into_any().unbind(),
+    })
+}
+
 /// Convert generic python object to [roqoqo_qryd::SimulatorBackend].
 ///
 /// Fallible conversion of generic python object to [roqoqo_qryd::SimulatorBackend].
@@ -372,6 +808,7 @@ pub fn convert_into_backend(input: &Bound<PyAny>) -> Result<SimulatorBackend, Qo
         let bytes = get_bytes
             .extract::<Vec<u8>>()
             .map_err(|_| QoqoBackendError::CannotExtractObject)?;
-        deserialize(&bytes[..]).map_err(|_| QoqoBackendError::CannotExtractObject)
+        SimulatorBackend::from_bincode(&bytes[..])
+            .map_err(|_| QoqoBackendError::CannotExtractObject)
     }
 }
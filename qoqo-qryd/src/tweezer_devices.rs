@@ -10,16 +10,22 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::HashSet, io::Cursor};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Cursor,
+};
 
-use bincode::{deserialize, serialize};
+use bincode::serialize;
+use numpy::{ndarray::Array2, PyArray2, ToPyArray};
 use pyo3::{
     exceptions::{PyTypeError, PyValueError},
     prelude::*,
-    types::{IntoPyDict, PyByteArray},
+    types::{IntoPyDict, PyByteArray, PyDict},
 };
 
-use qoqo::{devices::GenericDeviceWrapper, QoqoBackendError};
+use qoqo::{
+    devices::GenericDeviceWrapper, operations::PragmaChangeDeviceWrapper, QoqoBackendError,
+};
 use qoqo_calculator_pyo3::convert_into_calculator_float;
 use roqoqo::devices::Device;
 
@@ -27,7 +33,12 @@ use roqoqo_qryd::tweezer_devices::{
     ALLOWED_NATIVE_SINGLE_QUBIT_GATES, ALLOWED_NATIVE_THREE_QUBIT_GATES,
     ALLOWED_NATIVE_TWO_QUBIT_GATES,
 };
-use roqoqo_qryd::{QRydAPIDevice, TweezerDevice};
+use roqoqo_qryd::{
+    QRydAPIDevice, ReadoutConfusionMatrix, TweezerDecoherenceRates, TweezerDevice, TweezerLattice,
+    ValidationReport,
+};
+
+use crate::interaction_models::extract_interaction_model;
 
 /// Tweezer Device
 ///
@@ -126,6 +137,8 @@ impl TweezerDeviceWrapper {
     ///     seed (Optional[int]): Optionally overwrite seed value from downloaded device instance.
     ///     dev (Optional[bool]): The boolean to set the dev header to.
     ///     api_version (Optional[str]): The version of the QRYD API to use. Defaults to "v1_1".
+    ///     cache_dir (Optional[str]): The directory of the on-disk device cache to use.
+    ///                         Defaults to the environmental variable `$QRYD_API_CACHE_DIR`.
     ///
     /// Returns:
     ///     TweezerDevice: The new TweezerDevice instance with populated tweezer data.
@@ -134,7 +147,9 @@ impl TweezerDeviceWrapper {
     ///     RoqoqoBackendError
     #[staticmethod]
     #[cfg(feature = "web-api")]
-    #[pyo3(text_signature = "(device_name, access_token, mock_port, seed, api_version, /)")]
+    #[pyo3(
+        text_signature = "(device_name, access_token, mock_port, seed, api_version, cache_dir, /)"
+    )]
     pub fn from_api(
         device_name: Option<String>,
         access_token: Option<String>,
@@ -142,10 +157,39 @@ impl TweezerDeviceWrapper {
         seed: Option<usize>,
         dev: Option<bool>,
         api_version: Option<String>,
+        cache_dir: Option<String>,
     ) -> PyResult<Self> {
-        let internal =
-            TweezerDevice::from_api(device_name, access_token, mock_port, seed, dev, api_version)
-                .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+        let internal = TweezerDevice::from_api_with_options(
+            device_name,
+            access_token,
+            mock_port,
+            seed,
+            dev,
+            api_version,
+            &crate::download_options(cache_dir),
+        )
+        .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
+        Ok(TweezerDeviceWrapper { internal })
+    }
+
+    /// Creates a new TweezerDevice instance from a device snapshot file.
+    ///
+    /// The offline counterpart of `from_api`.
+    ///
+    /// Args:
+    ///     path (str): The path of the snapshot file, as written by `download_device_snapshot`.
+    ///     seed (Optional[int]): Optionally overwrite seed value from the stored device instance.
+    ///
+    /// Returns:
+    ///     TweezerDevice: The new TweezerDevice instance.
+    ///
+    /// Raises:
+    ///     ValueError: The file could not be read or does not contain a compatible device.
+    #[staticmethod]
+    #[pyo3(text_signature = "(path, seed, /)")]
+    pub fn from_snapshot(path: String, seed: Option<usize>) -> PyResult<Self> {
+        let internal = TweezerDevice::from_snapshot(path, seed)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?;
         Ok(TweezerDeviceWrapper { internal })
     }
 
@@ -190,7 +234,9 @@ impl TweezerDeviceWrapper {
 
     /// Modifies the qubit -> tweezer mapping of the device.
     ///
-    /// If a qubit -> tweezer mapping is already present, it is overwritten.
+    /// If a qubit -> tweezer mapping is already present, it is overwritten and a qubit
+    /// previously occupying the tweezer is removed from the mapping. Use `insert_qubit`,
+    /// `move_qubit` and `remove_qubit` to report such conflicts instead.
     ///
     /// Args:
     ///     qubit (int): The index of the qubit.
@@ -215,6 +261,86 @@ impl TweezerDeviceWrapper {
         })
     }
 
+    /// Places an unmapped qubit in a free tweezer of the current Layout.
+    ///
+    /// Args:
+    ///     qubit (int): The index of the qubit.
+    ///     tweezer (int): The index of the tweezer.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present in the device or occupied, or the qubit is already mapped.
+    #[pyo3(text_signature = "(qubit, tweezer, /)")]
+    pub fn insert_qubit(&mut self, qubit: usize, tweezer: usize) -> PyResult<()> {
+        self.internal
+            .insert_qubit(qubit, tweezer)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Moves a mapped qubit into a free tweezer of the current Layout.
+    ///
+    /// Args:
+    ///     qubit (int): The index of the qubit.
+    ///     tweezer (int): The index of the target tweezer.
+    ///
+    /// Returns:
+    ///     int: The tweezer the qubit has been moved out of.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present in the device or occupied, or the qubit is not mapped.
+    #[pyo3(text_signature = "(qubit, tweezer, /)")]
+    pub fn move_qubit(&mut self, qubit: usize, tweezer: usize) -> PyResult<usize> {
+        self.internal
+            .move_qubit(qubit, tweezer)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Removes a qubit from the qubit -> tweezer mapping.
+    ///
+    /// Args:
+    ///     qubit (int): The index of the qubit.
+    ///
+    /// Returns:
+    ///     int: The tweezer the qubit occupied.
+    ///
+    /// Raises:
+    ///     ValueError: The qubit is not mapped.
+    #[pyo3(text_signature = "(qubit, /)")]
+    pub fn remove_qubit(&mut self, qubit: usize) -> PyResult<usize> {
+        self.internal
+            .remove_qubit(qubit)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Plans the tweezer shifts moving the qubits of the device into a target arrangement.
+    ///
+    /// Only the allowed tweezer shifts of the current Layout are used. The search finds the
+    /// smallest number of parallel PragmaShiftQubitsTweezers operations, whose shifts move
+    /// different qubits and are checked in order as in `change_device`.
+    ///
+    /// Args:
+    ///     target (Dict[int, int]): The target qubit -> tweezer mapping, containing the qubits of the device mapping.
+    ///     max_states (Optional[int]): The maximal number of qubit arrangements to explore.
+    ///
+    /// Returns:
+    ///     List[PragmaChangeDevice]: The wrapped PragmaShiftQubitsTweezers operations to apply in order.
+    ///
+    /// Raises:
+    ///     ValueError: The target is invalid or not reachable, or the search exceeded max_states.
+    #[pyo3(text_signature = "(target, max_states, /)")]
+    pub fn plan_shifts(
+        &self,
+        target: HashMap<usize, usize>,
+        max_states: Option<usize>,
+    ) -> PyResult<Vec<PragmaChangeDeviceWrapper>> {
+        Ok(self
+            .internal
+            .plan_shifts(&target, max_states)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?
+            .into_iter()
+            .map(|internal| PragmaChangeDeviceWrapper { internal })
+            .collect())
+    }
+
     /// Get the qubit -> tweezer mapping of the device.
     ///
     /// Returns:
@@ -223,12 +349,48 @@ impl TweezerDeviceWrapper {
     pub fn get_qubit_to_tweezer_mapping(&self) -> Option<PyObject> {
         Python::with_gil(|py| -> Option<PyObject> {
             self.internal
-                .qubit_to_tweezer
-                .as_ref()
-                .map(|mapping| mapping.into_py_dict_bound(py).into())
+                .qubit_to_tweezer()
+                .map(|mapping| mapping.as_map().into_py_dict_bound(py).into())
         })
     }
 
+    /// Get the qubit occupying the given tweezer.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///
+    /// Returns:
+    ///     Optional[int]: The qubit in the tweezer, None if the tweezer is free.
+    #[pyo3(text_signature = "(tweezer, /)")]
+    pub fn qubit_at(&self, tweezer: usize) -> Option<usize> {
+        self.internal.qubit_at(tweezer)
+    }
+
+    /// Get the tweezers occupied by a qubit.
+    ///
+    /// Returns:
+    ///     list[int]: The occupied tweezers, in ascending order.
+    pub fn occupied_tweezers(&self) -> Vec<usize> {
+        self.internal.occupied_tweezers()
+    }
+
+    /// Get the tweezers of a Layout not occupied by a qubit.
+    ///
+    /// Args:
+    ///     layout_name (Optional[str]): The name of the Layout. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     list[int]: The free tweezers, in ascending order.
+    ///
+    /// Raises:
+    ///     ValueError: The layout does not exist or no current layout is set.
+    #[pyo3(text_signature = "(layout_name, /)")]
+    pub fn free_tweezers(&self, layout_name: Option<String>) -> PyResult<Vec<usize>> {
+        self.internal
+            .free_tweezers(layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
     /// Get the names of the available gates in the given layout.
     ///
     /// Args:
@@ -489,7 +651,7 @@ impl TweezerDeviceWrapper {
             .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
 
         Ok(TweezerDeviceWrapper {
-            internal: deserialize(&bytes[..]).map_err(|_| {
+            internal: TweezerDevice::from_bincode(&bytes[..]).map_err(|_| {
                 PyValueError::new_err("Input cannot be deserialized to TweezerDevice")
             })?,
         })
@@ -611,152 +773,509 @@ impl TweezerDeviceWrapper {
             .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Return the list of pairs of qubits linked by a native two-qubit-gate in the device.
-    ///
-    /// A pair of qubits is considered linked by a native two-qubit-gate if the device
-    /// can implement a two-qubit-gate between the two qubits without decomposing it
-    /// into a sequence of gates that involves a third qubit of the device.
-    /// The two-qubit-gate also has to form a universal set together with the available
-    /// single qubit gates.
+    /// Checks the self-consistency of all Layouts of the device.
     ///
-    /// The returned vectors is a simple, graph-library independent, representation of
-    /// the undirected connectivity graph of the device.
-    /// It can be used to construct the connectivity graph in a graph library of the user's
-    /// choice from a list of edges and can be used for applications like routing in quantum algorithms.
+    /// The following rules are checked for each Layout:
+    /// AsymmetricTwoQubitGate: two-qubit gates are set for both orders of each tweezer pair, with the same gate time.
+    /// ShiftWithUnknownTweezer: allowed tweezer shifts only involve tweezers with gates.
+    /// TweezersPerRowMismatch: the tweezers per row, if set, add up to the number of tweezers.
+    /// UnconnectedThreeQubitGate: three-qubit gates only involve tweezer pairs connected by a two-qubit gate.
+    /// PositionOfUnknownTweezer: tweezer positions are only set for tweezers with gates.
     ///
     /// Returns:
-    ///     Sequence[(int, int)]: List of two qubit edges in the undirected connectivity graph
-    fn two_qubit_edges(&self) -> Vec<(usize, usize)> {
-        self.internal.two_qubit_edges()
+    ///     Dict[str, List[Dict[str, Any]]]: The issues of each Layout, by Layout name. Each issue
+    ///         contains the violated "rule", the involved "tweezers" and a "message".
+    pub fn validate(&self) -> PyResult<PyObject> {
+        validation_report_to_dict(self.internal.validate())
     }
 
-    /// Returns the two tweezer edges of the device.
+    /// Returns the position of a tweezer in a given Layout.
     ///
-    /// And edge between two tweezer is valid only if the
-    /// PhaseShiftedControlledPhase gate can be performed.
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
     ///
     /// Returns:
-    ///     Sequence[(int, int)]: List of two tweezer edges
-    fn two_tweezer_edges(&self) -> Vec<(usize, usize)> {
-        self.internal.two_tweezer_edges()
+    ///     Tuple[float, float, float]: The (x, y, z) coordinates of the tweezer in micrometers.
+    ///
+    /// Raises:
+    ///     ValueError: The layout does not exist or has no position for the tweezer.
+    #[pyo3(text_signature = "(tweezer, layout_name, /)")]
+    pub fn get_tweezer_position(
+        &self,
+        tweezer: usize,
+        layout_name: Option<String>,
+    ) -> PyResult<(f64, f64, f64)> {
+        self.internal
+            .get_tweezer_position(tweezer, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Returns the backend associated with the device.
-    pub fn qrydbackend(&self) -> String {
-        self.internal.qrydbackend()
+    /// Returns the decoherence rates of a qubit held in a tweezer in a given Layout.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     Tuple[float, float, float]: The damping, dephasing and depolarising rates, zero if none are set.
+    ///
+    /// Raises:
+    ///     ValueError: The layout does not exist.
+    #[pyo3(text_signature = "(tweezer, layout_name, /)")]
+    pub fn get_tweezer_decoherence_rates(
+        &self,
+        tweezer: usize,
+        layout_name: Option<String>,
+    ) -> PyResult<(f64, f64, f64)> {
+        self.internal
+            .get_tweezer_decoherence_rates(tweezer, layout_name)
+            .map(|rates| (rates.damping, rates.dephasing, rates.depolarising))
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Returns the seed usized for the API.
-    pub fn seed(&self) -> Option<usize> {
-        self.internal.seed()
+    /// Returns the decoherence rates matrix of the tweezer a qubit occupies in the current Layout.
+    ///
+    /// Args:
+    ///     qubit (int): The qubit.
+    ///
+    /// Returns:
+    ///     np.ndarray: The 3x3 decoherence rates matrix, zero for qubits without rates.
+    #[pyo3(text_signature = "(qubit, /)")]
+    pub fn qubit_decoherence_rates(&self, qubit: usize) -> Py<PyArray2<f64>> {
+        Python::with_gil(|py| {
+            self.internal
+                .qubit_decoherence_rates(&qubit)
+                .unwrap_or_else(|| Array2::zeros((3, 3)))
+                .to_pyarray_bound(py)
+                .unbind()
+        })
     }
 
-    /// Return the bincode representation of the Enum variant of the Device.
+    /// Returns the error rate of a single-qubit gate on a qubit in the current Layout.
     ///
-    /// Only used for internal interfacing.
+    /// Args:
+    ///     hqslang (str): The hqslang name of the single-qubit gate.
+    ///     qubit (int): The qubit the gate acts on.
     ///
     /// Returns:
-    ///     ByteArray: The serialized TweezerDevice (in [bincode] form).
+    ///     float: The error rate, zero if none is set.
     ///
     /// Raises:
-    ///     ValueError: Cannot serialize Device to bytes.
-    pub fn _enum_to_bincode(&self) -> PyResult<Py<PyByteArray>> {
-        let qryd_enum: QRydAPIDevice = (&self.internal).into();
-        let serialized = bincode::serialize(&qryd_enum)
-            .map_err(|_| PyValueError::new_err("Cannot serialize TweezerDevice to bytes"))?;
-        let b: Py<PyByteArray> = Python::with_gil(|py| -> Py<PyByteArray> {
-            PyByteArray::new_bound(py, &serialized[..]).into()
-        });
-        Ok(b)
+    ///     ValueError: The gate is not available on the qubit.
+    #[pyo3(text_signature = "(hqslang, qubit, /)")]
+    pub fn single_qubit_gate_error(&self, hqslang: &str, qubit: usize) -> PyResult<f64> {
+        self.internal
+            .single_qubit_gate_error(hqslang, &qubit)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
     }
 
-    /// Creates a graph representing a TweezerDevice.
+    /// Returns the error rate of a two-qubit gate on a qubit couple in the current Layout.
     ///
     /// Args:
-    ///     draw_shifts (Optional[bool]): Whether to draw shifts or not. Default: false
-    ///     pixel_per_point (Optional[float]): The quality of the image.
-    ///     file_save_path (Optional[str]): Path to save the image to. Default: output the image with the display method.
+    ///     hqslang (str): The hqslang name of the two-qubit gate.
+    ///     control (int): The control qubit the gate acts on.
+    ///     target (int): The target qubit the gate acts on.
     ///
-    /// Raises:
-    ///     PyValueError - if there is no layout, an error occurred during the compilation or and invalid path was provided.
+    /// Returns:
+    ///     float: The error rate, zero if none is set.
     ///
-    #[pyo3(text_signature = "(draw_shifts, pixel_per_point, file_save_path, /)")]
-    pub fn draw(
+    /// Raises:
+    ///     ValueError: The gate is not available on the qubits.
+    #[pyo3(text_signature = "(hqslang, control, target, /)")]
+    pub fn two_qubit_gate_error(
         &self,
-        draw_shifts: Option<bool>,
-        pixel_per_point: Option<f32>,
-        file_save_path: Option<String>,
-    ) -> PyResult<()> {
-        let display_image = file_save_path.is_none();
-        let image = self
-            .internal
-            .draw(
-                pixel_per_point,
-                draw_shifts.unwrap_or(false),
-                &file_save_path,
-            )
-            .map_err(|x| PyValueError::new_err(format!("Error during Circuit drawing: {x:?}")))?;
-
-        if display_image {
-            let mut buffer = Cursor::new(Vec::new());
-            image
-                .write_to(&mut buffer, image::ImageFormat::Png)
-                .map_err(|x| {
-                    PyValueError::new_err(format!(
-                        "Error during the generation of the Png file: {x:?}"
-                    ))
-                })?;
-            Python::with_gil(|py| {
-                let pil = PyModule::import_bound(py, "PIL.Image").unwrap();
-                let io = PyModule::import_bound(py, "io").unwrap();
-                let display = PyModule::import_bound(py, "IPython.display").unwrap();
-                let builtins = PyModule::import_bound(py, "builtins").unwrap();
-
-                let bytes_image_data = builtins
-                    .call_method1("bytes", (buffer.clone().into_inner(),))
-                    .unwrap();
-                let bytes_io = io.call_method1("BytesIO", (bytes_image_data,)).unwrap();
-                let image = pil.call_method1("open", (bytes_io,)).unwrap();
-
-                display.call_method1("display", (image,)).unwrap();
-            });
-        }
-        Ok(())
+        hqslang: &str,
+        control: usize,
+        target: usize,
+    ) -> PyResult<f64> {
+        self.internal
+            .two_qubit_gate_error(hqslang, &control, &target)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
     }
-}
 
-/// Tweezer Mutable Device
-///
-/// This interface allows setting any piece of information about the device
-/// tweezer.
-///
-/// Args:
-///     controlled_z_phase_relation ((Optional[Union[str, float]])): The relation to use for the PhaseShiftedControlledZ gate.
-///                                   It can be hardcoded to a specific value if a float is passed in as String.
-///     controlled_phase_phase_relation ((Optional[Union[str, float]])): The relation to use for the PhaseShiftedControlledPhase gate.
-#[pyclass(name = "TweezerMutableDevice", module = "qoqo_qryd")]
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct TweezerMutableDeviceWrapper {
-    /// Internal storage of [roqoqo_qryd::TweezerDevice]
-    pub internal: TweezerDevice,
-}
+    /// Returns the error rate of a three-qubit gate on a qubit trio in the current Layout.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of the three-qubit gate.
+    ///     control_0 (int): The first control qubit the gate acts on.
+    ///     control_1 (int): The second control qubit the gate acts on.
+    ///     target (int): The target qubit the gate acts on.
+    ///
+    /// Returns:
+    ///     float: The error rate, zero if none is set.
+    ///
+    /// Raises:
+    ///     ValueError: The gate is not available on the qubits.
+    #[pyo3(text_signature = "(hqslang, control_0, control_1, target, /)")]
+    pub fn three_qubit_gate_error(
+        &self,
+        hqslang: &str,
+        control_0: usize,
+        control_1: usize,
+        target: usize,
+    ) -> PyResult<f64> {
+        self.internal
+            .three_qubit_gate_error(hqslang, &control_0, &control_1, &target)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
+    }
 
-#[pymethods]
-impl TweezerMutableDeviceWrapper {
-    /// Creates a new TweezerMutableDevice instance.
+    /// Returns the readout error rate of a qubit in the current Layout.
     ///
     /// Args:
-    ///     seed (int): Optional seed, for simulation purposes.
-    ///     controlled_z_phase_relation (Optional[Union[str, float]]): The relation to use for the PhaseShiftedControlledZ gate.
-    ///     controlled_phase_phase_relation (Optional[Union[str, float]]): The relation to use for the PhaseShiftedControlledPhase gate.
+    ///     qubit (int): The measured qubit.
     ///
     /// Returns:
-    ///     TweezerMutableDevice: The new TweezerMutableDevice instance.
-    #[new]
-    #[pyo3(
-        text_signature = "(seed, controlled_z_phase_relation, controlled_phase_phase_relation, /)"
-    )]
-    pub fn new(
+    ///     float: The readout error rate of the tweezer the qubit occupies, zero if none is set.
+    ///
+    /// Raises:
+    ///     ValueError: The qubit is not mapped to a tweezer.
+    #[pyo3(text_signature = "(qubit, /)")]
+    pub fn readout_error(&self, qubit: usize) -> PyResult<f64> {
+        self.internal
+            .readout_error(&qubit)
+            .ok_or_else(|| PyValueError::new_err("The qubit is not mapped to a tweezer."))
+    }
+
+    /// Returns the readout confusion matrix of a qubit in the current Layout.
+    ///
+    /// Args:
+    ///     qubit (int): The measured qubit.
+    ///
+    /// Returns:
+    ///     Tuple[float, float]: The probabilities of reading out 1 for state |0> and 0 for state |1>
+    ///         in the tweezer the qubit occupies, zero if none are set.
+    ///
+    /// Raises:
+    ///     ValueError: The qubit is not mapped to a tweezer.
+    #[pyo3(text_signature = "(qubit, /)")]
+    pub fn readout_confusion_matrix(&self, qubit: usize) -> PyResult<(f64, f64)> {
+        self.internal
+            .readout_confusion_matrix(&qubit)
+            .map(|confusion_matrix| (confusion_matrix.zero_to_one, confusion_matrix.one_to_zero))
+            .ok_or_else(|| PyValueError::new_err("The qubit is not mapped to a tweezer."))
+    }
+
+    /// Returns the readout confusion matrices of the qubits measured into a register, in register order.
+    ///
+    /// Args:
+    ///     qubits (List[int]): The qubit measured into each bit of the register.
+    ///
+    /// Returns:
+    ///     List[Tuple[float, float]]: The confusion matrix of each qubit, as for `readout_confusion_matrix`.
+    ///
+    /// Raises:
+    ///     ValueError: A qubit is not mapped to a tweezer.
+    #[pyo3(text_signature = "(qubits, /)")]
+    pub fn readout_confusion_matrices(&self, qubits: Vec<usize>) -> PyResult<Vec<(f64, f64)>> {
+        self.internal
+            .readout_confusion_matrices(&qubits)
+            .map(|confusion_matrices| {
+                confusion_matrices
+                    .into_iter()
+                    .map(|confusion_matrix| {
+                        (confusion_matrix.zero_to_one, confusion_matrix.one_to_zero)
+                    })
+                    .collect()
+            })
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Returns the error score of a tweezer in a given Layout.
+    ///
+    /// The score is the sum of the largest single-qubit gate error rate of the tweezer, the
+    /// largest two-qubit gate error rate over the pairs of tweezers it belongs to and its
+    /// readout error rate.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     float: The error score of the tweezer, zero if no error rates are set.
+    ///
+    /// Raises:
+    ///     ValueError: The layout does not exist.
+    #[pyo3(text_signature = "(tweezer, layout_name, /)")]
+    pub fn tweezer_error_score(
+        &self,
+        tweezer: usize,
+        layout_name: Option<String>,
+    ) -> PyResult<f64> {
+        self.internal
+            .tweezer_error_score(tweezer, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Returns the number of coordinates of the tweezer positions of a given Layout.
+    ///
+    /// Args:
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     int: 2 for two-dimensional and 3 for three-dimensional positions.
+    ///
+    /// Raises:
+    ///     ValueError: The layout does not exist or has no tweezer positions.
+    #[pyo3(text_signature = "(layout_name, /)")]
+    pub fn tweezer_position_dimensions(&self, layout_name: Option<String>) -> PyResult<usize> {
+        self.internal
+            .get_tweezer_positions(layout_name)
+            .map(|positions| positions.number_dimensions)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Returns the distance between two tweezers in a given Layout.
+    ///
+    /// Args:
+    ///     tweezer0 (int): The index of the first tweezer.
+    ///     tweezer1 (int): The index of the second tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     float: The distance between the tweezers in micrometers.
+    ///
+    /// Raises:
+    ///     ValueError: The position of one of the tweezers is not set.
+    #[pyo3(text_signature = "(tweezer0, tweezer1, layout_name, /)")]
+    pub fn tweezer_distance(
+        &self,
+        tweezer0: usize,
+        tweezer1: usize,
+        layout_name: Option<String>,
+    ) -> PyResult<f64> {
+        self.internal
+            .tweezer_distance(tweezer0, tweezer1, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Returns the distance between the tweezers two qubits are mapped to in the current Layout.
+    ///
+    /// Args:
+    ///     qubit0 (int): The index of the first qubit.
+    ///     qubit1 (int): The index of the second qubit.
+    ///
+    /// Returns:
+    ///     float: The distance between the qubits in micrometers.
+    ///
+    /// Raises:
+    ///     ValueError: A qubit is not mapped or the position of its tweezer is not set.
+    #[pyo3(text_signature = "(qubit0, qubit1, /)")]
+    pub fn qubit_distance(&self, qubit0: usize, qubit1: usize) -> PyResult<f64> {
+        self.internal
+            .qubit_distance(qubit0, qubit1)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Returns the tweezers closest to a given tweezer in a given Layout.
+    ///
+    /// Only tweezers with a set position are considered.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     number (int): The maximal number of tweezers to return.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     List[Tuple[int, float]]: The closest tweezers with their distances, sorted by distance.
+    ///
+    /// Raises:
+    ///     ValueError: The position of the given tweezer is not set.
+    #[pyo3(text_signature = "(tweezer, number, layout_name, /)")]
+    pub fn nearest_tweezers(
+        &self,
+        tweezer: usize,
+        number: usize,
+        layout_name: Option<String>,
+    ) -> PyResult<Vec<(usize, f64)>> {
+        self.internal
+            .nearest_tweezers(tweezer, number, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Returns the tweezers within a given distance of a given tweezer in a given Layout.
+    ///
+    /// Only tweezers with a set position are considered.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     max_distance (float): The maximal distance in micrometers, inclusive.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     List[Tuple[int, float]]: The tweezers in range with their distances, sorted by distance.
+    ///
+    /// Raises:
+    ///     ValueError: The position of the given tweezer is not set.
+    #[pyo3(text_signature = "(tweezer, max_distance, layout_name, /)")]
+    pub fn tweezers_within_distance(
+        &self,
+        tweezer: usize,
+        max_distance: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<Vec<(usize, f64)>> {
+        self.internal
+            .tweezers_within_distance(tweezer, max_distance, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Return the list of pairs of qubits linked by a native two-qubit-gate in the device.
+    ///
+    /// A pair of qubits is considered linked by a native two-qubit-gate if the device
+    /// can implement a two-qubit-gate between the two qubits without decomposing it
+    /// into a sequence of gates that involves a third qubit of the device.
+    /// The two-qubit-gate also has to form a universal set together with the available
+    /// single qubit gates.
+    ///
+    /// The returned vectors is a simple, graph-library independent, representation of
+    /// the undirected connectivity graph of the device.
+    /// It can be used to construct the connectivity graph in a graph library of the user's
+    /// choice from a list of edges and can be used for applications like routing in quantum algorithms.
+    ///
+    /// Returns:
+    ///     Sequence[(int, int)]: List of two qubit edges in the undirected connectivity graph
+    fn two_qubit_edges(&self) -> Vec<(usize, usize)> {
+        self.internal.two_qubit_edges()
+    }
+
+    /// Returns the two tweezer edges of the device.
+    ///
+    /// And edge between two tweezer is valid only if the
+    /// PhaseShiftedControlledPhase gate can be performed.
+    ///
+    /// Returns:
+    ///     Sequence[(int, int)]: List of two tweezer edges
+    fn two_tweezer_edges(&self) -> Vec<(usize, usize)> {
+        self.internal.two_tweezer_edges()
+    }
+
+    /// Returns the backend associated with the device.
+    pub fn qrydbackend(&self) -> String {
+        self.internal.qrydbackend()
+    }
+
+    /// Returns the seed usized for the API.
+    pub fn seed(&self) -> Option<usize> {
+        self.internal.seed()
+    }
+
+    /// Returns whether the device is a cached copy, used because the QRyd WebAPI was unreachable.
+    ///
+    /// Only set by `from_api` and `device_from_api` with the device cache enabled
+    /// via the environmental variable `$QRYD_API_CACHE_DIR`.
+    ///
+    /// Returns:
+    ///     bool: Whether the device definition may be outdated.
+    pub fn is_offline_fallback(&self) -> bool {
+        self.internal.is_offline_fallback()
+    }
+
+    /// Return the bincode representation of the Enum variant of the Device.
+    ///
+    /// Only used for internal interfacing.
+    ///
+    /// Returns:
+    ///     ByteArray: The serialized TweezerDevice (in [bincode] form).
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize Device to bytes.
+    pub fn _enum_to_bincode(&self) -> PyResult<Py<PyByteArray>> {
+        let qryd_enum: QRydAPIDevice = (&self.internal).into();
+        let serialized = bincode::serialize(&qryd_enum)
+            .map_err(|_| PyValueError::new_err("Cannot serialize TweezerDevice to bytes"))?;
+        let b: Py<PyByteArray> = Python::with_gil(|py| -> Py<PyByteArray> {
+            PyByteArray::new_bound(py, &serialized[..]).into()
+        });
+        Ok(b)
+    }
+
+    /// Creates a graph representing a TweezerDevice.
+    ///
+    /// Args:
+    ///     draw_shifts (Optional[bool]): Whether to draw shifts or not. Default: false
+    ///     pixel_per_point (Optional[float]): The quality of the image.
+    ///     file_save_path (Optional[str]): Path to save the image to. Default: output the image with the display method.
+    ///
+    /// Raises:
+    ///     PyValueError - if there is no layout, an error occurred during the compilation or and invalid path was provided.
+    ///
+    #[pyo3(text_signature = "(draw_shifts, pixel_per_point, file_save_path, /)")]
+    pub fn draw(
+        &self,
+        draw_shifts: Option<bool>,
+        pixel_per_point: Option<f32>,
+        file_save_path: Option<String>,
+    ) -> PyResult<()> {
+        let display_image = file_save_path.is_none();
+        let image = self
+            .internal
+            .draw(
+                pixel_per_point,
+                draw_shifts.unwrap_or(false),
+                &file_save_path,
+            )
+            .map_err(|x| PyValueError::new_err(format!("Error during Circuit drawing: {x:?}")))?;
+
+        if display_image {
+            let mut buffer = Cursor::new(Vec::new());
+            image
+                .write_to(&mut buffer, image::ImageFormat::Png)
+                .map_err(|x| {
+                    PyValueError::new_err(format!(
+                        "Error during the generation of the Png file: {x:?}"
+                    ))
+                })?;
+            Python::with_gil(|py| {
+                let pil = PyModule::import_bound(py, "PIL.Image").unwrap();
+                let io = PyModule::import_bound(py, "io").unwrap();
+                let display = PyModule::import_bound(py, "IPython.display").unwrap();
+                let builtins = PyModule::import_bound(py, "builtins").unwrap();
+
+                let bytes_image_data = builtins
+                    .call_method1("bytes", (buffer.clone().into_inner(),))
+                    .unwrap();
+                let bytes_io = io.call_method1("BytesIO", (bytes_image_data,)).unwrap();
+                let image = pil.call_method1("open", (bytes_io,)).unwrap();
+
+                display.call_method1("display", (image,)).unwrap();
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Tweezer Mutable Device
+///
+/// This interface allows setting any piece of information about the device
+/// tweezer.
+///
+/// Args:
+///     controlled_z_phase_relation ((Optional[Union[str, float]])): The relation to use for the PhaseShiftedControlledZ gate.
+///                                   It can be hardcoded to a specific value if a float is passed in as String.
+///     controlled_phase_phase_relation ((Optional[Union[str, float]])): The relation to use for the PhaseShiftedControlledPhase gate.
+#[pyclass(name = "TweezerMutableDevice", module = "qoqo_qryd")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TweezerMutableDeviceWrapper {
+    /// Internal storage of [roqoqo_qryd::TweezerDevice]
+    pub internal: TweezerDevice,
+}
+
+#[pymethods]
+impl TweezerMutableDeviceWrapper {
+    /// Creates a new TweezerMutableDevice instance.
+    ///
+    /// Args:
+    ///     seed (int): Optional seed, for simulation purposes.
+    ///     controlled_z_phase_relation (Optional[Union[str, float]]): The relation to use for the PhaseShiftedControlledZ gate.
+    ///     controlled_phase_phase_relation (Optional[Union[str, float]]): The relation to use for the PhaseShiftedControlledPhase gate.
+    ///
+    /// Returns:
+    ///     TweezerMutableDevice: The new TweezerMutableDevice instance.
+    #[new]
+    #[pyo3(
+        text_signature = "(seed, controlled_z_phase_relation, controlled_phase_phase_relation, /)"
+    )]
+    pub fn new(
         seed: Option<usize>,
         controlled_z_phase_relation: Option<&Bound<PyAny>>,
         controlled_phase_phase_relation: Option<&Bound<PyAny>>,
@@ -772,206 +1291,899 @@ impl TweezerMutableDeviceWrapper {
                         .unwrap(),
                 )
             }
-        } else {
-            None
-        };
-        let cppr = if let Some(value) = controlled_phase_phase_relation {
-            if convert_into_calculator_float(value).is_ok() {
-                Some(convert_into_calculator_float(value).unwrap().to_string())
-            } else {
-                Some(
-                    controlled_phase_phase_relation
-                        .unwrap()
-                        .extract::<String>()
-                        .unwrap(),
-                )
+        } else {
+            None
+        };
+        let cppr = if let Some(value) = controlled_phase_phase_relation {
+            if convert_into_calculator_float(value).is_ok() {
+                Some(convert_into_calculator_float(value).unwrap().to_string())
+            } else {
+                Some(
+                    controlled_phase_phase_relation
+                        .unwrap()
+                        .extract::<String>()
+                        .unwrap(),
+                )
+            }
+        } else {
+            None
+        };
+        Self {
+            internal: TweezerDevice::new(seed, czpr, cppr),
+        }
+    }
+
+    /// Get the name of the current layout.
+    ///
+    /// Returns:
+    ///     str: The name of the current layout.
+    pub fn current_layout(&self) -> &str {
+        self.internal
+            .current_layout
+            .as_ref()
+            .expect("None")
+            .as_str()
+    }
+
+    /// Add a new layout to the device.
+    ///
+    /// Args:
+    ///     name (str): The name that is assigned to the new Layout.
+    #[pyo3(text_signature = "(name, /)")]
+    pub fn add_layout(&mut self, name: &str) -> PyResult<()> {
+        self.internal
+            .add_layout(name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Add a new Layout generated from a regular lattice of tweezers to the device.
+    ///
+    /// Every tweezer supports the given single-qubit gates. Two-qubit gates are set for
+    /// every pair of tweezers within the interaction radius, three-qubit gates for every
+    /// trio of tweezers pairwise within the interaction radius.
+    ///
+    /// Args:
+    ///     name (str): The name that is assigned to the new Layout.
+    ///     lattice (str): The lattice type, one of "square", "triangular", "hexagonal" or "kagome".
+    ///     rows (int): The number of rows of tweezers.
+    ///     columns (int): The number of tweezers per row. For kagome lattices, the number of tweezers in the full rows.
+    ///     spacing (float): The distance between nearest neighbours in micrometers.
+    ///     gate_times (Dict[str, float]): The gate time of each native gate supported by the Layout.
+    ///     interaction_radius (Optional[float]): The maximal distance of interacting tweezers in micrometers. Defaults to the spacing.
+    ///     row_shifts (Optional[bool]): Whether to allow shifting qubits along the rows. Defaults to False.
+    ///
+    /// Raises:
+    ///     ValueError: The name is already in use or the Layout could not be generated.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(
+        text_signature = "(name, lattice, rows, columns, spacing, gate_times, interaction_radius, row_shifts, /)"
+    )]
+    pub fn add_lattice_layout(
+        &mut self,
+        name: &str,
+        lattice: &str,
+        rows: usize,
+        columns: usize,
+        spacing: f64,
+        gate_times: HashMap<String, f64>,
+        interaction_radius: Option<f64>,
+        row_shifts: Option<bool>,
+    ) -> PyResult<()> {
+        let lattice = match lattice {
+            "square" => TweezerLattice::Square {
+                rows,
+                columns,
+                spacing,
+            },
+            "triangular" => TweezerLattice::Triangular {
+                rows,
+                columns,
+                spacing,
+            },
+            "hexagonal" => TweezerLattice::Hexagonal {
+                rows,
+                columns,
+                spacing,
+            },
+            "kagome" => TweezerLattice::Kagome {
+                rows,
+                columns,
+                spacing,
+            },
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Lattice type {} is not supported. Supported lattice types: square, triangular, hexagonal, kagome.",
+                    lattice
+                )))
+            }
+        };
+        self.internal
+            .add_lattice_layout(
+                name,
+                &lattice,
+                &gate_times,
+                interaction_radius,
+                row_shifts.unwrap_or(false),
+            )
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Add a new Layout generated from arbitrary tweezer positions to the device.
+    ///
+    /// Every tweezer supports the given single-qubit gates. Two-qubit gates are set for
+    /// every pair of tweezers within the interaction radius, three-qubit gates for every
+    /// trio of tweezers pairwise within the interaction radius.
+    ///
+    /// Args:
+    ///     name (str): The name that is assigned to the new Layout.
+    ///     positions (List[List[List[float]]]): The (x, y) or (x, y, z) coordinates of the tweezers in micrometers, row by row.
+    ///     gate_times (Dict[str, float]): The gate time of each native gate supported by the Layout.
+    ///     interaction_radius (float): The maximal distance of interacting tweezers in micrometers.
+    ///     row_shifts (Optional[bool]): Whether to allow shifting qubits along the rows. Defaults to False.
+    ///
+    /// Raises:
+    ///     ValueError: The name is already in use or the Layout could not be generated.
+    #[pyo3(text_signature = "(name, positions, gate_times, interaction_radius, row_shifts, /)")]
+    pub fn add_layout_from_positions(
+        &mut self,
+        name: &str,
+        positions: Vec<Vec<Vec<f64>>>,
+        gate_times: HashMap<String, f64>,
+        interaction_radius: f64,
+        row_shifts: Option<bool>,
+    ) -> PyResult<()> {
+        let rows = positions
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|position| match position.as_slice() {
+                        [x, y] => Ok((*x, *y, 0.0)),
+                        [x, y, z] => Ok((*x, *y, *z)),
+                        _ => Err(PyValueError::new_err(format!(
+                            "A tweezer position needs 2 or 3 coordinates, {} were given.",
+                            position.len()
+                        ))),
+                    })
+                    .collect::<PyResult<Vec<(f64, f64, f64)>>>()
+            })
+            .collect::<PyResult<Vec<Vec<(f64, f64, f64)>>>>()?;
+        self.internal
+            .add_lattice_layout(
+                name,
+                &TweezerLattice::Arbitrary { rows },
+                &gate_times,
+                Some(interaction_radius),
+                row_shifts.unwrap_or(false),
+            )
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Derive the two- and three-qubit gate times of a Layout from an interaction model.
+    ///
+    /// The model is evaluated for every pair and trio of tweezers with a set position and
+    /// every native two- and three-qubit gate. Two-qubit gate times are set for both orders
+    /// of each pair. The existing two- and three-qubit gate times of the Layout are replaced.
+    ///
+    /// Args:
+    ///     model (Union[CutoffInteractionModel, BlockadeInteractionModel]): The interaction model to evaluate.
+    ///     layout_name (Optional[str]): The name of the Layout to set the gate times in. Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     TypeError: The model is not an interaction model.
+    ///     ValueError: The layout does not exist.
+    #[pyo3(text_signature = "(model, layout_name, /)")]
+    pub fn apply_interaction_model(
+        &mut self,
+        model: &Bound<PyAny>,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        let model = extract_interaction_model(model)?;
+        self.internal
+            .apply_interaction_model(model.as_ref(), layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Switch to a different pre-defined Layout.
+    ///
+    /// It is updated only if the given Layout name is present in the device's
+    /// Layout register. If the qubit -> tweezer mapping is empty, it is
+    /// trivially populated by default.
+    ///
+    /// Args:
+    ///     layout_number (str): The number index of the new Layout.
+    ///     with_trivial_map (bool): Whether the qubit -> tweezer mapping should be trivially populated. Defaults to true.
+    ///
+    /// Raises:
+    ///     PyValueError
+    #[pyo3(text_signature = "(name, with_trivial_map, /)")]
+    pub fn switch_layout(&mut self, name: &str, with_trivial_map: Option<bool>) -> PyResult<()> {
+        self.internal
+            .switch_layout(name, with_trivial_map)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Returns a list of all available Layout names.
+    ///
+    /// Returns:
+    ///     List[str]: The list of all available Layout names.
+    pub fn available_layouts(&self) -> Vec<&str> {
+        self.internal.available_layouts()
+    }
+
+    /// Modifies the qubit -> tweezer mapping of the device.
+    ///
+    /// If a qubit -> tweezer mapping is already present, it is overwritten and a qubit
+    /// previously occupying the tweezer is removed from the mapping. Use `insert_qubit`,
+    /// `move_qubit` and `remove_qubit` to report such conflicts instead.
+    ///
+    /// Args:
+    ///     qubit (int): The index of the qubit.
+    ///     tweezer (int): The index of the tweezer.
+    ///
+    /// Returns:
+    ///     dict[int, int]: The updated qubit -> tweezer mapping.
+    ///     
+    /// Raises:
+    ///     ValueError: The tweezer is not present in the device.
+    #[pyo3(text_signature = "(qubit, tweezer, /)")]
+    pub fn add_qubit_tweezer_mapping(
+        &mut self,
+        qubit: usize,
+        tweezer: usize,
+    ) -> PyResult<PyObject> {
+        Python::with_gil(|py| -> PyResult<PyObject> {
+            match self.internal.add_qubit_tweezer_mapping(qubit, tweezer) {
+                Ok(mapping) => Ok(mapping.into_py_dict_bound(py).into()),
+                Err(err) => Err(PyValueError::new_err(format!("{:}", err))),
+            }
+        })
+    }
+
+    /// Places an unmapped qubit in a free tweezer of the current Layout.
+    ///
+    /// Args:
+    ///     qubit (int): The index of the qubit.
+    ///     tweezer (int): The index of the tweezer.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present in the device or occupied, or the qubit is already mapped.
+    #[pyo3(text_signature = "(qubit, tweezer, /)")]
+    pub fn insert_qubit(&mut self, qubit: usize, tweezer: usize) -> PyResult<()> {
+        self.internal
+            .insert_qubit(qubit, tweezer)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Moves a mapped qubit into a free tweezer of the current Layout.
+    ///
+    /// Args:
+    ///     qubit (int): The index of the qubit.
+    ///     tweezer (int): The index of the target tweezer.
+    ///
+    /// Returns:
+    ///     int: The tweezer the qubit has been moved out of.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present in the device or occupied, or the qubit is not mapped.
+    #[pyo3(text_signature = "(qubit, tweezer, /)")]
+    pub fn move_qubit(&mut self, qubit: usize, tweezer: usize) -> PyResult<usize> {
+        self.internal
+            .move_qubit(qubit, tweezer)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Removes a qubit from the qubit -> tweezer mapping.
+    ///
+    /// Args:
+    ///     qubit (int): The index of the qubit.
+    ///
+    /// Returns:
+    ///     int: The tweezer the qubit occupied.
+    ///
+    /// Raises:
+    ///     ValueError: The qubit is not mapped.
+    #[pyo3(text_signature = "(qubit, /)")]
+    pub fn remove_qubit(&mut self, qubit: usize) -> PyResult<usize> {
+        self.internal
+            .remove_qubit(qubit)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Plans the tweezer shifts moving the qubits of the device into a target arrangement.
+    ///
+    /// Only the allowed tweezer shifts of the current Layout are used. The search finds the
+    /// smallest number of parallel PragmaShiftQubitsTweezers operations, whose shifts move
+    /// different qubits and are checked in order as in `change_device`.
+    ///
+    /// Args:
+    ///     target (Dict[int, int]): The target qubit -> tweezer mapping, containing the qubits of the device mapping.
+    ///     max_states (Optional[int]): The maximal number of qubit arrangements to explore.
+    ///
+    /// Returns:
+    ///     List[PragmaChangeDevice]: The wrapped PragmaShiftQubitsTweezers operations to apply in order.
+    ///
+    /// Raises:
+    ///     ValueError: The target is invalid or not reachable, or the search exceeded max_states.
+    #[pyo3(text_signature = "(target, max_states, /)")]
+    pub fn plan_shifts(
+        &self,
+        target: HashMap<usize, usize>,
+        max_states: Option<usize>,
+    ) -> PyResult<Vec<PragmaChangeDeviceWrapper>> {
+        Ok(self
+            .internal
+            .plan_shifts(&target, max_states)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))?
+            .into_iter()
+            .map(|internal| PragmaChangeDeviceWrapper { internal })
+            .collect())
+    }
+
+    /// Get the qubit -> tweezer mapping of the device.
+    ///
+    /// Returns:
+    ///     dict[int, int]: The qubit -> tweezer mapping.
+    ///     None: The mapping is empty.
+    pub fn get_qubit_to_tweezer_mapping(&self) -> Option<PyObject> {
+        Python::with_gil(|py| -> Option<PyObject> {
+            self.internal
+                .qubit_to_tweezer()
+                .map(|mapping| mapping.as_map().into_py_dict_bound(py).into())
+        })
+    }
+
+    /// Get the qubit occupying the given tweezer.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///
+    /// Returns:
+    ///     Optional[int]: The qubit in the tweezer, None if the tweezer is free.
+    #[pyo3(text_signature = "(tweezer, /)")]
+    pub fn qubit_at(&self, tweezer: usize) -> Option<usize> {
+        self.internal.qubit_at(tweezer)
+    }
+
+    /// Get the tweezers occupied by a qubit.
+    ///
+    /// Returns:
+    ///     list[int]: The occupied tweezers, in ascending order.
+    pub fn occupied_tweezers(&self) -> Vec<usize> {
+        self.internal.occupied_tweezers()
+    }
+
+    /// Get the tweezers of a Layout not occupied by a qubit.
+    ///
+    /// Args:
+    ///     layout_name (Optional[str]): The name of the Layout. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     list[int]: The free tweezers, in ascending order.
+    ///
+    /// Raises:
+    ///     ValueError: The layout does not exist or no current layout is set.
+    #[pyo3(text_signature = "(layout_name, /)")]
+    pub fn free_tweezers(&self, layout_name: Option<String>) -> PyResult<Vec<usize>> {
+        self.internal
+            .free_tweezers(layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Get the names of the available gates in the given layout.
+    ///
+    /// Args:
+    ///     layout_name (Optional[str]): The name of the layout. Defaults to the current Layout.
+    ///
+    /// Returns:
+    ///     list[str]: List of the names of the available gates in the given layout.
+    ///
+    /// Raises:
+    ///     ValueError: No layout name provided and no current layout set.
+    #[pyo3(text_signature = "(layout_name, /)")]
+    pub fn get_available_gates_names(&self, layout_name: Option<String>) -> PyResult<Vec<&str>> {
+        self.internal
+            .get_available_gates_names(layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Get whether the device allows PragmaActiveReset operations or not.
+    ///
+    /// Returns:
+    ///     bool: Whether the device allows PragmaActiveReset operations or not.
+    pub fn get_allow_reset(&self) -> bool {
+        self.internal.allow_reset
+    }
+
+    /// Deactivate the given qubit in the device.
+    ///
+    /// Args:
+    ///     qubit (int): The input qubit identifier.
+    ///
+    /// Returns:
+    ///     dict[int, int]: The updated qubit -> tweezer mapping.
+    ///
+    /// Raises:
+    ///     PyValueError: If the given qubit identifier is not present in the mapping.
+    #[pyo3(text_signature = "(qubit, /)")]
+    pub fn deactivate_qubit(&mut self, qubit: usize) -> PyResult<PyObject> {
+        Python::with_gil(|py| -> PyResult<PyObject> {
+            match self.internal.deactivate_qubit(qubit) {
+                Ok(tweezers) => Ok(tweezers.into_py_dict_bound(py).into()),
+                Err(err) => Err(PyValueError::new_err(format!("{:}", err))),
             }
+        })
+    }
+
+    /// Returns the gate time of a single qubit operation on this device.
+    ///
+    /// Returns:
+    ///     float: The gate time.
+    ///
+    /// Raises:
+    ///     ValueError: The gate is not available in the device.
+    #[pyo3(text_signature = "(hqslang, qubit, /)")]
+    pub fn single_qubit_gate_time(&self, hqslang: &str, qubit: usize) -> PyResult<f64> {
+        self.internal
+            .single_qubit_gate_time(hqslang, &qubit)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
+    }
+
+    /// Returns the gate time of a two qubit operation on this device.
+    ///
+    /// Returns:
+    ///     float: The gate time.
+    ///
+    /// Raises:
+    ///     ValueError: The gate is not available in the device.
+    #[pyo3(text_signature = "(hqslang, control, target, /)")]
+    pub fn two_qubit_gate_time(
+        &self,
+        hqslang: &str,
+        control: usize,
+        target: usize,
+    ) -> PyResult<f64> {
+        self.internal
+            .two_qubit_gate_time(hqslang, &control, &target)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
+    }
+
+    /// Returns the gate time of a three qubit operation on this device.
+    ///
+    /// Returns:
+    ///     float: The gate time.
+    ///
+    /// Raises:
+    ///     ValueError: The gate is not available in the device.
+    #[pyo3(text_signature = "(hqslang, control_0, control_1, target, /)")]
+    pub fn three_qubit_gate_time(
+        &self,
+        hqslang: &str,
+        control_0: usize,
+        control_1: usize,
+        target: usize,
+    ) -> PyResult<f64> {
+        self.internal
+            .three_qubit_gate_time(hqslang, &control_0, &control_1, &target)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
+    }
+
+    /// Returns the gate time of a multi qubit operation on this device.
+    ///
+    /// Returns:
+    ///     float: The gate time.
+    ///
+    /// Raises:
+    ///     ValueError: The gate is not available in the device.
+    #[pyo3(text_signature = "(hqslang, qubits, /)")]
+    pub fn multi_qubit_gate_time(&self, hqslang: &str, qubits: Vec<usize>) -> PyResult<f64> {
+        self.internal
+            .multi_qubit_gate_time(hqslang, &qubits)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
+    }
+
+    /// Returns the PhaseShiftedControlledZ phase shift according to the device's relation.
+    ///
+    /// Returns:
+    ///     float: The PhaseShiftedControlledZ phase shift.
+    ///
+    /// Raises:
+    ///     ValueError: Error in relation selection.
+    pub fn phase_shift_controlled_z(&self) -> PyResult<f64> {
+        self.internal
+            .phase_shift_controlled_z()
+            .ok_or_else(|| PyValueError::new_err("Error in relation selection."))
+    }
+
+    /// Returns the PhaseShiftedControlledPhase phase shift according to the device's relation.
+    ///
+    /// Returns:
+    ///     float: The PhaseShiftedControlledPhase phase shift.
+    ///
+    /// Raises:
+    ///     ValueError: Error in relation selection.
+    #[pyo3(text_signature = "(theta, /)")]
+    pub fn phase_shift_controlled_phase(&self, theta: &Bound<PyAny>) -> PyResult<f64> {
+        let float = if let Ok(conv) = convert_into_calculator_float(theta) {
+            *conv
+                .float()
+                .map_err(|err| PyValueError::new_err(format!("{:}", err)))?
         } else {
-            None
+            theta.extract::<f64>()?
         };
-        Self {
-            internal: TweezerDevice::new(seed, czpr, cppr),
+
+        self.internal
+            .phase_shift_controlled_phase(float)
+            .ok_or_else(|| PyValueError::new_err("Error in relation selection."))
+    }
+
+    /// Returns the gate time of a PhaseShiftedControlledZ operation with the given qubits and phi angle.
+    ///
+    /// Args:
+    ///     control (int): The control qubit the gate acts on
+    ///     target (int): The target qubit the gate acts on
+    ///     phi (float): The phi angle to be checked.
+    ///
+    /// Returns:
+    ///     float: The gate time.
+    ///
+    /// Raises:
+    ///     ValueError: The gate is not available on the device.
+    #[pyo3(text_signature = "(control, target, phi, /)")]
+    pub fn gate_time_controlled_z(&self, control: usize, target: usize, phi: f64) -> PyResult<f64> {
+        self.internal
+            .gate_time_controlled_z(&control, &target, phi)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
+    }
+
+    /// Returns the gate time of a PhaseShiftedControlledPhase operation with the given qubits and phi and theta angles.
+    ///
+    /// Args:
+    ///     control (int): The control qubit the gate acts on
+    ///     target (int): The target qubit the gate acts on
+    ///     phi (float): The phi angle to be checked.
+    ///     theta (float): The theta angle to be checked.
+    ///
+    /// Returns:
+    ///     float: The gate time.
+    ///
+    /// Raises:
+    ///     ValueError: The gate is not available on the device.
+    #[pyo3(text_signature = "(control, target, phi, theta, /)")]
+    pub fn gate_time_controlled_phase(
+        &self,
+        control: usize,
+        target: usize,
+        phi: f64,
+        theta: f64,
+    ) -> PyResult<f64> {
+        self.internal
+            .gate_time_controlled_phase(&control, &target, phi, theta)
+            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
+    }
+
+    /// Turns Device into GenericDevice
+    ///
+    /// Can be used as a generic interface for devices when a boxed dyn trait object cannot be used
+    /// (for example when the interface needs to be serialized)
+    ///
+    /// Returns:
+    ///     GenericDevice: The device in generic representation
+    ///
+    /// Note:
+    ///     GenericDevice uses nested HashMaps to represent the most general device connectivity.
+    ///     The memory usage will be inefficient for devices with large qubit numbers.
+    fn generic_device(&self) -> GenericDeviceWrapper {
+        GenericDeviceWrapper {
+            internal: self.internal.to_generic_device(),
+        }
+    }
+
+    /// Return a copy of the TweezerMutableDevice (copy here produces a deepcopy).
+    ///
+    /// Returns:
+    ///     TweezerMutableDevice: A deep copy of self.
+    pub fn __copy__(&self) -> TweezerMutableDeviceWrapper {
+        self.clone()
+    }
+
+    /// Return a deep copy of the TweezerMutableDevice.
+    ///
+    /// Returns:
+    ///     TweezerMutableDevice: A deep copy of self.
+    pub fn __deepcopy__(&self, _memodict: Py<PyAny>) -> TweezerMutableDeviceWrapper {
+        self.clone()
+    }
+
+    /// Return the bincode representation of the TweezerMutableDevice using the bincode crate.
+    ///
+    /// Returns:
+    ///     ByteArray: The serialized TweezerMutableDevice (in bincode form).
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize TweezerMutableDevice to bytes.
+    pub fn to_bincode(&self) -> PyResult<Py<PyByteArray>> {
+        let serialized = serialize(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize TweezerMutableDevice to bytes"))?;
+        let b: Py<PyByteArray> = Python::with_gil(|py| -> Py<PyByteArray> {
+            PyByteArray::new_bound(py, &serialized[..]).into()
+        });
+        Ok(b)
+    }
+
+    /// Convert the bincode representation of the TweezerMutableDevice to an
+    /// TweezerMutableDevice using the bincode crate.
+    ///
+    /// Args:
+    ///     input (ByteArray): The serialized TweezerMutableDevice (in bincode form).
+    ///
+    /// Returns:
+    ///     TweezerMutableDevice: The deserialized TweezerMutableDevice.
+    ///
+    /// Raises:
+    ///     TypeError: Input cannot be converted to byte array.
+    ///     ValueError: Input cannot be deserialized to TweezerMutableDevice.
+    #[staticmethod]
+    #[pyo3(text_signature = "(input, /)")]
+    pub fn from_bincode(input: &Bound<PyAny>) -> PyResult<TweezerMutableDeviceWrapper> {
+        let bytes = input
+            .extract::<Vec<u8>>()
+            .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
+
+        Ok(TweezerMutableDeviceWrapper {
+            internal: TweezerDevice::from_bincode(&bytes[..]).map_err(|_| {
+                PyValueError::new_err("Input cannot be deserialized to TweezerMutableDevice")
+            })?,
+        })
+    }
+
+    /// Return the json representation of the TweezerMutableDevice.
+    ///
+    /// Additionally, a gate set check is performed.
+    ///
+    /// Returns:
+    ///     str: The serialized form of TweezerMutableDevice.
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize TweezerMutableDevice to json or
+    ///         the device does not have valid QRyd gates available.
+    fn to_json(&self) -> PyResult<String> {
+        let mut all_gates_names: HashSet<&str> = HashSet::new();
+        for layout in self.internal.available_layouts() {
+            all_gates_names.extend(
+                &self
+                    .internal
+                    .get_available_gates_names(Some(layout.to_string()))
+                    .unwrap(),
+            );
+        }
+        if all_gates_names.iter().any(|name| {
+            !ALLOWED_NATIVE_SINGLE_QUBIT_GATES.contains(name)
+                && !ALLOWED_NATIVE_TWO_QUBIT_GATES.contains(name)
+                && !ALLOWED_NATIVE_THREE_QUBIT_GATES.contains(name)
+        }) || all_gates_names.is_empty()
+        {
+            return Err(PyValueError::new_err(
+                "The device does not support valid gates in a layout. ".to_owned()
+                    + "The valid gates are: "
+                    + &ALLOWED_NATIVE_SINGLE_QUBIT_GATES.join(", ")
+                    + ", "
+                    + &ALLOWED_NATIVE_TWO_QUBIT_GATES.join(", ")
+                    + ", "
+                    + &ALLOWED_NATIVE_THREE_QUBIT_GATES.join(", ")
+                    + ".",
+            ));
+        }
+        let serialized = serde_json::to_string(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize TweezerMutableDevice to json"))?;
+        Ok(serialized)
+    }
+
+    /// Convert the json representation of a TweezerMutableDevice to an TweezerMutableDevice.
+    ///
+    /// Additionally, a gate set check is performed.
+    ///
+    /// Args:
+    ///     input (str): The serialized TweezerMutableDevice in json form.
+    ///
+    /// Returns:
+    ///     TweezerMutableDevice: The deserialized TweezerMutableDevice.
+    ///
+    /// Raises:
+    ///     ValueError: Input cannot be deserialized to TweezerMutableDevice or
+    ///         the device does not have valid QRyd gates available.
+    #[staticmethod]
+    #[pyo3(text_signature = "(input, /)")]
+    fn from_json(input: &str) -> PyResult<TweezerMutableDeviceWrapper> {
+        let internal: TweezerDevice = serde_json::from_str(input).map_err(|_| {
+            PyValueError::new_err("Input cannot be deserialized to TweezerMutableDevice")
+        })?;
+        let mut all_gates_names: HashSet<&str> = HashSet::new();
+        for layout in internal.available_layouts() {
+            all_gates_names.extend(
+                &internal
+                    .get_available_gates_names(Some(layout.to_string()))
+                    .unwrap(),
+            );
+        }
+        if all_gates_names.iter().any(|name| {
+            !ALLOWED_NATIVE_SINGLE_QUBIT_GATES.contains(name)
+                && !ALLOWED_NATIVE_TWO_QUBIT_GATES.contains(name)
+                && !ALLOWED_NATIVE_THREE_QUBIT_GATES.contains(name)
+        }) || all_gates_names.is_empty()
+        {
+            return Err(PyValueError::new_err(
+                "The device does not support valid gates in a layout. ".to_owned()
+                    + "The valid gates are: "
+                    + &ALLOWED_NATIVE_SINGLE_QUBIT_GATES.join(", ")
+                    + ", "
+                    + &ALLOWED_NATIVE_TWO_QUBIT_GATES.join(", ")
+                    + ", "
+                    + &ALLOWED_NATIVE_THREE_QUBIT_GATES.join(", ")
+                    + ".",
+            ));
         }
+        Ok(TweezerMutableDeviceWrapper { internal })
     }
 
-    /// Get the name of the current layout.
+    /// Return number of qubits in device.
     ///
     /// Returns:
-    ///     str: The name of the current layout.
-    pub fn current_layout(&self) -> &str {
-        self.internal
-            .current_layout
-            .as_ref()
-            .expect("None")
-            .as_str()
-    }
-
-    /// Add a new layout to the device.
+    ///     int: The number of qubits.
     ///
-    /// Args:
-    ///     name (str): The name that is assigned to the new Layout.
-    #[pyo3(text_signature = "(name, /)")]
-    pub fn add_layout(&mut self, name: &str) -> PyResult<()> {
-        self.internal
-            .add_layout(name)
-            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    pub fn number_qubits(&self) -> usize {
+        self.internal.number_qubits()
     }
 
-    /// Switch to a different pre-defined Layout.
-    ///
-    /// It is updated only if the given Layout name is present in the device's
-    /// Layout register. If the qubit -> tweezer mapping is empty, it is
-    /// trivially populated by default.
+    /// Returns the number of total tweezer positions in the device.
     ///
     /// Args:
-    ///     layout_number (str): The number index of the new Layout.
-    ///     with_trivial_map (bool): Whether the qubit -> tweezer mapping should be trivially populated. Defaults to true.
+    ///     layout_name (Optional[str]): The name of the layout to reference. Defaults to the current layout.
     ///
-    /// Raises:
-    ///     PyValueError
-    #[pyo3(text_signature = "(name, with_trivial_map, /)")]
-    pub fn switch_layout(&mut self, name: &str, with_trivial_map: Option<bool>) -> PyResult<()> {
+    /// Returns:
+    ///     int: The number of tweezer positions in the device.
+    #[pyo3(text_signature = "(layout_name, /)")]
+    pub fn number_tweezer_positions(&self, layout_name: Option<String>) -> PyResult<usize> {
         self.internal
-            .switch_layout(name, with_trivial_map)
+            .number_tweezer_positions(layout_name)
             .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Returns a list of all available Layout names.
+    /// Checks the self-consistency of all Layouts of the device.
+    ///
+    /// The following rules are checked for each Layout:
+    /// AsymmetricTwoQubitGate: two-qubit gates are set for both orders of each tweezer pair, with the same gate time.
+    /// ShiftWithUnknownTweezer: allowed tweezer shifts only involve tweezers with gates.
+    /// TweezersPerRowMismatch: the tweezers per row, if set, add up to the number of tweezers.
+    /// UnconnectedThreeQubitGate: three-qubit gates only involve tweezer pairs connected by a two-qubit gate.
+    /// PositionOfUnknownTweezer: tweezer positions are only set for tweezers with gates.
     ///
     /// Returns:
-    ///     List[str]: The list of all available Layout names.
-    pub fn available_layouts(&self) -> Vec<&str> {
-        self.internal.available_layouts()
+    ///     Dict[str, List[Dict[str, Any]]]: The issues of each Layout, by Layout name. Each issue
+    ///         contains the violated "rule", the involved "tweezers" and a "message".
+    pub fn validate(&self) -> PyResult<PyObject> {
+        validation_report_to_dict(self.internal.validate())
     }
 
-    /// Modifies the qubit -> tweezer mapping of the device.
-    ///
-    /// If a qubit -> tweezer mapping is already present, it is overwritten.
+    /// Returns the position of a tweezer in a given Layout.
     ///
     /// Args:
-    ///     qubit (int): The index of the qubit.
     ///     tweezer (int): The index of the tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
     ///
     /// Returns:
-    ///     dict[int, int]: The updated qubit -> tweezer mapping.
-    ///     
+    ///     Tuple[float, float, float]: The (x, y, z) coordinates of the tweezer in micrometers.
+    ///
     /// Raises:
-    ///     ValueError: The tweezer is not present in the device.
-    #[pyo3(text_signature = "(qubit, tweezer, /)")]
-    pub fn add_qubit_tweezer_mapping(
-        &mut self,
-        qubit: usize,
+    ///     ValueError: The layout does not exist or has no position for the tweezer.
+    #[pyo3(text_signature = "(tweezer, layout_name, /)")]
+    pub fn get_tweezer_position(
+        &self,
         tweezer: usize,
-    ) -> PyResult<PyObject> {
-        Python::with_gil(|py| -> PyResult<PyObject> {
-            match self.internal.add_qubit_tweezer_mapping(qubit, tweezer) {
-                Ok(mapping) => Ok(mapping.into_py_dict_bound(py).into()),
-                Err(err) => Err(PyValueError::new_err(format!("{:}", err))),
-            }
-        })
-    }
-
-    /// Get the qubit -> tweezer mapping of the device.
-    ///
-    /// Returns:
-    ///     dict[int, int]: The qubit -> tweezer mapping.
-    ///     None: The mapping is empty.
-    pub fn get_qubit_to_tweezer_mapping(&self) -> Option<PyObject> {
-        Python::with_gil(|py| -> Option<PyObject> {
-            self.internal
-                .qubit_to_tweezer
-                .as_ref()
-                .map(|mapping| mapping.into_py_dict_bound(py).into())
-        })
+        layout_name: Option<String>,
+    ) -> PyResult<(f64, f64, f64)> {
+        self.internal
+            .get_tweezer_position(tweezer, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Get the names of the available gates in the given layout.
+    /// Returns the decoherence rates of a qubit held in a tweezer in a given Layout.
     ///
     /// Args:
-    ///     layout_name (Optional[str]): The name of the layout. Defaults to the current Layout.
+    ///     tweezer (int): The index of the tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
     ///
     /// Returns:
-    ///     list[str]: List of the names of the available gates in the given layout.
+    ///     Tuple[float, float, float]: The damping, dephasing and depolarising rates, zero if none are set.
     ///
     /// Raises:
-    ///     ValueError: No layout name provided and no current layout set.
-    #[pyo3(text_signature = "(layout_name, /)")]
-    pub fn get_available_gates_names(&self, layout_name: Option<String>) -> PyResult<Vec<&str>> {
+    ///     ValueError: The layout does not exist.
+    #[pyo3(text_signature = "(tweezer, layout_name, /)")]
+    pub fn get_tweezer_decoherence_rates(
+        &self,
+        tweezer: usize,
+        layout_name: Option<String>,
+    ) -> PyResult<(f64, f64, f64)> {
         self.internal
-            .get_available_gates_names(layout_name)
+            .get_tweezer_decoherence_rates(tweezer, layout_name)
+            .map(|rates| (rates.damping, rates.dephasing, rates.depolarising))
             .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Get whether the device allows PragmaActiveReset operations or not.
-    ///
-    /// Returns:
-    ///     bool: Whether the device allows PragmaActiveReset operations or not.
-    pub fn get_allow_reset(&self) -> bool {
-        self.internal.allow_reset
-    }
-
-    /// Deactivate the given qubit in the device.
+    /// Returns the decoherence rates matrix of the tweezer a qubit occupies in the current Layout.
     ///
     /// Args:
-    ///     qubit (int): The input qubit identifier.
+    ///     qubit (int): The qubit.
     ///
     /// Returns:
-    ///     dict[int, int]: The updated qubit -> tweezer mapping.
-    ///
-    /// Raises:
-    ///     PyValueError: If the given qubit identifier is not present in the mapping.
+    ///     np.ndarray: The 3x3 decoherence rates matrix, zero for qubits without rates.
     #[pyo3(text_signature = "(qubit, /)")]
-    pub fn deactivate_qubit(&mut self, qubit: usize) -> PyResult<PyObject> {
-        Python::with_gil(|py| -> PyResult<PyObject> {
-            match self.internal.deactivate_qubit(qubit) {
-                Ok(tweezers) => Ok(tweezers.into_py_dict_bound(py).into()),
-                Err(err) => Err(PyValueError::new_err(format!("{:}", err))),
-            }
+    pub fn qubit_decoherence_rates(&self, qubit: usize) -> Py<PyArray2<f64>> {
+        Python::with_gil(|py| {
+            self.internal
+                .qubit_decoherence_rates(&qubit)
+                .unwrap_or_else(|| Array2::zeros((3, 3)))
+                .to_pyarray_bound(py)
+                .unbind()
         })
     }
 
-    /// Returns the gate time of a single qubit operation on this device.
+    /// Returns the error rate of a single-qubit gate on a qubit in the current Layout.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of the single-qubit gate.
+    ///     qubit (int): The qubit the gate acts on.
     ///
     /// Returns:
-    ///     float: The gate time.
+    ///     float: The error rate, zero if none is set.
     ///
     /// Raises:
-    ///     ValueError: The gate is not available in the device.
+    ///     ValueError: The gate is not available on the qubit.
     #[pyo3(text_signature = "(hqslang, qubit, /)")]
-    pub fn single_qubit_gate_time(&self, hqslang: &str, qubit: usize) -> PyResult<f64> {
+    pub fn single_qubit_gate_error(&self, hqslang: &str, qubit: usize) -> PyResult<f64> {
         self.internal
-            .single_qubit_gate_time(hqslang, &qubit)
+            .single_qubit_gate_error(hqslang, &qubit)
             .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
     }
 
-    /// Returns the gate time of a two qubit operation on this device.
+    /// Returns the error rate of a two-qubit gate on a qubit couple in the current Layout.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of the two-qubit gate.
+    ///     control (int): The control qubit the gate acts on.
+    ///     target (int): The target qubit the gate acts on.
     ///
     /// Returns:
-    ///     float: The gate time.
+    ///     float: The error rate, zero if none is set.
     ///
     /// Raises:
-    ///     ValueError: The gate is not available in the device.
+    ///     ValueError: The gate is not available on the qubits.
     #[pyo3(text_signature = "(hqslang, control, target, /)")]
-    pub fn two_qubit_gate_time(
+    pub fn two_qubit_gate_error(
         &self,
         hqslang: &str,
         control: usize,
         target: usize,
     ) -> PyResult<f64> {
         self.internal
-            .two_qubit_gate_time(hqslang, &control, &target)
+            .two_qubit_gate_error(hqslang, &control, &target)
             .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
     }
 
-    /// Returns the gate time of a three qubit operation on this device.
+    /// Returns the error rate of a three-qubit gate on a qubit trio in the current Layout.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of the three-qubit gate.
+    ///     control_0 (int): The first control qubit the gate acts on.
+    ///     control_1 (int): The second control qubit the gate acts on.
+    ///     target (int): The target qubit the gate acts on.
     ///
     /// Returns:
-    ///     float: The gate time.
+    ///     float: The error rate, zero if none is set.
     ///
     /// Raises:
-    ///     ValueError: The gate is not available in the device.
+    ///     ValueError: The gate is not available on the qubits.
     #[pyo3(text_signature = "(hqslang, control_0, control_1, target, /)")]
-    pub fn three_qubit_gate_time(
+    pub fn three_qubit_gate_error(
         &self,
         hqslang: &str,
         control_0: usize,
@@ -979,288 +2191,206 @@ impl TweezerMutableDeviceWrapper {
         target: usize,
     ) -> PyResult<f64> {
         self.internal
-            .three_qubit_gate_time(hqslang, &control_0, &control_1, &target)
+            .three_qubit_gate_error(hqslang, &control_0, &control_1, &target)
             .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
     }
 
-    /// Returns the gate time of a multi qubit operation on this device.
-    ///
-    /// Returns:
-    ///     float: The gate time.
+    /// Returns the readout error rate of a qubit in the current Layout.
     ///
-    /// Raises:
-    ///     ValueError: The gate is not available in the device.
-    #[pyo3(text_signature = "(hqslang, qubits, /)")]
-    pub fn multi_qubit_gate_time(&self, hqslang: &str, qubits: Vec<usize>) -> PyResult<f64> {
-        self.internal
-            .multi_qubit_gate_time(hqslang, &qubits)
-            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
-    }
-
-    /// Returns the PhaseShiftedControlledZ phase shift according to the device's relation.
+    /// Args:
+    ///     qubit (int): The measured qubit.
     ///
     /// Returns:
-    ///     float: The PhaseShiftedControlledZ phase shift.
+    ///     float: The readout error rate of the tweezer the qubit occupies, zero if none is set.
     ///
     /// Raises:
-    ///     ValueError: Error in relation selection.
-    pub fn phase_shift_controlled_z(&self) -> PyResult<f64> {
+    ///     ValueError: The qubit is not mapped to a tweezer.
+    #[pyo3(text_signature = "(qubit, /)")]
+    pub fn readout_error(&self, qubit: usize) -> PyResult<f64> {
         self.internal
-            .phase_shift_controlled_z()
-            .ok_or_else(|| PyValueError::new_err("Error in relation selection."))
+            .readout_error(&qubit)
+            .ok_or_else(|| PyValueError::new_err("The qubit is not mapped to a tweezer."))
     }
 
-    /// Returns the PhaseShiftedControlledPhase phase shift according to the device's relation.
+    /// Returns the readout confusion matrix of a qubit in the current Layout.
+    ///
+    /// Args:
+    ///     qubit (int): The measured qubit.
     ///
     /// Returns:
-    ///     float: The PhaseShiftedControlledPhase phase shift.
+    ///     Tuple[float, float]: The probabilities of reading out 1 for state |0> and 0 for state |1>
+    ///         in the tweezer the qubit occupies, zero if none are set.
     ///
     /// Raises:
-    ///     ValueError: Error in relation selection.
-    #[pyo3(text_signature = "(theta, /)")]
-    pub fn phase_shift_controlled_phase(&self, theta: &Bound<PyAny>) -> PyResult<f64> {
-        let float = if let Ok(conv) = convert_into_calculator_float(theta) {
-            *conv
-                .float()
-                .map_err(|err| PyValueError::new_err(format!("{:}", err)))?
-        } else {
-            theta.extract::<f64>()?
-        };
-
+    ///     ValueError: The qubit is not mapped to a tweezer.
+    #[pyo3(text_signature = "(qubit, /)")]
+    pub fn readout_confusion_matrix(&self, qubit: usize) -> PyResult<(f64, f64)> {
         self.internal
-            .phase_shift_controlled_phase(float)
-            .ok_or_else(|| PyValueError::new_err("Error in relation selection."))
+            .readout_confusion_matrix(&qubit)
+            .map(|confusion_matrix| (confusion_matrix.zero_to_one, confusion_matrix.one_to_zero))
+            .ok_or_else(|| PyValueError::new_err("The qubit is not mapped to a tweezer."))
     }
 
-    /// Returns the gate time of a PhaseShiftedControlledZ operation with the given qubits and phi angle.
+    /// Returns the readout confusion matrices of the qubits measured into a register, in register order.
     ///
     /// Args:
-    ///     control (int): The control qubit the gate acts on
-    ///     target (int): The target qubit the gate acts on
-    ///     phi (float): The phi angle to be checked.
+    ///     qubits (List[int]): The qubit measured into each bit of the register.
     ///
     /// Returns:
-    ///     float: The gate time.
+    ///     List[Tuple[float, float]]: The confusion matrix of each qubit, as for `readout_confusion_matrix`.
     ///
     /// Raises:
-    ///     ValueError: The gate is not available on the device.
-    #[pyo3(text_signature = "(control, target, phi, /)")]
-    pub fn gate_time_controlled_z(&self, control: usize, target: usize, phi: f64) -> PyResult<f64> {
+    ///     ValueError: A qubit is not mapped to a tweezer.
+    #[pyo3(text_signature = "(qubits, /)")]
+    pub fn readout_confusion_matrices(&self, qubits: Vec<usize>) -> PyResult<Vec<(f64, f64)>> {
         self.internal
-            .gate_time_controlled_z(&control, &target, phi)
-            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
+            .readout_confusion_matrices(&qubits)
+            .map(|confusion_matrices| {
+                confusion_matrices
+                    .into_iter()
+                    .map(|confusion_matrix| {
+                        (confusion_matrix.zero_to_one, confusion_matrix.one_to_zero)
+                    })
+                    .collect()
+            })
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Returns the gate time of a PhaseShiftedControlledPhase operation with the given qubits and phi and theta angles.
+    /// Returns the error score of a tweezer in a given Layout.
+    ///
+    /// The score is the sum of the largest single-qubit gate error rate of the tweezer, the
+    /// largest two-qubit gate error rate over the pairs of tweezers it belongs to and its
+    /// readout error rate.
     ///
     /// Args:
-    ///     control (int): The control qubit the gate acts on
-    ///     target (int): The target qubit the gate acts on
-    ///     phi (float): The phi angle to be checked.
-    ///     theta (float): The theta angle to be checked.
+    ///     tweezer (int): The index of the tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
     ///
     /// Returns:
-    ///     float: The gate time.
+    ///     float: The error score of the tweezer, zero if no error rates are set.
     ///
     /// Raises:
-    ///     ValueError: The gate is not available on the device.
-    #[pyo3(text_signature = "(control, target, phi, theta, /)")]
-    pub fn gate_time_controlled_phase(
+    ///     ValueError: The layout does not exist.
+    #[pyo3(text_signature = "(tweezer, layout_name, /)")]
+    pub fn tweezer_error_score(
         &self,
-        control: usize,
-        target: usize,
-        phi: f64,
-        theta: f64,
+        tweezer: usize,
+        layout_name: Option<String>,
     ) -> PyResult<f64> {
         self.internal
-            .gate_time_controlled_phase(&control, &target, phi, theta)
-            .ok_or_else(|| PyValueError::new_err("The gate is not available on the device."))
-    }
-
-    /// Turns Device into GenericDevice
-    ///
-    /// Can be used as a generic interface for devices when a boxed dyn trait object cannot be used
-    /// (for example when the interface needs to be serialized)
-    ///
-    /// Returns:
-    ///     GenericDevice: The device in generic representation
-    ///
-    /// Note:
-    ///     GenericDevice uses nested HashMaps to represent the most general device connectivity.
-    ///     The memory usage will be inefficient for devices with large qubit numbers.
-    fn generic_device(&self) -> GenericDeviceWrapper {
-        GenericDeviceWrapper {
-            internal: self.internal.to_generic_device(),
-        }
-    }
-
-    /// Return a copy of the TweezerMutableDevice (copy here produces a deepcopy).
-    ///
-    /// Returns:
-    ///     TweezerMutableDevice: A deep copy of self.
-    pub fn __copy__(&self) -> TweezerMutableDeviceWrapper {
-        self.clone()
+            .tweezer_error_score(tweezer, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Return a deep copy of the TweezerMutableDevice.
+    /// Returns the number of coordinates of the tweezer positions of a given Layout.
     ///
-    /// Returns:
-    ///     TweezerMutableDevice: A deep copy of self.
-    pub fn __deepcopy__(&self, _memodict: Py<PyAny>) -> TweezerMutableDeviceWrapper {
-        self.clone()
-    }
-
-    /// Return the bincode representation of the TweezerMutableDevice using the bincode crate.
+    /// Args:
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
     ///
     /// Returns:
-    ///     ByteArray: The serialized TweezerMutableDevice (in bincode form).
+    ///     int: 2 for two-dimensional and 3 for three-dimensional positions.
     ///
     /// Raises:
-    ///     ValueError: Cannot serialize TweezerMutableDevice to bytes.
-    pub fn to_bincode(&self) -> PyResult<Py<PyByteArray>> {
-        let serialized = serialize(&self.internal)
-            .map_err(|_| PyValueError::new_err("Cannot serialize TweezerMutableDevice to bytes"))?;
-        let b: Py<PyByteArray> = Python::with_gil(|py| -> Py<PyByteArray> {
-            PyByteArray::new_bound(py, &serialized[..]).into()
-        });
-        Ok(b)
+    ///     ValueError: The layout does not exist or has no tweezer positions.
+    #[pyo3(text_signature = "(layout_name, /)")]
+    pub fn tweezer_position_dimensions(&self, layout_name: Option<String>) -> PyResult<usize> {
+        self.internal
+            .get_tweezer_positions(layout_name)
+            .map(|positions| positions.number_dimensions)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
-
-    /// Convert the bincode representation of the TweezerMutableDevice to an
-    /// TweezerMutableDevice using the bincode crate.
+
+    /// Returns the distance between two tweezers in a given Layout.
     ///
     /// Args:
-    ///     input (ByteArray): The serialized TweezerMutableDevice (in bincode form).
+    ///     tweezer0 (int): The index of the first tweezer.
+    ///     tweezer1 (int): The index of the second tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
     ///
     /// Returns:
-    ///     TweezerMutableDevice: The deserialized TweezerMutableDevice.
+    ///     float: The distance between the tweezers in micrometers.
     ///
     /// Raises:
-    ///     TypeError: Input cannot be converted to byte array.
-    ///     ValueError: Input cannot be deserialized to TweezerMutableDevice.
-    #[staticmethod]
-    #[pyo3(text_signature = "(input, /)")]
-    pub fn from_bincode(input: &Bound<PyAny>) -> PyResult<TweezerMutableDeviceWrapper> {
-        let bytes = input
-            .extract::<Vec<u8>>()
-            .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
-
-        Ok(TweezerMutableDeviceWrapper {
-            internal: deserialize(&bytes[..]).map_err(|_| {
-                PyValueError::new_err("Input cannot be deserialized to TweezerMutableDevice")
-            })?,
-        })
+    ///     ValueError: The position of one of the tweezers is not set.
+    #[pyo3(text_signature = "(tweezer0, tweezer1, layout_name, /)")]
+    pub fn tweezer_distance(
+        &self,
+        tweezer0: usize,
+        tweezer1: usize,
+        layout_name: Option<String>,
+    ) -> PyResult<f64> {
+        self.internal
+            .tweezer_distance(tweezer0, tweezer1, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Return the json representation of the TweezerMutableDevice.
+    /// Returns the distance between the tweezers two qubits are mapped to in the current Layout.
     ///
-    /// Additionally, a gate set check is performed.
+    /// Args:
+    ///     qubit0 (int): The index of the first qubit.
+    ///     qubit1 (int): The index of the second qubit.
     ///
     /// Returns:
-    ///     str: The serialized form of TweezerMutableDevice.
+    ///     float: The distance between the qubits in micrometers.
     ///
     /// Raises:
-    ///     ValueError: Cannot serialize TweezerMutableDevice to json or
-    ///         the device does not have valid QRyd gates available.
-    fn to_json(&self) -> PyResult<String> {
-        let mut all_gates_names: HashSet<&str> = HashSet::new();
-        for layout in self.internal.available_layouts() {
-            all_gates_names.extend(
-                &self
-                    .internal
-                    .get_available_gates_names(Some(layout.to_string()))
-                    .unwrap(),
-            );
-        }
-        if all_gates_names.iter().any(|name| {
-            !ALLOWED_NATIVE_SINGLE_QUBIT_GATES.contains(name)
-                && !ALLOWED_NATIVE_TWO_QUBIT_GATES.contains(name)
-                && !ALLOWED_NATIVE_THREE_QUBIT_GATES.contains(name)
-        }) || all_gates_names.is_empty()
-        {
-            return Err(PyValueError::new_err(
-                "The device does not support valid gates in a layout. ".to_owned()
-                    + "The valid gates are: "
-                    + &ALLOWED_NATIVE_SINGLE_QUBIT_GATES.join(", ")
-                    + ", "
-                    + &ALLOWED_NATIVE_TWO_QUBIT_GATES.join(", ")
-                    + ", "
-                    + &ALLOWED_NATIVE_THREE_QUBIT_GATES.join(", ")
-                    + ".",
-            ));
-        }
-        let serialized = serde_json::to_string(&self.internal)
-            .map_err(|_| PyValueError::new_err("Cannot serialize TweezerMutableDevice to json"))?;
-        Ok(serialized)
+    ///     ValueError: A qubit is not mapped or the position of its tweezer is not set.
+    #[pyo3(text_signature = "(qubit0, qubit1, /)")]
+    pub fn qubit_distance(&self, qubit0: usize, qubit1: usize) -> PyResult<f64> {
+        self.internal
+            .qubit_distance(qubit0, qubit1)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Convert the json representation of a TweezerMutableDevice to an TweezerMutableDevice.
+    /// Returns the tweezers closest to a given tweezer in a given Layout.
     ///
-    /// Additionally, a gate set check is performed.
+    /// Only tweezers with a set position are considered.
     ///
     /// Args:
-    ///     input (str): The serialized TweezerMutableDevice in json form.
+    ///     tweezer (int): The index of the tweezer.
+    ///     number (int): The maximal number of tweezers to return.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
     ///
     /// Returns:
-    ///     TweezerMutableDevice: The deserialized TweezerMutableDevice.
+    ///     List[Tuple[int, float]]: The closest tweezers with their distances, sorted by distance.
     ///
     /// Raises:
-    ///     ValueError: Input cannot be deserialized to TweezerMutableDevice or
-    ///         the device does not have valid QRyd gates available.
-    #[staticmethod]
-    #[pyo3(text_signature = "(input, /)")]
-    fn from_json(input: &str) -> PyResult<TweezerMutableDeviceWrapper> {
-        let internal: TweezerDevice = serde_json::from_str(input).map_err(|_| {
-            PyValueError::new_err("Input cannot be deserialized to TweezerMutableDevice")
-        })?;
-        let mut all_gates_names: HashSet<&str> = HashSet::new();
-        for layout in internal.available_layouts() {
-            all_gates_names.extend(
-                &internal
-                    .get_available_gates_names(Some(layout.to_string()))
-                    .unwrap(),
-            );
-        }
-        if all_gates_names.iter().any(|name| {
-            !ALLOWED_NATIVE_SINGLE_QUBIT_GATES.contains(name)
-                && !ALLOWED_NATIVE_TWO_QUBIT_GATES.contains(name)
-                && !ALLOWED_NATIVE_THREE_QUBIT_GATES.contains(name)
-        }) || all_gates_names.is_empty()
-        {
-            return Err(PyValueError::new_err(
-                "The device does not support valid gates in a layout. ".to_owned()
-                    + "The valid gates are: "
-                    + &ALLOWED_NATIVE_SINGLE_QUBIT_GATES.join(", ")
-                    + ", "
-                    + &ALLOWED_NATIVE_TWO_QUBIT_GATES.join(", ")
-                    + ", "
-                    + &ALLOWED_NATIVE_THREE_QUBIT_GATES.join(", ")
-                    + ".",
-            ));
-        }
-        Ok(TweezerMutableDeviceWrapper { internal })
+    ///     ValueError: The position of the given tweezer is not set.
+    #[pyo3(text_signature = "(tweezer, number, layout_name, /)")]
+    pub fn nearest_tweezers(
+        &self,
+        tweezer: usize,
+        number: usize,
+        layout_name: Option<String>,
+    ) -> PyResult<Vec<(usize, f64)>> {
+        self.internal
+            .nearest_tweezers(tweezer, number, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
-    /// Return number of qubits in device.
+    /// Returns the tweezers within a given distance of a given tweezer in a given Layout.
     ///
-    /// Returns:
-    ///     int: The number of qubits.
-    ///
-    pub fn number_qubits(&self) -> usize {
-        self.internal.number_qubits()
-    }
-
-    /// Returns the number of total tweezer positions in the device.
+    /// Only tweezers with a set position are considered.
     ///
     /// Args:
-    ///     layout_name (Optional[str]): The name of the layout to reference. Defaults to the current layout.
+    ///     tweezer (int): The index of the tweezer.
+    ///     max_distance (float): The maximal distance in micrometers, inclusive.
+    ///     layout_name (Optional[str]): The name of the Layout to reference. Defaults to the current Layout.
     ///
     /// Returns:
-    ///     int: The number of tweezer positions in the device.
-    #[pyo3(text_signature = "(layout_name, /)")]
-    pub fn number_tweezer_positions(&self, layout_name: Option<String>) -> PyResult<usize> {
+    ///     List[Tuple[int, float]]: The tweezers in range with their distances, sorted by distance.
+    ///
+    /// Raises:
+    ///     ValueError: The position of the given tweezer is not set.
+    #[pyo3(text_signature = "(tweezer, max_distance, layout_name, /)")]
+    pub fn tweezers_within_distance(
+        &self,
+        tweezer: usize,
+        max_distance: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<Vec<(usize, f64)>> {
         self.internal
-            .number_tweezer_positions(layout_name)
+            .tweezers_within_distance(tweezer, max_distance, layout_name)
             .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
@@ -1522,6 +2652,221 @@ impl TweezerMutableDeviceWrapper {
             .map_err(|err| PyValueError::new_err(format!("{:}", err)))
     }
 
+    /// Set the position of a tweezer in a given Layout.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     position (List[float]): The (x, y) or (x, y, z) coordinates of the tweezer in micrometers.
+    ///     layout_name (Optional[str]): The name of the Layout to set the position in. Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present, the position is not valid or has a different
+    ///         number of coordinates than the positions already set in the Layout.
+    #[pyo3(text_signature = "(tweezer, position, layout_name, /)")]
+    pub fn set_tweezer_position(
+        &mut self,
+        tweezer: usize,
+        position: Vec<f64>,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        self.internal
+            .set_tweezer_position(tweezer, &position, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Set the decoherence rates of a qubit held in a tweezer in a given Layout.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     damping (float): The damping rate, 1/T1.
+    ///     dephasing (float): The pure dephasing rate.
+    ///     depolarising (float): The depolarising rate.
+    ///     layout_name (Optional[str]): The name of the Layout to set the rates in. Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present or a rate is negative or not finite.
+    #[pyo3(text_signature = "(tweezer, damping, dephasing, depolarising, layout_name, /)")]
+    pub fn set_tweezer_decoherence_rates(
+        &mut self,
+        tweezer: usize,
+        damping: f64,
+        dephasing: f64,
+        depolarising: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        let rates = TweezerDecoherenceRates {
+            damping,
+            dephasing,
+            depolarising,
+        };
+        self.internal
+            .set_tweezer_decoherence_rates(tweezer, rates, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Set the decoherence rates of a qubit held in a tweezer from its T1 and T2 times.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     t1 (float): The relaxation time T1.
+    ///     t2 (float): The coherence time T2, at most 2 T1.
+    ///     layout_name (Optional[str]): The name of the Layout to set the rates in. Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present or the times are not valid.
+    #[pyo3(text_signature = "(tweezer, t1, t2, layout_name, /)")]
+    pub fn set_tweezer_t1_t2(
+        &mut self,
+        tweezer: usize,
+        t1: f64,
+        t2: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        TweezerDecoherenceRates::from_t1_t2(t1, t2)
+            .and_then(|rates| {
+                self.internal
+                    .set_tweezer_decoherence_rates(tweezer, rates, layout_name)
+            })
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Set the error rate of a single-qubit gate for a tweezer in a given Layout.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of a single-qubit gate.
+    ///     tweezer (int): The index of the tweezer.
+    ///     error_rate (float): The probability of an error during the gate.
+    ///     layout_name (Optional[str]): The name of the Layout to set the error rate in.
+    ///         Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     ValueError: The gate has no gate time for the tweezer or the error rate is not in [0, 1].
+    #[pyo3(text_signature = "(hqslang, tweezer, error_rate, layout_name, /)")]
+    pub fn set_tweezer_single_qubit_gate_error(
+        &mut self,
+        hqslang: &str,
+        tweezer: usize,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        self.internal
+            .set_tweezer_single_qubit_gate_error(hqslang, tweezer, error_rate, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Set the error rate of a two-qubit gate for a tweezer couple in a given Layout.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of a two-qubit gate.
+    ///     tweezer0 (int): The index of the first tweezer.
+    ///     tweezer1 (int): The index of the second tweezer.
+    ///     error_rate (float): The probability of an error during the gate.
+    ///     layout_name (Optional[str]): The name of the Layout to set the error rate in.
+    ///         Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     ValueError: The gate has no gate time for the tweezers or the error rate is not in [0, 1].
+    #[pyo3(text_signature = "(hqslang, tweezer0, tweezer1, error_rate, layout_name, /)")]
+    pub fn set_tweezer_two_qubit_gate_error(
+        &mut self,
+        hqslang: &str,
+        tweezer0: usize,
+        tweezer1: usize,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        self.internal
+            .set_tweezer_two_qubit_gate_error(hqslang, tweezer0, tweezer1, error_rate, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Set the error rate of a three-qubit gate for a tweezer trio in a given Layout.
+    ///
+    /// Args:
+    ///     hqslang (str): The hqslang name of a three-qubit gate.
+    ///     tweezer0 (int): The index of the first tweezer.
+    ///     tweezer1 (int): The index of the second tweezer.
+    ///     tweezer2 (int): The index of the third tweezer.
+    ///     error_rate (float): The probability of an error during the gate.
+    ///     layout_name (Optional[str]): The name of the Layout to set the error rate in.
+    ///         Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     ValueError: The gate has no gate time for the tweezers or the error rate is not in [0, 1].
+    #[pyo3(text_signature = "(hqslang, tweezer0, tweezer1, tweezer2, error_rate, layout_name, /)")]
+    pub fn set_tweezer_three_qubit_gate_error(
+        &mut self,
+        hqslang: &str,
+        tweezer0: usize,
+        tweezer1: usize,
+        tweezer2: usize,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        self.internal
+            .set_tweezer_three_qubit_gate_error(
+                hqslang,
+                tweezer0,
+                tweezer1,
+                tweezer2,
+                error_rate,
+                layout_name,
+            )
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Set the readout error rate of a tweezer in a given Layout.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     error_rate (float): The probability of reading out the wrong state of the qubit held in the tweezer.
+    ///     layout_name (Optional[str]): The name of the Layout to set the error rate in.
+    ///         Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present or the error rate is not in [0, 1].
+    #[pyo3(text_signature = "(tweezer, error_rate, layout_name, /)")]
+    pub fn set_tweezer_readout_error(
+        &mut self,
+        tweezer: usize,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        self.internal
+            .set_tweezer_readout_error(tweezer, error_rate, layout_name)
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
+    /// Set the readout confusion matrix of a tweezer in a given Layout.
+    ///
+    /// Args:
+    ///     tweezer (int): The index of the tweezer.
+    ///     zero_to_one (float): The probability of reading out 1 for a qubit in state |0>.
+    ///     one_to_zero (float): The probability of reading out 0 for a qubit in state |1>.
+    ///     layout_name (Optional[str]): The name of the Layout to set the confusion matrix in.
+    ///         Defaults to the current Layout.
+    ///
+    /// Raises:
+    ///     ValueError: The tweezer is not present or a probability is not in [0, 1].
+    #[pyo3(text_signature = "(tweezer, zero_to_one, one_to_zero, layout_name, /)")]
+    pub fn set_tweezer_readout_confusion_matrix(
+        &mut self,
+        tweezer: usize,
+        zero_to_one: f64,
+        one_to_zero: f64,
+        layout_name: Option<String>,
+    ) -> PyResult<()> {
+        ReadoutConfusionMatrix::new(zero_to_one, one_to_zero)
+            .and_then(|confusion_matrix| {
+                self.internal.set_tweezer_readout_confusion_matrix(
+                    tweezer,
+                    confusion_matrix,
+                    layout_name,
+                )
+            })
+            .map_err(|err| PyValueError::new_err(format!("{:}", err)))
+    }
+
     /// Set whether the device allows PragmaActiveReset operations or not.
     ///
     /// Args:
@@ -1642,7 +2987,7 @@ pub fn convert_into_device(input: &Bound<PyAny>) -> Result<TweezerDevice, QoqoBa
     let bytes = get_bytes
         .extract::<Vec<u8>>()
         .map_err(|_| QoqoBackendError::CannotExtractObject)?;
-    bincode::deserialize(&bytes[..]).map_err(|_| QoqoBackendError::CannotExtractObject)
+    TweezerDevice::from_bincode(&bytes[..]).map_err(|_| QoqoBackendError::CannotExtractObject)
 }
 
 /// Tweezer devices for the QRyd platform.
@@ -1659,3 +3004,24 @@ pub fn tweezer_devices(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<TweezerMutableDeviceWrapper>()?;
     Ok(())
 }
+
+/// Converts a [roqoqo_qryd::ValidationReport] to a python dictionary of issues by Layout name.
+fn validation_report_to_dict(report: ValidationReport) -> PyResult<PyObject> {
+    Python::with_gil(|py| -> PyResult<PyObject> {
+        let layouts = PyDict::new_bound(py);
+        for (layout_name, issues) in report.layouts {
+            let issues = issues
+                .iter()
+                .map(|issue| -> PyResult<PyObject> {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("rule", issue.rule())?;
+                    dict.set_item("tweezers", issue.tweezers())?;
+                    dict.set_item("message", issue.to_string())?;
+                    Ok(dict.into_py(py))
+                })
+                .collect::<PyResult<Vec<PyObject>>>()?;
+            layouts.set_item(layout_name, issues)?;
+        }
+        Ok(layouts.into_py(py))
+    })
+}
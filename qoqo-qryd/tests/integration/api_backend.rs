@@ -894,3 +894,32 @@ async fn test_dev() {
         assert!(internal.dev);
     });
 }
+
+/// Test the mitigate_counts function of APIBackendWrapper
+#[test]
+fn test_mitigate_counts() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let backend_type = py.get_type_bound::<APIBackendWrapper>();
+        let counts: HashMap<String, u64> = [("0x1".to_string(), 80), ("0x0".to_string(), 20)]
+            .into_iter()
+            .collect();
+        let confusion_matrices = vec![(0.1, 0.2), (0.0, 0.0)];
+        let mitigated = backend_type
+            .call_method1(
+                "mitigate_counts",
+                (counts.clone(), 2, confusion_matrices.clone()),
+            )
+            .unwrap()
+            .extract::<HashMap<String, f64>>()
+            .unwrap();
+        assert_eq!(mitigated.len(), 1);
+        assert!((mitigated["10"] - 1.0).abs() < 1e-12);
+        assert!(backend_type
+            .call_method1(
+                "mitigate_counts",
+                (counts, 2, confusion_matrices, "unknown")
+            )
+            .is_err());
+    });
+}
@@ -46,6 +46,12 @@ fn test_new() {
             Some(2)
         );
 
+        assert!(!res
+            .call_method0("is_offline_fallback")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+
         let res_emp = device_type.call0().unwrap();
 
         assert_eq!(
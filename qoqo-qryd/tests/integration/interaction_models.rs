@@ -0,0 +1,174 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test for Interaction Models
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+
+use qoqo_qryd::{
+    BlockadeInteractionModelWrapper, CutoffInteractionModelWrapper, TweezerMutableDeviceWrapper,
+};
+
+/// Test CutoffInteractionModel
+#[test]
+fn test_cutoff_model() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let model_type = py.get_type_bound::<CutoffInteractionModelWrapper>();
+        let model = model_type.call1((2.0, 0.5)).unwrap();
+        assert_eq!(
+            model
+                .call_method1("two_qubit_gate_time", ("PhaseShiftedControlledZ", 2.0))
+                .unwrap()
+                .extract::<Option<f64>>()
+                .unwrap(),
+            Some(2.0)
+        );
+        assert!(model
+            .call_method1("two_qubit_gate_time", ("PhaseShiftedControlledZ", 2.5))
+            .unwrap()
+            .is_none());
+        assert!(model
+            .call_method1("two_qubit_gate_time", ("ControlledPauliZ", 1.0))
+            .unwrap()
+            .is_none());
+
+        let same = model_type.call1((2.0, 0.5)).unwrap();
+        let other = model_type.call1((3.0, 0.5)).unwrap();
+        assert!(model.eq(&same).unwrap());
+        assert!(model.ne(&other).unwrap());
+        assert!(model.lt(&other).is_err());
+        assert!(model
+            .repr()
+            .unwrap()
+            .to_string()
+            .contains("CutoffInteractionModel"));
+    })
+}
+
+/// Test BlockadeInteractionModel
+#[test]
+fn test_blockade_model() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let model_type = py.get_type_bound::<BlockadeInteractionModelWrapper>();
+        let gate_times = HashMap::from([
+            ("PhaseShiftedControlledPhase".to_string(), 0.5),
+            ("ControlledControlledPauliZ".to_string(), 0.8),
+        ]);
+        assert!(model_type
+            .call1((0.0, 1.0, gate_times.clone(), 0.99))
+            .is_err());
+        let model = model_type
+            .call1((64.0, 1.0, gate_times.clone(), 0.99))
+            .unwrap();
+        assert_eq!(
+            model
+                .call_method0("blockade_radius")
+                .unwrap()
+                .extract::<f64>()
+                .unwrap(),
+            2.0
+        );
+        assert!(model
+            .call_method1("two_qubit_gate_time", ("PhaseShiftedControlledPhase", 1.0))
+            .unwrap()
+            .extract::<Option<f64>>()
+            .unwrap()
+            .is_some());
+        assert!(model
+            .call_method1(
+                "two_qubit_gate_fidelity",
+                ("PhaseShiftedControlledPhase", 2.5)
+            )
+            .unwrap()
+            .is_none());
+        assert!(model
+            .call_method1(
+                "three_qubit_gate_time",
+                ("ControlledControlledPauliZ", (1.0, 1.0, 1.0))
+            )
+            .unwrap()
+            .extract::<Option<f64>>()
+            .unwrap()
+            .is_some());
+
+        model.call_method1("set_blockade_radius", (3.0,)).unwrap();
+        assert!(model.call_method1("set_blockade_radius", (0.0,)).is_err());
+        assert!(model
+            .call_method1("set_blockade_radius", (f64::NAN,))
+            .is_err());
+        assert_eq!(
+            model
+                .call_method0("blockade_radius")
+                .unwrap()
+                .extract::<f64>()
+                .unwrap(),
+            3.0
+        );
+        let unchanged = model_type.call1((64.0, 1.0, gate_times, 0.99)).unwrap();
+        assert!(model.ne(&unchanged).unwrap());
+    })
+}
+
+/// Test apply_interaction_model function of TweezerMutableDeviceWrapper
+#[test]
+fn test_apply_interaction_model() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device = device_type.call0().unwrap();
+        device
+            .call_method1(
+                "add_layout_from_positions",
+                (
+                    "line",
+                    vec![vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![3.0, 0.0]]],
+                    HashMap::from([
+                        ("RotateX".to_string(), 0.2),
+                        ("PhaseShiftedControlledZ".to_string(), 0.3),
+                    ]),
+                    3.0,
+                ),
+            )
+            .unwrap();
+        device.call_method1("switch_layout", ("line",)).unwrap();
+
+        let model = py
+            .get_type_bound::<CutoffInteractionModelWrapper>()
+            .call1((1.5, 1.0))
+            .unwrap();
+        device
+            .call_method1("apply_interaction_model", (model,))
+            .unwrap();
+        assert_eq!(
+            device
+                .call_method1("two_qubit_edges", ())
+                .unwrap()
+                .extract::<Vec<(usize, usize)>>()
+                .unwrap(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert!(device
+            .call_method1("apply_interaction_model", (1.0,))
+            .is_err());
+        let model = py
+            .get_type_bound::<CutoffInteractionModelWrapper>()
+            .call1((1.5, 1.0))
+            .unwrap();
+        assert!(device
+            .call_method1("apply_interaction_model", (model, "error"))
+            .is_err());
+    })
+}
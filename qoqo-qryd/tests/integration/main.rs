@@ -29,6 +29,9 @@ mod operations;
 #[cfg(test)]
 mod emulator_devices;
 
+#[cfg(test)]
+mod interaction_models;
+
 #[cfg(test)]
 #[cfg(feature = "simulator")]
 mod simulator_backend;
@@ -38,7 +41,50 @@ mod simulator_backend;
 fn test_device_from_api() {
     use qoqo_qryd::device_from_api;
 
-    let response = device_from_api(None, None, None, None, None);
+    let response = device_from_api(None, None, None, None, None, None);
     assert!(response.is_ok());
     // TODO: add more specific testing once the available devices gathered from the API endpoint can be distinguished
 }
+
+#[test]
+fn test_device_from_snapshot() {
+    use pyo3::prelude::*;
+    use qoqo_qryd::{device_from_snapshot, EmulatorDeviceWrapper, TweezerDeviceWrapper};
+    use roqoqo_qryd::{DeviceSnapshot, TweezerDevice};
+
+    pyo3::prepare_freethreaded_python();
+    let path = std::env::temp_dir().join(format!(
+        "qoqo_qryd_device_snapshot_{}.json",
+        std::process::id()
+    ));
+    let path_str = path.to_str().unwrap().to_string();
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.23, Some("default".to_string()))
+        .unwrap();
+    device.default_layout = Some("default".to_string());
+    DeviceSnapshot::new(device, "qryd_tweezer_device".into(), "v1_1".into())
+        .save(&path)
+        .unwrap();
+
+    Python::with_gil(|py| {
+        let loaded = device_from_snapshot(path_str.clone(), Some(2)).unwrap();
+        let loaded = loaded.bind(py);
+        assert!(loaded.is_instance_of::<TweezerDeviceWrapper>());
+        let current_layout: String = loaded
+            .call_method0("current_layout")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(current_layout, "default");
+        let seed: Option<usize> = loaded.call_method0("seed").unwrap().extract().unwrap();
+        assert_eq!(seed, Some(2));
+    });
+    let tweezer = TweezerDeviceWrapper::from_snapshot(path_str.clone(), None).unwrap();
+    assert_eq!(tweezer.internal.device_name, "qryd_tweezer_device");
+    assert!(EmulatorDeviceWrapper::from_snapshot(path_str.clone(), None).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(device_from_snapshot(path_str, None).is_err());
+}
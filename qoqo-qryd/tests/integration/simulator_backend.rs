@@ -17,7 +17,7 @@ use pyo3::Python;
 use qoqo::measurements::{ClassicalRegisterWrapper, PauliZProductWrapper};
 use qoqo::CircuitWrapper;
 use qoqo_qryd::simulator_backend::{convert_into_backend, SimulatorBackendWrapper};
-use qoqo_qryd::{TweezerDeviceWrapper, TweezerMutableDeviceWrapper};
+use qoqo_qryd::{mitigate_bit_register, TweezerDeviceWrapper, TweezerMutableDeviceWrapper};
 use roqoqo::measurements::{ClassicalRegister, PauliZProduct, PauliZProductInput};
 use roqoqo::operations;
 use roqoqo::Circuit;
@@ -559,3 +559,658 @@ fn test_pyo3_new_change_layout() {
         assert!(helper_eq);
     })
 }
+
+/// Test the noise model functions of SimulatorBackendWrapper
+#[test]
+fn test_noise_model() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::RotateX::new(0, std::f64::consts::PI.into());
+    circuit += operations::MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += operations::PragmaSetNumberOfMeasurements::new(50, "ro".to_string());
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call0().unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1(
+                "set_tweezer_single_qubit_gate_time",
+                ("RotateX", 0, 1.0, "test"),
+            )
+            .unwrap();
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1("set_tweezer_decoherence_rates", (0, 5.0, 0.0, 0.0))
+            .unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        assert!(!backend
+            .call_method0("is_noisy")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        let unchanged = backend
+            .call_method1(
+                "noisy_circuit",
+                (CircuitWrapper {
+                    internal: circuit.clone(),
+                },),
+            )
+            .unwrap()
+            .extract::<CircuitWrapper>()
+            .unwrap();
+        assert_eq!(unchanged.internal, circuit);
+
+        let rates = [("RotateX".to_string(), 0.1)]
+            .into_iter()
+            .collect::<std::collections::HashMap<String, f64>>();
+        backend.call_method1("set_noise_model", (rates,)).unwrap();
+        assert!(backend
+            .call_method0("is_noisy")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        let noisy = backend
+            .call_method1(
+                "noisy_circuit",
+                (CircuitWrapper {
+                    internal: circuit.clone(),
+                },),
+            )
+            .unwrap()
+            .extract::<CircuitWrapper>()
+            .unwrap();
+        assert_eq!(noisy.internal.len(), circuit.len() + 2);
+        let (bits, _, _) = backend
+            .call_method1(
+                "run_circuit",
+                (CircuitWrapper {
+                    internal: circuit.clone(),
+                },),
+            )
+            .unwrap()
+            .extract::<(
+                std::collections::HashMap<String, Vec<Vec<bool>>>,
+                Py<PyAny>,
+                Py<PyAny>,
+            )>()
+            .unwrap();
+        assert!(bits["ro"].iter().filter(|shot| shot[0]).count() < 25);
+
+        backend.call_method0("remove_noise_model").unwrap();
+        assert!(!backend
+            .call_method0("is_noisy")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        let mut circuit = Circuit::new();
+        circuit += operations::Hadamard::new(0);
+        backend.call_method0("set_noise_model").unwrap();
+        assert!(backend
+            .call_method1("noisy_circuit", (CircuitWrapper { internal: circuit },))
+            .is_err());
+    })
+}
+
+/// Test the atom loss functions of SimulatorBackendWrapper
+#[test]
+fn test_atom_loss_model() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::RotateX::new(0, std::f64::consts::PI.into());
+    circuit += operations::MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += operations::PragmaSetNumberOfMeasurements::new(20, "ro".to_string());
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call0().unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1(
+                "set_tweezer_single_qubit_gate_time",
+                ("RotateX", 0, 1.0, "test"),
+            )
+            .unwrap();
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        assert!(!backend
+            .call_method0("has_atom_loss")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        assert!(backend
+            .call_method1("set_atom_loss_model", (0.0, 1.5, 0.0))
+            .is_err());
+        assert!(!backend
+            .call_method0("has_atom_loss")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+
+        backend
+            .call_method1("set_atom_loss_model", (0.0, 1.0, 0.0, "lost"))
+            .unwrap();
+        assert!(backend
+            .call_method0("has_atom_loss")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        let (bits, _, _) = backend
+            .call_method1(
+                "run_circuit",
+                (CircuitWrapper {
+                    internal: circuit.clone(),
+                },),
+            )
+            .unwrap()
+            .extract::<(
+                std::collections::HashMap<String, Vec<Vec<bool>>>,
+                Py<PyAny>,
+                Py<PyAny>,
+            )>()
+            .unwrap();
+        assert_eq!(bits["lost"].len(), 20);
+        assert!(bits["lost"].iter().all(|shot| shot == &vec![true]));
+        assert!(bits["ro"].iter().all(|shot| shot == &vec![true]));
+        let selected = backend
+            .call_method1("post_select_shots", (bits.clone(),))
+            .unwrap()
+            .extract::<std::collections::HashMap<String, Vec<Vec<bool>>>>()
+            .unwrap();
+        assert!(!selected.contains_key("lost"));
+        assert!(selected["ro"].is_empty());
+
+        backend.call_method0("remove_atom_loss_model").unwrap();
+        assert!(!backend
+            .call_method0("has_atom_loss")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        assert!(backend.call_method1("post_select_shots", (bits,)).is_err());
+    })
+}
+
+/// Test the readout error functions of SimulatorBackendWrapper
+#[test]
+fn test_readout_errors() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::RotateX::new(0, std::f64::consts::PI.into());
+    circuit += operations::MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += operations::PragmaSetNumberOfMeasurements::new(20, "ro".to_string());
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call0().unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1(
+                "set_tweezer_single_qubit_gate_time",
+                ("RotateX", 0, 1.0, "test"),
+            )
+            .unwrap();
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1("set_tweezer_readout_confusion_matrix", (0, 0.0, 1.0))
+            .unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        assert!(!backend
+            .call_method0("has_readout_errors")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        backend.call_method1("set_readout_errors", (true,)).unwrap();
+        assert!(backend
+            .call_method0("has_readout_errors")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        let (bits, _, _) = backend
+            .call_method1("run_circuit", (CircuitWrapper { internal: circuit },))
+            .unwrap()
+            .extract::<(
+                std::collections::HashMap<String, Vec<Vec<bool>>>,
+                Py<PyAny>,
+                Py<PyAny>,
+            )>()
+            .unwrap();
+        assert!(bits["ro"].iter().all(|shot| shot == &vec![false]));
+        backend
+            .call_method1("set_readout_errors", (false,))
+            .unwrap();
+        assert!(!backend
+            .call_method0("has_readout_errors")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+    })
+}
+
+/// Test the crosstalk functions of SimulatorBackendWrapper
+#[test]
+fn test_crosstalk_model() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::PhaseShiftedControlledZ::new(0, 1, 0.0.into());
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call0().unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        for (control, target) in [(0, 1), (1, 0), (1, 2), (2, 1)] {
+            device_tw
+                .call_method1(
+                    "set_tweezer_two_qubit_gate_time",
+                    ("PhaseShiftedControlledZ", control, target, 1.0, "test"),
+                )
+                .unwrap();
+        }
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        assert!(!backend
+            .call_method0("has_crosstalk")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        assert!(backend
+            .call_method1("set_crosstalk_model", (0.1, -1.0))
+            .is_err());
+        backend.call_method1("set_crosstalk_model", (0.1,)).unwrap();
+        assert!(backend
+            .call_method0("has_crosstalk")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+        let noisy = backend
+            .call_method1(
+                "noisy_circuit",
+                (CircuitWrapper {
+                    internal: circuit.clone(),
+                },),
+            )
+            .unwrap()
+            .extract::<CircuitWrapper>()
+            .unwrap();
+        let mut expected = circuit.clone();
+        expected += operations::ControlledPhaseShift::new(1, 2, 0.1.into());
+        assert_eq!(noisy.internal, expected);
+        backend.call_method0("remove_crosstalk_model").unwrap();
+        assert!(!backend
+            .call_method0("has_crosstalk")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+    })
+}
+
+/// Test the reproducibility of simulations with a seeded device
+#[test]
+fn test_seeded_simulation() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::RotateX::new(0, std::f64::consts::FRAC_PI_2.into());
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call1((42,)).unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1(
+                "set_tweezer_single_qubit_gate_time",
+                ("RotateX", 0, 1.0, "test"),
+            )
+            .unwrap();
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        let run = || {
+            backend
+                .call_method1(
+                    "run_circuit",
+                    (CircuitWrapper {
+                        internal: circuit.clone(),
+                    },),
+                )
+                .unwrap()
+                .extract::<(
+                    std::collections::HashMap<String, Vec<Vec<bool>>>,
+                    Py<PyAny>,
+                    Py<PyAny>,
+                )>()
+                .unwrap()
+                .0
+        };
+        let bits = run();
+        assert_eq!(bits["ro"].len(), 100);
+        assert_eq!(run(), bits);
+    })
+}
+
+/// Test the state inspection functions of SimulatorBackendWrapper
+#[test]
+fn test_state_inspection() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::PauliX::new(1);
+    circuit += operations::Hadamard::new(0);
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call0().unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        for gate in ["PauliX", "Hadamard"] {
+            for tweezer in [0, 1] {
+                device_tw
+                    .call_method1(
+                        "set_tweezer_single_qubit_gate_time",
+                        (gate, tweezer, 1.0, "test"),
+                    )
+                    .unwrap();
+            }
+        }
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        let input = (CircuitWrapper {
+            internal: circuit.clone(),
+        },);
+        let amplitude = std::f64::consts::FRAC_1_SQRT_2;
+
+        let (state, labels) = backend
+            .call_method1("state_vector", input.clone())
+            .unwrap()
+            .extract::<(numpy::PyReadonlyArray1<numpy::Complex64>, Vec<usize>)>()
+            .unwrap();
+        assert_eq!(labels, vec![0, 1]);
+        let state = state.as_array();
+        assert_eq!(state.len(), 4);
+        assert!((state[2].re - amplitude).abs() < 1e-10);
+        assert!((state[3].re - amplitude).abs() < 1e-10);
+
+        let (density_matrix, labels) = backend
+            .call_method1("density_matrix", input.clone())
+            .unwrap()
+            .extract::<(numpy::PyReadonlyArray2<numpy::Complex64>, Vec<usize>)>()
+            .unwrap();
+        assert_eq!(labels, vec![0, 1]);
+        let density_matrix = density_matrix.as_array();
+        assert_eq!(density_matrix.dim(), (4, 4));
+        assert!((density_matrix[[2, 3]].re - 0.5).abs() < 1e-10);
+
+        let snapshots = backend
+            .call_method1("state_snapshots", (input.0.clone(), true, true))
+            .unwrap()
+            .extract::<Vec<(usize, Py<PyAny>, Vec<usize>)>>()
+            .unwrap();
+        assert_eq!(
+            snapshots
+                .iter()
+                .map(|(position, _, _)| *position)
+                .collect::<Vec<usize>>(),
+            vec![1, 2]
+        );
+
+        assert!(backend
+            .call_method1(
+                "state_vector",
+                (CircuitWrapper {
+                    internal: Circuit::new(),
+                },)
+            )
+            .is_err());
+        assert!(backend.call_method1("state_vector", (0,)).is_err());
+    })
+}
+
+/// Test the parallel runs of the circuits of a measurement
+#[test]
+fn test_number_threads() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::RotateX::new(0, std::f64::consts::FRAC_PI_2.into());
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let measurement = ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![circuit; 4],
+    };
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call1((42,)).unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1(
+                "set_tweezer_single_qubit_gate_time",
+                ("RotateX", 0, 1.0, "test"),
+            )
+            .unwrap();
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        let input = (ClassicalRegisterWrapper {
+            internal: measurement.clone(),
+        },);
+        let run = || {
+            backend
+                .call_method1("run_measurement_registers", input.clone())
+                .unwrap()
+                .extract::<(
+                    std::collections::HashMap<String, Vec<Vec<bool>>>,
+                    Py<PyAny>,
+                    Py<PyAny>,
+                )>()
+                .unwrap()
+                .0
+        };
+        assert_eq!(
+            backend
+                .call_method0("number_threads")
+                .unwrap()
+                .extract::<usize>()
+                .unwrap(),
+            1
+        );
+        let sequential = run();
+        backend.call_method1("set_number_threads", (3,)).unwrap();
+        assert_eq!(
+            backend
+                .call_method0("number_threads")
+                .unwrap()
+                .extract::<usize>()
+                .unwrap(),
+            3
+        );
+        assert_eq!(run(), sequential);
+
+        assert!(backend
+            .call_method0("shot_batch_size")
+            .unwrap()
+            .extract::<Option<usize>>()
+            .unwrap()
+            .is_none());
+        backend.call_method1("set_shot_batch_size", (4,)).unwrap();
+        assert_eq!(
+            backend
+                .call_method0("shot_batch_size")
+                .unwrap()
+                .extract::<Option<usize>>()
+                .unwrap(),
+            Some(4)
+        );
+        let batched = run();
+        assert_eq!(batched["ro"].len(), 40);
+        assert_ne!(batched, sequential);
+    })
+}
+
+/// Test the simulation mode functions of SimulatorBackendWrapper
+#[test]
+fn test_simulation_mode() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::RotateX::new(0, std::f64::consts::PI.into());
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call0().unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1(
+                "set_tweezer_single_qubit_gate_time",
+                ("RotateX", 0, 1.0, "test"),
+            )
+            .unwrap();
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        let mode = || {
+            backend
+                .call_method0("simulation_mode")
+                .unwrap()
+                .extract::<String>()
+                .unwrap()
+        };
+        assert_eq!(mode(), "state_vector");
+        for simulation_mode in ["stabilizer", "automatic", "state_vector"] {
+            backend
+                .call_method1("set_simulation_mode", (simulation_mode,))
+                .unwrap();
+            assert_eq!(mode(), simulation_mode);
+        }
+        assert!(backend
+            .call_method1("set_simulation_mode", ("clifford",))
+            .is_err());
+
+        backend
+            .call_method1("set_simulation_mode", ("stabilizer",))
+            .unwrap();
+        let (bits, _, _) = backend
+            .call_method1(
+                "run_circuit",
+                (CircuitWrapper {
+                    internal: circuit.clone(),
+                },),
+            )
+            .unwrap()
+            .extract::<(
+                std::collections::HashMap<String, Vec<Vec<bool>>>,
+                Py<PyAny>,
+                Py<PyAny>,
+            )>()
+            .unwrap();
+        assert_eq!(bits["ro"], vec![vec![true]; 10]);
+
+        let mut non_clifford = circuit.clone();
+        non_clifford += operations::RotateX::new(0, 0.3.into());
+        assert!(backend
+            .call_method1(
+                "run_circuit",
+                (CircuitWrapper {
+                    internal: non_clifford,
+                },),
+            )
+            .is_err());
+    })
+}
+
+/// Test the check_circuit function of SimulatorBackendWrapper
+#[test]
+fn test_check_circuit() {
+    pyo3::prepare_freethreaded_python();
+    let mut circuit = Circuit::new();
+    circuit += operations::RotateX::new(0, 1.0.into());
+    circuit += operations::Hadamard::new(0);
+    circuit += operations::RotateX::new(4, 1.0.into());
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_tw = device_type.call0().unwrap();
+        device_tw.call_method1("add_layout", ("test",)).unwrap();
+        device_tw
+            .call_method1(
+                "set_tweezer_single_qubit_gate_time",
+                ("RotateX", 0, 1.0, "test"),
+            )
+            .unwrap();
+        device_tw.call_method1("switch_layout", ("test",)).unwrap();
+
+        let backend_type = py.get_type_bound::<SimulatorBackendWrapper>();
+        let backend = backend_type.call1((device_tw,)).unwrap();
+        let input = (CircuitWrapper {
+            internal: circuit.clone(),
+        },);
+        let violations = backend
+            .call_method1("check_circuit", input.clone())
+            .unwrap()
+            .extract::<Vec<Bound<pyo3::types::PyDict>>>()
+            .unwrap();
+        assert_eq!(violations.len(), 2);
+        let rule = |index: usize| -> String {
+            violations[index]
+                .get_item("rule")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap()
+        };
+        assert_eq!(rule(0), "UnavailableGate");
+        assert_eq!(rule(1), "QubitNotInTweezer");
+        assert_eq!(
+            violations[1]
+                .get_item("tweezers")
+                .unwrap()
+                .unwrap()
+                .extract::<Vec<Option<usize>>>()
+                .unwrap(),
+            vec![None]
+        );
+        assert!(backend.call_method1("run_circuit", input).is_err());
+        assert!(backend.call_method1("check_circuit", (0,)).is_err());
+    })
+}
+
+/// Test the mitigate_bit_register function
+#[test]
+fn test_mitigate_bit_register() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let function = wrap_pyfunction_bound!(mitigate_bit_register, py).unwrap();
+        let mut register = vec![vec![true, false]; 8];
+        register.extend(vec![vec![false, false]; 2]);
+        let confusion_matrices = vec![(0.1, 0.2), (0.0, 0.0)];
+        let mitigated = function
+            .call1((register.clone(), confusion_matrices.clone()))
+            .unwrap()
+            .extract::<std::collections::HashMap<String, f64>>()
+            .unwrap();
+        assert_eq!(mitigated.len(), 1);
+        assert!((mitigated["10"] - 1.0).abs() < 1e-12);
+        let mitigated = function
+            .call1((
+                register.clone(),
+                confusion_matrices.clone(),
+                "least_squares",
+            ))
+            .unwrap()
+            .extract::<std::collections::HashMap<String, f64>>()
+            .unwrap();
+        assert!((mitigated["10"] - 1.0).abs() < 1e-12);
+        assert!(function
+            .call1((register.clone(), confusion_matrices, "unknown"))
+            .is_err());
+        assert!(function
+            .call1((register, vec![(1.5, 0.0), (0.0, 0.0)]))
+            .is_err());
+    })
+}
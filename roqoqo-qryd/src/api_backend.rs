@@ -11,6 +11,7 @@
 // limitations under the License.
 
 use crate::api_devices::QRydAPIDevice;
+use crate::{mitigate_frequencies, ReadoutConfusionMatrix, ReadoutMitigationMethod};
 use bitvec::prelude::*;
 use num_complex::Complex64;
 use reqwest::blocking::Client;
@@ -37,7 +38,7 @@ use std::{thread, time};
 /// This limitation is introduced by design to check the compatability of quantum programs with a model of the QRyd hardware.
 /// For simulations of the QRyd quantum computer use the backend simulator [crate::Backend].
 ///
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct APIBackend {
     /// Device representing the model of a QRyd device.
     pub device: QRydAPIDevice,
@@ -55,6 +56,20 @@ pub struct APIBackend {
     api_version: String,
 }
 
+// The access token is redacted so that logging a backend does not leak it
+impl std::fmt::Debug for APIBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("APIBackend")
+            .field("device", &self.device)
+            .field("access_token", &"<redacted>")
+            .field("timeout", &self.timeout)
+            .field("mock_port", &self.mock_port)
+            .field("dev", &self.dev)
+            .field("api_version", &self.api_version)
+            .finish()
+    }
+}
+
 /// Local struct representing the body of the request message
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 struct QRydRunData {
@@ -310,7 +325,7 @@ impl APIBackend {
         if mock_port.is_some() {
             Ok(Self {
                 device,
-                access_token: "".to_string(),
+                access_token: access_token.unwrap_or_default(),
                 timeout: timeout.unwrap_or(30),
                 mock_port,
                 dev: false,
@@ -350,6 +365,18 @@ impl APIBackend {
     ///
     /// * `quantumprogram` - Roqoqo QuantumProgram to be executed.
     ///
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(
+                backend = %self.device.qrydbackend(),
+                api_version = %self.api_version,
+                dev = self.dev
+            )
+        )
+    )]
     pub fn post_job(&self, quantumprogram: QuantumProgram) -> Result<String, RoqoqoBackendError> {
         // Prepare data that need to be passed to the WebAPI client
         let seed_param: Option<usize> = self.device.seed(); // seed.unwrap_or(0);
@@ -493,9 +520,12 @@ impl APIBackend {
         // Call WebAPI client
         // here: value for put() temporarily fixed.
         // needs to be derived dynamically based on the provided parameter 'qrydbackend'
+        #[cfg(feature = "tracing")]
+        let start = time::Instant::now();
         let resp = if let Some(mock_port) = &self.mock_port {
             client
                 .post(format!("http://127.0.0.1:{}", mock_port))
+                .header("X-API-KEY", self.access_token.clone())
                 .json(&data)
                 .send()
                 .map_err(|e| RoqoqoBackendError::NetworkError {
@@ -554,6 +584,9 @@ impl APIBackend {
             }
         };
 
+        #[cfg(feature = "tracing")]
+        crate::trace_response("post_job", &resp, start);
+
         let status_code = resp.status();
         if status_code != reqwest::StatusCode::CREATED {
             if status_code == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
@@ -574,12 +607,15 @@ impl APIBackend {
         } else {
             let resp_headers = resp.headers();
             if resp_headers.contains_key("Location") {
-                Ok(resp_headers["Location"]
+                let job_location = resp_headers["Location"]
                     .to_str()
                     .map_err(|err| RoqoqoBackendError::NetworkError {
                         msg: format!("Server response missing the Location header {:?}", err),
                     })?
-                    .to_string())
+                    .to_string();
+                #[cfg(feature = "tracing")]
+                tracing::info!(job_location = %job_location, "Job posted to QRyd WebAPI");
+                Ok(job_location)
             } else {
                 Err(RoqoqoBackendError::NetworkError {
                     msg: "Server response missing the Location header".to_string(),
@@ -599,6 +635,10 @@ impl APIBackend {
     /// * QRydJobStatus - status and message of the job.
     /// * RoqoqoBackendError in case of a network failure.
     ///
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self), fields(dev = self.dev))
+    )]
     pub fn get_job_status(
         &self,
         job_location: String,
@@ -623,6 +663,8 @@ impl APIBackend {
         let hqs_env_var = env::var("QRYD_API_HQS").is_ok();
 
         // Call WebAPI client
+        #[cfg(feature = "tracing")]
+        let start = time::Instant::now();
         let resp = match (self.dev, hqs_env_var) {
             (true, true) => client
                 .get(url_string)
@@ -658,6 +700,9 @@ impl APIBackend {
                 })?,
         };
 
+        #[cfg(feature = "tracing")]
+        crate::trace_response("get_job_status", &resp, start);
+
         let status_code = resp.status();
         if status_code != reqwest::StatusCode::OK {
             if status_code == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
@@ -682,6 +727,10 @@ impl APIBackend {
                 .map_err(|e| RoqoqoBackendError::NetworkError {
                     msg: format!("second {:?}", e),
                 });
+            #[cfg(feature = "tracing")]
+            if let Ok(job_status) = &response {
+                tracing::debug!(status = %job_status.status, msg = %job_status.msg, "Job status retrieved");
+            }
             response
         }
     }
@@ -696,6 +745,10 @@ impl APIBackend {
     /// * Result of the job.
     /// * RoqoqoBackendError in case of a network failure.
     ///
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self), fields(dev = self.dev))
+    )]
     pub fn get_job_result(
         &self,
         job_location: String,
@@ -721,6 +774,8 @@ impl APIBackend {
         let hqs_env_var = env::var("QRYD_API_HQS").is_ok();
 
         // Call WebAPI client
+        #[cfg(feature = "tracing")]
+        let start = time::Instant::now();
         let resp = match (self.dev, hqs_env_var) {
             (true, true) => client
                 .get(url_string)
@@ -756,6 +811,9 @@ impl APIBackend {
                 })?,
         };
 
+        #[cfg(feature = "tracing")]
+        crate::trace_response("get_job_result", &resp, start);
+
         let status_code = resp.status();
         if status_code != reqwest::StatusCode::OK {
             if status_code == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
@@ -793,6 +851,10 @@ impl APIBackend {
     /// # Returns
     /// * RoqoqoBackendError in case of a network failure.
     ///
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self), fields(dev = self.dev))
+    )]
     pub fn delete_job(&self, job_location: String) -> Result<(), RoqoqoBackendError> {
         // Prepare WebAPI client
         let client: Client = if self.mock_port.is_some() {
@@ -813,6 +875,8 @@ impl APIBackend {
         let hqs_env_var = env::var("QRYD_API_HQS").is_ok();
 
         // Call WebAPI client
+        #[cfg(feature = "tracing")]
+        let start = time::Instant::now();
         let resp = match (self.dev, hqs_env_var) {
             (true, true) => client
                 .delete(job_location)
@@ -848,6 +912,9 @@ impl APIBackend {
                 })?,
         };
 
+        #[cfg(feature = "tracing")]
+        crate::trace_response("delete_job", &resp, start);
+
         let status_code = resp.status();
         if status_code != reqwest::StatusCode::OK {
             if status_code == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
@@ -890,35 +957,7 @@ impl APIBackend {
         let complex_map: HashMap<String, Vec<Vec<Complex64>>> = HashMap::new();
         let mut measurement_record: Vec<Vec<bool>> = Vec::new();
         for (measurement, count) in counts.counts.into_iter() {
-            let bit_representation: Vec<u8> = hex::decode(
-                measurement
-                    .strip_prefix("0x")
-                    .map(|s| {
-                        if s.len() % 2 == 0 {
-                            s.to_string()
-                        } else {
-                            format!("0{}", s)
-                        }
-                    })
-                    .ok_or(RoqoqoBackendError::GenericError {
-                        msg: format!(
-                            "Cannot parse a measurement result as bit representation {}",
-                            measurement.clone()
-                        ),
-                    })?,
-            )
-            .map_err(|err| RoqoqoBackendError::GenericError {
-                msg: format!(
-                    "Cannot parse a measurement result as bit representation {:?}",
-                    err
-                ),
-            })?;
-            let qubit_results = bit_representation.view_bits::<Lsb0>();
-            let mut tmp_vec: Vec<bool> = (0..number_qubits).map(|_| false).collect();
-            // only iterating over qubits in number_qubits returns of larger qubits will be ignored
-            for (mut_val, tmp_val) in (tmp_vec.iter_mut()).zip(qubit_results.iter()) {
-                *mut_val = *tmp_val
-            }
+            let tmp_vec = Self::measurement_to_bits(&measurement, number_qubits)?;
             for _ in 0..count {
                 measurement_record.push(tmp_vec.clone())
             }
@@ -927,6 +966,71 @@ impl APIBackend {
         Ok((bit_map, float_map, complex_map))
     }
 
+    /// Mitigates the readout errors of the counts returned from the QRyd WebAPI.
+    ///
+    /// # Arguments
+    ///
+    /// `counts` - The counts returned from the QRyd WebAPI
+    /// `number_qubits` - The number of measured qubits. Needs to be specified based on original circuit
+    /// `confusion_matrices` - The readout confusion matrix of each measured qubit, see `TweezerDevice::readout_confusion_matrices`
+    /// `method` - The mitigation method
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<String, f64>)` - The mitigated probabilities, keyed by bitstrings whose i-th character is the result of qubit i.
+    /// * `Err(RoqoqoBackendError)` - A measurement result cannot be parsed or the mitigation failed.
+    pub fn mitigate_counts(
+        counts: &ResultCounts,
+        number_qubits: usize,
+        confusion_matrices: &[ReadoutConfusionMatrix],
+        method: ReadoutMitigationMethod,
+    ) -> Result<HashMap<String, f64>, RoqoqoBackendError> {
+        let mut frequencies: HashMap<Vec<bool>, u64> = HashMap::new();
+        for (measurement, count) in counts.counts.iter() {
+            *frequencies
+                .entry(Self::measurement_to_bits(measurement, number_qubits)?)
+                .or_default() += count;
+        }
+        mitigate_frequencies(&frequencies, confusion_matrices, method)
+    }
+
+    /// Converts a measurement result returned from the QRyd WebAPI to the results of the qubits.
+    fn measurement_to_bits(
+        measurement: &str,
+        number_qubits: usize,
+    ) -> Result<Vec<bool>, RoqoqoBackendError> {
+        let bit_representation: Vec<u8> = hex::decode(
+            measurement
+                .strip_prefix("0x")
+                .map(|s| {
+                    if s.len() % 2 == 0 {
+                        s.to_string()
+                    } else {
+                        format!("0{}", s)
+                    }
+                })
+                .ok_or(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Cannot parse a measurement result as bit representation {}",
+                        measurement
+                    ),
+                })?,
+        )
+        .map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Cannot parse a measurement result as bit representation {:?}",
+                err
+            ),
+        })?;
+        let qubit_results = bit_representation.view_bits::<Lsb0>();
+        let mut tmp_vec: Vec<bool> = (0..number_qubits).map(|_| false).collect();
+        // only iterating over qubits in number_qubits returns of larger qubits will be ignored
+        for (mut_val, tmp_val) in (tmp_vec.iter_mut()).zip(qubit_results.iter()) {
+            *mut_val = *tmp_val
+        }
+        Ok(tmp_vec)
+    }
+
     /// Setter for the dev option of the APIDevice.
     ///
     /// # Arguments
@@ -1042,6 +1146,14 @@ impl APIBackend {
 }
 
 impl EvaluatingBackend for APIBackend {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(backend = %self.device.qrydbackend(), timeout = self.timeout)
+        )
+    )]
     fn run_circuit_iterator<'a>(
         &self,
         circuit: impl Iterator<Item = &'a Operation>,
@@ -1084,12 +1196,23 @@ impl EvaluatingBackend for APIBackend {
             test_counter += 1;
             let job_status = self.get_job_status(job_loc.clone()).unwrap();
             status.clone_from(&job_status.status);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                job_location = %job_loc,
+                attempt = test_counter,
+                status = %status,
+                "Polled job status"
+            );
             thread::sleep(fifteen);
             if status == *"completed" {
                 job_result = self.get_job_result(job_loc.clone()).unwrap();
             }
         }
 
+        #[cfg(feature = "tracing")]
+        if status != "completed" {
+            tracing::warn!(job_location = %job_loc, status = %status, "Job did not complete");
+        }
         if status == "completed" {
             APIBackend::counts_to_result(job_result.data, readout, number_qubits)
         } else if status == "error" {
@@ -20,6 +20,7 @@ use std::str::FromStr;
 ///
 /// Contains a square device, a triangular device, and a tweezer device.
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(clippy::large_enum_variant)]
 pub enum QRydAPIDevice {
     /// Square Device
     QrydEmuSquareDevice(QrydEmuSquareDevice),
@@ -0,0 +1,197 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Atom loss
+//!
+//! Model of the loss of the atoms held in the tweezers during shifts, measurements and idle
+//! times, applied by the SimulatorBackend, and post-selection of the shots without lost qubits.
+
+use std::collections::HashMap;
+
+use roqoqo::registers::BitOutputRegister;
+use roqoqo::RoqoqoBackendError;
+
+/// Default name of the bit register of lost qubits.
+pub const DEFAULT_LOST_QUBITS_REGISTER: &str = "lost_qubits";
+
+/// Model of the loss of the atoms held in the tweezers
+///
+/// A lost qubit reads out as false in every later measurement. The quantum state of the other
+/// qubits is not affected by the loss.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AtomLossModel {
+    /// Loss rate per unit of shift distance, the distance between the two tweezers of a shift
+    /// or 1 if their positions are not set.
+    pub shift_loss_rate: f64,
+    /// Probability of losing a qubit when it is measured.
+    pub measurement_loss_probability: f64,
+    /// Loss rate per unit of time a qubit is idle while a gate acts on other qubits.
+    pub idle_loss_rate: f64,
+    /// Name of the bit register of lost qubits, with one entry per qubit and shot.
+    pub lost_register: String,
+}
+
+impl Default for AtomLossModel {
+    fn default() -> Self {
+        Self {
+            shift_loss_rate: 0.0,
+            measurement_loss_probability: 0.0,
+            idle_loss_rate: 0.0,
+            lost_register: DEFAULT_LOST_QUBITS_REGISTER.to_string(),
+        }
+    }
+}
+
+impl AtomLossModel {
+    /// Creates a new atom loss model without losses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the model with the given loss rate per unit of shift distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - The loss rate per unit of shift distance.
+    pub fn with_shift_loss_rate(mut self, rate: f64) -> Self {
+        self.shift_loss_rate = rate;
+        self
+    }
+
+    /// Returns the model with the given probability of losing a measured qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `probability` - The probability of losing a qubit when it is measured.
+    pub fn with_measurement_loss_probability(mut self, probability: f64) -> Self {
+        self.measurement_loss_probability = probability;
+        self
+    }
+
+    /// Returns the model with the given loss rate per unit of idle time.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - The loss rate per unit of time a qubit is idle.
+    pub fn with_idle_loss_rate(mut self, rate: f64) -> Self {
+        self.idle_loss_rate = rate;
+        self
+    }
+
+    /// Returns the model with the given name of the bit register of lost qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register.
+    pub fn with_lost_register(mut self, name: &str) -> Self {
+        self.lost_register = name.to_string();
+        self
+    }
+
+    /// Checks that the rates are finite and non-negative and the probability is in [0, 1].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The model is valid.
+    /// * `Err(RoqoqoBackendError)` - A rate or the probability is out of range.
+    pub fn validate(&self) -> Result<(), RoqoqoBackendError> {
+        if ![self.shift_loss_rate, self.idle_loss_rate]
+            .iter()
+            .all(|rate| rate.is_finite() && *rate >= 0.0)
+        {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The atom loss rates need to be finite and non-negative.".to_string(),
+            });
+        }
+        if !(0.0..=1.0).contains(&self.measurement_loss_probability) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The measurement loss probability {} is not between 0 and 1.",
+                    self.measurement_loss_probability
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the probability of losing a qubit shifted over the given distance.
+    pub fn shift_loss_probability(&self, distance: f64) -> f64 {
+        1.0 - (-self.shift_loss_rate * distance).exp()
+    }
+
+    /// Returns the probability of losing a qubit idle for the given time.
+    pub fn idle_loss_probability(&self, time: f64) -> f64 {
+        1.0 - (-self.idle_loss_rate * time).exp()
+    }
+}
+
+/// Post-selects the shots in which none of the given qubits has been lost.
+///
+/// Every bit register is reduced to the selected shots and needs as many shots as the register
+/// of lost qubits. The register of lost qubits is not part of the result.
+///
+/// # Arguments
+///
+/// * `bit_registers` - The bit registers of a run, including the register of lost qubits.
+/// * `lost_register` - The name of the register of lost qubits.
+/// * `qubits` - The qubits that must not be lost. Defaults to all qubits.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, BitOutputRegister>)` - The bit registers of the selected shots.
+/// * `Err(RoqoqoBackendError)` - The register of lost qubits is missing or a register has a different number of shots.
+pub fn post_select_shots(
+    bit_registers: &HashMap<String, BitOutputRegister>,
+    lost_register: &str,
+    qubits: Option<&[usize]>,
+) -> Result<HashMap<String, BitOutputRegister>, RoqoqoBackendError> {
+    let lost =
+        bit_registers
+            .get(lost_register)
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: format!("The register of lost qubits {} is missing.", lost_register),
+            })?;
+    let selected: Vec<bool> = lost
+        .iter()
+        .map(|shot| match qubits {
+            Some(qubits) => qubits
+                .iter()
+                .all(|qubit| !shot.get(*qubit).copied().unwrap_or(false)),
+            None => !shot.iter().any(|lost| *lost),
+        })
+        .collect();
+    let mut post_selected = HashMap::new();
+    for (name, register) in bit_registers.iter() {
+        if name == lost_register {
+            continue;
+        }
+        if register.len() != selected.len() {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The register {} has {} shots, but the register of lost qubits has {}.",
+                    name,
+                    register.len(),
+                    selected.len()
+                ),
+            });
+        }
+        let shots: BitOutputRegister = register
+            .iter()
+            .zip(selected.iter())
+            .filter(|(_, keep)| **keep)
+            .map(|(shot, _)| shot.clone())
+            .collect();
+        post_selected.insert(name.clone(), shots);
+    }
+    Ok(post_selected)
+}
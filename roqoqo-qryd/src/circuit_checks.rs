@@ -0,0 +1,239 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Circuit checks
+//!
+//! Checks of all the operations of a circuit against a TweezerDevice, reporting every violation
+//! instead of stopping at the first one.
+
+use std::fmt;
+
+use itertools::Itertools;
+use roqoqo::devices::Device;
+use roqoqo::operations::{
+    InvolveQubits, InvolvedQubits, MultiQubitGateOperation, Operate, OperateMultiQubit,
+    OperateSingleQubit, OperateThreeQubit, OperateTwoQubit, Operation, SingleQubitGateOperation,
+    ThreeQubitGateOperation, TwoQubitGateOperation,
+};
+use roqoqo::{Circuit, RoqoqoBackendError};
+
+use crate::scheduling::gate_time;
+use crate::TweezerDevice;
+
+/// The reason an operation of a circuit violates the device.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ViolationReason {
+    /// A qubit of the gate is not held in a tweezer.
+    QubitNotInTweezer,
+    /// The gate is not available on the tweezers of its qubits.
+    UnavailableGate,
+    /// A qubit of the operation exceeds the number of qubits of the simulator.
+    QubitOutOfRange {
+        /// The number of qubits of the simulator.
+        number_qubits: usize,
+    },
+    /// A PragmaChangeDevice operation cannot be applied to the device.
+    FailedDeviceChange {
+        /// The error message of the device change.
+        msg: String,
+    },
+}
+
+impl ViolationReason {
+    /// Returns the name of the violated rule.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            ViolationReason::QubitNotInTweezer => "QubitNotInTweezer",
+            ViolationReason::UnavailableGate => "UnavailableGate",
+            ViolationReason::QubitOutOfRange { .. } => "QubitOutOfRange",
+            ViolationReason::FailedDeviceChange { .. } => "FailedDeviceChange",
+        }
+    }
+}
+
+impl fmt::Display for ViolationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViolationReason::QubitNotInTweezer => write!(f, "A qubit is not in a tweezer."),
+            ViolationReason::UnavailableGate => {
+                write!(f, "The gate is not available on the tweezers.")
+            }
+            ViolationReason::QubitOutOfRange { number_qubits } => {
+                write!(f, "The simulator has {} qubits only.", number_qubits)
+            }
+            ViolationReason::FailedDeviceChange { msg } => {
+                write!(f, "The device change failed: {}", msg)
+            }
+        }
+    }
+}
+
+/// An operation of a circuit violating the device.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeviceViolation {
+    /// The index of the operation in the circuit.
+    pub index: usize,
+    /// The hqslang name of the operation, the wrapped operation for PragmaChangeDevice.
+    pub hqslang: String,
+    /// The qubits of the operation, empty for PragmaChangeDevice.
+    pub qubits: Vec<usize>,
+    /// The tweezer of each qubit at the operation, None for qubits not in a tweezer.
+    pub tweezers: Vec<Option<usize>>,
+    /// The reason of the violation.
+    pub reason: ViolationReason,
+}
+
+impl fmt::Display for DeviceViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Operation {} ({} on the qubits {:?}, tweezers {:?}): {}",
+            self.index, self.hqslang, self.qubits, self.tweezers, self.reason
+        )
+    }
+}
+
+/// Converts the violations of a circuit into an error if there are any.
+///
+/// # Arguments
+///
+/// * `violations` - The violations of the circuit.
+///
+/// # Returns
+///
+/// * `Ok(())` - There are no violations.
+/// * `Err(RoqoqoBackendError::GenericError)` - The error listing all violations.
+pub fn violations_into_result(violations: &[DeviceViolation]) -> Result<(), RoqoqoBackendError> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(RoqoqoBackendError::GenericError {
+        msg: format!(
+            "The circuit violates the device:\n{}",
+            violations.iter().join("\n")
+        ),
+    })
+}
+
+impl TweezerDevice {
+    /// Checks every operation of a circuit against the device.
+    ///
+    /// The PragmaChangeDevice operations of the circuit are applied in order with
+    /// `change_device` on a copy of the device. A PragmaChangeDevice operation that fails is
+    /// reported and skipped, the following operations are checked against the unchanged device.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to check.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<DeviceViolation>` - The violations, in the order of the circuit.
+    pub fn check_circuit(&self, circuit: &Circuit) -> Vec<DeviceViolation> {
+        self.check_operations(circuit.iter(), None)
+    }
+
+    /// Checks every operation of a sequence of operations against the device, see
+    /// [TweezerDevice::check_circuit].
+    ///
+    /// With a number of qubits, operations on larger qubits are reported as well.
+    pub(crate) fn check_operations<'a>(
+        &self,
+        operations: impl Iterator<Item = &'a Operation>,
+        number_qubits: Option<usize>,
+    ) -> Vec<DeviceViolation> {
+        let mut device = self.clone();
+        let mut violations = Vec::new();
+        for (index, operation) in operations.enumerate() {
+            if let (Some(number_qubits), InvolvedQubits::Set(qubits)) =
+                (number_qubits, operation.involved_qubits())
+            {
+                if qubits.iter().any(|qubit| *qubit >= number_qubits) {
+                    let qubits = gate_qubits(operation)
+                        .unwrap_or_else(|| qubits.into_iter().sorted().collect());
+                    violations.push(DeviceViolation {
+                        index,
+                        hqslang: operation.hqslang().to_string(),
+                        tweezers: tweezers_of(&device, &qubits),
+                        qubits,
+                        reason: ViolationReason::QubitOutOfRange { number_qubits },
+                    });
+                    continue;
+                }
+            }
+            if let Operation::PragmaChangeDevice(pragma) = operation {
+                let mut changed = device.clone();
+                match changed.change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation) {
+                    Ok(()) => device = changed,
+                    Err(err) => violations.push(DeviceViolation {
+                        index,
+                        hqslang: pragma.wrapped_hqslang.clone(),
+                        qubits: Vec::new(),
+                        tweezers: Vec::new(),
+                        reason: ViolationReason::FailedDeviceChange {
+                            msg: match err {
+                                RoqoqoBackendError::GenericError { msg } => msg,
+                                _ => err.to_string(),
+                            },
+                        },
+                    }),
+                }
+            } else if let Some(qubits) = gate_qubits(operation) {
+                let tweezers = tweezers_of(&device, &qubits);
+                let reason = if tweezers.iter().any(|tweezer| tweezer.is_none()) {
+                    ViolationReason::QubitNotInTweezer
+                } else if gate_time(&device, operation).is_err() {
+                    ViolationReason::UnavailableGate
+                } else {
+                    continue;
+                };
+                violations.push(DeviceViolation {
+                    index,
+                    hqslang: operation.hqslang().to_string(),
+                    qubits,
+                    tweezers,
+                    reason,
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Returns the tweezer of each qubit, None for qubits not in a tweezer.
+fn tweezers_of(device: &TweezerDevice, qubits: &[usize]) -> Vec<Option<usize>> {
+    qubits
+        .iter()
+        .map(|qubit| {
+            device
+                .qubit_to_tweezer
+                .as_ref()
+                .and_then(|mapping| mapping.tweezer_of(*qubit))
+        })
+        .collect()
+}
+
+/// Returns the qubits of a gate in the order of its arguments, None for other operations.
+pub(crate) fn gate_qubits(operation: &Operation) -> Option<Vec<usize>> {
+    if let Ok(gate) = SingleQubitGateOperation::try_from(operation.clone()) {
+        Some(vec![*gate.qubit()])
+    } else if let Ok(gate) = TwoQubitGateOperation::try_from(operation.clone()) {
+        Some(vec![*gate.control(), *gate.target()])
+    } else if let Ok(gate) = ThreeQubitGateOperation::try_from(operation.clone()) {
+        Some(vec![*gate.control_0(), *gate.control_1(), *gate.target()])
+    } else if let Ok(gate) = MultiQubitGateOperation::try_from(operation.clone()) {
+        Some(gate.qubits().clone())
+    } else {
+        None
+    }
+}
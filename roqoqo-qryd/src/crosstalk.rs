@@ -0,0 +1,152 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Crosstalk
+//!
+//! Model of the Rydberg blockade crosstalk between the qubits of an entangling gate and the
+//! spectator qubits held in nearby tweezers, applied by the SimulatorBackend.
+
+use std::collections::BTreeSet;
+
+use roqoqo::devices::Device;
+use roqoqo::RoqoqoBackendError;
+
+use crate::TweezerDevice;
+
+/// Model of the Rydberg blockade crosstalk during entangling gates
+///
+/// During an entangling gate, every spectator qubit close to one of the qubits of the gate
+/// acquires a phase when both the gate qubit and the spectator are in state |1>. The phase is
+/// correlated, it is the phase of a ControlledPhaseShift between the gate qubit and the spectator.
+///
+/// Without range, the spectators of a gate qubit are the qubits in the tweezers connected to its
+/// tweezer by a two-qubit gate in either direction. With a range, they are the qubits in the tweezers within the
+/// range of its tweezer, which requires the tweezer positions of the layout.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CrosstalkModel {
+    /// The phase acquired by a spectator qubit during an entangling gate on a nearby qubit.
+    pub strength: f64,
+    /// The maximal distance in micrometers between the tweezers of a gate qubit and a spectator,
+    /// None to use the two-qubit connectivity of the device.
+    pub range: Option<f64>,
+}
+
+impl CrosstalkModel {
+    /// Creates a new crosstalk model using the two-qubit connectivity of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `strength` - The phase acquired by a spectator qubit during an entangling gate.
+    pub fn new(strength: f64) -> Self {
+        Self {
+            strength,
+            range: None,
+        }
+    }
+
+    /// Returns the model with the spectators given by the distance between the tweezers.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The maximal distance in micrometers between a gate qubit and a spectator.
+    pub fn with_range(mut self, range: f64) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Checks that the strength is finite and the range is finite and positive.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The model is valid.
+    /// * `Err(RoqoqoBackendError)` - The strength or the range is out of range.
+    pub fn validate(&self) -> Result<(), RoqoqoBackendError> {
+        if !self.strength.is_finite() {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!("The crosstalk strength {} is not finite.", self.strength),
+            });
+        }
+        if let Some(range) = self.range {
+            if !(range.is_finite() && range > 0.0) {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "The crosstalk range {} needs to be finite and positive.",
+                        range
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the phases acquired by the spectators of an entangling gate.
+    ///
+    /// The qubits are followed in the current Layout and qubit mapping of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device holding the qubits.
+    /// * `gate_qubits` - The qubits of the entangling gate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(usize, usize, f64)>)` - The gate qubit, the spectator and the phase, sorted by gate qubit and spectator.
+    /// * `Err(RoqoqoBackendError)` - A gate qubit is not mapped to a tweezer or, with a range, the position of its tweezer is not set.
+    pub fn spectator_phases(
+        &self,
+        device: &TweezerDevice,
+        gate_qubits: &[usize],
+    ) -> Result<Vec<(usize, usize, f64)>, RoqoqoBackendError> {
+        if self.strength == 0.0 {
+            return Ok(Vec::new());
+        }
+        let gate_qubits: BTreeSet<usize> = gate_qubits.iter().copied().collect();
+        let edges = match self.range {
+            Some(_) => Vec::new(),
+            None => device.two_qubit_edges(),
+        };
+        let mut phases = Vec::new();
+        for qubit in gate_qubits.iter() {
+            let spectators: BTreeSet<usize> = match self.range {
+                Some(range) => device
+                    .tweezers_within_distance(device.get_tweezer_from_qubit(qubit)?, range, None)?
+                    .into_iter()
+                    .filter_map(|(tweezer, _)| device.qubit_at(tweezer))
+                    .collect(),
+                None => {
+                    device.get_tweezer_from_qubit(qubit)?;
+                    // The gate times may be set in one direction only, the blockade is symmetric
+                    edges
+                        .iter()
+                        .filter_map(|(qubit0, qubit1)| {
+                            if qubit0 == qubit {
+                                Some(*qubit1)
+                            } else if qubit1 == qubit {
+                                Some(*qubit0)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect()
+                }
+            };
+            phases.extend(
+                spectators
+                    .into_iter()
+                    .filter(|spectator| !gate_qubits.contains(spectator))
+                    .map(|spectator| (*qubit, spectator, self.strength)),
+            );
+        }
+        Ok(phases)
+    }
+}
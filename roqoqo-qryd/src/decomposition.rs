@@ -0,0 +1,423 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Native gate decomposition
+//!
+//! Rewriting of the gates of a circuit into the native gates of a TweezerDevice.
+
+use std::collections::HashSet;
+use std::f64::consts::PI;
+
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoBackendError};
+
+use crate::TweezerDevice;
+
+/// Tolerance of the comparison of a phase with the phi-theta relation of the device.
+const PHASE_TOLERANCE: f64 = 1e-4;
+
+/// Tolerance below which rotation angles and matrix elements are treated as zero.
+const ANGLE_TOLERANCE: f64 = 1e-12;
+
+/// Native gates of the current Layout of a device and its phase relations.
+struct NativeGates<'a> {
+    names: HashSet<&'a str>,
+    device: &'a TweezerDevice,
+}
+
+impl NativeGates<'_> {
+    fn contains(&self, hqslang: &str) -> bool {
+        self.names.contains(hqslang)
+    }
+}
+
+/// Decomposes the gates of a circuit into the native gates of the current Layout of a device.
+///
+/// Native gates are kept, other gates are rewritten into RotateZ (or PhaseShiftState1 or
+/// PhaseShiftState0), RotateX or RotateXY single-qubit gates and ControlledPauliZ,
+/// PhaseShiftedControlledZ, ControlledPhaseShift or PhaseShiftedControlledPhase two-qubit
+/// gates, in this order of preference. The single-qubit phases of PhaseShiftedControlledZ and
+/// PhaseShiftedControlledPhase given by the phi-theta relation of the device are compensated,
+/// and PhaseShiftedControlledZ and PhaseShiftedControlledPhase gates whose phi does not match
+/// the relation are rewritten. Three- and multi-qubit gates that are not native are rewritten
+/// with their two-qubit decomposition. The result is equal to the circuit up to a global phase.
+///
+/// Operations that are not gates are kept unchanged. The parameters of rewritten gates must
+/// not be symbolic. For an EmulatorDevice, use its `internal` TweezerDevice.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to decompose.
+/// * `device` - The TweezerDevice with the current Layout providing the native gates.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The decomposed circuit.
+/// * `Err(RoqoqoBackendError)` - A gate has symbolic parameters or the device lacks the gates to decompose it.
+pub fn decompose_to_native_gates(
+    circuit: &Circuit,
+    device: &TweezerDevice,
+) -> Result<Circuit, RoqoqoBackendError> {
+    let natives = NativeGates {
+        names: device
+            .get_available_gates_names(None)?
+            .into_iter()
+            .collect(),
+        device,
+    };
+    let mut decomposed = Circuit::new();
+    for operation in circuit.iter() {
+        decompose_operation(operation, &natives, &mut decomposed)?;
+    }
+    Ok(decomposed)
+}
+
+/// Appends the native decomposition of an operation to a circuit.
+fn decompose_operation(
+    operation: &Operation,
+    natives: &NativeGates,
+    decomposed: &mut Circuit,
+) -> Result<(), RoqoqoBackendError> {
+    if is_native(operation, natives) {
+        decomposed.add_operation(operation.clone());
+        return Ok(());
+    }
+    if let Ok(gate) = SingleQubitGateOperation::try_from(operation.clone()) {
+        return decompose_single_qubit_gate(&gate, natives, decomposed);
+    }
+    match operation {
+        Operation::ControlledPauliZ(gate) => {
+            return append_controlled_z(*gate.control(), *gate.target(), natives, decomposed);
+        }
+        Operation::CNOT(gate) => {
+            let target = *gate.target();
+            decompose_operation(&Hadamard::new(target).into(), natives, decomposed)?;
+            append_controlled_z(*gate.control(), target, natives, decomposed)?;
+            return decompose_operation(&Hadamard::new(target).into(), natives, decomposed);
+        }
+        Operation::PhaseShiftedControlledZ(gate) => {
+            let phi = parameter(gate.phi(), gate.hqslang())?;
+            append_controlled_z(*gate.control(), *gate.target(), natives, decomposed)?;
+            append_z_rotation(*gate.control(), phi, natives, decomposed)?;
+            return append_z_rotation(*gate.target(), phi, natives, decomposed);
+        }
+        Operation::ControlledPhaseShift(gate) => {
+            let theta = parameter(gate.theta(), gate.hqslang())?;
+            return append_controlled_phase(
+                *gate.control(),
+                *gate.target(),
+                theta,
+                natives,
+                decomposed,
+            );
+        }
+        Operation::PhaseShiftedControlledPhase(gate) => {
+            let theta = parameter(gate.theta(), gate.hqslang())?;
+            let phi = parameter(gate.phi(), gate.hqslang())?;
+            append_controlled_phase(*gate.control(), *gate.target(), theta, natives, decomposed)?;
+            append_z_rotation(*gate.control(), phi, natives, decomposed)?;
+            return append_z_rotation(*gate.target(), phi, natives, decomposed);
+        }
+        Operation::Toffoli(gate) if natives.contains("ControlledControlledPauliZ") => {
+            let target = *gate.target();
+            decompose_operation(&Hadamard::new(target).into(), natives, decomposed)?;
+            decomposed.add_operation(ControlledControlledPauliZ::new(
+                *gate.control_0(),
+                *gate.control_1(),
+                target,
+            ));
+            return decompose_operation(&Hadamard::new(target).into(), natives, decomposed);
+        }
+        _ => (),
+    }
+    if let Ok(gate) = TwoQubitGateOperation::try_from(operation.clone()) {
+        return decompose_two_qubit_gate(&gate, natives, decomposed);
+    }
+    if let Ok(gate) = ThreeQubitGateOperation::try_from(operation.clone()) {
+        for sub_operation in gate.circuit().iter() {
+            decompose_operation(sub_operation, natives, decomposed)?;
+        }
+        return Ok(());
+    }
+    if let Ok(gate) = MultiQubitGateOperation::try_from(operation.clone()) {
+        for sub_operation in gate.circuit().iter() {
+            decompose_operation(sub_operation, natives, decomposed)?;
+        }
+        return Ok(());
+    }
+    decomposed.add_operation(operation.clone());
+    Ok(())
+}
+
+/// Returns whether an operation is a native gate, or not a gate, and is kept unchanged.
+fn is_native(operation: &Operation, natives: &NativeGates) -> bool {
+    match operation {
+        Operation::PhaseShiftedControlledZ(gate) => {
+            natives.contains(gate.hqslang())
+                && matches_relation(gate.phi(), natives.device.phase_shift_controlled_z())
+        }
+        Operation::PhaseShiftedControlledPhase(gate) => {
+            natives.contains(gate.hqslang())
+                && gate.theta().float().is_ok_and(|theta| {
+                    matches_relation(
+                        gate.phi(),
+                        natives.device.phase_shift_controlled_phase(*theta),
+                    )
+                })
+        }
+        _ => {
+            let is_gate = SingleQubitGateOperation::try_from(operation.clone()).is_ok()
+                || TwoQubitGateOperation::try_from(operation.clone()).is_ok()
+                || ThreeQubitGateOperation::try_from(operation.clone()).is_ok()
+                || MultiQubitGateOperation::try_from(operation.clone()).is_ok();
+            !is_gate || natives.contains(operation.hqslang())
+        }
+    }
+}
+
+/// Returns whether a phase matches the phase of the phi-theta relation, with the tolerance
+/// of the gate time checks of the device.
+fn matches_relation(phi: &CalculatorFloat, relation: Option<f64>) -> bool {
+    match (phi.float(), relation) {
+        (Ok(phi), Some(relation)) => (relation.abs() - phi.abs()).abs() < PHASE_TOLERANCE,
+        _ => false,
+    }
+}
+
+/// Returns the value of a gate parameter, which must not be symbolic.
+fn parameter(value: &CalculatorFloat, hqslang: &str) -> Result<f64, RoqoqoBackendError> {
+    value.float().copied().map_err(|_| RoqoqoBackendError::GenericError {
+        msg: format!(
+            "The {} gate has the symbolic parameter {} and cannot be decomposed. Substitute the parameters first.",
+            hqslang, value
+        ),
+    })
+}
+
+/// Returns an angle in the interval (-π, π].
+fn normalized_angle(angle: f64) -> f64 {
+    if angle > -PI && angle <= PI {
+        return angle;
+    }
+    let angle = angle.rem_euclid(2.0 * PI);
+    if angle > PI {
+        angle - 2.0 * PI
+    } else {
+        angle
+    }
+}
+
+/// Appends a rotation around the z-axis, up to a global phase.
+fn append_z_rotation(
+    qubit: usize,
+    theta: f64,
+    natives: &NativeGates,
+    decomposed: &mut Circuit,
+) -> Result<(), RoqoqoBackendError> {
+    let theta = normalized_angle(theta);
+    if theta.abs() < ANGLE_TOLERANCE {
+        return Ok(());
+    }
+    if natives.contains("RotateZ") {
+        decomposed.add_operation(RotateZ::new(qubit, theta.into()));
+    } else if natives.contains("PhaseShiftState1") {
+        decomposed.add_operation(PhaseShiftState1::new(qubit, theta.into()));
+    } else if natives.contains("PhaseShiftState0") {
+        decomposed.add_operation(PhaseShiftState0::new(qubit, (-theta).into()));
+    } else {
+        return Err(RoqoqoBackendError::GenericError {
+            msg:
+                "The device supports no z-rotation: RotateZ, PhaseShiftState1 or PhaseShiftState0."
+                    .to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Appends the native decomposition of a single-qubit gate, up to a global phase.
+///
+/// The gate is written as RotateZ(a) RotateY(b) RotateZ(c), with RotateY(b) as
+/// RotateXY(b, π/2) or as RotateZ(-π/2), RotateX(b), RotateZ(π/2).
+fn decompose_single_qubit_gate(
+    gate: &SingleQubitGateOperation,
+    natives: &NativeGates,
+    decomposed: &mut Circuit,
+) -> Result<(), RoqoqoBackendError> {
+    let qubit = *gate.qubit();
+    let alpha_r = parameter(&gate.alpha_r(), gate.hqslang())?;
+    let alpha_i = parameter(&gate.alpha_i(), gate.hqslang())?;
+    let beta_r = parameter(&gate.beta_r(), gate.hqslang())?;
+    let beta_i = parameter(&gate.beta_i(), gate.hqslang())?;
+    let alpha_abs = alpha_r.hypot(alpha_i);
+    let beta_abs = beta_r.hypot(beta_i);
+
+    // alpha = exp(-i (a + c) / 2) cos(b / 2) and beta = exp(i (a - c) / 2) sin(b / 2)
+    let b = 2.0 * beta_abs.atan2(alpha_abs);
+    let sum = if alpha_abs > ANGLE_TOLERANCE {
+        -2.0 * alpha_i.atan2(alpha_r)
+    } else {
+        0.0
+    };
+    let difference = if beta_abs > ANGLE_TOLERANCE {
+        2.0 * beta_i.atan2(beta_r)
+    } else {
+        0.0
+    };
+    let a = (sum + difference) / 2.0;
+    let c = (sum - difference) / 2.0;
+
+    if b.abs() < ANGLE_TOLERANCE {
+        return append_z_rotation(qubit, a + c, natives, decomposed);
+    }
+    if natives.contains("RotateXY") {
+        append_z_rotation(qubit, c, natives, decomposed)?;
+        decomposed.add_operation(RotateXY::new(qubit, b.into(), (PI / 2.0).into()));
+        append_z_rotation(qubit, a, natives, decomposed)
+    } else if natives.contains("RotateX") {
+        append_z_rotation(qubit, c - PI / 2.0, natives, decomposed)?;
+        decomposed.add_operation(RotateX::new(qubit, b.into()));
+        append_z_rotation(qubit, a + PI / 2.0, natives, decomposed)
+    } else {
+        Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "The device supports neither RotateXY nor RotateX needed to decompose the {} gate.",
+                gate.hqslang()
+            ),
+        })
+    }
+}
+
+/// Appends a native ControlledPauliZ gate, up to a global phase.
+fn append_controlled_z(
+    control: usize,
+    target: usize,
+    natives: &NativeGates,
+    decomposed: &mut Circuit,
+) -> Result<(), RoqoqoBackendError> {
+    if natives.contains("ControlledPauliZ") {
+        decomposed.add_operation(ControlledPauliZ::new(control, target));
+        return Ok(());
+    }
+    if natives.contains("PhaseShiftedControlledZ") {
+        if let Some(phi) = natives.device.phase_shift_controlled_z() {
+            decomposed.add_operation(PhaseShiftedControlledZ::new(control, target, phi.into()));
+            append_z_rotation(control, -phi, natives, decomposed)?;
+            return append_z_rotation(target, -phi, natives, decomposed);
+        }
+    }
+    if natives.contains("ControlledPhaseShift") {
+        decomposed.add_operation(ControlledPhaseShift::new(control, target, PI.into()));
+        return Ok(());
+    }
+    if natives.contains("PhaseShiftedControlledPhase") {
+        if let Some(phi) = natives.device.phase_shift_controlled_phase(PI) {
+            decomposed.add_operation(PhaseShiftedControlledPhase::new(
+                control,
+                target,
+                PI.into(),
+                phi.into(),
+            ));
+            append_z_rotation(control, -phi, natives, decomposed)?;
+            return append_z_rotation(target, -phi, natives, decomposed);
+        }
+    }
+    Err(RoqoqoBackendError::GenericError {
+        msg: "The device supports no two-qubit gate equivalent to ControlledPauliZ.".to_string(),
+    })
+}
+
+/// Appends a native ControlledPhaseShift gate, up to a global phase.
+fn append_controlled_phase(
+    control: usize,
+    target: usize,
+    theta: f64,
+    natives: &NativeGates,
+    decomposed: &mut Circuit,
+) -> Result<(), RoqoqoBackendError> {
+    if natives.contains("ControlledPhaseShift") {
+        decomposed.add_operation(ControlledPhaseShift::new(control, target, theta.into()));
+        return Ok(());
+    }
+    if natives.contains("PhaseShiftedControlledPhase") {
+        if let Some(phi) = natives.device.phase_shift_controlled_phase(theta) {
+            decomposed.add_operation(PhaseShiftedControlledPhase::new(
+                control,
+                target,
+                theta.into(),
+                phi.into(),
+            ));
+            append_z_rotation(control, -phi, natives, decomposed)?;
+            return append_z_rotation(target, -phi, natives, decomposed);
+        }
+    }
+    // Phases θ/2 on both qubits and a ZZ-interaction of -θ/4
+    append_z_rotation(target, theta / 2.0, natives, decomposed)?;
+    decompose_operation(&CNOT::new(control, target).into(), natives, decomposed)?;
+    append_z_rotation(target, -theta / 2.0, natives, decomposed)?;
+    decompose_operation(&CNOT::new(control, target).into(), natives, decomposed)?;
+    append_z_rotation(control, theta / 2.0, natives, decomposed)
+}
+
+/// Appends the native decomposition of a two-qubit gate based on its KAK decomposition.
+///
+/// Each term exp(i k PP) of the entangling part is a ZZ-interaction between two CNOT gates,
+/// in the basis of the Pauli operator P.
+fn decompose_two_qubit_gate(
+    gate: &TwoQubitGateOperation,
+    natives: &NativeGates,
+    decomposed: &mut Circuit,
+) -> Result<(), RoqoqoBackendError> {
+    let control = *gate.control();
+    let target = *gate.target();
+    let kak = gate.kak_decomposition();
+    if let Some(circuit) = kak.circuit_before.as_ref() {
+        for operation in circuit.iter() {
+            decompose_operation(operation, natives, decomposed)?;
+        }
+    }
+    // Basis changes mapping Z to X and Y, applied before and after the ZZ-interaction
+    let basis_change = |term: usize, qubit: usize, before: bool| -> Option<Operation> {
+        match term {
+            0 => Some(Hadamard::new(qubit).into()),
+            1 if before => Some(RotateX::new(qubit, (PI / 2.0).into()).into()),
+            1 => Some(RotateX::new(qubit, (-PI / 2.0).into()).into()),
+            _ => None,
+        }
+    };
+    for (term, k) in kak.k_vector.iter().enumerate() {
+        let k = parameter(k, gate.hqslang())?;
+        if normalized_angle(2.0 * k).abs() < ANGLE_TOLERANCE {
+            continue;
+        }
+        for qubit in [control, target] {
+            if let Some(operation) = basis_change(term, qubit, true) {
+                decompose_operation(&operation, natives, decomposed)?;
+            }
+        }
+        decompose_operation(&CNOT::new(control, target).into(), natives, decomposed)?;
+        append_z_rotation(target, -2.0 * k, natives, decomposed)?;
+        decompose_operation(&CNOT::new(control, target).into(), natives, decomposed)?;
+        for qubit in [control, target] {
+            if let Some(operation) = basis_change(term, qubit, false) {
+                decompose_operation(&operation, natives, decomposed)?;
+            }
+        }
+    }
+    if let Some(circuit) = kak.circuit_after.as_ref() {
+        for operation in circuit.iter() {
+            decompose_operation(operation, natives, decomposed)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,823 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Device cache
+//!
+//! Provides an optional on-disk cache of the device definitions downloaded from the QRyd WebAPI.
+//! The cache is enabled via the `DownloadOptions` passed to `from_api_with_options`, or by setting
+//! the environmental variable `$QRYD_API_CACHE_DIR` to the cache directory.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use reqwest::blocking::RequestBuilder;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use roqoqo::RoqoqoBackendError;
+
+use crate::TweezerDevice;
+
+/// Environmental variable enabling the device cache, holding the path of the cache directory.
+pub const DEVICE_CACHE_ENV_VAR: &str = "QRYD_API_CACHE_DIR";
+
+/// Environmental variable enabling the validation of downloaded devices.
+pub const VALIDATE_DEVICE_ENV_VAR: &str = "QRYD_VALIDATE_DEVICE";
+
+/// Counter making the names of temporary cache files unique within the process.
+static TEMPORARY_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// On-disk cache of device definitions downloaded from the QRyd WebAPI.
+///
+/// Cached definitions are revalidated with conditional requests (`If-None-Match`,
+/// `If-Modified-Since`) and reused when the QRyd WebAPI is unreachable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceCache {
+    /// The directory the device definitions are stored in.
+    directory: PathBuf,
+}
+
+/// Options for creating devices from the QRyd WebAPI.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DownloadOptions {
+    /// The on-disk cache of device definitions to use, disabled if None.
+    pub cache: Option<DeviceCache>,
+    /// Whether to validate the downloaded device and return any issue as an error.
+    pub validate: bool,
+}
+
+impl DownloadOptions {
+    /// Returns the options configured via environmental variables, as used by `from_api`.
+    ///
+    /// The device cache is enabled via `$QRYD_API_CACHE_DIR`, the validation of the downloaded
+    /// device via `$QRYD_VALIDATE_DEVICE` set to any value other than "0".
+    ///
+    /// # Returns
+    ///
+    /// * `DownloadOptions` - The options configured via environmental variables.
+    pub fn from_env() -> Self {
+        DownloadOptions {
+            cache: DeviceCache::from_env(),
+            validate: validation_requested(env::var(VALIDATE_DEVICE_ENV_VAR).ok().as_deref()),
+        }
+    }
+
+    /// Enables the device cache in the given directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to store the device definitions in. Created when needed.
+    ///
+    /// # Returns
+    ///
+    /// * `DownloadOptions` - The options with the device cache enabled.
+    pub fn with_cache_dir<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.cache = Some(DeviceCache::new(directory));
+        self
+    }
+
+    /// Sets whether to validate the downloaded device.
+    ///
+    /// Layouts of a TweezerDevice are checked with `TweezerDevice::validate`, the available gates
+    /// of an EmulatorDevice with `EmulatorDevice::validate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `validate` - Whether to validate the downloaded device.
+    ///
+    /// # Returns
+    ///
+    /// * `DownloadOptions` - The options with the validation set.
+    pub fn with_validation(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+}
+
+/// Returns whether the value of `$QRYD_VALIDATE_DEVICE` requests the validation of downloaded devices.
+fn validation_requested(value: Option<&str>) -> bool {
+    value.is_some_and(|value| value != "0" && !value.is_empty())
+}
+
+/// Identifies a device definition of the QRyd WebAPI.
+///
+/// The dev and HQS variants of the QRyd WebAPI can serve different definitions of a device,
+/// so they are cached separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CacheKey<'a> {
+    /// The name of the device.
+    pub(crate) device_name: &'a str,
+    /// The version of the QRyd WebAPI.
+    pub(crate) api_version: &'a str,
+    /// Whether the dev header is set.
+    pub(crate) dev: bool,
+    /// Whether the HQS header is set.
+    pub(crate) hqs: bool,
+}
+
+/// A cached device definition together with its validators.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct CacheEntry {
+    /// The ETag header returned with the device definition.
+    etag: Option<String>,
+    /// The Last-Modified header returned with the device definition.
+    last_modified: Option<String>,
+    /// The device definition, as returned by the QRyd WebAPI.
+    body: String,
+}
+
+impl DeviceCache {
+    /// Creates a new DeviceCache instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to store the device definitions in. Created when needed.
+    ///
+    /// # Returns
+    ///
+    /// * `DeviceCache` - The new DeviceCache instance.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        DeviceCache {
+            directory: directory.into(),
+        }
+    }
+
+    /// Returns the DeviceCache configured via the environmental variable `$QRYD_API_CACHE_DIR`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(DeviceCache)` - The cache is enabled.
+    /// * `None` - The environmental variable is not set or empty.
+    pub fn from_env() -> Option<Self> {
+        env::var_os(DEVICE_CACHE_ENV_VAR)
+            .filter(|directory| !directory.is_empty())
+            .map(DeviceCache::new)
+    }
+
+    /// Returns the directory the device definitions are stored in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the cached definition of a device, if present.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_name` - The name of the device.
+    /// * `api_version` - The version of the QRyd WebAPI the device was downloaded from.
+    /// * `dev` - Whether the device was downloaded with the dev header.
+    /// * `hqs` - Whether the device was downloaded with the HQS header, set via `$QRYD_API_HQS`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(TweezerDevice)` - The cached device definition, without any post-processing.
+    /// * `None` - The device is not cached or the cached definition is invalid.
+    pub fn cached_device(
+        &self,
+        device_name: &str,
+        api_version: &str,
+        dev: bool,
+        hqs: bool,
+    ) -> Option<TweezerDevice> {
+        self.read_entry(&CacheKey {
+            device_name,
+            api_version,
+            dev,
+            hqs,
+        })
+        .and_then(|entry| serde_json::from_str(&entry.body).ok())
+    }
+
+    /// Removes all cached device definitions.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError::GenericError` - The cache directory could not be removed.
+    pub fn clear(&self) -> Result<(), RoqoqoBackendError> {
+        if !self.directory.exists() {
+            return Ok(());
+        }
+        std::fs::remove_dir_all(&self.directory).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Could not clear device cache {}: {}",
+                self.directory.display(),
+                err
+            ),
+        })
+    }
+
+    /// Returns the path of the cache file of a device.
+    ///
+    /// The API version and device name are percent-encoded: lowercase ASCII letters, digits,
+    /// '-' and '_' are kept and every other byte is written as '%' followed by its two
+    /// hexadecimal digits. Distinct names thus map to distinct files, also on case-insensitive
+    /// file systems, and cannot leave the cache directory.
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        let encode = |name: &str| -> String {
+            name.bytes()
+                .map(|byte| {
+                    if byte.is_ascii_lowercase()
+                        || byte.is_ascii_digit()
+                        || byte == b'-'
+                        || byte == b'_'
+                    {
+                        (byte as char).to_string()
+                    } else {
+                        format!("%{:02X}", byte)
+                    }
+                })
+                .collect()
+        };
+        let variant = match (key.dev, key.hqs) {
+            (false, false) => "production",
+            (true, false) => "dev",
+            (false, true) => "hqs",
+            (true, true) => "dev_hqs",
+        };
+        self.directory
+            .join(encode(key.api_version))
+            .join(variant)
+            .join(format!("{}.json", encode(key.device_name)))
+    }
+
+    /// Reads the cache file of a device, if present and valid.
+    fn read_entry(&self, key: &CacheKey) -> Option<CacheEntry> {
+        let serialized = std::fs::read_to_string(self.entry_path(key)).ok()?;
+        serde_json::from_str(&serialized).ok()
+    }
+
+    /// Writes the cache file of a device.
+    ///
+    /// The entry is written to a temporary file in the same directory and renamed into place, so
+    /// that concurrent readers never see a partially written file.
+    fn write_entry(&self, key: &CacheKey, entry: &CacheEntry) -> Result<(), RoqoqoBackendError> {
+        let path = self.entry_path(key);
+        let write_error = |err: String| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Could not write device cache file {}: {}",
+                path.display(),
+                err
+            ),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| write_error(err.to_string()))?;
+        }
+        let serialized =
+            serde_json::to_string(entry).map_err(|err| write_error(err.to_string()))?;
+        let mut temporary_name = path.as_os_str().to_os_string();
+        temporary_name.push(format!(
+            ".{}-{}.tmp",
+            std::process::id(),
+            TEMPORARY_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let temporary_path = PathBuf::from(temporary_name);
+        std::fs::write(&temporary_path, serialized)
+            .and_then(|_| std::fs::rename(&temporary_path, &path))
+            .map_err(|err| {
+                let _ = std::fs::remove_file(&temporary_path);
+                write_error(err.to_string())
+            })
+    }
+}
+
+/// Downloads a device definition from the QRyd WebAPI, using the device cache if enabled.
+///
+/// Shared by all functions creating devices from the QRyd WebAPI, so that a device is
+/// always downloaded the same way.
+///
+/// # Arguments
+///
+/// * `device_name` - The name of the device to download.
+/// * `access_token` - The access_token, falling back to the environmental variable `$QRYD_API_TOKEN`.
+/// * `mock_port` - Server port to be used for testing purposes.
+/// * `dev` - The boolean to set the dev header to.
+/// * `api_version` - The version of the QRyd WebAPI to use.
+/// * `cache` - The device cache to use, if enabled.
+///
+/// # Returns
+///
+/// * `TweezerDevice` - The device definition, without any post-processing.
+///
+/// # Errors
+///
+/// * `RoqoqoBackendError`
+pub(crate) fn download_device(
+    device_name: &str,
+    access_token: Option<String>,
+    mock_port: Option<String>,
+    dev: bool,
+    api_version: &str,
+    cache: Option<&DeviceCache>,
+) -> Result<TweezerDevice, RoqoqoBackendError> {
+    let hqs = env::var("QRYD_API_HQS").is_ok();
+    let request = if let Some(port) = mock_port {
+        reqwest::blocking::Client::builder()
+            .build()
+            .map_err(|x| RoqoqoBackendError::NetworkError {
+                msg: format!("Could not create test client {:?}.", x),
+            })?
+            .get(format!("http://127.0.0.1:{}", port))
+            .body(device_name.to_string())
+    } else {
+        let access_token_internal: String = match access_token {
+            Some(s) => s,
+            None => env::var("QRYD_API_TOKEN").map_err(|_| {
+                RoqoqoBackendError::MissingAuthentication {
+                    msg: "QRYD access token is missing.".to_string(),
+                }
+            })?,
+        };
+        let mut request = reqwest::blocking::Client::builder()
+            .https_only(true)
+            .build()
+            .map_err(|x| RoqoqoBackendError::NetworkError {
+                msg: format!("Could not create https client {:?}.", x),
+            })?
+            .get(format!(
+                "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
+                api_version, device_name
+            ))
+            .header("X-API-KEY", access_token_internal);
+        if dev {
+            request = request.header("X-DEV", "?1");
+        }
+        if hqs {
+            request = request.header("X-HQS", "?1");
+        }
+        request
+    };
+    let key = CacheKey {
+        device_name,
+        api_version,
+        dev,
+        hqs,
+    };
+    fetch_device(request, cache, &key)
+}
+
+/// Sends a request for a device definition to the QRyd WebAPI, using the device cache if enabled.
+///
+/// With the cache enabled, a cached definition is revalidated with a conditional request and
+/// reused if it is unchanged. If the QRyd WebAPI is unreachable or fails with a server error,
+/// the cached definition is returned, flagged with `is_offline_fallback()`.
+///
+/// # Arguments
+///
+/// * `request` - The prepared request for the device definition.
+/// * `cache` - The device cache to use, if enabled.
+/// * `key` - The requested device, used as key of the cache.
+///
+/// # Returns
+///
+/// * `TweezerDevice` - The device definition, without any post-processing.
+///
+/// # Errors
+///
+/// * `RoqoqoBackendError`
+pub(crate) fn fetch_device(
+    request: RequestBuilder,
+    cache: Option<&DeviceCache>,
+    key: &CacheKey,
+) -> Result<TweezerDevice, RoqoqoBackendError> {
+    let device_name = key.device_name;
+    let entry = cache.and_then(|cache| cache.read_entry(key));
+    let mut request = request;
+    if let Some(entry) = &entry {
+        if let Some(etag) = &entry.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    let start = std::time::Instant::now();
+    let resp = match request.send() {
+        Ok(resp) => resp,
+        Err(err) => {
+            return match entry {
+                Some(entry) => offline_fallback(&entry, device_name, &format!("{:?}", err)),
+                None => Err(RoqoqoBackendError::NetworkError {
+                    msg: format!("{:?}", err),
+                }),
+            }
+        }
+    };
+    #[cfg(feature = "tracing")]
+    crate::trace_response("get_device", &resp, start);
+
+    let status_code = resp.status();
+    match entry {
+        Some(entry) if status_code == StatusCode::NOT_MODIFIED => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(device_name, "Reusing unchanged cached device definition");
+            parse_device(&entry.body)
+        }
+        _ if status_code == StatusCode::OK => {
+            let header = |name| {
+                resp.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| value.to_string())
+            };
+            let etag = header(ETAG);
+            let last_modified = header(LAST_MODIFIED);
+            let body = resp
+                .text()
+                .map_err(|err| RoqoqoBackendError::NetworkError {
+                    msg: format!("{:?}", err),
+                })?;
+            let device = parse_device(&body)?;
+            if let Some(cache) = cache {
+                let written = cache.write_entry(
+                    key,
+                    &CacheEntry {
+                        etag,
+                        last_modified,
+                        body,
+                    },
+                );
+                #[cfg(feature = "tracing")]
+                if let Err(err) = written {
+                    tracing::warn!(device_name, error = %err, "Could not cache device definition");
+                }
+                #[cfg(not(feature = "tracing"))]
+                let _ = written;
+            }
+            Ok(device)
+        }
+        Some(entry) if status_code.is_server_error() => offline_fallback(
+            &entry,
+            device_name,
+            &format!("HTTP status code {:?}", status_code),
+        ),
+        _ => Err(RoqoqoBackendError::NetworkError {
+            msg: format!(
+                "Request to server failed with HTTP status code {:?}.",
+                status_code
+            ),
+        }),
+    }
+}
+
+/// Returns the cached device definition, flagged as offline fallback.
+fn offline_fallback(
+    entry: &CacheEntry,
+    device_name: &str,
+    reason: &str,
+) -> Result<TweezerDevice, RoqoqoBackendError> {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        device_name,
+        reason,
+        "QRyd WebAPI unreachable, using cached device definition"
+    );
+    let mut device = parse_device(&entry.body).map_err(|err| RoqoqoBackendError::NetworkError {
+        msg: format!(
+            "Request for device {} failed ({}) and its cached definition is unusable: {}",
+            device_name, reason, err
+        ),
+    })?;
+    device.offline_fallback = true;
+    Ok(device)
+}
+
+/// Deserializes a device definition returned by the QRyd WebAPI.
+fn parse_device(body: &str) -> Result<TweezerDevice, RoqoqoBackendError> {
+    serde_json::from_str(body).map_err(|err| RoqoqoBackendError::GenericError {
+        msg: format!("Failed deserialization of the device definition: {}", err),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wiremock::matchers::{header, header_exists, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Returns a cache in a fresh directory, unique to the calling test.
+    fn test_cache(name: &str) -> DeviceCache {
+        let cache = DeviceCache::new(std::env::temp_dir().join(format!(
+            "roqoqo_qryd_cache_{}_{}",
+            name,
+            std::process::id()
+        )));
+        cache.clear().unwrap();
+        cache
+    }
+
+    /// Key of the production definition of the device "test_device".
+    const TEST_KEY: CacheKey = CacheKey {
+        device_name: "test_device",
+        api_version: "v1_1",
+        dev: false,
+        hqs: false,
+    };
+
+    /// Fetches the device "test_device" from the given port in a blocking task.
+    async fn fetch(
+        port: u16,
+        cache: Option<DeviceCache>,
+    ) -> Result<TweezerDevice, RoqoqoBackendError> {
+        fetch_with_key(port, cache, TEST_KEY).await
+    }
+
+    /// Fetches a device from the given port in a blocking task.
+    async fn fetch_with_key(
+        port: u16,
+        cache: Option<DeviceCache>,
+        key: CacheKey<'static>,
+    ) -> Result<TweezerDevice, RoqoqoBackendError> {
+        tokio::task::spawn_blocking(move || {
+            let request =
+                reqwest::blocking::Client::new().get(format!("http://127.0.0.1:{}", port));
+            fetch_device(request, cache.as_ref(), &key)
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Returns a port nothing is listening on.
+    fn closed_port() -> u16 {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    /// Test fetching a device with conditional requests and offline fallback
+    #[tokio::test]
+    async fn conditional_requests_and_fallback() {
+        let cache = test_cache("conditional");
+        let mut device = TweezerDevice::new(None, None, None);
+        device.add_layout("default").unwrap();
+        let server = MockServer::start().await;
+        let port = server.address().port();
+
+        // Initial download fills the cache
+        let _mock = Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .insert_header("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT")
+                    .set_body_json(&device),
+            )
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        let fetched = fetch(port, Some(cache.clone())).await.unwrap();
+        assert_eq!(fetched, device);
+        assert!(!fetched.is_offline_fallback());
+        assert_eq!(
+            cache.cached_device("test_device", "v1_1", false, false),
+            Some(device.clone())
+        );
+        // The temporary file has been renamed into place
+        let entry_path = cache.entry_path(&CacheKey {
+            device_name: "test_device",
+            api_version: "v1_1",
+            dev: false,
+            hqs: false,
+        });
+        let entries: Vec<PathBuf> = std::fs::read_dir(entry_path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(entries, vec![entry_path]);
+        drop(_mock);
+
+        // Unchanged definition is revalidated and reused
+        let _mock = Mock::given(method("GET"))
+            .and(header("If-None-Match", "\"v1\""))
+            .and(header_exists("If-Modified-Since"))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        let fetched = fetch(port, Some(cache.clone())).await.unwrap();
+        assert_eq!(fetched, device);
+        assert!(!fetched.is_offline_fallback());
+        drop(_mock);
+
+        // Server errors fall back to the cached definition
+        let _mock = Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        let fetched = fetch(port, Some(cache.clone())).await.unwrap();
+        assert!(fetched.is_offline_fallback());
+        drop(_mock);
+
+        // Client errors are not hidden by the cache
+        let _mock = Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        assert!(fetch(port, Some(cache.clone())).await.is_err());
+        drop(_mock);
+
+        // Unreachable API falls back to the cached definition
+        let fetched = fetch(closed_port(), Some(cache.clone())).await.unwrap();
+        assert_eq!(fetched, device);
+        assert!(fetched.is_offline_fallback());
+        assert!(fetch(closed_port(), None).await.is_err());
+
+        cache.clear().unwrap();
+        assert!(!cache.directory().exists());
+        assert!(fetch(closed_port(), Some(cache)).await.is_err());
+    }
+
+    /// Test that updated definitions replace the cached ones
+    #[tokio::test]
+    async fn updated_definition() {
+        let cache = test_cache("updated");
+        let server = MockServer::start().await;
+        let port = server.address().port();
+        let old_device = TweezerDevice::new(Some(1), None, None);
+        let new_device = TweezerDevice::new(Some(2), None, None);
+
+        let _mock = Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_json(&old_device),
+            )
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        fetch(port, Some(cache.clone())).await.unwrap();
+        drop(_mock);
+
+        let _mock = Mock::given(method("GET"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v2\"")
+                    .set_body_json(&new_device),
+            )
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        let fetched = fetch(port, Some(cache.clone())).await.unwrap();
+        assert_eq!(fetched, new_device);
+        assert_eq!(
+            cache.cached_device("test_device", "v1_1", false, false),
+            Some(new_device)
+        );
+        assert_eq!(
+            cache.read_entry(&TEST_KEY).unwrap().etag,
+            Some("\"v2\"".to_string())
+        );
+        drop(_mock);
+        cache.clear().unwrap();
+    }
+
+    /// Test that the dev and HQS definitions of a device are cached separately
+    #[tokio::test]
+    async fn variants_cached_separately() {
+        let cache = test_cache("variants");
+        let server = MockServer::start().await;
+        let port = server.address().port();
+        let production_device = TweezerDevice::new(Some(1), None, None);
+        let dev_device = TweezerDevice::new(Some(2), None, None);
+        let dev_key = CacheKey {
+            dev: true,
+            ..TEST_KEY
+        };
+
+        let _mock = Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&production_device))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        fetch(port, Some(cache.clone())).await.unwrap();
+        drop(_mock);
+        let _mock = Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&dev_device))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+        fetch_with_key(port, Some(cache.clone()), dev_key.clone())
+            .await
+            .unwrap();
+        drop(_mock);
+
+        assert_eq!(
+            cache.cached_device("test_device", "v1_1", false, false),
+            Some(production_device.clone())
+        );
+        assert_eq!(
+            cache.cached_device("test_device", "v1_1", true, false),
+            Some(dev_device.clone())
+        );
+        assert_eq!(
+            cache.cached_device("test_device", "v1_1", false, true),
+            None
+        );
+
+        // The offline fallback of a request returns the definition of its own variant
+        let fetched = fetch(closed_port(), Some(cache.clone())).await.unwrap();
+        assert!(fetched.is_offline_fallback());
+        assert_eq!(fetched, production_device);
+        let fetched = fetch_with_key(closed_port(), Some(cache.clone()), dev_key)
+            .await
+            .unwrap();
+        assert!(fetched.is_offline_fallback());
+        assert_eq!(fetched, dev_device);
+        let hqs_key = CacheKey {
+            hqs: true,
+            ..TEST_KEY
+        };
+        assert!(fetch_with_key(closed_port(), Some(cache.clone()), hqs_key)
+            .await
+            .is_err());
+        cache.clear().unwrap();
+    }
+
+    /// Test the download options
+    #[test]
+    fn download_options() {
+        assert_eq!(DownloadOptions::default().cache, None);
+        assert_eq!(
+            DownloadOptions::default().with_cache_dir("cache").cache,
+            Some(DeviceCache::new("cache"))
+        );
+        assert!(!DownloadOptions::default().validate);
+        assert!(DownloadOptions::default().with_validation(true).validate);
+    }
+
+    /// Test the parsing of `$QRYD_VALIDATE_DEVICE`
+    #[test]
+    fn validation_from_env_value() {
+        assert!(!validation_requested(None));
+        assert!(!validation_requested(Some("")));
+        assert!(!validation_requested(Some("0")));
+        assert!(validation_requested(Some("1")));
+        assert!(validation_requested(Some("true")));
+    }
+
+    /// Test that device names map to distinct files inside the cache directory
+    #[test]
+    fn entry_path() {
+        let cache = DeviceCache::new("cache");
+        assert_eq!(
+            cache.entry_path(&CacheKey {
+                device_name: "qryd_emulator",
+                ..TEST_KEY
+            }),
+            Path::new("cache")
+                .join("v1_1")
+                .join("production")
+                .join("qryd_emulator.json")
+        );
+        assert_eq!(
+            cache.entry_path(&CacheKey {
+                device_name: "qryd_emulator",
+                dev: true,
+                hqs: true,
+                ..TEST_KEY
+            }),
+            Path::new("cache")
+                .join("v1_1")
+                .join("dev_hqs")
+                .join("qryd_emulator.json")
+        );
+        assert_eq!(
+            cache.entry_path(&CacheKey {
+                device_name: "../../etc/passwd",
+                ..TEST_KEY
+            }),
+            Path::new("cache")
+                .join("v1_1")
+                .join("production")
+                .join("%2E%2E%2F%2E%2E%2Fetc%2Fpasswd.json")
+        );
+        // Names differing only in replaced characters or in case do not collide
+        let paths: std::collections::HashSet<PathBuf> = [
+            "qryd.emulator",
+            "qryd_emulator",
+            "qryd%2Eemulator",
+            "QRyd_emulator",
+        ]
+        .into_iter()
+        .map(|device_name| {
+            cache.entry_path(&CacheKey {
+                device_name,
+                ..TEST_KEY
+            })
+        })
+        .collect();
+        assert_eq!(paths.len(), 4);
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Device snapshots
+//!
+//! Provides offline copies of device definitions downloaded from the QRyd WebAPI,
+//! so that devices can be used without network access.
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use roqoqo::RoqoqoBackendError;
+
+use crate::{CombinedDevice, EmulatorDevice, TweezerDevice};
+
+/// Version of the snapshot file format written by this version of roqoqo-qryd.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Provenance information stored alongside a device snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DeviceSnapshotMetadata {
+    /// Version of the snapshot file format.
+    pub format_version: u32,
+    /// The name of the device on the QRyd WebAPI.
+    pub device_name: String,
+    /// The version of the QRyd WebAPI the device was downloaded from.
+    pub api_version: String,
+    /// Time of the download, in seconds since the UNIX epoch.
+    pub downloaded_at: u64,
+    /// Version of roqoqo-qryd that created the snapshot.
+    pub roqoqo_qryd_version: String,
+}
+
+/// Offline copy of a device definition of the QRyd WebAPI.
+///
+/// The device is stored as it was downloaded. The post-processing of `from_api`
+/// (switching to the default layout, overwriting the seed) is applied when loading it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeviceSnapshot {
+    /// Provenance information of the snapshot.
+    pub metadata: DeviceSnapshotMetadata,
+    /// The device definition, as returned by the QRyd WebAPI.
+    pub device: TweezerDevice,
+}
+
+impl DeviceSnapshot {
+    /// Creates a new DeviceSnapshot of a device definition, timestamped with the current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device definition to store.
+    /// * `device_name` - The name of the device on the QRyd WebAPI.
+    /// * `api_version` - The version of the QRyd WebAPI the device was downloaded from.
+    ///
+    /// # Returns
+    ///
+    /// * `DeviceSnapshot` - The new DeviceSnapshot instance.
+    pub fn new(device: TweezerDevice, device_name: String, api_version: String) -> Self {
+        let downloaded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        DeviceSnapshot {
+            metadata: DeviceSnapshotMetadata {
+                format_version: SNAPSHOT_FORMAT_VERSION,
+                device_name,
+                api_version,
+                downloaded_at,
+                roqoqo_qryd_version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            device,
+        }
+    }
+
+    /// Downloads a device definition from the QRyd WebAPI into a new DeviceSnapshot.
+    ///
+    /// The device is requested the same way as in `TweezerDevice::from_api`, bypassing the
+    /// device cache.
+    ///
+    /// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_name` - The name of the device to download. Defaults to "qryd_emulator".
+    /// * `access_token` - An access_token is required to access QRYD hardware and emulators.
+    ///   The access_token can either be given as an argument here or set via the environmental
+    ///   variable `$QRYD_API_TOKEN`.
+    /// * `mock_port` - Server port to be used for testing purposes.
+    /// * `dev` - The boolean to set the dev header to.
+    /// * `api_version` - The version of the QRYD API to use. Defaults to "v1_1".
+    ///
+    /// # Returns
+    ///
+    /// * `DeviceSnapshot` - The snapshot of the downloaded device definition.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError`
+    #[cfg(feature = "web-api")]
+    pub fn from_api(
+        device_name: Option<String>,
+        access_token: Option<String>,
+        mock_port: Option<String>,
+        dev: Option<bool>,
+        api_version: Option<String>,
+    ) -> Result<Self, RoqoqoBackendError> {
+        let device_name = device_name.unwrap_or_else(|| String::from("qryd_emulator"));
+        let api_version = api_version.unwrap_or_else(|| String::from("v1_1"));
+        let device = crate::device_cache::download_device(
+            &device_name,
+            access_token,
+            mock_port,
+            dev.unwrap_or(false),
+            &api_version,
+            None,
+        )?;
+        Ok(DeviceSnapshot::new(device, device_name, api_version))
+    }
+
+    /// Writes the DeviceSnapshot to a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to write. An existing file is overwritten.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError::GenericError` - The snapshot could not be serialized or written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), RoqoqoBackendError> {
+        let serialized =
+            serde_json::to_string_pretty(self).map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not serialize device snapshot: {}", err),
+            })?;
+        std::fs::write(path.as_ref(), serialized).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Could not write device snapshot to {}: {}",
+                path.as_ref().display(),
+                err
+            ),
+        })
+    }
+
+    /// Reads a DeviceSnapshot from a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to read.
+    ///
+    /// # Returns
+    ///
+    /// * `DeviceSnapshot` - The snapshot stored in the file.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError::GenericError` - The file could not be read, is not a valid snapshot
+    ///   or was written with a newer snapshot format.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, RoqoqoBackendError> {
+        let serialized = std::fs::read_to_string(path.as_ref()).map_err(|err| {
+            RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Could not read device snapshot from {}: {}",
+                    path.as_ref().display(),
+                    err
+                ),
+            }
+        })?;
+        let snapshot: DeviceSnapshot =
+            serde_json::from_str(&serialized).map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not deserialize device snapshot: {}", err),
+            })?;
+        if snapshot.metadata.format_version > SNAPSHOT_FORMAT_VERSION {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Device snapshot format version {} is not supported, the latest supported version is {}.",
+                    snapshot.metadata.format_version, SNAPSHOT_FORMAT_VERSION
+                ),
+            });
+        }
+        Ok(snapshot)
+    }
+
+    /// Returns the TweezerDevice stored in the snapshot.
+    ///
+    /// Applies the same post-processing as `TweezerDevice::from_api`, without validation.
+    /// Use `TweezerDevice::validate` to check the Layouts of the stored device.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Optionally overwrite seed value from the stored device instance.
+    ///
+    /// # Returns
+    ///
+    /// * `TweezerDevice` - The stored device, switched to its default layout.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError` - The default layout of the stored device is not in its Layout register.
+    pub fn to_tweezer_device(
+        &self,
+        seed: Option<usize>,
+    ) -> Result<TweezerDevice, RoqoqoBackendError> {
+        let mut device = self.device.clone();
+        device.prepare_downloaded(self.metadata.device_name.clone(), seed, false)?;
+        Ok(device)
+    }
+
+    /// Returns the EmulatorDevice stored in the snapshot.
+    ///
+    /// Applies the same post-processing as `EmulatorDevice::from_api`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Optionally overwrite seed value from the stored device instance.
+    ///
+    /// # Returns
+    ///
+    /// * `EmulatorDevice` - The stored device.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError` - The stored device is incompatible with EmulatorDevice.
+    pub fn to_emulator_device(
+        &self,
+        seed: Option<usize>,
+    ) -> Result<EmulatorDevice, RoqoqoBackendError> {
+        if self.device.layout_register.is_some() {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The device snapshot contains a TweezerDevice instance incompatible with EmulatorDevice.".to_string(),
+            });
+        }
+        EmulatorDevice::from_downloaded(
+            self.device.clone(),
+            self.metadata.device_name.clone(),
+            seed,
+            false,
+        )
+    }
+
+    /// Returns the device stored in the snapshot as a TweezerDevice or an EmulatorDevice.
+    ///
+    /// Applies the same post-processing as `device_from_api`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Optionally overwrite seed value from the stored device instance.
+    ///
+    /// # Returns
+    ///
+    /// * `CombinedDevice` - The stored device, with variant TweezerDevice or EmulatorDevice
+    ///   depending on the stored information.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError`
+    pub fn to_combined_device(
+        &self,
+        seed: Option<usize>,
+    ) -> Result<CombinedDevice, RoqoqoBackendError> {
+        if self.device.available_gates.is_some() {
+            Ok(CombinedDevice::Emulator(EmulatorDevice::from_downloaded(
+                self.device.clone(),
+                self.metadata.device_name.clone(),
+                seed,
+                false,
+            )?))
+        } else {
+            Ok(CombinedDevice::Tweezer(self.to_tweezer_device(seed)?))
+        }
+    }
+}
+
+/// Creates a new TweezerDevice or EmulatorDevice instance from a device snapshot file.
+///
+/// The offline counterpart of `device_from_api`.
+///
+/// # Arguments
+///
+/// * `path` - The path of the snapshot file, as written by `DeviceSnapshot::save`.
+/// * `seed` - Optionally overwrite seed value from the stored device instance.
+///
+/// # Returns
+///
+/// * `CombinedDevice` - The new CombinedDevice instance, with variant TweezerDevice or
+///   EmulatorDevice depending on the stored information.
+///
+/// # Errors
+///
+/// * `RoqoqoBackendError`
+pub fn device_from_snapshot<P: AsRef<Path>>(
+    path: P,
+    seed: Option<usize>,
+) -> Result<CombinedDevice, RoqoqoBackendError> {
+    DeviceSnapshot::load(path)?.to_combined_device(seed)
+}
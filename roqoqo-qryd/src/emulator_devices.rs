@@ -19,13 +19,17 @@
 use bincode::deserialize;
 use ndarray::Array2;
 use std::collections::HashMap;
-use std::env;
 
 use roqoqo::devices::{Device, GenericDevice};
 use roqoqo::operations::*;
 use roqoqo::RoqoqoBackendError;
 
-use crate::{tweezer_devices::TweezerDevice, PragmaDeactivateQRydQubit, PragmaShiftQubitsTweezers};
+#[cfg(feature = "web-api")]
+use crate::device_cache::{download_device, DownloadOptions};
+use crate::{
+    tweezer_devices::TweezerDevice, DeviceSnapshot, PragmaDeactivateQRydQubit,
+    PragmaShiftQubitsTweezers,
+};
 
 /// Emulator Device
 ///
@@ -70,6 +74,9 @@ impl EmulatorDevice {
                 allow_reset: false,
                 device_name: String::from("qryd_tweezer_device"),
                 available_gates: Some(vec![]),
+                tweezer_positions: HashMap::new(),
+                offline_fallback: false,
+                layout_indices: Default::default(),
             },
         }
     }
@@ -78,6 +85,9 @@ impl EmulatorDevice {
     ///
     /// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
     ///
+    /// Uses the `DownloadOptions` configured via environmental variables, see
+    /// `from_api_with_options` to set them explicitly.
+    ///
     /// # Arguments
     ///
     /// * `device_name` - The name of the device to instantiate. Defaults to "qryd_emulator".
@@ -105,117 +115,155 @@ impl EmulatorDevice {
         dev: Option<bool>,
         api_version: Option<String>,
     ) -> Result<Self, RoqoqoBackendError> {
-        // Preparing variables
+        EmulatorDevice::from_api_with_options(
+            device_name,
+            access_token,
+            mock_port,
+            seed,
+            dev,
+            api_version,
+            &DownloadOptions::from_env(),
+        )
+    }
+
+    /// Creates a new EmulatorDevice instance containing populated tweezer data, with explicit options.
+    ///
+    /// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_name` - The name of the device to instantiate. Defaults to "qryd_emulator".
+    /// * `access_token` - An access_token is required to access QRYD hardware and emulators.
+    ///                    The access_token can either be given as an argument here
+    ///                         or set via the environmental variable `$QRYD_API_TOKEN`.
+    /// * `mock_port` - The address of the Mock server, used for testing purposes.
+    /// * `seed` - Optionally overwrite seed value from downloaded device instance.
+    /// * `dev` - The boolean to set the dev header to.
+    /// * `api_version` - The version of the QRYD API to use. Defaults to "v1_1".
+    /// * `options` - The options of the download, e.g. the device cache to use.
+    ///
+    /// # Returns
+    ///
+    /// * `EmulatorDevice` - The new EmulatorDevice instance with populated tweezer data.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError`
+    #[cfg(feature = "web-api")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            name = "from_api",
+            skip_all,
+            fields(
+                device_name = ?device_name,
+                api_version = ?api_version,
+                dev = ?dev,
+                mock = mock_port.is_some(),
+                cache = options.cache.is_some()
+            )
+        )
+    )]
+    pub fn from_api_with_options(
+        device_name: Option<String>,
+        access_token: Option<String>,
+        mock_port: Option<String>,
+        seed: Option<usize>,
+        dev: Option<bool>,
+        api_version: Option<String>,
+        options: &DownloadOptions,
+    ) -> Result<Self, RoqoqoBackendError> {
         let device_name_internal = device_name.unwrap_or_else(|| String::from("qryd_emulator"));
         let api_version = api_version.unwrap_or_else(|| String::from("v1_1"));
-        let dev = dev.unwrap_or(false);
-        let hqs_env_var = env::var("QRYD_API_HQS").is_ok();
-        let access_token_internal: String = if mock_port.is_some() {
-            "".to_string()
-        } else {
-            match access_token {
-                Some(s) => s,
-                None => env::var("QRYD_API_TOKEN").map_err(|_| {
-                    RoqoqoBackendError::MissingAuthentication {
-                        msg: "QRYD access token is missing.".to_string(),
-                    }
-                })?,
-            }
-        };
+        let device = download_device(
+            &device_name_internal,
+            access_token,
+            mock_port,
+            dev.unwrap_or(false),
+            &api_version,
+            options.cache.as_ref(),
+        )?;
+        if device.layout_register.is_some() {
+            return Err(RoqoqoBackendError::NetworkError {
+                msg: "`.from_api()` pulled a TweezerDevice instance incompatible with EmulatorDevice.".to_string(),
+            });
+        }
+        EmulatorDevice::from_downloaded(device, device_name_internal, seed, options.validate)
+    }
 
-        // Client setup
-        let client = if mock_port.is_some() {
-            reqwest::blocking::Client::builder().build().map_err(|x| {
-                RoqoqoBackendError::NetworkError {
-                    msg: format!("Could not create test client {:?}.", x),
-                }
-            })?
-        } else {
-            reqwest::blocking::Client::builder()
-                .https_only(true)
-                .build()
-                .map_err(|x| RoqoqoBackendError::NetworkError {
-                    msg: format!("Could not create https client {:?}.", x),
-                })?
-        };
-
-        // Response gathering
-        let resp = if let Some(port) = mock_port {
-            client
-                .get(format!("http://127.0.0.1:{}", port))
-                .body(device_name_internal.clone())
-                .send()
-                .map_err(|e| RoqoqoBackendError::NetworkError {
-                    msg: format!("{:?}", e),
-                })?
-        } else {
-            match (dev, hqs_env_var) {
-                (true, true) => client
-                    .get(format!(
-                        "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                        api_version, device_name_internal
-                    ))
-                    .header("X-API-KEY", access_token_internal)
-                    .header("X-DEV", "?1")
-                    .header("X-HQS", "?1")
-                    .send()
-                    .map_err(|e| RoqoqoBackendError::NetworkError {
-                        msg: format!("{:?}", e),
-                    })?,
-                (true, false) => client
-                    .get(format!(
-                        "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                        api_version, device_name_internal
-                    ))
-                    .header("X-API-KEY", access_token_internal)
-                    .header("X-DEV", "?1")
-                    .send()
-                    .map_err(|e| RoqoqoBackendError::NetworkError {
-                        msg: format!("{:?}", e),
-                    })?,
-                (false, true) => client
-                    .get(format!(
-                        "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                        api_version, device_name_internal
-                    ))
-                    .header("X-API-KEY", access_token_internal)
-                    .header("X-HQS", "?1")
-                    .send()
-                    .map_err(|e| RoqoqoBackendError::NetworkError {
-                        msg: format!("{:?}", e),
-                    })?,
-                (false, false) => client
-                    .get(format!(
-                        "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                        api_version, device_name_internal
-                    ))
-                    .header("X-API-KEY", access_token_internal)
-                    .send()
-                    .map_err(|e| RoqoqoBackendError::NetworkError {
-                        msg: format!("{:?}", e),
-                    })?,
-            }
-        };
-
-        // Response handling
-        let status_code = resp.status();
-        if status_code == reqwest::StatusCode::OK {
-            let mut device = resp.json::<TweezerDevice>().unwrap();
-            if device.layout_register.is_some() {
-                return Err(RoqoqoBackendError::NetworkError {
-                    msg: "`.from_api()` pulled a TweezerDevice instance incompatible with EmulatorDevice.".to_string(),
-                });
-            }
-            if let Some(new_seed) = seed {
-                device.seed = Some(new_seed);
-            }
-            device.device_name = device_name_internal;
-            Ok(EmulatorDevice { internal: device })
+    /// Creates a new EmulatorDevice instance from a device snapshot file.
+    ///
+    /// The offline counterpart of `from_api`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the snapshot file, as written by `DeviceSnapshot::save`.
+    /// * `seed` - Optionally overwrite seed value from the stored device instance.
+    ///
+    /// # Returns
+    ///
+    /// * `EmulatorDevice` - The new EmulatorDevice instance.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError`
+    pub fn from_snapshot<P: AsRef<std::path::Path>>(
+        path: P,
+        seed: Option<usize>,
+    ) -> Result<Self, RoqoqoBackendError> {
+        DeviceSnapshot::load(path)?.to_emulator_device(seed)
+    }
+
+    /// Applies the post-processing to a device definition downloaded from the QRyd WebAPI.
+    ///
+    /// Validates the available gates if requested and sets the device name and the seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The downloaded device definition.
+    /// * `device_name` - The name of the device on the QRyd WebAPI.
+    /// * `seed` - Optionally overwrite seed value from downloaded device instance.
+    /// * `validate` - Whether to return the issues found by `validate()` as an error.
+    pub(crate) fn from_downloaded(
+        mut device: TweezerDevice,
+        device_name: String,
+        seed: Option<usize>,
+        validate: bool,
+    ) -> Result<Self, RoqoqoBackendError> {
+        if let Some(new_seed) = seed {
+            device.seed = Some(new_seed);
+        }
+        device.device_name = device_name;
+        let device = EmulatorDevice { internal: device };
+        if validate {
+            device.validate()?;
+        }
+        Ok(device)
+    }
+
+    /// Checks that all available gates of the device are known gates.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - All available gates are known.
+    /// * `Err(RoqoqoBackendError)` - The error listing the unknown gates.
+    pub fn validate(&self) -> Result<(), RoqoqoBackendError> {
+        let unknown_gates: Vec<&str> = self
+            .internal
+            .available_gates
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|hqslang| !AVAILABLE_GATES_HQSLANG.contains(hqslang))
+            .collect();
+        if unknown_gates.is_empty() {
+            Ok(())
         } else {
-            Err(RoqoqoBackendError::NetworkError {
+            Err(RoqoqoBackendError::GenericError {
                 msg: format!(
-                    "Request to server failed with HTTP status code {:?}.",
-                    status_code
+                    "The available gates of the device contain unknown gates: {}.",
+                    unknown_gates.join(", ")
                 ),
             })
         }
@@ -430,6 +478,17 @@ impl EmulatorDevice {
         self.internal.seed
     }
 
+    /// Returns whether the device is a cached copy, used because the QRyd WebAPI was unreachable.
+    ///
+    /// Only set by `from_api` and `device_from_api` with the device cache enabled.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the device definition may be outdated.
+    pub fn is_offline_fallback(&self) -> bool {
+        self.internal.offline_fallback
+    }
+
     /// Returns the backend associated with the device.
     pub fn qrydbackend(&self) -> String {
         self.internal.device_name.clone()
@@ -492,6 +551,10 @@ impl Device for EmulatorDevice {
         vec![]
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self, operation))
+    )]
     fn change_device(&mut self, hqslang: &str, operation: &[u8]) -> Result<(), RoqoqoBackendError> {
         match hqslang {
             "PragmaChangeQRydLayout" => Err(RoqoqoBackendError::GenericError {
@@ -529,18 +592,14 @@ impl Device for EmulatorDevice {
                                 msg: "The device qubit -> tweezer mapping is empty: no qubits to shift.".to_string(),
                             });
                         }
-                        // Start applying the shifts
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(
+                            shifts = ?pragma.shifts,
+                            "Applying PragmaShiftQubitsTweezers"
+                        );
+                        // Start applying the shifts, all or none of them
                         if let Some(map) = &mut self.internal.qubit_to_tweezer {
-                            for (shift_start, shift_end) in &pragma.shifts {
-                                if let Some(qubit_to_move) =
-                                    map.iter()
-                                        .find_map(|(&qbt, &twz)| if twz == *shift_start { Some(qbt) } else { None })
-                                {
-                                    // Move the qubit into the new tweezer
-                                    map.remove(&qubit_to_move);
-                                    map.insert(qubit_to_move, *shift_end);
-                                }
-                            }
+                            map.apply_shifts(&pragma.shifts)?;
                         }
                         Ok(())
                     }
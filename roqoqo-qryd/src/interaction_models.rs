@@ -0,0 +1,281 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Interaction models
+//!
+//! Models deriving the availability, gate time and fidelity of multi-qubit gates
+//! from the distances between tweezers.
+
+use std::collections::HashMap;
+
+use roqoqo::RoqoqoBackendError;
+
+use crate::{ALLOWED_NATIVE_THREE_QUBIT_GATES, ALLOWED_NATIVE_TWO_QUBIT_GATES};
+
+/// Distance-based model of the interaction between atoms in tweezers.
+///
+/// Distances are given in micrometers.
+pub trait InteractionModel {
+    /// Returns the gate time of a two-qubit gate between tweezers at a given distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the two-qubit gate.
+    /// * `distance` - The distance between the tweezers.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The gate time.
+    /// * `None` - The gate is not available at the given distance.
+    fn two_qubit_gate_time(&self, hqslang: &str, distance: f64) -> Option<f64>;
+
+    /// Returns the fidelity of a two-qubit gate between tweezers at a given distance.
+    ///
+    /// Defaults to a perfect fidelity wherever the gate is available.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the two-qubit gate.
+    /// * `distance` - The distance between the tweezers.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The gate fidelity.
+    /// * `None` - The gate is not available at the given distance.
+    fn two_qubit_gate_fidelity(&self, hqslang: &str, distance: f64) -> Option<f64> {
+        self.two_qubit_gate_time(hqslang, distance).map(|_| 1.0)
+    }
+
+    /// Returns the gate time of a three-qubit gate between tweezers at given distances.
+    ///
+    /// Defaults to unavailable.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the three-qubit gate.
+    /// * `distances` - The distances between the three pairs of tweezers.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The gate time.
+    /// * `None` - The gate is not available at the given distances.
+    fn three_qubit_gate_time(&self, _hqslang: &str, _distances: [f64; 3]) -> Option<f64> {
+        None
+    }
+}
+
+/// Interaction model with a hard distance cut-off and a gate time increasing with the square of the distance.
+///
+/// This is the example model of the `FirstDevice` prototype, supporting only
+/// PhaseShiftedControlledZ and PhaseShiftedControlledPhase gates.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CutoffInteractionModel {
+    /// The distance above which two-qubit gates are not possible.
+    pub cutoff: f64,
+    /// The gate time per squared distance.
+    pub gate_time_coefficient: f64,
+}
+
+impl CutoffInteractionModel {
+    /// Creates a new CutoffInteractionModel.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The distance above which two-qubit gates are not possible.
+    /// * `gate_time_coefficient` - The gate time per squared distance.
+    ///
+    /// # Returns
+    ///
+    /// * `CutoffInteractionModel` - The new CutoffInteractionModel instance.
+    pub fn new(cutoff: f64, gate_time_coefficient: f64) -> Self {
+        Self {
+            cutoff,
+            gate_time_coefficient,
+        }
+    }
+}
+
+impl InteractionModel for CutoffInteractionModel {
+    fn two_qubit_gate_time(&self, hqslang: &str, distance: f64) -> Option<f64> {
+        match hqslang {
+            "PhaseShiftedControlledZ" | "PhaseShiftedControlledPhase" => (),
+            _ => return None,
+        }
+        if distance > self.cutoff {
+            None
+        } else {
+            Some(self.gate_time_coefficient * distance.powi(2))
+        }
+    }
+}
+
+/// Interaction model based on the Rydberg blockade with a van der Waals interaction C6 / r^6.
+///
+/// A gate is available if the tweezers are within the blockade radius. Inside the blockade
+/// radius the imperfect blockade is described to leading order by the leakage (Ω / V(r))^2,
+/// with Ω the Rabi frequency and V(r) = C6 / r^6 the interaction strength. The leakage
+/// lengthens the gate time by a factor (1 + leakage) and reduces the fidelity by a
+/// factor (1 - leakage / 2).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BlockadeInteractionModel {
+    /// The C6 coefficient of the van der Waals interaction in MHz µm^6.
+    pub c6_coefficient: f64,
+    /// The Rabi frequency of the Rydberg excitation in MHz.
+    pub rabi_frequency: f64,
+    /// The blockade radius in µm, overriding the radius derived from C6 and the Rabi frequency.
+    blockade_radius: Option<f64>,
+    /// The gate time of each supported two- and three-qubit gate in the limit of perfect blockade.
+    pub gate_times: HashMap<String, f64>,
+    /// The gate fidelity in the limit of perfect blockade.
+    pub gate_fidelity: f64,
+}
+
+impl BlockadeInteractionModel {
+    /// Creates a new BlockadeInteractionModel.
+    ///
+    /// # Arguments
+    ///
+    /// * `c6_coefficient` - The C6 coefficient of the van der Waals interaction in MHz µm^6.
+    /// * `rabi_frequency` - The Rabi frequency of the Rydberg excitation in MHz.
+    /// * `gate_times` - The gate time of each supported two- and three-qubit gate in the limit of perfect blockade.
+    /// * `gate_fidelity` - The gate fidelity in the limit of perfect blockade.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BlockadeInteractionModel)` - The new BlockadeInteractionModel instance.
+    /// * `Err(RoqoqoBackendError)` - A parameter is out of range.
+    pub fn new(
+        c6_coefficient: f64,
+        rabi_frequency: f64,
+        gate_times: HashMap<String, f64>,
+        gate_fidelity: f64,
+    ) -> Result<Self, RoqoqoBackendError> {
+        if !(c6_coefficient.is_finite() && c6_coefficient > 0.0) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The C6 coefficient must be positive, got {}.",
+                    c6_coefficient
+                ),
+            });
+        }
+        if !(rabi_frequency.is_finite() && rabi_frequency > 0.0) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The Rabi frequency must be positive, got {}.",
+                    rabi_frequency
+                ),
+            });
+        }
+        if !(0.0..=1.0).contains(&gate_fidelity) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The gate fidelity must be between 0 and 1, got {}.",
+                    gate_fidelity
+                ),
+            });
+        }
+        Ok(Self {
+            c6_coefficient,
+            rabi_frequency,
+            blockade_radius: None,
+            gate_times,
+            gate_fidelity,
+        })
+    }
+
+    /// Sets a blockade radius overriding the radius derived from C6 and the Rabi frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `blockade_radius` - The blockade radius in µm.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The blockade radius has been set.
+    /// * `Err(RoqoqoBackendError)` - The blockade radius is not positive.
+    pub fn set_blockade_radius(&mut self, blockade_radius: f64) -> Result<(), RoqoqoBackendError> {
+        if !(blockade_radius.is_finite() && blockade_radius > 0.0) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The blockade radius must be positive, got {}.",
+                    blockade_radius
+                ),
+            });
+        }
+        self.blockade_radius = Some(blockade_radius);
+        Ok(())
+    }
+
+    /// Returns the blockade radius.
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The set blockade radius, or (C6 / Ω)^(1/6) if none is set.
+    pub fn blockade_radius(&self) -> f64 {
+        self.blockade_radius
+            .unwrap_or_else(|| (self.c6_coefficient / self.rabi_frequency).powf(1.0 / 6.0))
+    }
+
+    /// Returns the interaction strength V(r) = C6 / r^6 in MHz.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The distance between the atoms in µm.
+    pub fn interaction_strength(&self, distance: f64) -> f64 {
+        self.c6_coefficient / distance.powi(6)
+    }
+
+    /// Returns the gate time of a two-qubit gate in the limit of perfect blockade, None for
+    /// gates that are not native two-qubit gates or not supported by the model.
+    fn two_qubit_gate_time_limit(&self, hqslang: &str) -> Option<&f64> {
+        if !ALLOWED_NATIVE_TWO_QUBIT_GATES.contains(&hqslang) {
+            return None;
+        }
+        self.gate_times.get(hqslang)
+    }
+
+    /// Returns the leakage (Ω / V(r))^2 of the blockade, None outside the blockade radius.
+    fn leakage(&self, distance: f64) -> Option<f64> {
+        if distance > self.blockade_radius() {
+            None
+        } else {
+            Some((self.rabi_frequency / self.interaction_strength(distance)).powi(2))
+        }
+    }
+}
+
+impl InteractionModel for BlockadeInteractionModel {
+    fn two_qubit_gate_time(&self, hqslang: &str, distance: f64) -> Option<f64> {
+        let gate_time = self.two_qubit_gate_time_limit(hqslang)?;
+        self.leakage(distance)
+            .map(|leakage| gate_time * (1.0 + leakage))
+    }
+
+    fn two_qubit_gate_fidelity(&self, hqslang: &str, distance: f64) -> Option<f64> {
+        self.two_qubit_gate_time_limit(hqslang)?;
+        self.leakage(distance)
+            .map(|leakage| self.gate_fidelity * (1.0 - leakage / 2.0).max(0.0))
+    }
+
+    fn three_qubit_gate_time(&self, hqslang: &str, distances: [f64; 3]) -> Option<f64> {
+        if !ALLOWED_NATIVE_THREE_QUBIT_GATES.contains(&hqslang) {
+            return None;
+        }
+        let gate_time = self.gate_times.get(hqslang)?;
+        let mut max_leakage: f64 = 0.0;
+        for distance in distances {
+            max_leakage = max_leakage.max(self.leakage(distance)?);
+        }
+        Some(gate_time * (1.0 + max_leakage))
+    }
+}
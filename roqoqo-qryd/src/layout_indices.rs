@@ -0,0 +1,186 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Layout indices
+//!
+//! Lookup structures derived from the gate times of a Layout, cached per Layout name
+//! so that device queries do not scan every gate map on each call.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, RwLock},
+};
+
+use crate::TweezerLayoutInfo;
+
+/// Lookup structures derived from the gate times of a Layout.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct LayoutIndex {
+    /// The tweezers supporting at least one gate.
+    pub(crate) tweezers: BTreeSet<usize>,
+    /// Maps a tweezer to the tweezers it is the first tweezer of a two-qubit gate with.
+    pub(crate) two_qubit_neighbours: HashMap<usize, BTreeSet<usize>>,
+}
+
+impl LayoutIndex {
+    /// Builds the index of a Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - The Layout to index.
+    ///
+    /// # Returns
+    ///
+    /// * `LayoutIndex` - The index of the Layout.
+    pub(crate) fn new(info: &TweezerLayoutInfo) -> Self {
+        let mut index = LayoutIndex::default();
+        for times in info.tweezer_single_qubit_gate_times.values() {
+            index.tweezers.extend(times.keys());
+        }
+        for times in info.tweezer_two_qubit_gate_times.values() {
+            for &(tweezer0, tweezer1) in times.keys() {
+                index.tweezers.insert(tweezer0);
+                index.tweezers.insert(tweezer1);
+                index
+                    .two_qubit_neighbours
+                    .entry(tweezer0)
+                    .or_default()
+                    .insert(tweezer1);
+            }
+        }
+        for times in info.tweezer_three_qubit_gate_times.values() {
+            for &(tweezer0, tweezer1, tweezer2) in times.keys() {
+                index.tweezers.extend([tweezer0, tweezer1, tweezer2]);
+            }
+        }
+        for times in info.tweezer_multi_qubit_gate_times.values() {
+            for tweezers in times.keys() {
+                index.tweezers.extend(tweezers);
+            }
+        }
+        index
+    }
+
+    /// Returns the highest tweezer index supporting a gate, None for an empty Layout.
+    pub(crate) fn max_tweezer(&self) -> Option<usize> {
+        self.tweezers.last().copied()
+    }
+}
+
+/// Cache of the LayoutIndex of each Layout of a device, built on first use.
+///
+/// The cache is not part of the device state: it is skipped when serializing
+/// and comparing devices. Clones share the cached indices until one of them
+/// changes a Layout.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LayoutIndexCache {
+    indices: Arc<RwLock<HashMap<String, Arc<LayoutIndex>>>>,
+}
+
+impl LayoutIndexCache {
+    /// Returns the index of a Layout, building and caching it if necessary.
+    ///
+    /// # Arguments
+    ///
+    /// * `layout_name` - The name of the Layout.
+    /// * `info` - The Layout, used if no index is cached yet.
+    ///
+    /// # Returns
+    ///
+    /// * `Arc<LayoutIndex>` - The index of the Layout.
+    pub(crate) fn get_or_build(
+        &self,
+        layout_name: &str,
+        info: &TweezerLayoutInfo,
+    ) -> Arc<LayoutIndex> {
+        if let Some(index) = self
+            .indices
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(layout_name)
+        {
+            return index.clone();
+        }
+        let index = Arc::new(LayoutIndex::new(info));
+        self.indices
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(layout_name.to_string(), index.clone());
+        index
+    }
+
+    /// Drops the cached indices of all Layouts, without affecting clones sharing them.
+    pub(crate) fn clear(&mut self) {
+        self.indices = Arc::default();
+    }
+
+    /// Returns the number of cached indices.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.indices
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index_of_layout() {
+        let mut info = TweezerLayoutInfo::default();
+        info.tweezer_single_qubit_gate_times.insert(
+            "RotateX".to_string(),
+            HashMap::from([(0, 1.0), (1, 1.0), (5, 1.0)]),
+        );
+        info.tweezer_two_qubit_gate_times.insert(
+            "PhaseShiftedControlledZ".to_string(),
+            HashMap::from([((0, 1), 1.0), ((1, 0), 1.0), ((1, 6), 1.0)]),
+        );
+        info.tweezer_three_qubit_gate_times.insert(
+            "ControlledControlledPauliZ".to_string(),
+            HashMap::from([((0, 1, 7), 1.0)]),
+        );
+        let index = LayoutIndex::new(&info);
+        assert_eq!(index.tweezers, BTreeSet::from([0, 1, 5, 6, 7]));
+        assert_eq!(index.max_tweezer(), Some(7));
+        assert_eq!(index.two_qubit_neighbours[&0], BTreeSet::from([1]));
+        assert_eq!(index.two_qubit_neighbours[&1], BTreeSet::from([0, 6]));
+        assert!(!index.two_qubit_neighbours.contains_key(&6));
+        assert_eq!(LayoutIndex::default().max_tweezer(), None);
+    }
+
+    #[test]
+    fn cache_builds_once_and_clears() {
+        let mut cache = LayoutIndexCache::default();
+        let mut info = TweezerLayoutInfo::default();
+        info.tweezer_single_qubit_gate_times
+            .insert("RotateX".to_string(), HashMap::from([(3, 1.0)]));
+        let index = cache.get_or_build("default", &info);
+        assert_eq!(cache.len(), 1);
+        // A cached index is returned even if the given Layout differs
+        let cached = cache.get_or_build("default", &TweezerLayoutInfo::default());
+        assert!(Arc::ptr_eq(&index, &cached));
+        let clone = cache.clone();
+        assert_eq!(clone.len(), 1);
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+        assert_eq!(clone.len(), 1);
+        assert!(cache
+            .get_or_build("default", &TweezerLayoutInfo::default())
+            .tweezers
+            .is_empty());
+    }
+}
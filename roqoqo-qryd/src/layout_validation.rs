@@ -0,0 +1,385 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Layout validation
+//!
+//! Consistency checks of the Layouts of a TweezerDevice.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use itertools::Itertools;
+use roqoqo::RoqoqoBackendError;
+
+use crate::{TweezerDevice, TweezerLayoutInfo, TweezerPositions};
+
+/// A consistency issue of a Layout.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum LayoutIssue {
+    /// A two-qubit gate is set for a tweezer pair in one order only, or with different gate times.
+    AsymmetricTwoQubitGate {
+        /// The hqslang name of the gate.
+        hqslang: String,
+        /// The tweezer pair.
+        tweezers: (usize, usize),
+    },
+    /// An allowed shift involves a tweezer without any gate.
+    ShiftWithUnknownTweezer {
+        /// The tweezer the shift is allowed for.
+        tweezer: usize,
+        /// The tweezer without any gate.
+        unknown_tweezer: usize,
+    },
+    /// The tweezers per row do not add up to the number of tweezers.
+    TweezersPerRowMismatch {
+        /// The sum of the tweezers per row.
+        tweezers_per_row: usize,
+        /// The number of tweezers with at least one gate.
+        number_tweezers: usize,
+    },
+    /// A three-qubit gate involves a tweezer pair without any two-qubit gate.
+    UnconnectedThreeQubitGate {
+        /// The hqslang name of the gate.
+        hqslang: String,
+        /// The tweezer trio.
+        tweezers: (usize, usize, usize),
+        /// The tweezer pair without any two-qubit gate.
+        pair: (usize, usize),
+    },
+    /// A position is set for a tweezer without any gate.
+    PositionOfUnknownTweezer {
+        /// The tweezer without any gate.
+        tweezer: usize,
+    },
+}
+
+impl LayoutIssue {
+    /// Returns the name of the violated rule.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            LayoutIssue::AsymmetricTwoQubitGate { .. } => "AsymmetricTwoQubitGate",
+            LayoutIssue::ShiftWithUnknownTweezer { .. } => "ShiftWithUnknownTweezer",
+            LayoutIssue::TweezersPerRowMismatch { .. } => "TweezersPerRowMismatch",
+            LayoutIssue::UnconnectedThreeQubitGate { .. } => "UnconnectedThreeQubitGate",
+            LayoutIssue::PositionOfUnknownTweezer { .. } => "PositionOfUnknownTweezer",
+        }
+    }
+
+    /// Returns the tweezers involved in the issue.
+    pub fn tweezers(&self) -> Vec<usize> {
+        match self {
+            LayoutIssue::AsymmetricTwoQubitGate { tweezers, .. } => vec![tweezers.0, tweezers.1],
+            LayoutIssue::ShiftWithUnknownTweezer {
+                tweezer,
+                unknown_tweezer,
+            } => vec![*tweezer, *unknown_tweezer],
+            LayoutIssue::TweezersPerRowMismatch { .. } => vec![],
+            LayoutIssue::UnconnectedThreeQubitGate { tweezers, .. } => {
+                vec![tweezers.0, tweezers.1, tweezers.2]
+            }
+            LayoutIssue::PositionOfUnknownTweezer { tweezer } => vec![*tweezer],
+        }
+    }
+}
+
+impl fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutIssue::AsymmetricTwoQubitGate { hqslang, tweezers } => write!(
+                f,
+                "{} between tweezers {} and {} is not set symmetrically.",
+                hqslang, tweezers.0, tweezers.1
+            ),
+            LayoutIssue::ShiftWithUnknownTweezer {
+                tweezer,
+                unknown_tweezer,
+            } => write!(
+                f,
+                "The allowed shifts of tweezer {} involve tweezer {}, which has no gates.",
+                tweezer, unknown_tweezer
+            ),
+            LayoutIssue::TweezersPerRowMismatch {
+                tweezers_per_row,
+                number_tweezers,
+            } => write!(
+                f,
+                "The tweezers per row add up to {}, but the Layout has {} tweezers.",
+                tweezers_per_row, number_tweezers
+            ),
+            LayoutIssue::UnconnectedThreeQubitGate {
+                hqslang,
+                tweezers,
+                pair,
+            } => write!(
+                f,
+                "{} on tweezers {}, {} and {} involves tweezers {} and {}, which have no two-qubit gate.",
+                hqslang, tweezers.0, tweezers.1, tweezers.2, pair.0, pair.1
+            ),
+            LayoutIssue::PositionOfUnknownTweezer { tweezer } => write!(
+                f,
+                "A position is set for tweezer {}, which has no gates.",
+                tweezer
+            ),
+        }
+    }
+}
+
+/// Report of the consistency issues of the Layouts of a TweezerDevice.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ValidationReport {
+    /// The issues of each Layout, by Layout name. Layouts without issues have an empty list.
+    pub layouts: BTreeMap<String, Vec<LayoutIssue>>,
+}
+
+impl ValidationReport {
+    /// Returns whether no issues were found.
+    pub fn is_valid(&self) -> bool {
+        self.layouts.values().all(|issues| issues.is_empty())
+    }
+
+    /// Returns the issues of a Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `layout_name` - The name of the Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `&[LayoutIssue]` - The issues of the Layout, empty if the Layout was not validated.
+    pub fn layout_issues(&self, layout_name: &str) -> &[LayoutIssue] {
+        self.layouts
+            .get(layout_name)
+            .map(|issues| issues.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Converts the report into an error if any issues were found.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - No issues were found.
+    /// * `Err(RoqoqoBackendError::GenericError)` - The error listing all issues.
+    pub fn into_result(self) -> Result<(), RoqoqoBackendError> {
+        if self.is_valid() {
+            return Ok(());
+        }
+        let messages = self
+            .layouts
+            .iter()
+            .flat_map(|(layout_name, issues)| {
+                issues
+                    .iter()
+                    .map(move |issue| format!("Layout {}: {}", layout_name, issue))
+            })
+            .join("\n");
+        Err(RoqoqoBackendError::GenericError {
+            msg: format!("The device Layouts are inconsistent:\n{}", messages),
+        })
+    }
+}
+
+impl TweezerDevice {
+    /// Checks the self-consistency of all Layouts of the device.
+    ///
+    /// The following rules are checked for each Layout:
+    /// * Two-qubit gates are set for both orders of each tweezer pair, with the same gate time.
+    /// * Allowed tweezer shifts only involve tweezers with gates.
+    /// * The tweezers per row, if set, add up to the number of tweezers.
+    /// * Three-qubit gates only involve tweezer pairs connected by a two-qubit gate.
+    /// * Tweezer positions are only set for tweezers with gates.
+    ///
+    /// # Returns
+    ///
+    /// * `ValidationReport` - The issues found in each Layout.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        if let Some(layout_register) = &self.layout_register {
+            for (layout_name, info) in layout_register.iter() {
+                report.layouts.insert(
+                    layout_name.clone(),
+                    validate_layout(info, self.tweezer_positions.get(layout_name)),
+                );
+            }
+        }
+        report
+    }
+}
+
+fn validate_layout(
+    info: &TweezerLayoutInfo,
+    positions: Option<&TweezerPositions>,
+) -> Vec<LayoutIssue> {
+    let mut issues = Vec::new();
+    let tweezers = tweezers_with_gates(info);
+
+    for (hqslang, times) in info
+        .tweezer_two_qubit_gate_times
+        .iter()
+        .sorted_by_key(|(hqslang, _)| *hqslang)
+    {
+        for (&(tweezer0, tweezer1), gate_time) in times.iter().sorted_by_key(|(pair, _)| **pair) {
+            let is_symmetric = match times.get(&(tweezer1, tweezer0)) {
+                Some(reverse_time) => reverse_time == gate_time,
+                None => false,
+            };
+            // Pairs set in both orders are reported once
+            if !is_symmetric && (tweezer0 < tweezer1 || !times.contains_key(&(tweezer1, tweezer0)))
+            {
+                issues.push(LayoutIssue::AsymmetricTwoQubitGate {
+                    hqslang: hqslang.clone(),
+                    tweezers: (tweezer0, tweezer1),
+                });
+            }
+        }
+    }
+
+    for (&tweezer, shifts) in info
+        .allowed_tweezer_shifts
+        .iter()
+        .sorted_by_key(|(tweezer, _)| **tweezer)
+    {
+        let unknown_tweezers: BTreeSet<usize> = std::iter::once(tweezer)
+            .chain(shifts.iter().flatten().copied())
+            .filter(|shift_tweezer| !tweezers.contains(shift_tweezer))
+            .collect();
+        issues.extend(unknown_tweezers.into_iter().map(|unknown_tweezer| {
+            LayoutIssue::ShiftWithUnknownTweezer {
+                tweezer,
+                unknown_tweezer,
+            }
+        }));
+    }
+
+    if let Some(tweezers_per_row) = &info.tweezers_per_row {
+        let tweezers_per_row: usize = tweezers_per_row.iter().sum();
+        if tweezers_per_row != tweezers.len() {
+            issues.push(LayoutIssue::TweezersPerRowMismatch {
+                tweezers_per_row,
+                number_tweezers: tweezers.len(),
+            });
+        }
+    }
+
+    let connected = |tweezer0: usize, tweezer1: usize| {
+        info.tweezer_two_qubit_gate_times.values().any(|times| {
+            times.contains_key(&(tweezer0, tweezer1)) || times.contains_key(&(tweezer1, tweezer0))
+        })
+    };
+    for (hqslang, times) in info
+        .tweezer_three_qubit_gate_times
+        .iter()
+        .sorted_by_key(|(hqslang, _)| *hqslang)
+    {
+        for &(tweezer0, tweezer1, tweezer2) in times.keys().sorted() {
+            for pair in [
+                (tweezer0, tweezer1),
+                (tweezer0, tweezer2),
+                (tweezer1, tweezer2),
+            ] {
+                if !connected(pair.0, pair.1) {
+                    issues.push(LayoutIssue::UnconnectedThreeQubitGate {
+                        hqslang: hqslang.clone(),
+                        tweezers: (tweezer0, tweezer1, tweezer2),
+                        pair,
+                    });
+                }
+            }
+        }
+    }
+
+    issues.extend(
+        positions
+            .into_iter()
+            .flat_map(|positions| positions.positions.keys())
+            .filter(|tweezer| !tweezers.contains(tweezer))
+            .sorted()
+            .map(|&tweezer| LayoutIssue::PositionOfUnknownTweezer { tweezer }),
+    );
+    issues
+}
+
+/// Returns the tweezers with at least one gate.
+fn tweezers_with_gates(info: &TweezerLayoutInfo) -> BTreeSet<usize> {
+    let mut tweezers: BTreeSet<usize> = BTreeSet::new();
+    tweezers.extend(
+        info.tweezer_single_qubit_gate_times
+            .values()
+            .flat_map(HashMap::keys),
+    );
+    tweezers.extend(
+        info.tweezer_two_qubit_gate_times
+            .values()
+            .flat_map(HashMap::keys)
+            .flat_map(|&(tweezer0, tweezer1)| [tweezer0, tweezer1]),
+    );
+    tweezers.extend(
+        info.tweezer_three_qubit_gate_times
+            .values()
+            .flat_map(HashMap::keys)
+            .flat_map(|&(tweezer0, tweezer1, tweezer2)| [tweezer0, tweezer1, tweezer2]),
+    );
+    tweezers.extend(
+        info.tweezer_multi_qubit_gate_times
+            .values()
+            .flat_map(HashMap::keys)
+            .flatten(),
+    );
+    tweezers
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns a device with an asymmetric two-qubit gate in its default layout.
+    fn asymmetric_device() -> TweezerDevice {
+        let mut device = TweezerDevice::new(None, None, None);
+        device.add_layout("default").unwrap();
+        device
+            .set_tweezer_two_qubit_gate_time(
+                "PhaseShiftedControlledPhase",
+                0,
+                1,
+                0.5,
+                Some("default".to_string()),
+            )
+            .unwrap();
+        device.default_layout = Some("default".to_string());
+        device
+    }
+
+    #[test]
+    fn validation_of_downloaded_devices() {
+        assert!(asymmetric_device()
+            .prepare_downloaded("device".to_string(), None, false)
+            .is_ok());
+
+        let result = asymmetric_device().prepare_downloaded("device".to_string(), None, true);
+        assert!(result.is_err());
+        assert!(format!("{:?}", result.unwrap_err()).contains("not set symmetrically"));
+
+        let mut device = asymmetric_device();
+        device
+            .set_tweezer_two_qubit_gate_time(
+                "PhaseShiftedControlledPhase",
+                1,
+                0,
+                0.5,
+                Some("default".to_string()),
+            )
+            .unwrap();
+        assert!(device
+            .prepare_downloaded("device".to_string(), None, true)
+            .is_ok());
+    }
+}
@@ -50,6 +50,41 @@ pub use qryd_devices::*;
 pub mod tweezer_devices;
 pub use tweezer_devices::*;
 
+/// Generators for Layouts of tweezer devices
+pub mod tweezer_layouts;
+pub use tweezer_layouts::*;
+
+/// Distance-based models of the interaction between tweezers
+pub mod interaction_models;
+pub use interaction_models::*;
+
+/// Consistency checks of the Layouts of tweezer devices
+pub mod layout_validation;
+pub use layout_validation::*;
+
+/// Cached lookup indices of the Layouts of tweezer devices
+mod layout_indices;
+
+/// Planning of the tweezer shifts reaching a target qubit arrangement
+pub mod shift_planning;
+pub use shift_planning::*;
+
+/// Routing of circuits onto the tweezers of a TweezerDevice
+pub mod routing;
+pub use routing::*;
+
+/// Decomposition of circuits into the native gates of a TweezerDevice
+pub mod decomposition;
+pub use decomposition::*;
+
+/// Scheduling of circuits with the gate times of a TweezerDevice
+pub mod scheduling;
+pub use scheduling::*;
+
+/// Bidirectional mapping between qubits and the tweezers they occupy
+pub mod qubit_tweezer_mapping;
+pub use qubit_tweezer_mapping::*;
+
 /// Devices representing QRyd quantum computer(s)
 pub mod api_devices;
 pub use api_devices::*;
@@ -62,12 +97,50 @@ pub use pragma_operations::*;
 pub mod emulator_devices;
 pub use emulator_devices::*;
 
+/// Offline snapshots of devices downloaded from the QRyd WebAPI
+pub mod device_snapshots;
+pub use device_snapshots::*;
+
+/// Optional on-disk cache of devices downloaded from the QRyd WebAPI
+#[cfg(feature = "web-api")]
+pub mod device_cache;
+#[cfg(feature = "web-api")]
+pub use device_cache::*;
+
+/// Model of the loss of atoms from the tweezers and post-selection of the shots without losses
+pub mod atom_loss;
+pub use atom_loss::*;
+
+/// Readout confusion matrices and mitigation of readout errors in measured results
+pub mod readout_mitigation;
+pub use readout_mitigation::*;
+
+/// Model of the Rydberg blockade crosstalk between entangling gates and nearby spectator qubits
+pub mod crosstalk;
+pub use crosstalk::*;
+
+/// Checks of all the operations of a circuit against a TweezerDevice
+pub mod circuit_checks;
+pub use circuit_checks::*;
+
 /// Simulator backend for the QRyd quantum computer
 #[cfg(feature = "simulator")]
 mod simulator_backend;
 #[cfg(feature = "simulator")]
 pub use simulator_backend::*;
 
+/// Inspection of the quantum states simulated by the SimulatorBackend
+#[cfg(feature = "simulator")]
+mod simulator_states;
+#[cfg(feature = "simulator")]
+pub use simulator_states::*;
+
+/// Stabilizer simulation of Clifford circuits by the SimulatorBackend
+#[cfg(feature = "simulator")]
+mod stabilizer;
+#[cfg(feature = "simulator")]
+pub use stabilizer::*;
+
 /// WebAPI backend for the QRyd quantum computer(s)
 #[cfg(feature = "web-api")]
 pub mod api_backend;
@@ -75,9 +148,54 @@ pub mod api_backend;
 pub use api_backend::*;
 
 #[cfg(feature = "web-api")]
-use roqoqo::RoqoqoBackendError;
+use device_cache::download_device;
 #[cfg(feature = "web-api")]
-use std::env;
+use roqoqo::RoqoqoBackendError;
+
+/// Records the outcome of a QRyd WebAPI request as a tracing event.
+///
+/// Only the request URL, the HTTP status code, the request ID returned by the server
+/// and the elapsed time are recorded. Request headers, and with them the access token,
+/// are never part of the event.
+///
+/// # Arguments
+///
+/// * `request` - Short name of the request that was sent, e.g. "post_job".
+/// * `response` - The response returned by the WebAPI.
+/// * `start` - The instant the request was started at.
+#[cfg(all(feature = "web-api", feature = "tracing"))]
+pub(crate) fn trace_response(
+    request: &str,
+    response: &reqwest::blocking::Response,
+    start: std::time::Instant,
+) {
+    let status = response.status();
+    let request_id = response
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    if status.is_client_error() || status.is_server_error() {
+        tracing::warn!(
+            request,
+            url = %response.url(),
+            status = status.as_u16(),
+            request_id,
+            elapsed_ms,
+            "QRyd WebAPI request failed"
+        );
+    } else {
+        tracing::debug!(
+            request,
+            url = %response.url(),
+            status = status.as_u16(),
+            request_id,
+            elapsed_ms,
+            "QRyd WebAPI request succeeded"
+        );
+    }
+}
 
 /// Compute the angle according to the appropriate relation and phi/theta values.
 ///
@@ -121,6 +239,9 @@ pub enum CombinedDevice {
 ///
 /// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
 ///
+/// Uses the `DownloadOptions` configured via environmental variables, see
+/// `device_from_api_with_options` to set them explicitly.
+///
 /// # Arguments
 ///
 /// * `device_name` - The name of the device to instantiate. Defaults to "qryd_emulator".
@@ -147,109 +268,92 @@ pub fn device_from_api(
     dev: Option<bool>,
     api_version: Option<String>,
 ) -> Result<CombinedDevice, RoqoqoBackendError> {
-    // Preparing variables
+    device_from_api_with_options(
+        device_name,
+        access_token,
+        seed,
+        dev,
+        api_version,
+        &DownloadOptions::from_env(),
+    )
+}
+
+/// Creates a new TweezerDevice or EmulatorDevice instance, with explicit options.
+///
+/// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
+///
+/// # Arguments
+///
+/// * `device_name` - The name of the device to instantiate. Defaults to "qryd_emulator".
+/// * `access_token` - An access_token is required to access QRYD hardware and emulators.
+///                    The access_token can either be given as an argument here
+///                         or set via the environmental variable `$QRYD_API_TOKEN`.
+/// * `seed` - Optionally overwrite seed value from downloaded device instance.
+/// * `dev` - The boolean to set the dev header to.
+/// * `api_version` - The version of the QRYD API to use. Defaults to "v1_1".
+/// * `options` - The options of the download, e.g. the device cache to use.
+///
+/// # Returns
+///
+/// * `CombinedDevice` - The new CombinedDevice instance, with variant TweezerDevice or
+///     EmulatorDevice depending on the pulled information.
+///
+/// # Errors
+///
+/// * `RoqoqoBackendError`
+#[cfg(feature = "web-api")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        name = "device_from_api",
+        skip_all,
+        fields(
+            device_name = ?device_name,
+            api_version = ?api_version,
+            dev = ?dev,
+            cache = options.cache.is_some()
+        )
+    )
+)]
+pub fn device_from_api_with_options(
+    device_name: Option<String>,
+    access_token: Option<String>,
+    seed: Option<usize>,
+    dev: Option<bool>,
+    api_version: Option<String>,
+    options: &DownloadOptions,
+) -> Result<CombinedDevice, RoqoqoBackendError> {
     let device_name_internal = device_name.unwrap_or_else(|| String::from("qryd_emulator"));
     let api_version = api_version.unwrap_or_else(|| String::from("v1_1"));
-    let dev = dev.unwrap_or(false);
-    let hqs_env_var = env::var("QRYD_API_HQS").is_ok();
-    let access_token_internal: String = match access_token {
-        Some(s) => s,
-        None => {
-            env::var("QRYD_API_TOKEN").map_err(|_| RoqoqoBackendError::MissingAuthentication {
-                msg: "QRYD access token is missing.".to_string(),
-            })?
-        }
-    };
-
-    // Client setup
-    let client = reqwest::blocking::Client::builder()
-        .https_only(true)
-        .build()
-        .map_err(|x| RoqoqoBackendError::NetworkError {
-            msg: format!("Could not create https client {:?}.", x),
-        })?;
-
-    // Response gathering
-    let resp = match (dev, hqs_env_var) {
-        (true, true) => client
-            .get(format!(
-                "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                api_version, device_name_internal
-            ))
-            .header("X-API-KEY", access_token_internal)
-            .header("X-DEV", "?1")
-            .header("X-HQS", "?1")
-            .send()
-            .map_err(|e| RoqoqoBackendError::NetworkError {
-                msg: format!("{:?}", e),
-            })?,
-        (true, false) => client
-            .get(format!(
-                "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                api_version, device_name_internal
-            ))
-            .header("X-API-KEY", access_token_internal)
-            .header("X-DEV", "?1")
-            .send()
-            .map_err(|e| RoqoqoBackendError::NetworkError {
-                msg: format!("{:?}", e),
-            })?,
-        (false, true) => client
-            .get(format!(
-                "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                api_version, device_name_internal
-            ))
-            .header("X-API-KEY", access_token_internal)
-            .header("X-HQS", "?1")
-            .send()
-            .map_err(|e| RoqoqoBackendError::NetworkError {
-                msg: format!("{:?}", e),
-            })?,
-        (false, false) => client
-            .get(format!(
-                "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                api_version, device_name_internal
-            ))
-            .header("X-API-KEY", access_token_internal)
-            .send()
-            .map_err(|e| RoqoqoBackendError::NetworkError {
-                msg: format!("{:?}", e),
-            })?,
-    };
-
-    // Response handling
-    let status_code = resp.status();
-    if status_code == reqwest::StatusCode::OK {
-        if let Ok(mut device) = resp.json::<TweezerDevice>() {
-            if device.available_gates.is_some() {
-                if let Some(new_seed) = seed {
-                    device.seed = Some(new_seed);
-                }
-                device.device_name = device_name_internal;
-                Ok(CombinedDevice::Emulator(EmulatorDevice {
-                    internal: device,
-                }))
-            } else {
-                if let Some(default) = device.default_layout.clone() {
-                    device.switch_layout(&default, None).unwrap();
-                }
-                if let Some(new_seed) = seed {
-                    device.seed = Some(new_seed);
-                }
-                device.device_name = device_name_internal;
-                Ok(CombinedDevice::Tweezer(device))
-            }
-        } else {
-            Err(RoqoqoBackendError::GenericError {
-                msg: "Failed deserialization from device_from_api().".to_string(),
-            })
-        }
+    let mut device = download_device(
+        &device_name_internal,
+        access_token,
+        None,
+        dev.unwrap_or(false),
+        &api_version,
+        options.cache.as_ref(),
+    )?;
+    if device.available_gates.is_some() {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            device_name = %device_name_internal,
+            "Downloaded device is an EmulatorDevice"
+        );
+        Ok(CombinedDevice::Emulator(EmulatorDevice::from_downloaded(
+            device,
+            device_name_internal,
+            seed,
+            options.validate,
+        )?))
     } else {
-        Err(RoqoqoBackendError::NetworkError {
-            msg: format!(
-                "Request to server failed with HTTP status code {:?}.",
-                status_code
-            ),
-        })
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            device_name = %device_name_internal,
+            default_layout = ?device.default_layout,
+            "Downloaded device is a TweezerDevice"
+        );
+        device.prepare_downloaded(device_name_internal, seed, options.validate)?;
+        Ok(CombinedDevice::Tweezer(device))
     }
 }
@@ -24,7 +24,10 @@ use std::str::FromStr;
 use roqoqo::devices::{Device, GenericDevice};
 use roqoqo::RoqoqoBackendError;
 
-use crate::{phi_theta_relation, PragmaChangeQRydLayout, PragmaShiftQRydQubit};
+use crate::{
+    phi_theta_relation, CutoffInteractionModel, InteractionModel, PragmaChangeQRydLayout,
+    PragmaShiftQRydQubit,
+};
 
 /// Collection of all QRyd devices
 ///
@@ -610,12 +613,8 @@ impl Device for FirstDevice {
         let y_distance =
             self.row_distance * ((control_position.0 as isize - target_position.0 as isize) as f64);
         let total_distance = (x_distance.powi(2) + y_distance.powi(2)).sqrt();
-        if total_distance > self.cutoff {
-            None
-        } else {
-            // Example of gate time dependence on distance. Here gate time increases with the square of the distance.
-            Some(2e-6 * total_distance.powi(2))
-        }
+        // Example of gate time dependence on distance. Here gate time increases with the square of the distance.
+        CutoffInteractionModel::new(self.cutoff, 2e-6).two_qubit_gate_time(hqslang, total_distance)
     }
 
     #[allow(unused_variables)]
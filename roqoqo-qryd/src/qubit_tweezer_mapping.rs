@@ -0,0 +1,325 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Qubit -> tweezer mapping
+//!
+//! Bidirectional mapping between qubits and the tweezers they occupy.
+
+use std::collections::HashMap;
+
+use roqoqo::RoqoqoBackendError;
+
+/// Bidirectional mapping between qubits and the tweezers they occupy.
+///
+/// Every tweezer holds at most one qubit. The checked operations return an error
+/// instead of leaving two qubits in the same tweezer.
+///
+/// Serializes to the qubit -> tweezer HashMap, see `as_map()`. Deserializing a map placing several
+/// qubits in the same tweezer fails.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "HashMap<usize, usize>")]
+#[serde(into = "HashMap<usize, usize>")]
+pub struct QubitTweezerMapping {
+    /// Maps a qubit to the tweezer it occupies.
+    qubit_to_tweezer: HashMap<usize, usize>,
+    /// Maps an occupied tweezer to its qubit.
+    tweezer_to_qubit: HashMap<usize, usize>,
+}
+
+impl QubitTweezerMapping {
+    /// Creates a new empty QubitTweezerMapping.
+    ///
+    /// # Returns
+    ///
+    /// * `QubitTweezerMapping` - The new empty mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a mapping placing each qubit in the tweezer with the same index.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezers` - The tweezers to fill.
+    ///
+    /// # Returns
+    ///
+    /// * `QubitTweezerMapping` - The trivial mapping of the given tweezers.
+    pub fn trivial<I: IntoIterator<Item = usize>>(tweezers: I) -> Self {
+        let qubit_to_tweezer: HashMap<usize, usize> = tweezers
+            .into_iter()
+            .map(|tweezer| (tweezer, tweezer))
+            .collect();
+        Self {
+            tweezer_to_qubit: qubit_to_tweezer.clone(),
+            qubit_to_tweezer,
+        }
+    }
+
+    /// Returns the tweezer occupied by a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The index of the qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The index of the tweezer, None if the qubit is not mapped.
+    pub fn tweezer_of(&self, qubit: usize) -> Option<usize> {
+        self.qubit_to_tweezer.get(&qubit).copied()
+    }
+
+    /// Returns the qubit occupying a tweezer.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The index of the qubit, None if the tweezer is free.
+    pub fn qubit_at(&self, tweezer: usize) -> Option<usize> {
+        self.tweezer_to_qubit.get(&tweezer).copied()
+    }
+
+    /// Returns whether a tweezer is occupied by a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    pub fn is_occupied(&self, tweezer: usize) -> bool {
+        self.tweezer_to_qubit.contains_key(&tweezer)
+    }
+
+    /// Returns the occupied tweezers in ascending order.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The indices of the occupied tweezers.
+    pub fn occupied_tweezers(&self) -> Vec<usize> {
+        let mut tweezers: Vec<usize> = self.tweezer_to_qubit.keys().copied().collect();
+        tweezers.sort_unstable();
+        tweezers
+    }
+
+    /// Returns the free tweezers among the given tweezers, in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezers` - The tweezers to check, usually all tweezers of a Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The indices of the free tweezers.
+    pub fn free_tweezers<I: IntoIterator<Item = usize>>(&self, tweezers: I) -> Vec<usize> {
+        let mut free: Vec<usize> = tweezers
+            .into_iter()
+            .filter(|tweezer| !self.is_occupied(*tweezer))
+            .collect();
+        free.sort_unstable();
+        free.dedup();
+        free
+    }
+
+    /// Places an unmapped qubit in a free tweezer.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The index of the qubit.
+    /// * `tweezer` - The index of the tweezer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The qubit has been placed.
+    /// * `Err(RoqoqoBackendError)` - The qubit is already mapped or the tweezer is occupied.
+    pub fn insert(&mut self, qubit: usize, tweezer: usize) -> Result<(), RoqoqoBackendError> {
+        if let Some(current) = self.tweezer_of(qubit) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Qubit {} is already in tweezer {}, cannot place it in tweezer {}.",
+                    qubit, current, tweezer
+                ),
+            });
+        }
+        self.check_free(qubit, tweezer)?;
+        self.qubit_to_tweezer.insert(qubit, tweezer);
+        self.tweezer_to_qubit.insert(tweezer, qubit);
+        Ok(())
+    }
+
+    /// Moves a mapped qubit into a free tweezer.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The index of the qubit.
+    /// * `tweezer` - The index of the target tweezer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The tweezer the qubit has been moved out of.
+    /// * `Err(RoqoqoBackendError)` - The qubit is not mapped or the target tweezer is occupied by another qubit.
+    pub fn move_qubit(
+        &mut self,
+        qubit: usize,
+        tweezer: usize,
+    ) -> Result<usize, RoqoqoBackendError> {
+        let previous = self
+            .tweezer_of(qubit)
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: format!("Qubit {} is not mapped to a tweezer.", qubit),
+            })?;
+        self.check_free(qubit, tweezer)?;
+        self.tweezer_to_qubit.remove(&previous);
+        self.qubit_to_tweezer.insert(qubit, tweezer);
+        self.tweezer_to_qubit.insert(tweezer, qubit);
+        Ok(previous)
+    }
+
+    /// Removes a qubit from the mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The index of the qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The tweezer the qubit occupied.
+    /// * `Err(RoqoqoBackendError)` - The qubit is not mapped.
+    pub fn remove(&mut self, qubit: usize) -> Result<usize, RoqoqoBackendError> {
+        let tweezer = self.qubit_to_tweezer.remove(&qubit).ok_or_else(|| {
+            RoqoqoBackendError::GenericError {
+                msg: format!("Qubit {} is not mapped to a tweezer.", qubit),
+            }
+        })?;
+        self.tweezer_to_qubit.remove(&tweezer);
+        Ok(tweezer)
+    }
+
+    /// Applies tweezer shifts in order, moving the qubit in the start tweezer of each shift.
+    ///
+    /// Either all shifts are applied or, if any shift fails, the mapping is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `shifts` - The (start tweezer, end tweezer) pairs. Shifts of free tweezers are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - All shifts have been applied.
+    /// * `Err(RoqoqoBackendError)` - A shift moves a qubit into an occupied tweezer.
+    pub fn apply_shifts(&mut self, shifts: &[(usize, usize)]) -> Result<(), RoqoqoBackendError> {
+        let mut shifted = self.clone();
+        for &(shift_start, shift_end) in shifts {
+            if let Some(qubit) = shifted.qubit_at(shift_start) {
+                shifted.move_qubit(qubit, shift_end)?;
+            }
+        }
+        *self = shifted;
+        Ok(())
+    }
+
+    /// Places a qubit in a tweezer, moving it if already mapped and evicting the qubit
+    /// previously occupying the tweezer.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The index of the qubit.
+    /// * `tweezer` - The index of the tweezer.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The evicted qubit, if any.
+    pub fn assign(&mut self, qubit: usize, tweezer: usize) -> Option<usize> {
+        let evicted = self.tweezer_to_qubit.get(&tweezer).copied();
+        if let Some(evicted) = evicted {
+            self.qubit_to_tweezer.remove(&evicted);
+        }
+        if let Some(previous) = self.qubit_to_tweezer.insert(qubit, tweezer) {
+            self.tweezer_to_qubit.remove(&previous);
+        }
+        self.tweezer_to_qubit.insert(tweezer, qubit);
+        evicted.filter(|&evicted| evicted != qubit)
+    }
+
+    /// Returns the number of mapped qubits.
+    pub fn len(&self) -> usize {
+        self.qubit_to_tweezer.len()
+    }
+
+    /// Returns whether no qubit is mapped.
+    pub fn is_empty(&self) -> bool {
+        self.qubit_to_tweezer.is_empty()
+    }
+
+    /// Returns the mapped qubits in ascending order.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The indices of the mapped qubits.
+    pub fn qubits(&self) -> Vec<usize> {
+        let mut qubits: Vec<usize> = self.qubit_to_tweezer.keys().copied().collect();
+        qubits.sort_unstable();
+        qubits
+    }
+
+    /// Returns an iterator over the (qubit, tweezer) pairs, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.qubit_to_tweezer
+            .iter()
+            .map(|(qubit, tweezer)| (*qubit, *tweezer))
+    }
+
+    /// Returns the qubit -> tweezer HashMap.
+    pub fn as_map(&self) -> &HashMap<usize, usize> {
+        &self.qubit_to_tweezer
+    }
+
+    /// Returns an error if a tweezer is occupied by a qubit other than the given one.
+    fn check_free(&self, qubit: usize, tweezer: usize) -> Result<(), RoqoqoBackendError> {
+        match self.qubit_at(tweezer) {
+            Some(occupant) if occupant != qubit => Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Tweezer {} is already occupied by qubit {}, cannot place qubit {}.",
+                    tweezer, occupant, qubit
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl PartialEq<HashMap<usize, usize>> for QubitTweezerMapping {
+    fn eq(&self, other: &HashMap<usize, usize>) -> bool {
+        &self.qubit_to_tweezer == other
+    }
+}
+
+impl TryFrom<HashMap<usize, usize>> for QubitTweezerMapping {
+    type Error = RoqoqoBackendError;
+
+    fn try_from(qubit_to_tweezer: HashMap<usize, usize>) -> Result<Self, Self::Error> {
+        let mut mapping = QubitTweezerMapping::new();
+        let mut qubits: Vec<(usize, usize)> = qubit_to_tweezer.into_iter().collect();
+        qubits.sort_unstable();
+        for (qubit, tweezer) in qubits {
+            mapping.insert(qubit, tweezer)?;
+        }
+        Ok(mapping)
+    }
+}
+
+impl From<QubitTweezerMapping> for HashMap<usize, usize> {
+    fn from(mapping: QubitTweezerMapping) -> Self {
+        mapping.qubit_to_tweezer
+    }
+}
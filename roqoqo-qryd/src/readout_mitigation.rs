@@ -0,0 +1,295 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Readout mitigation
+//!
+//! Confusion matrices of the readout of the qubits held in the tweezers and mitigation of the
+//! readout errors in measured bit registers and WebAPI counts.
+
+use std::{collections::HashMap, str::FromStr};
+
+use ndarray::{array, Array2};
+use roqoqo::registers::BitOutputRegister;
+use roqoqo::RoqoqoBackendError;
+
+/// Largest number of bits of the measurement results that can be mitigated.
+///
+/// The mitigation acts on the probabilities of all 2^n results of n bits.
+pub const MAX_MITIGATED_BITS: usize = 20;
+
+/// Mitigated probabilities with a smaller magnitude are dropped from the results.
+const PROBABILITY_CUTOFF: f64 = 1e-12;
+
+/// Confusion matrix of the readout of a qubit held in a tweezer
+///
+/// The default confusion matrix reads out every state correctly.
+#[derive(Debug, Default, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct ReadoutConfusionMatrix {
+    /// The probability of reading out 1 for a qubit in state |0>.
+    pub zero_to_one: f64,
+    /// The probability of reading out 0 for a qubit in state |1>.
+    pub one_to_zero: f64,
+}
+
+impl ReadoutConfusionMatrix {
+    /// Creates a new confusion matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `zero_to_one` - The probability of reading out 1 for a qubit in state |0>.
+    /// * `one_to_zero` - The probability of reading out 0 for a qubit in state |1>.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ReadoutConfusionMatrix)` - The confusion matrix.
+    /// * `Err(RoqoqoBackendError)` - A probability is not in [0, 1].
+    pub fn new(zero_to_one: f64, one_to_zero: f64) -> Result<Self, RoqoqoBackendError> {
+        let confusion_matrix = Self {
+            zero_to_one,
+            one_to_zero,
+        };
+        confusion_matrix.validate()?;
+        Ok(confusion_matrix)
+    }
+
+    /// Creates a confusion matrix reading out both states wrongly with the same probability.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_rate` - The probability of reading out the wrong state.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ReadoutConfusionMatrix)` - The confusion matrix.
+    /// * `Err(RoqoqoBackendError)` - The error rate is not in [0, 1].
+    pub fn symmetric(error_rate: f64) -> Result<Self, RoqoqoBackendError> {
+        Self::new(error_rate, error_rate)
+    }
+
+    /// Checks that both probabilities are in [0, 1].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The confusion matrix is valid.
+    /// * `Err(RoqoqoBackendError)` - A probability is not in [0, 1].
+    pub fn validate(&self) -> Result<(), RoqoqoBackendError> {
+        for error_rate in [self.zero_to_one, self.one_to_zero] {
+            if !(0.0..=1.0).contains(&error_rate) {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!("The error rate {} is not between 0 and 1.", error_rate),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the mean probability of reading out the wrong state.
+    pub fn error_rate(&self) -> f64 {
+        (self.zero_to_one + self.one_to_zero) / 2.0
+    }
+
+    /// Returns the probability of reading out the wrong state for a qubit in the given state.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state of the qubit, true for |1>.
+    pub fn flip_probability(&self, state: bool) -> f64 {
+        if state {
+            self.one_to_zero
+        } else {
+            self.zero_to_one
+        }
+    }
+
+    /// Returns the confusion matrix, the probability of each readout (row) for each state (column).
+    ///
+    /// # Returns
+    ///
+    /// * `Array2<f64>` - The 2x2 confusion matrix.
+    pub fn matrix(&self) -> Array2<f64> {
+        array![
+            [1.0 - self.zero_to_one, self.one_to_zero],
+            [self.zero_to_one, 1.0 - self.one_to_zero]
+        ]
+    }
+
+    /// Returns the inverse of the confusion matrix.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<f64>)` - The inverse 2x2 confusion matrix.
+    /// * `Err(RoqoqoBackendError)` - The readout does not depend on the state and cannot be inverted.
+    pub fn inverse(&self) -> Result<Array2<f64>, RoqoqoBackendError> {
+        let determinant = 1.0 - self.zero_to_one - self.one_to_zero;
+        if determinant.abs() < f64::EPSILON {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The readout does not depend on the state of the qubit, the confusion matrix cannot be inverted.".to_string(),
+            });
+        }
+        Ok(array![
+            [1.0 - self.one_to_zero, -self.one_to_zero],
+            [-self.zero_to_one, 1.0 - self.zero_to_one]
+        ] / determinant)
+    }
+}
+
+/// Method of the mitigation of readout errors
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ReadoutMitigationMethod {
+    /// Applies the inverse confusion matrices. The results can be negative quasi-probabilities.
+    #[default]
+    Inversion,
+    /// Applies the inverse confusion matrices and returns the closest probability distribution
+    /// in the least-squares sense.
+    LeastSquares,
+}
+
+impl FromStr for ReadoutMitigationMethod {
+    type Err = RoqoqoBackendError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "inversion" => Ok(Self::Inversion),
+            "least_squares" => Ok(Self::LeastSquares),
+            _ => Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Unknown readout mitigation method {}, use inversion or least_squares.",
+                    s
+                ),
+            }),
+        }
+    }
+}
+
+/// Mitigates the readout errors of the shots of a bit register.
+///
+/// # Arguments
+///
+/// * `register` - The measured shots, with one entry per bit of the register.
+/// * `confusion_matrices` - The confusion matrix of the qubit measured into each bit of the register.
+/// * `method` - The mitigation method.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, f64>)` - The mitigated probabilities of the results, see [mitigate_frequencies].
+/// * `Err(RoqoqoBackendError)` - The register is empty or the mitigation failed.
+pub fn mitigate_bit_register(
+    register: &BitOutputRegister,
+    confusion_matrices: &[ReadoutConfusionMatrix],
+    method: ReadoutMitigationMethod,
+) -> Result<HashMap<String, f64>, RoqoqoBackendError> {
+    let mut frequencies: HashMap<Vec<bool>, u64> = HashMap::new();
+    for shot in register.iter() {
+        *frequencies.entry(shot.clone()).or_default() += 1;
+    }
+    mitigate_frequencies(&frequencies, confusion_matrices, method)
+}
+
+/// Mitigates the readout errors of the frequencies of measurement results.
+///
+/// The results are keyed by bitstrings whose i-th character is the i-th bit of the result.
+/// Results whose mitigated probability is zero are dropped.
+///
+/// # Arguments
+///
+/// * `frequencies` - The number of times each result has been measured.
+/// * `confusion_matrices` - The confusion matrix of the qubit measured into each bit of the results.
+/// * `method` - The mitigation method.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, f64>)` - The mitigated probabilities of the results.
+/// * `Err(RoqoqoBackendError)` - There are no results, a result does not have one bit per confusion matrix, there are more than [MAX_MITIGATED_BITS] bits or a confusion matrix cannot be inverted.
+pub fn mitigate_frequencies(
+    frequencies: &HashMap<Vec<bool>, u64>,
+    confusion_matrices: &[ReadoutConfusionMatrix],
+    method: ReadoutMitigationMethod,
+) -> Result<HashMap<String, f64>, RoqoqoBackendError> {
+    let number_bits = confusion_matrices.len();
+    if number_bits > MAX_MITIGATED_BITS {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Cannot mitigate the readout of {} bits, at most {} are supported.",
+                number_bits, MAX_MITIGATED_BITS
+            ),
+        });
+    }
+    let total: u64 = frequencies.values().sum();
+    if total == 0 {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: "There are no measurement results to mitigate.".to_string(),
+        });
+    }
+    let mut probabilities = vec![0.0; 1 << number_bits];
+    for (result, frequency) in frequencies.iter() {
+        if result.len() != number_bits {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "A measurement result has {} bits, but {} confusion matrices are given.",
+                    result.len(),
+                    number_bits
+                ),
+            });
+        }
+        let index = result
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| **bit)
+            .fold(0, |index, (bit, _)| index | (1 << bit));
+        probabilities[index] += *frequency as f64 / total as f64;
+    }
+    for (bit, confusion_matrix) in confusion_matrices.iter().enumerate() {
+        let inverse = confusion_matrix.inverse()?;
+        for index in (0..probabilities.len()).filter(|index| index & (1 << bit) == 0) {
+            let zero = probabilities[index];
+            let one = probabilities[index | (1 << bit)];
+            probabilities[index] = inverse[[0, 0]] * zero + inverse[[0, 1]] * one;
+            probabilities[index | (1 << bit)] = inverse[[1, 0]] * zero + inverse[[1, 1]] * one;
+        }
+    }
+    if method == ReadoutMitigationMethod::LeastSquares {
+        probabilities = closest_probability_distribution(&probabilities);
+    }
+    Ok(probabilities
+        .into_iter()
+        .enumerate()
+        .filter(|(_, probability)| probability.abs() > PROBABILITY_CUTOFF)
+        .map(|(index, probability)| {
+            let bitstring: String = (0..number_bits)
+                .map(|bit| if index & (1 << bit) == 0 { '0' } else { '1' })
+                .collect();
+            (bitstring, probability)
+        })
+        .collect())
+}
+
+/// Returns the probability distribution closest to quasi-probabilities summing to one.
+///
+/// The quasi-probabilities are projected onto the probability simplex in the Euclidean norm.
+fn closest_probability_distribution(quasi_probabilities: &[f64]) -> Vec<f64> {
+    let mut sorted = quasi_probabilities.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let mut cumulative = 0.0;
+    let mut shift = 0.0;
+    for (index, value) in sorted.iter().enumerate() {
+        cumulative += value;
+        let candidate = (cumulative - 1.0) / (index + 1) as f64;
+        if value - candidate > 0.0 {
+            shift = candidate;
+        }
+    }
+    quasi_probabilities
+        .iter()
+        .map(|value| (value - shift).max(0.0))
+        .collect()
+}
@@ -0,0 +1,539 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Routing
+//!
+//! Compilation of circuits written for all-to-all connectivity onto a TweezerDevice: choice of
+//! an initial qubit -> tweezer mapping and insertion of the PragmaShiftQubitsTweezers and
+//! PragmaSwitchDeviceLayout operations making every gate executable.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use roqoqo::devices::Device;
+use roqoqo::operations::{
+    InvolveQubits, InvolvedQubits, MultiQubitGateOperation, Operate, OperateMultiQubit,
+    OperateSingleQubit, OperateThreeQubit, OperateTwoQubit, Operation, PragmaChangeDevice,
+    SingleQubitGateOperation, ThreeQubitGateOperation, TwoQubitGateOperation,
+};
+use roqoqo::{Circuit, RoqoqoBackendError};
+
+use crate::{
+    PragmaShiftQubitsTweezers, PragmaSwitchDeviceLayout, QubitTweezerMapping, TweezerDevice,
+    TweezerLayoutInfo,
+};
+
+/// Circuit compiled onto the tweezers of a TweezerDevice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutedCircuit {
+    /// The circuit with the inserted PragmaChangeDevice operations.
+    pub circuit: Circuit,
+    /// The qubit -> tweezer mapping the circuit has to start from.
+    pub initial_mapping: HashMap<usize, usize>,
+    /// The number of single-qubit shifts in the inserted PragmaShiftQubitsTweezers operations.
+    pub number_shifts: usize,
+    /// The number of inserted PragmaSwitchDeviceLayout operations.
+    pub number_layout_switches: usize,
+}
+
+/// Number of qubits of a gate, selecting the gate times it is looked up in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GateKind {
+    Single,
+    Two,
+    Three,
+    Multi,
+}
+
+/// Gate of a circuit, with its qubits in the order of the gate time keys.
+#[derive(Debug, Clone)]
+struct RoutedGate {
+    kind: GateKind,
+    hqslang: String,
+    qubits: Vec<usize>,
+}
+
+/// Routes a circuit written for all-to-all connectivity onto a TweezerDevice.
+///
+/// The initial mapping places the qubits greedily, the qubits sharing the most gates first, on
+/// connected tweezers of the current Layout, preferring tweezers with low error rates. The circuit is then walked in order: before each
+/// gate that is not executable, including single-qubit gates, the qubits are moved with the
+/// fewest shift operations after which the gate is available on their tweezers. If no
+/// shifts do, the device switches to the Layout with the same number of tweezers per row
+/// where the gate is available after the fewest shifts. The result is checked by walking it through `change_device`
+/// with `check_routed_circuit`.
+///
+/// The device is not modified: its qubit -> tweezer mapping has to be set to the returned
+/// initial mapping before running the routed circuit.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to route. It must not contain PragmaChangeDevice operations.
+/// * `device` - The TweezerDevice to route the circuit onto, with a current Layout.
+/// * `max_states` - The maximal number of qubit arrangements explored per routed gate. Defaults to `DEFAULT_MAX_PLANNING_STATES`.
+///
+/// # Returns
+///
+/// * `Ok(RoutedCircuit)` - The routed circuit and its initial mapping.
+/// * `Err(RoqoqoBackendError)` - The circuit does not fit on the device or a gate cannot be made executable.
+pub fn route_circuit(
+    circuit: &Circuit,
+    device: &TweezerDevice,
+    max_states: Option<usize>,
+) -> Result<RoutedCircuit, RoqoqoBackendError> {
+    let mut qubits: BTreeSet<usize> = BTreeSet::new();
+    let mut gates: Vec<RoutedGate> = Vec::new();
+    for operation in circuit.iter() {
+        if let Operation::PragmaChangeDevice(_) = operation {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The circuit to route already contains PragmaChangeDevice operations."
+                    .to_string(),
+            });
+        }
+        if let InvolvedQubits::Set(involved) = operation.involved_qubits() {
+            qubits.extend(involved);
+        }
+        if let Some(gate) = routed_gate(operation) {
+            gates.push(gate);
+        }
+    }
+
+    let initial_mapping = initial_placement(device, &qubits, &gates)?;
+    let mut working = device.clone();
+    working.qubit_to_tweezer = Some(QubitTweezerMapping::try_from(initial_mapping.clone())?);
+
+    let mut routed = Circuit::new();
+    let mut number_shifts = 0;
+    let mut number_layout_switches = 0;
+    for operation in circuit.iter() {
+        if let Some(gate) = routed_gate(operation) {
+            if !is_executable(&working, &gate)? {
+                let pragmas = match plan_gate_shifts(&working, &gate, max_states) {
+                    Ok(shifts) => {
+                        number_shifts += shifts
+                            .iter()
+                            .map(|pragma| pragma.shifts.len())
+                            .sum::<usize>();
+                        shifts
+                            .iter()
+                            .map(|pragma| pragma.to_pragma_change_device())
+                            .collect::<Result<Vec<PragmaChangeDevice>, RoqoqoBackendError>>()?
+                    }
+                    Err(shift_error) => {
+                        let (layout, shifts) = find_layout_switch(&working, &gate, max_states)?
+                            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                                msg: format!(
+                                    "The {} gate on the qubits {:?} cannot be made executable with the tweezer shifts or layout switches of the device: {}",
+                                    gate.hqslang, gate.qubits, shift_error
+                                ),
+                            })?;
+                        number_layout_switches += 1;
+                        number_shifts += shifts
+                            .iter()
+                            .map(|pragma| pragma.shifts.len())
+                            .sum::<usize>();
+                        let mut pragmas =
+                            vec![PragmaSwitchDeviceLayout::new(layout).to_pragma_change_device()?];
+                        for pragma in shifts.iter() {
+                            pragmas.push(pragma.to_pragma_change_device()?);
+                        }
+                        pragmas
+                    }
+                };
+                for pragma in pragmas {
+                    working.change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation)?;
+                    routed.add_operation(pragma);
+                }
+            }
+        }
+        routed.add_operation(operation.clone());
+    }
+
+    let mut check_device = device.clone();
+    check_device.qubit_to_tweezer = Some(QubitTweezerMapping::try_from(initial_mapping.clone())?);
+    check_routed_circuit(&routed, &check_device)?;
+
+    Ok(RoutedCircuit {
+        circuit: routed,
+        initial_mapping,
+        number_shifts,
+        number_layout_switches,
+    })
+}
+
+/// Checks that every gate of a routed circuit is executable on a TweezerDevice.
+///
+/// The PragmaChangeDevice operations of the circuit are applied in order with
+/// `change_device` on a copy of the device, and each single-, two-, three- and multi-qubit
+/// gate is checked against the qubit -> tweezer mapping and Layout at its position.
+///
+/// # Arguments
+///
+/// * `circuit` - The routed circuit.
+/// * `device` - The TweezerDevice, with the qubit -> tweezer mapping the circuit starts from.
+///
+/// # Returns
+///
+/// * `Ok(())` - Every gate of the circuit is executable.
+/// * `Err(RoqoqoBackendError)` - A PragmaChangeDevice operation failed or a gate is not executable.
+pub fn check_routed_circuit(
+    circuit: &Circuit,
+    device: &TweezerDevice,
+) -> Result<(), RoqoqoBackendError> {
+    let mut device = device.clone();
+    for operation in circuit.iter() {
+        if let Operation::PragmaChangeDevice(pragma) = operation {
+            device.change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation)?;
+        } else if let Some(gate) = routed_gate(operation) {
+            if !is_executable(&device, &gate)? {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "The {} gate on the qubits {:?} is not executable on the device.",
+                        gate.hqslang, gate.qubits
+                    ),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the gate of an operation, None for other operations.
+fn routed_gate(operation: &Operation) -> Option<RoutedGate> {
+    if let Ok(gate) = SingleQubitGateOperation::try_from(operation.clone()) {
+        Some(RoutedGate {
+            kind: GateKind::Single,
+            hqslang: gate.hqslang().to_string(),
+            qubits: vec![*gate.qubit()],
+        })
+    } else if let Ok(gate) = TwoQubitGateOperation::try_from(operation.clone()) {
+        Some(RoutedGate {
+            kind: GateKind::Two,
+            hqslang: gate.hqslang().to_string(),
+            qubits: vec![*gate.control(), *gate.target()],
+        })
+    } else if let Ok(gate) = ThreeQubitGateOperation::try_from(operation.clone()) {
+        Some(RoutedGate {
+            kind: GateKind::Three,
+            hqslang: gate.hqslang().to_string(),
+            qubits: vec![*gate.control_0(), *gate.control_1(), *gate.target()],
+        })
+    } else if let Ok(gate) = MultiQubitGateOperation::try_from(operation.clone()) {
+        Some(RoutedGate {
+            kind: GateKind::Multi,
+            hqslang: gate.hqslang().to_string(),
+            qubits: gate.qubits().clone(),
+        })
+    } else {
+        None
+    }
+}
+
+/// Returns the gate time of a gate on the given tweezers of a Layout.
+fn gate_time_on_tweezers(
+    info: &TweezerLayoutInfo,
+    gate: &RoutedGate,
+    tweezers: &[usize],
+) -> Option<f64> {
+    match (gate.kind, tweezers) {
+        (GateKind::Single, &[tweezer]) => info
+            .tweezer_single_qubit_gate_times
+            .get(&gate.hqslang)?
+            .get(&tweezer)
+            .copied(),
+        (GateKind::Two, &[tweezer0, tweezer1]) => info
+            .tweezer_two_qubit_gate_times
+            .get(&gate.hqslang)?
+            .get(&(tweezer0, tweezer1))
+            .copied(),
+        (GateKind::Three, &[tweezer0, tweezer1, tweezer2]) => info
+            .tweezer_three_qubit_gate_times
+            .get(&gate.hqslang)?
+            .get(&(tweezer0, tweezer1, tweezer2))
+            .copied(),
+        (GateKind::Multi, _) => info
+            .tweezer_multi_qubit_gate_times
+            .get(&gate.hqslang)?
+            .get(tweezers)
+            .copied(),
+        _ => None,
+    }
+}
+
+/// Returns whether a gate is executable with the current mapping and Layout of a device.
+fn is_executable(device: &TweezerDevice, gate: &RoutedGate) -> Result<bool, RoqoqoBackendError> {
+    let info = device.get_current_layout_info()?;
+    let tweezers: Option<Vec<usize>> = gate
+        .qubits
+        .iter()
+        .map(|qubit| {
+            device
+                .qubit_to_tweezer
+                .as_ref()
+                .and_then(|mapping| mapping.tweezer_of(*qubit))
+        })
+        .collect();
+    Ok(tweezers.is_some_and(|tweezers| gate_time_on_tweezers(info, gate, &tweezers).is_some()))
+}
+
+/// Returns the tweezers of the current Layout connected by a multi-qubit gate, in both directions.
+fn tweezer_connections(info: &TweezerLayoutInfo) -> HashMap<usize, BTreeSet<usize>> {
+    let mut connections: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+    let mut connect = |tweezers: &[usize]| {
+        for &tweezer0 in tweezers {
+            for &tweezer1 in tweezers {
+                if tweezer0 != tweezer1 {
+                    connections.entry(tweezer0).or_default().insert(tweezer1);
+                }
+            }
+        }
+    };
+    for times in info.tweezer_two_qubit_gate_times.values() {
+        for &(tweezer0, tweezer1) in times.keys() {
+            connect(&[tweezer0, tweezer1]);
+        }
+    }
+    for times in info.tweezer_three_qubit_gate_times.values() {
+        for &(tweezer0, tweezer1, tweezer2) in times.keys() {
+            connect(&[tweezer0, tweezer1, tweezer2]);
+        }
+    }
+    for times in info.tweezer_multi_qubit_gate_times.values() {
+        for tweezers in times.keys() {
+            connect(tweezers);
+        }
+    }
+    connections
+}
+
+/// Returns the representative tweezer of the group of tweezers connected by allowed shifts.
+///
+/// Tweezers without allowed shifts are not contained in the returned map.
+fn shift_components(info: &TweezerLayoutInfo) -> HashMap<usize, usize> {
+    let mut parents: HashMap<usize, usize> = HashMap::new();
+    fn find(parents: &mut HashMap<usize, usize>, tweezer: usize) -> usize {
+        let parent = *parents.entry(tweezer).or_insert(tweezer);
+        if parent == tweezer {
+            return tweezer;
+        }
+        let root = find(parents, parent);
+        parents.insert(tweezer, root);
+        root
+    }
+    for (start, shift_lists) in info.allowed_tweezer_shifts.iter() {
+        for end in shift_lists.iter().flatten() {
+            let root0 = find(&mut parents, *start);
+            let root1 = find(&mut parents, *end);
+            parents.insert(root0.max(root1), root0.min(root1));
+        }
+    }
+    let tweezers: Vec<usize> = parents.keys().copied().collect();
+    tweezers
+        .into_iter()
+        .map(|tweezer| (tweezer, find(&mut parents, tweezer)))
+        .collect()
+}
+
+/// Chooses the initial qubit -> tweezer mapping.
+///
+/// The qubits are placed in decreasing order of their number of shared gates with the already
+/// placed qubits. Each qubit goes to the free tweezer connected to the tweezers of the most of
+/// these shared gates, then to the free tweezer the shifts can bring next to them, ties going
+/// to the tweezer with the lowest error score, then to the best connected and then the lowest
+/// tweezer.
+fn initial_placement(
+    device: &TweezerDevice,
+    qubits: &BTreeSet<usize>,
+    gates: &[RoutedGate],
+) -> Result<HashMap<usize, usize>, RoqoqoBackendError> {
+    let info = device.get_current_layout_info()?;
+    let tweezers = device.get_layout_index(None)?.tweezers.clone();
+    if qubits.len() > tweezers.len() {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "The circuit uses {} qubits, but the current Layout only contains {} tweezers.",
+                qubits.len(),
+                tweezers.len()
+            ),
+        });
+    }
+    let connections = tweezer_connections(info);
+    let components = shift_components(info);
+    let degree = |tweezer: usize| connections.get(&tweezer).map_or(0, |set| set.len());
+    // Number of tweezers with a strictly lower error score, equal for all error free tweezers
+    let mut scores: Vec<(usize, f64)> = tweezers
+        .iter()
+        .map(|&tweezer| Ok((tweezer, device.tweezer_error_score(tweezer, None)?)))
+        .collect::<Result<Vec<(usize, f64)>, RoqoqoBackendError>>()?;
+    scores.sort_by(|(_, score0), (_, score1)| score0.total_cmp(score1));
+    let mut error_ranks: HashMap<usize, usize> = HashMap::new();
+    for (index, (tweezer, score)) in scores.iter().enumerate() {
+        let rank = match index.checked_sub(1).map(|previous| scores[previous]) {
+            Some((previous_tweezer, previous_score)) if previous_score == *score => {
+                error_ranks[&previous_tweezer]
+            }
+            _ => index,
+        };
+        error_ranks.insert(*tweezer, rank);
+    }
+
+    let mut weights: HashMap<(usize, usize), usize> = HashMap::new();
+    for gate in gates {
+        for &qubit0 in gate.qubits.iter() {
+            for &qubit1 in gate.qubits.iter() {
+                if qubit0 != qubit1 {
+                    *weights.entry((qubit0, qubit1)).or_default() += 1;
+                }
+            }
+        }
+    }
+    let weight =
+        |qubit0: usize, qubit1: usize| weights.get(&(qubit0, qubit1)).copied().unwrap_or(0);
+
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    let mut occupied: HashSet<usize> = HashSet::new();
+    let mut unplaced: BTreeSet<usize> = qubits.clone();
+    while !unplaced.is_empty() {
+        // Qubit sharing the most gates with the placed qubits, then with all qubits
+        let qubit = unplaced
+            .iter()
+            .copied()
+            .max_by_key(|&qubit| {
+                let placed: usize = mapping.keys().map(|&other| weight(qubit, other)).sum();
+                let total: usize = qubits.iter().map(|&other| weight(qubit, other)).sum();
+                (placed, total, std::cmp::Reverse(qubit))
+            })
+            .unwrap_or_default();
+        let tweezer = tweezers
+            .iter()
+            .copied()
+            .filter(|tweezer| !occupied.contains(tweezer))
+            .max_by_key(|&tweezer| {
+                let neighbours = connections.get(&tweezer);
+                let shared: usize = mapping
+                    .iter()
+                    .filter(|(_, other_tweezer)| {
+                        neighbours.is_some_and(|set| set.contains(other_tweezer))
+                    })
+                    .map(|(&other, _)| weight(qubit, other))
+                    .sum();
+                let component = components.get(&tweezer).copied().unwrap_or(tweezer);
+                let reachable: usize = mapping
+                    .iter()
+                    .filter(|(_, other_tweezer)| {
+                        connections.get(other_tweezer).is_some_and(|set| {
+                            set.iter().any(|neighbour| {
+                                components.get(neighbour).copied().unwrap_or(*neighbour)
+                                    == component
+                            })
+                        })
+                    })
+                    .map(|(&other, _)| weight(qubit, other))
+                    .sum();
+                (
+                    shared,
+                    reachable,
+                    std::cmp::Reverse(error_ranks[&tweezer]),
+                    degree(tweezer),
+                    std::cmp::Reverse(tweezer),
+                )
+            })
+            .unwrap_or_default();
+        mapping.insert(qubit, tweezer);
+        occupied.insert(tweezer);
+        unplaced.remove(&qubit);
+    }
+    Ok(mapping)
+}
+
+/// Plans the fewest shifts after which a gate is executable in the current Layout.
+fn plan_gate_shifts(
+    device: &TweezerDevice,
+    gate: &RoutedGate,
+    max_states: Option<usize>,
+) -> Result<Vec<PragmaShiftQubitsTweezers>, RoqoqoBackendError> {
+    let info = device.get_current_layout_info()?;
+    let qubits: Vec<usize> = device
+        .qubit_to_tweezer
+        .as_ref()
+        .map(|mapping| mapping.qubits())
+        .unwrap_or_default();
+    // Position of each gate qubit in the arrangements of the search
+    let positions: Vec<usize> = gate
+        .qubits
+        .iter()
+        .map(|qubit| qubits.binary_search(qubit))
+        .collect::<Result<Vec<usize>, usize>>()
+        .map_err(|_| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "The qubits {:?} of the {} gate are not all in the device qubit -> tweezer mapping.",
+                gate.qubits, gate.hqslang
+            ),
+        })?;
+    device.plan_shift_pragmas_until(
+        &qubits,
+        |arrangement| {
+            let tweezers: Vec<usize> = positions
+                .iter()
+                .map(|position| arrangement[*position])
+                .collect();
+            gate_time_on_tweezers(info, gate, &tweezers).is_some()
+        },
+        max_states,
+    )
+}
+
+/// Finds the Layout with the same number of tweezers per row where the gate is executable
+/// after the fewest shifts.
+///
+/// Returns None if there is no such Layout, ties going to the first Layout in alphabetical order.
+fn find_layout_switch(
+    device: &TweezerDevice,
+    gate: &RoutedGate,
+    max_states: Option<usize>,
+) -> Result<Option<(String, Vec<PragmaShiftQubitsTweezers>)>, RoqoqoBackendError> {
+    let Some(tweezers_per_row) = device.get_current_layout_info()?.tweezers_per_row.clone() else {
+        return Ok(None);
+    };
+    let Some(register) = device.layout_register() else {
+        return Ok(None);
+    };
+    let mut names: Vec<&String> = register
+        .iter()
+        .filter(|(name, info)| {
+            device.current_layout.as_ref() != Some(*name)
+                && info.tweezers_per_row.as_ref() == Some(&tweezers_per_row)
+        })
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+
+    let mut best: Option<(usize, String, Vec<PragmaShiftQubitsTweezers>)> = None;
+    for name in names {
+        let mut switched = device.clone();
+        switched.current_layout = Some(name.clone());
+        let Ok(shifts) = plan_gate_shifts(&switched, gate, max_states) else {
+            continue;
+        };
+        let cost = shifts
+            .iter()
+            .map(|pragma| pragma.shifts.len())
+            .sum::<usize>();
+        if best
+            .as_ref()
+            .map_or(true, |(best_cost, _, _)| cost < *best_cost)
+        {
+            best = Some((cost, name.clone(), shifts));
+        }
+    }
+    Ok(best.map(|(_, name, shifts)| (name, shifts)))
+}
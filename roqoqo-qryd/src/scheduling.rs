@@ -0,0 +1,271 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Scheduling
+//!
+//! Estimation of the start times and the duration of a circuit from the gate times of a
+//! TweezerDevice.
+
+use std::collections::{BTreeSet, HashMap};
+
+use roqoqo::devices::Device;
+use roqoqo::operations::{
+    InvolveQubits, InvolvedQubits, MultiQubitGateOperation, Operate, OperateMultiQubit,
+    OperateSingleQubit, OperateThreeQubit, OperateTwoQubit, Operation, SingleQubitGateOperation,
+    ThreeQubitGateOperation, TwoQubitGateOperation,
+};
+use roqoqo::{Circuit, RoqoqoBackendError};
+
+use crate::TweezerDevice;
+
+/// Strategy assigning the start times of the operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchedulingStrategy {
+    /// Every operation starts as soon as its qubits are available.
+    #[default]
+    Asap,
+    /// Every operation starts as late as possible without delaying the end of the circuit.
+    Alap,
+}
+
+/// Operation of a circuit with its start time and duration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledOperation {
+    /// The operation.
+    pub operation: Operation,
+    /// The start time of the operation.
+    pub start_time: f64,
+    /// The duration of the operation.
+    pub duration: f64,
+}
+
+/// Schedule of the operations of a circuit on a device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitSchedule {
+    /// The scheduled operations, in the order of the circuit.
+    pub operations: Vec<ScheduledOperation>,
+    /// The total duration of the circuit.
+    pub duration: f64,
+    /// The time each qubit is not acted on during the circuit.
+    pub idle_times: HashMap<usize, f64>,
+    /// The indices in `operations` of the chain of operations determining the duration.
+    pub critical_path: Vec<usize>,
+}
+
+/// Schedules the operations of a circuit with the gate times of a TweezerDevice.
+///
+/// Gates take the gate time of the device at the positions of their qubits when they are
+/// applied: the PragmaChangeDevice operations of the circuit, such as PragmaShiftQubitsTweezers
+/// and PragmaSwitchDeviceLayout, are applied to a copy of the device with `change_device`.
+/// They wait for all qubits. The device defines no durations for them, so each
+/// PragmaShiftQubitsTweezers takes `shift_time`, its shifts being performed in parallel, and
+/// each PragmaSwitchDeviceLayout takes `layout_switch_time`. Other PragmaChangeDevice
+/// operations and other operations, such as measurements, take no time, the latter waiting
+/// for the qubits they involve.
+///
+/// The idle time of a qubit is the duration of the circuit minus the duration of the gates
+/// acting on it. The critical path does not depend on the strategy.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to schedule.
+/// * `device` - The TweezerDevice, with the qubit -> tweezer mapping the circuit starts from.
+/// * `strategy` - The strategy assigning the start times.
+/// * `shift_time` - The duration of a PragmaShiftQubitsTweezers operation.
+/// * `layout_switch_time` - The duration of a PragmaSwitchDeviceLayout operation.
+///
+/// # Returns
+///
+/// * `Ok(CircuitSchedule)` - The schedule of the circuit.
+/// * `Err(RoqoqoBackendError)` - A gate is not available on the device or a PragmaChangeDevice operation failed.
+pub fn schedule_circuit(
+    circuit: &Circuit,
+    device: &TweezerDevice,
+    strategy: SchedulingStrategy,
+    shift_time: f64,
+    layout_switch_time: f64,
+) -> Result<CircuitSchedule, RoqoqoBackendError> {
+    let mut working = device.clone();
+    let mut all_qubits: BTreeSet<usize> = device
+        .qubit_to_tweezer
+        .as_ref()
+        .map(|mapping| mapping.qubits().into_iter().collect())
+        .unwrap_or_default();
+    let mut timed: Vec<(Operation, f64, Option<Vec<usize>>)> = Vec::new();
+    for operation in circuit.iter() {
+        if let Operation::PragmaChangeDevice(pragma) = operation {
+            working.change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation)?;
+            let duration = match pragma.wrapped_hqslang.as_str() {
+                "PragmaShiftQubitsTweezers" => shift_time,
+                "PragmaSwitchDeviceLayout" => layout_switch_time,
+                _ => 0.0,
+            };
+            timed.push((operation.clone(), duration, None));
+            continue;
+        }
+        let duration = gate_time(&working, operation)?;
+        let qubits = match operation.involved_qubits() {
+            InvolvedQubits::All => None,
+            InvolvedQubits::None => Some(Vec::new()),
+            InvolvedQubits::Set(set) => Some(set.into_iter().collect()),
+        };
+        if let Some(qubits) = qubits.as_ref() {
+            all_qubits.extend(qubits);
+        }
+        timed.push((operation.clone(), duration.unwrap_or(0.0), qubits));
+    }
+
+    let (finish_times, predecessors) = earliest_finish_times(&timed, &all_qubits);
+    let duration = finish_times.iter().copied().fold(0.0, f64::max);
+    let start_times: Vec<f64> = match strategy {
+        SchedulingStrategy::Asap => finish_times
+            .iter()
+            .zip(timed.iter())
+            .map(|(finish, (_, time, _))| finish - time)
+            .collect(),
+        SchedulingStrategy::Alap => {
+            let reversed: Vec<(Operation, f64, Option<Vec<usize>>)> =
+                timed.iter().rev().cloned().collect();
+            let (reversed_finish_times, _) = earliest_finish_times(&reversed, &all_qubits);
+            reversed_finish_times
+                .iter()
+                .rev()
+                .map(|finish| duration - finish)
+                .collect()
+        }
+    };
+
+    let mut busy_times: HashMap<usize, f64> =
+        all_qubits.iter().map(|qubit| (*qubit, 0.0)).collect();
+    for (_, time, qubits) in timed.iter() {
+        for qubit in qubits.iter().flatten() {
+            *busy_times.entry(*qubit).or_default() += time;
+        }
+    }
+    let idle_times = busy_times
+        .into_iter()
+        .map(|(qubit, busy)| (qubit, duration - busy))
+        .collect();
+
+    let mut critical_path = Vec::new();
+    let mut current = finish_times
+        .iter()
+        .enumerate()
+        .filter(|(_, finish)| **finish == duration)
+        .map(|(index, _)| index)
+        .next_back();
+    while let Some(index) = current {
+        critical_path.push(index);
+        current = predecessors[index];
+    }
+    critical_path.reverse();
+
+    Ok(CircuitSchedule {
+        operations: timed
+            .into_iter()
+            .zip(start_times)
+            .map(
+                |((operation, duration, _), start_time)| ScheduledOperation {
+                    operation,
+                    start_time,
+                    duration,
+                },
+            )
+            .collect(),
+        duration,
+        idle_times,
+        critical_path,
+    })
+}
+
+/// Returns the gate time of an operation at the current qubit positions, None for operations
+/// that are not gates.
+pub(crate) fn gate_time(
+    device: &TweezerDevice,
+    operation: &Operation,
+) -> Result<Option<f64>, RoqoqoBackendError> {
+    let (time, qubits) = if let Ok(gate) = SingleQubitGateOperation::try_from(operation.clone()) {
+        (
+            device.single_qubit_gate_time(gate.hqslang(), gate.qubit()),
+            vec![*gate.qubit()],
+        )
+    } else if let Ok(gate) = TwoQubitGateOperation::try_from(operation.clone()) {
+        (
+            device.two_qubit_gate_time(gate.hqslang(), gate.control(), gate.target()),
+            vec![*gate.control(), *gate.target()],
+        )
+    } else if let Ok(gate) = ThreeQubitGateOperation::try_from(operation.clone()) {
+        (
+            device.three_qubit_gate_time(
+                gate.hqslang(),
+                gate.control_0(),
+                gate.control_1(),
+                gate.target(),
+            ),
+            vec![*gate.control_0(), *gate.control_1(), *gate.target()],
+        )
+    } else if let Ok(gate) = MultiQubitGateOperation::try_from(operation.clone()) {
+        (
+            device.multi_qubit_gate_time(gate.hqslang(), gate.qubits()),
+            gate.qubits().clone(),
+        )
+    } else {
+        return Ok(None);
+    };
+    time.map(Some)
+        .ok_or_else(|| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "The {} gate on the qubits {:?} is not available on the device.",
+                operation.hqslang(),
+                qubits
+            ),
+        })
+}
+
+/// Returns the earliest finish time of each operation and the operation it waited for.
+///
+/// Operations without qubit list wait for all qubits.
+fn earliest_finish_times(
+    timed: &[(Operation, f64, Option<Vec<usize>>)],
+    all_qubits: &BTreeSet<usize>,
+) -> (Vec<f64>, Vec<Option<usize>>) {
+    // Finish time of the last operation on each qubit and its index
+    let mut last: HashMap<usize, (f64, usize)> = HashMap::new();
+    let mut finish_times = Vec::with_capacity(timed.len());
+    let mut predecessors = Vec::with_capacity(timed.len());
+    for (index, (_, time, qubits)) in timed.iter().enumerate() {
+        let qubits: Vec<usize> = match qubits {
+            Some(qubits) => qubits.clone(),
+            None => all_qubits.iter().copied().collect(),
+        };
+        let predecessor = qubits
+            .iter()
+            .filter_map(|qubit| last.get(qubit))
+            .copied()
+            .fold(
+                None,
+                |latest: Option<(f64, usize)>, candidate| match latest {
+                    Some(latest) if latest.0 >= candidate.0 => Some(latest),
+                    _ => Some(candidate),
+                },
+            );
+        let start = predecessor.map_or(0.0, |(finish, _)| finish);
+        let finish = start + time;
+        for qubit in qubits {
+            last.insert(qubit, (finish, index));
+        }
+        finish_times.push(finish);
+        predecessors.push(predecessor.map(|(_, index)| index));
+    }
+    (finish_times, predecessors)
+}
@@ -0,0 +1,267 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Shift planning
+//!
+//! Search for the PragmaShiftQubitsTweezers operations moving the qubits of a TweezerDevice
+//! into a target qubit -> tweezer mapping.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use roqoqo::operations::PragmaChangeDevice;
+use roqoqo::RoqoqoBackendError;
+
+use crate::tweezer_devices::shift_path;
+use crate::{PragmaShiftQubitsTweezers, QubitTweezerMapping, TweezerDevice};
+
+/// Default maximal number of qubit arrangements explored when planning shifts.
+pub const DEFAULT_MAX_PLANNING_STATES: usize = 100_000;
+
+/// Arrangement of the qubits during the search: the tweezer of each qubit, by qubit position
+/// in the sorted list of qubits.
+type Arrangement = Vec<usize>;
+
+/// A single shift: the position of the moved qubit, the start and the end tweezer.
+type Shift = (usize, usize, usize);
+
+impl TweezerDevice {
+    /// Plans the tweezer shifts moving the qubits of the device into a target arrangement.
+    ///
+    /// The search starts from the current qubit -> tweezer mapping and only uses the allowed
+    /// tweezer shifts of the current Layout, with the occupancy and path checks applied by
+    /// `change_device`. It finds the smallest number of parallel PragmaShiftQubitsTweezers
+    /// operations, and among those the smallest number of single shifts. The shifts of an
+    /// operation move different qubits and are checked in order against the mapping updated
+    /// after each shift, as in `change_device`, so a whole row of qubits can shift at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target qubit -> tweezer mapping, containing the qubits of the device mapping.
+    /// * `max_states` - The maximal number of qubit arrangements to explore. Defaults to `DEFAULT_MAX_PLANNING_STATES`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<PragmaShiftQubitsTweezers>)` - The operations to apply in order, empty if the target is already reached.
+    /// * `Err(RoqoqoBackendError)` - The target is invalid or not reachable, or the search exceeded `max_states`.
+    pub fn plan_shift_pragmas(
+        &self,
+        target: &HashMap<usize, usize>,
+        max_states: Option<usize>,
+    ) -> Result<Vec<PragmaShiftQubitsTweezers>, RoqoqoBackendError> {
+        let mapping =
+            self.qubit_to_tweezer
+                .as_ref()
+                .ok_or_else(|| RoqoqoBackendError::GenericError {
+                    msg: "The device qubit -> tweezer mapping is empty: no qubits to shift."
+                        .to_string(),
+                })?;
+        let qubits = mapping.qubits();
+        let mut target_qubits: Vec<usize> = target.keys().copied().collect();
+        target_qubits.sort_unstable();
+        if qubits != target_qubits {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The target mapping contains the qubits {:?}, but the device mapping contains the qubits {:?}.",
+                    target_qubits, qubits
+                ),
+            });
+        }
+        QubitTweezerMapping::try_from(target.clone())?;
+        if let Some(tweezer) = target
+            .values()
+            .find(|tweezer| !self.is_tweezer_present(**tweezer, None))
+        {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Target tweezer {} is not present in the current Layout.",
+                    tweezer
+                ),
+            });
+        }
+
+        let goal: Arrangement = qubits.iter().map(|qubit| target[qubit]).collect();
+        self.plan_shift_pragmas_until(
+            &qubits,
+            |arrangement| arrangement == goal.as_slice(),
+            max_states,
+        )
+    }
+
+    /// Plans the fewest shift operations reaching the first qubit arrangement accepted by `is_goal`.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The sorted qubits of the device mapping.
+    /// * `is_goal` - Whether an arrangement, the tweezer of each qubit of `qubits`, is a goal.
+    /// * `max_states` - The maximal number of qubit arrangements to explore. Defaults to `DEFAULT_MAX_PLANNING_STATES`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<PragmaShiftQubitsTweezers>)` - The operations to apply in order, empty if the current arrangement is a goal.
+    /// * `Err(RoqoqoBackendError)` - No goal is reachable or the search exceeded `max_states`.
+    pub(crate) fn plan_shift_pragmas_until(
+        &self,
+        qubits: &[usize],
+        is_goal: impl Fn(&[usize]) -> bool,
+        max_states: Option<usize>,
+    ) -> Result<Vec<PragmaShiftQubitsTweezers>, RoqoqoBackendError> {
+        let allowed_shifts = &self.get_current_layout_info()?.allowed_tweezer_shifts;
+        let start: Arrangement = qubits
+            .iter()
+            .map(|qubit| self.get_tweezer_from_qubit(qubit))
+            .collect::<Result<Arrangement, RoqoqoBackendError>>()?;
+        Ok(fewest_shift_operations(
+            start,
+            is_goal,
+            allowed_shifts,
+            max_states.unwrap_or(DEFAULT_MAX_PLANNING_STATES),
+        )?
+        .into_iter()
+        .map(PragmaShiftQubitsTweezers::new)
+        .collect())
+    }
+
+    /// Plans the tweezer shifts moving the qubits of the device into a target arrangement.
+    ///
+    /// See `plan_shift_pragmas` for the search. The operations are wrapped in
+    /// PragmaChangeDevice operations, ready to be added to a Circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target qubit -> tweezer mapping, containing the qubits of the device mapping.
+    /// * `max_states` - The maximal number of qubit arrangements to explore. Defaults to `DEFAULT_MAX_PLANNING_STATES`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<PragmaChangeDevice>)` - The operations to apply in order, empty if the target is already reached.
+    /// * `Err(RoqoqoBackendError)` - The target is invalid or not reachable, or the search exceeded `max_states`.
+    pub fn plan_shifts(
+        &self,
+        target: &HashMap<usize, usize>,
+        max_states: Option<usize>,
+    ) -> Result<Vec<PragmaChangeDevice>, RoqoqoBackendError> {
+        self.plan_shift_pragmas(target, max_states)?
+            .iter()
+            .map(|pragma| pragma.to_pragma_change_device())
+            .collect()
+    }
+}
+
+/// Returns the single shifts allowed in an arrangement.
+fn allowed_single_shifts(
+    arrangement: &Arrangement,
+    allowed_shifts: &HashMap<usize, Vec<Vec<usize>>>,
+) -> Vec<Shift> {
+    let occupied: HashSet<usize> = arrangement.iter().copied().collect();
+    let mut shifts = Vec::new();
+    for (position, &start) in arrangement.iter().enumerate() {
+        let Some(shift_lists) = allowed_shifts.get(&start) else {
+            continue;
+        };
+        let mut ends: Vec<usize> = shift_lists.iter().flatten().copied().collect();
+        ends.sort_unstable();
+        ends.dedup();
+        for end in ends {
+            if shift_path(end, shift_lists)
+                .is_some_and(|path| path.iter().all(|tweezer| !occupied.contains(tweezer)))
+            {
+                shifts.push((position, start, end));
+            }
+        }
+    }
+    shifts
+}
+
+/// Search state: the arrangement and the qubit positions moved by the last operation.
+type SearchState = (Arrangement, Vec<bool>);
+
+/// Parent of a search state: the previous state, the shift and whether it starts an operation.
+type SearchParent = Option<(SearchState, Shift, bool)>;
+
+/// Dijkstra search of the fewest parallel shift operations from `start` to a goal.
+///
+/// The shifts of an operation are checked in order against the arrangement updated after each
+/// shift, as `change_device` does, and move different qubits. Among the sequences with the
+/// fewest operations, one with the fewest single shifts is returned.
+fn fewest_shift_operations(
+    start: Arrangement,
+    is_goal: impl Fn(&[usize]) -> bool,
+    allowed_shifts: &HashMap<usize, Vec<Vec<usize>>>,
+    max_states: usize,
+) -> Result<Vec<Vec<(usize, usize)>>, RoqoqoBackendError> {
+    let start_state: SearchState = (start.clone(), vec![false; start.len()]);
+    // Cost of a state: the number of operations and the number of single shifts
+    let mut costs: HashMap<SearchState, (usize, usize)> = HashMap::new();
+    let mut parents: HashMap<SearchState, SearchParent> = HashMap::new();
+    let mut arrangements: HashSet<Arrangement> = HashSet::from([start]);
+    costs.insert(start_state.clone(), (0, 0));
+    parents.insert(start_state.clone(), None);
+    let mut queue = BinaryHeap::from([Reverse(((0, 0), start_state))]);
+    while let Some(Reverse((cost, state))) = queue.pop() {
+        if costs.get(&state).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        if is_goal(&state.0) {
+            let mut operations: Vec<Vec<(usize, usize)>> = Vec::new();
+            let mut operation: Vec<(usize, usize)> = Vec::new();
+            let mut current = state;
+            while let Some(Some((parent, shift, starts_operation))) = parents.get(&current) {
+                operation.push((shift.1, shift.2));
+                if *starts_operation {
+                    operation.reverse();
+                    operations.push(std::mem::take(&mut operation));
+                }
+                current = parent.clone();
+            }
+            operations.reverse();
+            return Ok(operations);
+        }
+        let (arrangement, moved) = &state;
+        let operation_open = moved.iter().any(|moved| *moved);
+        for shift in allowed_single_shifts(arrangement, allowed_shifts) {
+            let starts_operation = !operation_open || moved[shift.0];
+            let mut next_arrangement = arrangement.clone();
+            next_arrangement[shift.0] = shift.2;
+            let mut next_moved = if starts_operation {
+                vec![false; moved.len()]
+            } else {
+                moved.clone()
+            };
+            next_moved[shift.0] = true;
+            let next_cost = (cost.0 + starts_operation as usize, cost.1 + 1);
+            let next: SearchState = (next_arrangement, next_moved);
+            if costs.get(&next).is_some_and(|best| *best <= next_cost) {
+                continue;
+            }
+            if !arrangements.contains(&next.0) {
+                if arrangements.len() >= max_states {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: format!(
+                            "The shift planning explored {} qubit arrangements without reaching the target mapping.",
+                            max_states
+                        ),
+                    });
+                }
+                arrangements.insert(next.0.clone());
+            }
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), Some((state.clone(), shift, starts_operation)));
+            queue.push(Reverse((next_cost, next)));
+        }
+    }
+    Err(RoqoqoBackendError::GenericError {
+        msg: "The target mapping is not reachable with the allowed tweezer shifts of the current Layout."
+            .to_string(),
+    })
+}
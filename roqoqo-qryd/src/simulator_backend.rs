@@ -10,12 +10,25 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use roqoqo::backends::EvaluatingBackend;
 use roqoqo::backends::RegisterResult;
 use roqoqo::devices::Device;
+use roqoqo::measurements::Measure;
 use roqoqo::operations::*;
+use roqoqo::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister, Registers};
+use roqoqo::{Circuit, RoqoqoBackendError};
 
-use crate::TweezerDevice;
+use crate::scheduling::gate_time;
+use crate::{
+    violations_into_result, AtomLossModel, CrosstalkModel, DeviceViolation,
+    PragmaShiftQubitsTweezers, ReadoutConfusionMatrix, SimulationMode, StabilizerCircuit,
+    TweezerDevice, MAX_AUTOMATIC_STATE_VECTOR_QUBITS,
+};
 
 /// QRyd simulator backend
 ///
@@ -30,12 +43,104 @@ use crate::TweezerDevice;
 /// The simulator backend implements the [roqoqo::backends::EvaluatingBackend] trait
 /// and is compatible with running single circuits, running and evaluating measurements
 /// and running QuantumPrograms on simulated QRyd devices.
+///
+/// With a [SimulatorNoiseModel], the simulation is noisy: the decoherence of the device acts on
+/// the qubits of each gate for the gate time, see [SimulatorBackend::noisy_circuit].
+///
+/// With an [AtomLossModel], qubits are lost during shifts, measurements and idle times. Lost
+/// qubits read out as false and are reported per shot in a separate bit register, see
+/// [crate::post_select_shots].
+///
+/// With readout errors enabled, each measured bit is flipped with the probability given by the
+/// readout confusion matrix of the tweezer the qubit occupies, see
+/// `TweezerDevice::set_tweezer_readout_confusion_matrix`.
+///
+/// With a seeded device, see `TweezerDevice::seed`, the sampling of the shots, the noise, the
+/// atom losses and the readout errors is reproducible. The circuits of a measurement are run
+/// with distinct seeds derived from the device seed and their position in the measurement.
+///
+/// With a [CrosstalkModel], the spectator qubits close to the qubits of each entangling gate
+/// acquire a correlated phase, see [SimulatorBackend::noisy_circuit].
+///
+/// With several threads, see [SimulatorBackend::with_number_threads], the circuits of a
+/// measurement are run in parallel with the same seeds and results as sequential runs. With a
+/// shot batch size, see [SimulatorBackend::with_shot_batch_size], the shots of each circuit are
+/// split into batches run in parallel as well. Only stabilizer simulations run concurrently,
+/// the QuEST simulations are serialized.
+///
+/// With the stabilizer simulation mode, see [SimulatorBackend::with_simulation_mode], Clifford
+/// circuits on large devices are simulated with a stabilizer tableau instead of a state vector.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct SimulatorBackend {
     /// Device representing the model of a QRyd device.
     pub device: TweezerDevice,
     /// The number of qubits allocated by the simulator.
     pub number_qubits: usize,
+    /// The noise model of noisy simulations, None for noise-free simulations.
+    #[serde(default)]
+    pub noise_model: Option<SimulatorNoiseModel>,
+    /// The model of the loss of atoms, None for simulations without losses.
+    #[serde(default)]
+    pub atom_loss_model: Option<AtomLossModel>,
+    /// Whether the readout errors of the device are applied to the measured bits.
+    #[serde(default)]
+    pub readout_errors: bool,
+    /// The model of the crosstalk during entangling gates, None for simulations without crosstalk.
+    #[serde(default)]
+    pub crosstalk_model: Option<CrosstalkModel>,
+    /// The number of threads running the circuits of a measurement, None for sequential runs.
+    #[serde(default)]
+    pub number_threads: Option<usize>,
+    /// The maximal number of shots run at once, None to run all the shots of a circuit at once.
+    #[serde(default)]
+    pub shot_batch_size: Option<usize>,
+    /// The simulation method of the circuits.
+    #[serde(default)]
+    pub simulation_mode: SimulationMode,
+}
+
+/// Serialized form of a SimulatorBackend from before the simulation options were added.
+///
+/// bincode has no field names, so the missing options cannot be filled in by `#[serde(default)]`.
+#[derive(serde::Deserialize)]
+struct SimulatorBackendWithoutOptions {
+    device: TweezerDevice,
+    number_qubits: usize,
+}
+
+/// Lock serializing the runs of the QuEST simulator.
+///
+/// QuEST keeps its random number generator in global state, seeded at the start of every run
+/// and read by the measurements, so no two QuEST runs may overlap.
+static QUEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// Noise model of the noisy simulation mode of the [SimulatorBackend]
+///
+/// The decoherence rates of the qubits and the error rates of the gates are given by the device,
+/// see `TweezerDevice::set_tweezer_decoherence_rates` and
+/// `TweezerDevice::set_tweezer_single_qubit_gate_error`.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SimulatorNoiseModel {
+    /// Depolarising rates added to the decoherence of the qubits of a gate, by hqslang name.
+    pub gate_error_rates: HashMap<String, f64>,
+}
+
+impl SimulatorNoiseModel {
+    /// Creates a new noise model without gate errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the noise model with a depolarising rate added to the qubits of a gate.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the gate.
+    /// * `rate` - The depolarising rate during the gate.
+    pub fn with_gate_error_rate(mut self, hqslang: &str, rate: f64) -> Self {
+        self.gate_error_rates.insert(hqslang.to_string(), rate);
+        self
+    }
 }
 
 impl SimulatorBackend {
@@ -49,8 +154,659 @@ impl SimulatorBackend {
         Self {
             device: device.clone(),
             number_qubits: number_qubits.unwrap_or(device.number_qubits()),
+            noise_model: None,
+            atom_loss_model: None,
+            readout_errors: false,
+            crosstalk_model: None,
+            number_threads: None,
+            shot_batch_size: None,
+            simulation_mode: SimulationMode::StateVector,
+        }
+    }
+
+    /// Deserializes a SimulatorBackend from its bincode representation.
+    ///
+    /// Backends serialized before the simulation options were added are read with the default
+    /// options of [SimulatorBackend::new].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bincode representation of the SimulatorBackend.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SimulatorBackend)` - The deserialized SimulatorBackend.
+    /// * `Err(RoqoqoBackendError)` - The bytes are not a serialized SimulatorBackend.
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, RoqoqoBackendError> {
+        bincode::deserialize::<SimulatorBackend>(bytes)
+            .or_else(|_| {
+                bincode::deserialize::<SimulatorBackendWithoutOptions>(bytes)
+                    .map(|backend| Self::new(backend.device, Some(backend.number_qubits)))
+            })
+            .map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not deserialize the SimulatorBackend: {}", err),
+            })
+    }
+
+    /// Returns the backend running noisy simulations with the given noise model.
+    ///
+    /// # Arguments
+    ///
+    /// `noise_model` - The noise model of the simulations.
+    pub fn with_noise_model(mut self, noise_model: SimulatorNoiseModel) -> Self {
+        self.noise_model = Some(noise_model);
+        self
+    }
+
+    /// Returns the backend losing atoms as given by the atom loss model.
+    ///
+    /// # Arguments
+    ///
+    /// `atom_loss_model` - The model of the loss of atoms.
+    pub fn with_atom_loss_model(mut self, atom_loss_model: AtomLossModel) -> Self {
+        self.atom_loss_model = Some(atom_loss_model);
+        self
+    }
+
+    /// Returns the backend applying the readout errors of the device to the measured bits, or not.
+    ///
+    /// # Arguments
+    ///
+    /// `readout_errors` - Whether the readout errors are applied.
+    pub fn with_readout_errors(mut self, readout_errors: bool) -> Self {
+        self.readout_errors = readout_errors;
+        self
+    }
+
+    /// Returns the backend adding the crosstalk of the crosstalk model to entangling gates.
+    ///
+    /// # Arguments
+    ///
+    /// `crosstalk_model` - The model of the crosstalk during entangling gates.
+    pub fn with_crosstalk_model(mut self, crosstalk_model: CrosstalkModel) -> Self {
+        self.crosstalk_model = Some(crosstalk_model);
+        self
+    }
+
+    /// Returns the backend running the circuits of a measurement on the given number of threads.
+    ///
+    /// Every circuit, or batch of shots of a circuit, is run with the seed of its position in
+    /// the measurement, see [SimulatorBackend::run_seed], and the registers are merged in the
+    /// order of the circuits and batches, so the results are identical to sequential runs. The
+    /// QuEST simulations are serialized as QuEST keeps its random number generator in global
+    /// state, so several threads only speed up stabilizer simulations, see
+    /// [SimulatorBackend::with_simulation_mode].
+    ///
+    /// # Arguments
+    ///
+    /// `number_threads` - The number of threads, 0 for the available parallelism of the machine
+    ///                    and 1 for sequential runs.
+    pub fn with_number_threads(mut self, number_threads: usize) -> Self {
+        self.number_threads = Some(number_threads);
+        self
+    }
+
+    /// Returns the backend splitting the shots of each circuit into batches of at most the
+    /// given size.
+    ///
+    /// The number of shots is set by the PragmaSetNumberOfMeasurements or
+    /// PragmaRepeatedMeasurement operation of a circuit. Each batch is run with its own seed and
+    /// the batches of a circuit are run in parallel with several threads, see
+    /// [SimulatorBackend::with_number_threads]. The float and complex registers are returned
+    /// once per batch.
+    ///
+    /// # Arguments
+    ///
+    /// `shot_batch_size` - The maximal number of shots of a batch.
+    pub fn with_shot_batch_size(mut self, shot_batch_size: usize) -> Self {
+        self.shot_batch_size = Some(shot_batch_size);
+        self
+    }
+
+    /// Returns the backend simulating the circuits with the given simulation mode.
+    ///
+    /// The stabilizer simulation supports Clifford gates, measurements, active resets and the
+    /// crosstalk of a crosstalk model with a multiple of pi as strength, on any number of
+    /// qubits. The circuits are checked against the device as in state vector simulations, the
+    /// atom losses and readout errors are applied to the measured bits. Noise models are not
+    /// supported.
+    ///
+    /// # Arguments
+    ///
+    /// `simulation_mode` - The simulation mode, see [SimulationMode].
+    pub fn with_simulation_mode(mut self, simulation_mode: SimulationMode) -> Self {
+        self.simulation_mode = simulation_mode;
+        self
+    }
+
+    /// Returns the number of threads running the circuits of a measurement.
+    pub fn threads(&self) -> usize {
+        match self.number_threads {
+            Some(0) => std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
+            Some(number_threads) => number_threads,
+            None => 1,
         }
     }
+
+    /// Returns the circuit run in noisy simulations.
+    ///
+    /// After each gate, a PragmaGeneralNoise operation applies the decoherence rates of each of
+    /// its qubits, in the tweezer it occupies, for the gate time, followed by a PragmaDepolarising
+    /// operation for the error rate of the gate on its tweezers in the device and one for the
+    /// error rate of the gate in the noise model. The device error rate is the depolarising
+    /// probability of each qubit of the gate, error rates of at least 3/4 depolarise the qubits
+    /// completely.
+    ///
+    /// With a crosstalk model, each entangling gate is followed by a ControlledPhaseShift
+    /// operation between each of its qubits and each of their spectators, see
+    /// [CrosstalkModel::spectator_phases]. These operations are not gates of the device, circuits
+    /// with crosstalk are simulated without checking the operations against the device again.
+    ///
+    /// Qubit positions are followed through the PragmaChangeDevice operations of the circuit.
+    /// Without noise model and crosstalk model, the circuit is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to add the noise to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Circuit)` - The circuit with the noise operations.
+    /// * `Err(RoqoqoBackendError)` - A gate is not available on the device or a PragmaChangeDevice operation failed.
+    pub fn noisy_circuit(&self, circuit: &Circuit) -> Result<Circuit, RoqoqoBackendError> {
+        Ok(self.add_noise(circuit.iter())?.into_iter().collect())
+    }
+
+    /// Checks every operation of a circuit against the device without running it.
+    ///
+    /// Besides the checks of [TweezerDevice::check_circuit], the qubits of every operation need
+    /// to be within the number of qubits of the simulator. Runs of circuits with violations
+    /// fail before the simulation with an error listing all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to check.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<DeviceViolation>` - The violations, in the order of the circuit.
+    pub fn check_circuit(&self, circuit: &Circuit) -> Vec<DeviceViolation> {
+        self.device
+            .check_operations(circuit.iter(), Some(self.number_qubits))
+    }
+
+    /// Adds the noise operations of the noise model and the crosstalk of the crosstalk model to
+    /// a sequence of operations.
+    fn add_noise<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> Result<Vec<Operation>, RoqoqoBackendError> {
+        if self.noise_model.is_none() && self.crosstalk_model.is_none() {
+            return Ok(circuit.cloned().collect());
+        }
+        let mut device = self.device.clone();
+        let mut noisy = Vec::new();
+        for operation in circuit {
+            noisy.push(operation.clone());
+            self.push_noise(&mut device, operation, &mut noisy)?;
+        }
+        Ok(noisy)
+    }
+
+    /// Pushes the noise operations and the crosstalk following an operation.
+    ///
+    /// The device follows the PragmaChangeDevice operations, gates not available on the device
+    /// are rejected.
+    pub(crate) fn push_noise(
+        &self,
+        device: &mut TweezerDevice,
+        operation: &Operation,
+        noisy: &mut Vec<Operation>,
+    ) -> Result<(), RoqoqoBackendError> {
+        if let Operation::PragmaChangeDevice(pragma) = operation {
+            device.change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation)?;
+            return Ok(());
+        }
+        let time = match gate_time(device, operation)? {
+            Some(time) => time,
+            None => return Ok(()),
+        };
+        let qubits = match operation.involved_qubits() {
+            InvolvedQubits::Set(qubits) => qubits,
+            _ => return Ok(()),
+        };
+        if let Some(noise_model) = &self.noise_model {
+            let device_rate = device
+                .gate_error(operation)
+                .filter(|error_rate| *error_rate > 0.0 && time > 0.0)
+                .map(|error_rate| depolarising_rate(error_rate, time));
+            let error_rate = noise_model
+                .gate_error_rates
+                .get(operation.hqslang())
+                .copied()
+                .unwrap_or(0.0);
+            for qubit in qubits.iter().copied() {
+                if let Some(rates) = device.qubit_decoherence_rates(&qubit) {
+                    if rates.iter().any(|rate| *rate != 0.0) {
+                        noisy.push(PragmaGeneralNoise::new(qubit, time.into(), rates).into());
+                    }
+                }
+                if let Some(rate) = device_rate {
+                    noisy.push(PragmaDepolarising::new(qubit, time.into(), rate.into()).into());
+                }
+                if error_rate > 0.0 {
+                    noisy.push(
+                        PragmaDepolarising::new(qubit, time.into(), error_rate.into()).into(),
+                    );
+                }
+            }
+        }
+        if let Some(crosstalk_model) = &self.crosstalk_model {
+            if qubits.len() > 1 {
+                let gate_qubits: Vec<usize> = qubits.into_iter().collect();
+                for (qubit, spectator, phase) in
+                    crosstalk_model.spectator_phases(device, &gate_qubits)?
+                {
+                    noisy.push(ControlledPhaseShift::new(qubit, spectator, phase.into()).into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks the qubits lost in each shot in the bit registers of a run.
+    ///
+    /// The measurements of lost qubits are set to false and the register of lost qubits is
+    /// added, with one shot for each shot of the longest bit register.
+    fn apply_atom_loss<'a>(
+        &self,
+        atom_loss_model: &AtomLossModel,
+        circuit: impl Iterator<Item = &'a Operation>,
+        bit_registers: &mut HashMap<String, BitOutputRegister>,
+        rng: &mut StdRng,
+    ) -> Result<(), RoqoqoBackendError> {
+        atom_loss_model.validate()?;
+        let (losses, measurements) = self.atom_losses(atom_loss_model, circuit)?;
+        let number_shots = bit_registers
+            .values()
+            .map(|register| register.len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let mut lost_register: BitOutputRegister = Vec::with_capacity(number_shots);
+        for shot in 0..number_shots {
+            // Position in the circuit at which each lost qubit is lost
+            let mut lost_at: HashMap<usize, usize> = HashMap::new();
+            for loss in losses.iter() {
+                if !lost_at.contains_key(&loss.qubit) && rng.gen::<f64>() < loss.probability {
+                    lost_at.insert(loss.qubit, loss.position);
+                }
+            }
+            for measurement in measurements.iter() {
+                let Some(register) = bit_registers
+                    .get_mut(&measurement.readout)
+                    .and_then(|register| register.get_mut(shot))
+                else {
+                    continue;
+                };
+                for (qubit, index) in measurement.qubits.iter() {
+                    if lost_at
+                        .get(qubit)
+                        .is_some_and(|position| *position < measurement.position)
+                    {
+                        if let Some(bit) = register.get_mut(*index) {
+                            *bit = false;
+                        }
+                    }
+                }
+            }
+            lost_register.push(
+                (0..self.number_qubits)
+                    .map(|qubit| lost_at.contains_key(&qubit))
+                    .collect(),
+            );
+        }
+        bit_registers.insert(atom_loss_model.lost_register.clone(), lost_register);
+        Ok(())
+    }
+
+    /// Returns the possible losses and the measurements of a circuit, in circuit order.
+    ///
+    /// Qubit positions are followed through the PragmaChangeDevice operations of the circuit.
+    fn atom_losses<'a>(
+        &self,
+        atom_loss_model: &AtomLossModel,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> Result<(Vec<AtomLoss>, Vec<LossMeasurement>), RoqoqoBackendError> {
+        let mut device = self.device.clone();
+        let mut losses: Vec<AtomLoss> = Vec::new();
+        let mut measurements: Vec<LossMeasurement> = Vec::new();
+        let mut add_loss = |position: usize, qubit: usize, probability: f64| {
+            if probability > 0.0 {
+                losses.push(AtomLoss {
+                    position,
+                    qubit,
+                    probability,
+                });
+            }
+        };
+        for (position, operation) in circuit.enumerate() {
+            match operation {
+                Operation::PragmaChangeDevice(pragma) => {
+                    if pragma.wrapped_hqslang == "PragmaShiftQubitsTweezers" {
+                        let shift: PragmaShiftQubitsTweezers =
+                            bincode::deserialize(&pragma.wrapped_operation[..]).map_err(|_| {
+                                RoqoqoBackendError::GenericError {
+                                    msg: "Wrapped operation not supported in TweezerDevice"
+                                        .to_string(),
+                                }
+                            })?;
+                        for (start, end) in shift.shifts.iter() {
+                            if let Some(qubit) = device.qubit_at(*start) {
+                                let distance =
+                                    device.tweezer_distance(*start, *end, None).unwrap_or(1.0);
+                                add_loss(
+                                    position,
+                                    qubit,
+                                    atom_loss_model.shift_loss_probability(distance),
+                                );
+                            }
+                        }
+                    }
+                    device.change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation)?;
+                }
+                Operation::MeasureQubit(_) | Operation::PragmaRepeatedMeasurement(_) => {
+                    if let Some((readout, qubits)) = self.measured_qubits(operation) {
+                        for (qubit, _) in qubits.iter() {
+                            add_loss(
+                                position,
+                                *qubit,
+                                atom_loss_model.measurement_loss_probability,
+                            );
+                        }
+                        measurements.push(LossMeasurement {
+                            position,
+                            readout,
+                            qubits,
+                        });
+                    }
+                }
+                _ => {
+                    let Some(time) = gate_time(&device, operation)? else {
+                        continue;
+                    };
+                    let involved: HashSet<usize> = match operation.involved_qubits() {
+                        InvolvedQubits::Set(qubits) => qubits,
+                        _ => continue,
+                    };
+                    let probability = atom_loss_model.idle_loss_probability(time);
+                    for qubit in device.qubit_to_tweezer.iter().flat_map(|map| map.qubits()) {
+                        if !involved.contains(&qubit) {
+                            add_loss(position, qubit, probability);
+                        }
+                    }
+                }
+            }
+        }
+        Ok((losses, measurements))
+    }
+
+    /// Flips the measured bits of the bit registers of a run with the readout error probabilities.
+    fn apply_readout_errors<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+        bit_registers: &mut HashMap<String, BitOutputRegister>,
+        rng: &mut StdRng,
+    ) -> Result<(), RoqoqoBackendError> {
+        for (readout, index, confusion_matrix) in self.readout_confusion_matrices(circuit)? {
+            let Some(register) = bit_registers.get_mut(&readout) else {
+                continue;
+            };
+            for bit in register.iter_mut().filter_map(|shot| shot.get_mut(index)) {
+                if rng.gen::<f64>() < confusion_matrix.flip_probability(*bit) {
+                    *bit = !*bit;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the register, index and readout confusion matrix of each measured bit of a circuit.
+    ///
+    /// Qubit positions are followed through the PragmaChangeDevice operations of the circuit.
+    /// Bits measured from qubits without tweezer are not part of the result.
+    fn readout_confusion_matrices<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> Result<Vec<(String, usize, ReadoutConfusionMatrix)>, RoqoqoBackendError> {
+        let mut device = self.device.clone();
+        let mut measured_bits = Vec::new();
+        for operation in circuit {
+            if let Operation::PragmaChangeDevice(pragma) = operation {
+                device.change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation)?;
+            } else if let Some((readout, qubits)) = self.measured_qubits(operation) {
+                for (qubit, index) in qubits {
+                    if let Some(confusion_matrix) = device.readout_confusion_matrix(&qubit) {
+                        measured_bits.push((readout.clone(), index, confusion_matrix));
+                    }
+                }
+            }
+        }
+        Ok(measured_bits)
+    }
+
+    /// Returns the register and the measured qubits, with their index in the register, of a
+    /// measurement operation.
+    fn measured_qubits(&self, operation: &Operation) -> Option<(String, Vec<(usize, usize)>)> {
+        match operation {
+            Operation::MeasureQubit(measure) => Some((
+                measure.readout().clone(),
+                vec![(*measure.qubit(), *measure.readout_index())],
+            )),
+            Operation::PragmaRepeatedMeasurement(measure) => {
+                let qubits = match measure.qubit_mapping() {
+                    Some(mapping) => mapping
+                        .iter()
+                        .map(|(qubit, index)| (*qubit, *index))
+                        .collect(),
+                    None => (0..self.number_qubits)
+                        .map(|qubit| (qubit, qubit))
+                        .collect(),
+                };
+                Some((measure.readout().clone(), qubits))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the seed of the run of a batch of shots of the circuit at the given position in a
+    /// measurement.
+    ///
+    /// The seed is the device seed, the position and the batch, None for devices without seed.
+    /// Circuits run without shot batches are run as batch 0.
+    pub(crate) fn run_seed(&self, position: usize, batch: usize) -> Option<[u64; 3]> {
+        self.device
+            .seed()
+            .map(|seed| [seed as u64, position as u64, batch as u64])
+    }
+
+    /// Returns the device checking the operations run by the QuEST simulator.
+    pub(crate) fn quest_device(&self) -> Option<Box<dyn Device>> {
+        // The crosstalk operations are not gates of the device, the gates of the circuit are
+        // checked against the device when the crosstalk is added.
+        match self.crosstalk_model {
+            Some(_) => None,
+            None => Some(Box::new(self.device.clone())),
+        }
+    }
+
+    /// Runs a sequence of operations with the given seed, see [SimulatorBackend::run_seed].
+    fn run_circuit_iterator_with_seed<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+        seed: Option<[u64; 3]>,
+    ) -> RegisterResult {
+        let operations: Vec<&Operation> = circuit.collect();
+        violations_into_result(
+            &self
+                .device
+                .check_operations(operations.iter().copied(), Some(self.number_qubits)),
+        )?;
+        let noisy = self.add_noise(operations.iter().copied())?;
+        let (mut bit_registers, float_registers, complex_registers) = match self
+            .stabilizer_circuit(&noisy)?
+        {
+            Some(stabilizer_circuit) => stabilizer_circuit.run(&mut sampling_rng(seed, 2))?,
+            None => {
+                let quest_backend =
+                    roqoqo_quest::Backend::new(self.number_qubits, seed.map(|seed| seed.to_vec()));
+                run_quest(&quest_backend, noisy.iter(), &mut self.quest_device())?
+            }
+        };
+        if self.atom_loss_model.is_some() || self.readout_errors {
+            let mut rng = sampling_rng(seed, 1);
+            if let Some(atom_loss_model) = &self.atom_loss_model {
+                self.apply_atom_loss(
+                    atom_loss_model,
+                    operations.iter().copied(),
+                    &mut bit_registers,
+                    &mut rng,
+                )?;
+            }
+            if self.readout_errors {
+                self.apply_readout_errors(operations.into_iter(), &mut bit_registers, &mut rng)?;
+            }
+        }
+        Ok((bit_registers, float_registers, complex_registers))
+    }
+
+    /// Returns the circuit compiled for the stabilizer simulation, None to simulate the state
+    /// vector or density matrix with QuEST.
+    ///
+    /// In the automatic mode, circuits that cannot be compiled are simulated with QuEST, unless
+    /// the backend has more than [MAX_AUTOMATIC_STATE_VECTOR_QUBITS] qubits.
+    fn stabilizer_circuit(
+        &self,
+        operations: &[Operation],
+    ) -> Result<Option<StabilizerCircuit>, RoqoqoBackendError> {
+        match self.simulation_mode {
+            SimulationMode::StateVector => Ok(None),
+            SimulationMode::Stabilizer => {
+                if self.noise_model.is_some() {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: "The stabilizer simulation does not support noise models, use the state_vector simulation mode.".to_string(),
+                    });
+                }
+                StabilizerCircuit::compile(&operations.iter().collect::<Vec<&Operation>>())
+                    .map(Some)
+            }
+            SimulationMode::Automatic => {
+                if self.noise_model.is_some() {
+                    return Ok(None);
+                }
+                match StabilizerCircuit::compile(&operations.iter().collect::<Vec<&Operation>>()) {
+                    Ok(stabilizer_circuit) => Ok(Some(stabilizer_circuit)),
+                    Err(_) if self.number_qubits <= MAX_AUTOMATIC_STATE_VECTOR_QUBITS => Ok(None),
+                    Err(error) => Err(RoqoqoBackendError::GenericError {
+                        msg: format!(
+                            "The {} qubits of the backend exceed the {} qubits of state vector simulations in the automatic simulation mode and the stabilizer simulation failed: {}",
+                            self.number_qubits, MAX_AUTOMATIC_STATE_VECTOR_QUBITS, error
+                        ),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Runs a sequence of operations with the QuEST simulator while holding the [QUEST_LOCK].
+pub(crate) fn run_quest<'a>(
+    quest_backend: &roqoqo_quest::Backend,
+    circuit: impl Iterator<Item = &'a Operation>,
+    device: &mut Option<Box<dyn Device>>,
+) -> RegisterResult {
+    let _lock = QUEST_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    quest_backend.run_circuit_iterator_with_device(circuit, device)
+}
+
+/// Returns the number of shots of a circuit, set by its PragmaSetNumberOfMeasurements or
+/// PragmaRepeatedMeasurement operation.
+fn number_shots<'a>(circuit: impl Iterator<Item = &'a Operation>) -> Option<usize> {
+    circuit
+        .filter_map(|operation| match operation {
+            Operation::PragmaSetNumberOfMeasurements(pragma) => Some(*pragma.number_measurements()),
+            Operation::PragmaRepeatedMeasurement(pragma) => Some(*pragma.number_measurements()),
+            _ => None,
+        })
+        .next()
+}
+
+/// Returns the operation with the given number of shots if it sets the number of shots of a
+/// circuit, see [number_shots].
+fn with_number_shots(operation: &Operation, shots: usize) -> Operation {
+    match operation {
+        Operation::PragmaSetNumberOfMeasurements(pragma) => {
+            PragmaSetNumberOfMeasurements::new(shots, pragma.readout().clone()).into()
+        }
+        Operation::PragmaRepeatedMeasurement(pragma) => PragmaRepeatedMeasurement::new(
+            pragma.readout().clone(),
+            shots,
+            pragma.qubit_mapping().clone(),
+        )
+        .into(),
+        _ => operation.clone(),
+    }
+}
+
+/// Possible loss of a qubit at an operation of a circuit.
+#[derive(Debug, Clone, Copy)]
+struct AtomLoss {
+    /// The position of the operation in the circuit.
+    position: usize,
+    /// The qubit that can be lost.
+    qubit: usize,
+    /// The probability of losing the qubit.
+    probability: f64,
+}
+
+/// Measurement of qubits into a bit register at an operation of a circuit.
+#[derive(Debug, Clone)]
+struct LossMeasurement {
+    /// The position of the operation in the circuit.
+    position: usize,
+    /// The name of the bit register.
+    readout: String,
+    /// The measured qubits with their index in the register.
+    qubits: Vec<(usize, usize)>,
+}
+
+/// Returns the depolarising rate whose PragmaDepolarising probability over the gate time is the
+/// error rate.
+fn depolarising_rate(error_rate: f64, time: f64) -> f64 {
+    let remaining = (1.0 - error_rate * 4.0 / 3.0).max(f64::MIN_POSITIVE);
+    -remaining.ln() / time
+}
+
+/// Returns the random number generator of a stream of random numbers of a run.
+///
+/// Stream 1 samples the atom losses and readout errors, stream 2 the measurements of the
+/// stabilizer simulation. With a seed, every stream is distinct from the sampling of the shots
+/// by the QuEST simulator, which is seeded with the same seed.
+fn sampling_rng(seed: Option<[u64; 3]>, stream: u64) -> StdRng {
+    match seed {
+        Some(seed) => {
+            let mut bytes = [0u8; 32];
+            for (chunk, value) in bytes.chunks_mut(8).zip([seed[0], seed[1], seed[2], stream]) {
+                chunk.copy_from_slice(&value.to_le_bytes());
+            }
+            StdRng::from_seed(bytes)
+        }
+        None => StdRng::from_entropy(),
+    }
 }
 
 impl EvaluatingBackend for SimulatorBackend {
@@ -58,10 +814,101 @@ impl EvaluatingBackend for SimulatorBackend {
         &self,
         circuit: impl Iterator<Item = &'a Operation>,
     ) -> RegisterResult {
-        let mut tmp_device: Option<Box<dyn Device>> = Some(Box::new(self.device.clone()));
+        self.run_circuit_iterator_with_seed(circuit, self.run_seed(0, 0))
+    }
+
+    fn run_measurement_registers<T>(&self, measurement: &T) -> RegisterResult
+    where
+        T: Measure,
+    {
+        let circuits: Vec<&Circuit> = measurement.circuits().collect();
+        let constant_circuit = measurement.constant_circuit().as_ref();
+        let operations = |position: usize| {
+            constant_circuit
+                .into_iter()
+                .flat_map(|circuit| circuit.iter())
+                .chain(circuits[position].iter())
+        };
 
-        let quest_backend = roqoqo_quest::Backend::new(self.number_qubits, None);
+        // Each run is a circuit or a batch of its shots, with the number of shots of the batch
+        let mut runs: Vec<(usize, usize, Option<usize>)> = Vec::new();
+        for position in 0..circuits.len() {
+            match (self.shot_batch_size, number_shots(operations(position))) {
+                (Some(batch_size), Some(shots)) if batch_size > 0 && shots > batch_size => runs
+                    .extend(
+                        (0..shots)
+                            .step_by(batch_size)
+                            .enumerate()
+                            .map(|(batch, first)| {
+                                (position, batch, Some(batch_size.min(shots - first)))
+                            }),
+                    ),
+                _ => runs.push((position, 0, None)),
+            }
+        }
+        let run = |(position, batch, shots): (usize, usize, Option<usize>)| -> RegisterResult {
+            let seed = self.run_seed(position, batch);
+            match shots {
+                Some(shots) => {
+                    let batch_operations: Vec<Operation> = operations(position)
+                        .map(|operation| with_number_shots(operation, shots))
+                        .collect();
+                    self.run_circuit_iterator_with_seed(batch_operations.iter(), seed)
+                }
+                None => self.run_circuit_iterator_with_seed(operations(position), seed),
+            }
+        };
 
-        quest_backend.run_circuit_iterator_with_device(circuit, &mut tmp_device)
+        let number_threads = self.threads().min(runs.len());
+        let results: Vec<Registers> = if number_threads > 1 {
+            // Each thread runs a contiguous range of runs, joined in the order of the runs
+            let chunk_size = (runs.len() + number_threads - 1) / number_threads;
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = runs
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        let run = &run;
+                        scope.spawn(move || {
+                            chunk
+                                .iter()
+                                .map(|batch| run(*batch))
+                                .collect::<Result<Vec<Registers>, RoqoqoBackendError>>()
+                        })
+                    })
+                    .collect();
+                let mut results = Vec::with_capacity(runs.len());
+                for handle in handles {
+                    let mut chunk =
+                        handle
+                            .join()
+                            .map_err(|_| RoqoqoBackendError::GenericError {
+                                msg: "A thread running the circuits of the measurement panicked."
+                                    .to_string(),
+                            })??;
+                    results.append(&mut chunk);
+                }
+                Ok::<Vec<Registers>, RoqoqoBackendError>(results)
+            })?
+        } else {
+            runs.into_iter()
+                .map(run)
+                .collect::<Result<Vec<Registers>, RoqoqoBackendError>>()?
+        };
+
+        let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
+        let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
+        let mut complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
+        for (tmp_bit_reg, tmp_float_reg, tmp_complex_reg) in results {
+            for (key, mut val) in tmp_bit_reg.into_iter() {
+                bit_registers.entry(key).or_default().append(&mut val);
+            }
+            for (key, mut val) in tmp_float_reg.into_iter() {
+                float_registers.entry(key).or_default().append(&mut val);
+            }
+            for (key, mut val) in tmp_complex_reg.into_iter() {
+                complex_registers.entry(key).or_default().append(&mut val);
+            }
+        }
+        Ok((bit_registers, float_registers, complex_registers))
     }
 }
@@ -0,0 +1,243 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Simulated states
+//!
+//! Inspection of the quantum state of the qubits during and after a circuit run by the
+//! SimulatorBackend.
+
+use ndarray::{Array1, Array2};
+use num_complex::Complex64;
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoBackendError};
+
+use crate::simulator_backend::run_quest;
+use crate::{violations_into_result, QubitTweezerMapping, SimulatorBackend};
+
+/// Prefix of the complex registers holding the snapshots of the state.
+const SNAPSHOT_REGISTER_PREFIX: &str = "__state_snapshot_";
+
+/// Basis of the quantum states simulated by the SimulatorBackend
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StateBasis {
+    /// Bit i of the index of a basis state is the state of qubit i.
+    #[default]
+    Qubits,
+    /// Bit i of the index of a basis state is the state of the qubit in the i-th occupied tweezer,
+    /// in ascending order of the tweezers at the position of the state.
+    Tweezers,
+}
+
+/// Quantum state of the qubits simulated by the SimulatorBackend
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulatedState {
+    /// The state vector of a pure state.
+    StateVector(Array1<Complex64>),
+    /// The density matrix.
+    DensityMatrix(Array2<Complex64>),
+}
+
+/// Snapshot of the quantum state of the qubits at a position of a circuit
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSnapshot {
+    /// The number of operations of the circuit applied to the state.
+    pub position: usize,
+    /// The qubit or the tweezer of each bit of the index of a basis state.
+    pub labels: Vec<usize>,
+    /// The quantum state.
+    pub state: SimulatedState,
+}
+
+impl SimulatorBackend {
+    /// Returns the quantum state of the qubits after a circuit.
+    ///
+    /// The circuit is run once, with the noise and the crosstalk of the backend, see
+    /// [SimulatorBackend::noisy_circuit]. Measurements collapse the state, atom losses and
+    /// readout errors do not affect it. Noisy simulations only provide density matrices.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to run.
+    /// * `density_matrix` - Whether to return the density matrix instead of the state vector.
+    /// * `basis` - The basis of the state.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StateSnapshot)` - The state after the last operation of the circuit.
+    /// * `Err(RoqoqoBackendError)` - The circuit does not act on any qubit, a qubit is not in a tweezer for the tweezer basis, the state vector of a noisy simulation is requested or running the circuit failed.
+    pub fn simulate_state(
+        &self,
+        circuit: &Circuit,
+        density_matrix: bool,
+        basis: StateBasis,
+    ) -> Result<StateSnapshot, RoqoqoBackendError> {
+        self.snapshots(circuit, density_matrix, basis, false)?
+            .pop()
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "The circuit is empty.".to_string(),
+            })
+    }
+
+    /// Returns the quantum state of the qubits after each operation of a circuit.
+    ///
+    /// The snapshots are taken in a single run of the circuit, see
+    /// [SimulatorBackend::simulate_state]. The noise and the crosstalk following an operation
+    /// are part of its snapshot. All snapshots are given on the qubits of the whole circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to run.
+    /// * `density_matrix` - Whether to return density matrices instead of state vectors.
+    /// * `basis` - The basis of the states.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<StateSnapshot>)` - The state after each operation, in the order of the circuit.
+    /// * `Err(RoqoqoBackendError)` - The circuit does not act on any qubit, a qubit is not in a tweezer for the tweezer basis, the state vector of a noisy simulation is requested or running the circuit failed.
+    pub fn state_snapshots(
+        &self,
+        circuit: &Circuit,
+        density_matrix: bool,
+        basis: StateBasis,
+    ) -> Result<Vec<StateSnapshot>, RoqoqoBackendError> {
+        self.snapshots(circuit, density_matrix, basis, true)
+    }
+
+    /// Runs a circuit with snapshots of the state after each or after the last operation.
+    fn snapshots(
+        &self,
+        circuit: &Circuit,
+        density_matrix: bool,
+        basis: StateBasis,
+        every_operation: bool,
+    ) -> Result<Vec<StateSnapshot>, RoqoqoBackendError> {
+        violations_into_result(&self.check_circuit(circuit))?;
+        let mut device = self.device.clone();
+        let mut operations: Vec<Operation> = Vec::new();
+        // Position, register and qubit -> tweezer mapping of each snapshot
+        let mut snapshots: Vec<(usize, String, Option<QubitTweezerMapping>)> = Vec::new();
+        for (index, operation) in circuit.iter().enumerate() {
+            operations.push(operation.clone());
+            self.push_noise(&mut device, operation, &mut operations)?;
+            if every_operation || index + 1 == circuit.len() {
+                let readout = format!("{}{}", SNAPSHOT_REGISTER_PREFIX, index + 1);
+                operations.push(if density_matrix {
+                    PragmaGetDensityMatrix::new(readout.clone(), None).into()
+                } else {
+                    PragmaGetStateVector::new(readout.clone(), None).into()
+                });
+                snapshots.push((index + 1, readout, device.qubit_to_tweezer.clone()));
+            }
+        }
+        let number_qubits = operations
+            .iter()
+            .filter_map(|operation| match operation.involved_qubits() {
+                InvolvedQubits::Set(qubits) => qubits.into_iter().max(),
+                _ => None,
+            })
+            .max()
+            .map(|qubit| qubit + 1)
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "The circuit does not act on any qubit.".to_string(),
+            })?;
+        let dimension = 1 << number_qubits;
+        let length = if density_matrix {
+            dimension * dimension
+        } else {
+            dimension
+        };
+        let run: Vec<Operation> = snapshots
+            .iter()
+            .map(|(_, readout, _)| DefinitionComplex::new(readout.clone(), length, true).into())
+            .chain(operations)
+            .collect();
+
+        let quest_backend = roqoqo_quest::Backend::new(
+            self.number_qubits,
+            self.run_seed(0, 0).map(|seed| seed.to_vec()),
+        );
+        let (_, _, complex_registers) =
+            run_quest(&quest_backend, run.iter(), &mut self.quest_device())?;
+
+        snapshots
+            .into_iter()
+            .map(|(position, readout, mapping)| {
+                let values = complex_registers
+                    .get(&readout)
+                    .and_then(|register| register.first())
+                    .filter(|values| values.len() == length)
+                    .ok_or_else(|| RoqoqoBackendError::GenericError {
+                        msg: format!("The snapshot after operation {} is missing.", position),
+                    })?;
+                let (labels, qubits) = basis_labels(number_qubits, basis, mapping.as_ref())?;
+                let indices = permuted_indices(&qubits);
+                let state = if density_matrix {
+                    // The density matrix is flattened in row-major order
+                    let mut state = Array2::zeros((dimension, dimension));
+                    for (index, value) in values.iter().enumerate() {
+                        state[[indices[index / dimension], indices[index % dimension]]] = *value;
+                    }
+                    SimulatedState::DensityMatrix(state)
+                } else {
+                    let mut state = Array1::zeros(dimension);
+                    for (index, value) in values.iter().enumerate() {
+                        state[indices[index]] = *value;
+                    }
+                    SimulatedState::StateVector(state)
+                };
+                Ok(StateSnapshot {
+                    position,
+                    labels,
+                    state,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the label and the qubit of each bit of the index of a basis state.
+fn basis_labels(
+    number_qubits: usize,
+    basis: StateBasis,
+    mapping: Option<&QubitTweezerMapping>,
+) -> Result<(Vec<usize>, Vec<usize>), RoqoqoBackendError> {
+    match basis {
+        StateBasis::Qubits => Ok(((0..number_qubits).collect(), (0..number_qubits).collect())),
+        StateBasis::Tweezers => {
+            let mut tweezers = (0..number_qubits)
+                .map(|qubit| {
+                    mapping
+                        .and_then(|mapping| mapping.tweezer_of(qubit))
+                        .map(|tweezer| (tweezer, qubit))
+                        .ok_or_else(|| RoqoqoBackendError::GenericError {
+                            msg: format!("Qubit {} is not in a tweezer.", qubit),
+                        })
+                })
+                .collect::<Result<Vec<(usize, usize)>, RoqoqoBackendError>>()?;
+            tweezers.sort_unstable();
+            Ok(tweezers.into_iter().unzip())
+        }
+    }
+}
+
+/// Returns the index of each basis state of the qubits in the basis with the given qubit of
+/// each bit.
+fn permuted_indices(qubits: &[usize]) -> Vec<usize> {
+    (0..1usize << qubits.len())
+        .map(|index| {
+            qubits.iter().enumerate().fold(0, |permuted, (bit, qubit)| {
+                permuted | (((index >> qubit) & 1) << bit)
+            })
+        })
+        .collect()
+}
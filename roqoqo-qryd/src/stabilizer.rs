@@ -0,0 +1,672 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Stabilizer simulation
+//!
+//! Simulation of Clifford circuits with the stabilizer tableau of Aaronson and Gottesman
+//! (<https://arxiv.org/abs/quant-ph/0406196>), which scales to circuits on hundreds of qubits.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use ndarray::Array2;
+use num_complex::Complex64;
+use rand::rngs::StdRng;
+use rand::Rng;
+use roqoqo::backends::RegisterResult;
+use roqoqo::operations::*;
+use roqoqo::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister};
+use roqoqo::RoqoqoBackendError;
+
+use crate::circuit_checks::gate_qubits;
+
+/// Largest number of qubits of a gate applied by the stabilizer simulation.
+///
+/// The action of a gate is derived from its unitary matrix, of dimension 2^n for n qubits.
+const MAX_STABILIZER_GATE_QUBITS: usize = 6;
+
+/// Largest number of qubits simulated with QuEST in the automatic simulation mode.
+///
+/// Larger circuits that cannot be simulated with a stabilizer tableau fail instead of
+/// allocating a state vector of 2^n amplitudes, see [SimulationMode::Automatic].
+pub const MAX_AUTOMATIC_STATE_VECTOR_QUBITS: usize = 30;
+
+/// Tolerance of the comparison of the entries of conjugated Pauli operators.
+const CLIFFORD_TOLERANCE: f64 = 1e-9;
+
+/// Simulation method of the SimulatorBackend
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SimulationMode {
+    /// Simulates the state vector or density matrix with QuEST.
+    #[default]
+    StateVector,
+    /// Simulates the stabilizer tableau, only Clifford gates and measurements are supported.
+    Stabilizer,
+    /// Simulates the stabilizer tableau for noise-free Clifford circuits and the state vector
+    /// otherwise, up to [MAX_AUTOMATIC_STATE_VECTOR_QUBITS] qubits.
+    Automatic,
+}
+
+impl FromStr for SimulationMode {
+    type Err = RoqoqoBackendError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "state_vector" => Ok(Self::StateVector),
+            "stabilizer" => Ok(Self::Stabilizer),
+            "automatic" => Ok(Self::Automatic),
+            _ => Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Unknown simulation mode {}, use state_vector, stabilizer or automatic.",
+                    s
+                ),
+            }),
+        }
+    }
+}
+
+/// Pauli operator on the qubits of a gate, i^phase times a product of X, Y and Z operators.
+///
+/// Bit j of `x` and `z` is the Pauli operator on the j-th qubit of the gate, with Y for both bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LocalPauli {
+    x: u64,
+    z: u64,
+    phase: u8,
+}
+
+impl LocalPauli {
+    /// Returns the product of two Pauli operators.
+    fn multiply(&self, other: &LocalPauli) -> LocalPauli {
+        let (plus, minus) = product_phase_words(self.x, self.z, other.x, other.z);
+        let phase = self.phase as i64 + other.phase as i64 + plus.count_ones() as i64
+            - minus.count_ones() as i64;
+        LocalPauli {
+            x: self.x ^ other.x,
+            z: self.z ^ other.z,
+            phase: phase.rem_euclid(4) as u8,
+        }
+    }
+}
+
+/// Returns the qubits whose single-qubit Pauli products contribute a factor i or -i.
+///
+/// Multiplying the Pauli operator (x1, z1) by (x2, z2) on one qubit gives a factor of i^g, with
+/// the function g of Aaronson and Gottesman. The first word holds the qubits with g = 1, the
+/// second those with g = -1.
+fn product_phase_words(x1: u64, z1: u64, x2: u64, z2: u64) -> (u64, u64) {
+    let y1 = x1 & z1;
+    let only_x1 = x1 & !z1;
+    let only_z1 = !x1 & z1;
+    let plus = (y1 & z2 & !x2) | (only_x1 & x2 & z2) | (only_z1 & x2 & !z2);
+    let minus = (y1 & x2 & !z2) | (only_x1 & z2 & !x2) | (only_z1 & x2 & z2);
+    (plus, minus)
+}
+
+/// Clifford gate given by the conjugation of the X and Z operators of each of its qubits.
+#[derive(Debug, Clone, PartialEq)]
+struct CliffordGate {
+    /// The qubits of the gate.
+    qubits: Vec<usize>,
+    /// U X_j U^dagger for the j-th qubit of the gate.
+    x_images: Vec<LocalPauli>,
+    /// U Z_j U^dagger for the j-th qubit of the gate.
+    z_images: Vec<LocalPauli>,
+}
+
+impl CliffordGate {
+    /// Derives the Clifford gate from the unitary matrix of a gate.
+    ///
+    /// The first qubit of the gate is the most significant bit of the basis of the matrix.
+    /// Returns None if the gate is not a Clifford gate.
+    fn from_unitary(qubits: Vec<usize>, unitary: &Array2<Complex64>) -> Option<Self> {
+        let number_qubits = qubits.len();
+        let adjoint = unitary.t().mapv(|value| value.conj());
+        let mut x_images = Vec::with_capacity(number_qubits);
+        let mut z_images = Vec::with_capacity(number_qubits);
+        for position in 0..number_qubits {
+            let bit = number_qubits - 1 - position;
+            for (pauli, images) in [(0b10, &mut x_images), (0b01, &mut z_images)] {
+                let operator = single_pauli_matrix(number_qubits, bit, pauli);
+                let conjugated = unitary.dot(&operator).dot(&adjoint);
+                images.push(pauli_of_matrix(&conjugated, number_qubits)?);
+            }
+        }
+        Some(CliffordGate {
+            qubits,
+            x_images,
+            z_images,
+        })
+    }
+
+    /// Returns the image of the Pauli operator with the given bits on the qubits of the gate.
+    fn image(&self, x: u64, z: u64) -> LocalPauli {
+        let mut image = LocalPauli {
+            x: 0,
+            z: 0,
+            phase: 0,
+        };
+        for position in 0..self.qubits.len() {
+            let (has_x, has_z) = ((x >> position) & 1 == 1, (z >> position) & 1 == 1);
+            if has_x && has_z {
+                // Y = i X Z
+                let y_image = self.x_images[position].multiply(&self.z_images[position]);
+                image = image.multiply(&LocalPauli {
+                    phase: (y_image.phase + 1) % 4,
+                    ..y_image
+                });
+            } else if has_x {
+                image = image.multiply(&self.x_images[position]);
+            } else if has_z {
+                image = image.multiply(&self.z_images[position]);
+            }
+        }
+        image
+    }
+}
+
+/// Returns the matrix of a single X (pauli = 0b10) or Z (pauli = 0b01) operator on the given bit.
+fn single_pauli_matrix(number_qubits: usize, bit: usize, pauli: u8) -> Array2<Complex64> {
+    let dimension = 1 << number_qubits;
+    let mut matrix = Array2::zeros((dimension, dimension));
+    for column in 0..dimension {
+        if pauli == 0b10 {
+            matrix[[column ^ (1 << bit), column]] = Complex64::new(1.0, 0.0);
+        } else {
+            let sign = if (column >> bit) & 1 == 1 { -1.0 } else { 1.0 };
+            matrix[[column, column]] = Complex64::new(sign, 0.0);
+        }
+    }
+    matrix
+}
+
+/// Returns the Pauli operator equal to a matrix, None if the matrix is not a Pauli operator
+/// with sign +1 or -1.
+///
+/// Bit j of the returned operator is the qubit given by bit (n - 1 - j) of the matrix basis.
+fn pauli_of_matrix(matrix: &Array2<Complex64>, number_qubits: usize) -> Option<LocalPauli> {
+    let dimension = 1 << number_qubits;
+    // The row of the entry of the first column gives the X part
+    let x_mask = (0..dimension).find(|row| matrix[[*row, 0]].norm() > 0.5)?;
+    let reference = matrix[[x_mask, 0]];
+    let mut z_mask = 0;
+    for bit in 0..number_qubits {
+        let ratio = matrix[[(1 << bit) ^ x_mask, 1 << bit]] / reference;
+        if (ratio + 1.0).norm() < CLIFFORD_TOLERANCE {
+            z_mask |= 1 << bit;
+        }
+    }
+    // The matrix is i^(number of Y) (-1)^sign X^x Z^z
+    let number_y = (x_mask & z_mask).count_ones();
+    let sign = reference / Complex64::i().powu(number_y);
+    let negative = if (sign - 1.0).norm() < CLIFFORD_TOLERANCE {
+        false
+    } else if (sign + 1.0).norm() < CLIFFORD_TOLERANCE {
+        true
+    } else {
+        return None;
+    };
+    for column in 0..dimension {
+        for row in 0..dimension {
+            let expected = if row == column ^ x_mask {
+                let parity = (z_mask & column).count_ones() % 2;
+                reference * if parity == 1 { -1.0 } else { 1.0 }
+            } else {
+                Complex64::new(0.0, 0.0)
+            };
+            if (matrix[[row, column]] - expected).norm() > CLIFFORD_TOLERANCE {
+                return None;
+            }
+        }
+    }
+    let reverse = |mask: usize| -> u64 {
+        (0..number_qubits)
+            .filter(|bit| (mask >> bit) & 1 == 1)
+            .fold(0, |reversed, bit| reversed | 1 << (number_qubits - 1 - bit))
+    };
+    Some(LocalPauli {
+        x: reverse(x_mask),
+        z: reverse(z_mask),
+        phase: if negative { 2 } else { 0 },
+    })
+}
+
+/// Stabilizer tableau of Aaronson and Gottesman
+///
+/// Rows 0..n are the destabilizers, rows n..2n the stabilizers and row 2n is scratch space.
+/// Each row is a Pauli operator with sign (-1)^r, with the X and Z bits packed in words.
+#[derive(Debug, Clone)]
+struct Tableau {
+    number_qubits: usize,
+    words: usize,
+    x: Vec<u64>,
+    z: Vec<u64>,
+    r: Vec<bool>,
+}
+
+impl Tableau {
+    /// Creates the tableau of the state |0...0>.
+    fn new(number_qubits: usize) -> Self {
+        let words = ((number_qubits + 63) / 64).max(1);
+        let rows = 2 * number_qubits + 1;
+        let mut tableau = Tableau {
+            number_qubits,
+            words,
+            x: vec![0; rows * words],
+            z: vec![0; rows * words],
+            r: vec![false; rows],
+        };
+        for qubit in 0..number_qubits {
+            tableau.set_x(qubit, qubit, true);
+            tableau.set_z(number_qubits + qubit, qubit, true);
+        }
+        tableau
+    }
+
+    fn x_bit(&self, row: usize, qubit: usize) -> bool {
+        (self.x[row * self.words + qubit / 64] >> (qubit % 64)) & 1 == 1
+    }
+
+    fn z_bit(&self, row: usize, qubit: usize) -> bool {
+        (self.z[row * self.words + qubit / 64] >> (qubit % 64)) & 1 == 1
+    }
+
+    fn set_x(&mut self, row: usize, qubit: usize, value: bool) {
+        let word = &mut self.x[row * self.words + qubit / 64];
+        *word = (*word & !(1 << (qubit % 64))) | ((value as u64) << (qubit % 64));
+    }
+
+    fn set_z(&mut self, row: usize, qubit: usize, value: bool) {
+        let word = &mut self.z[row * self.words + qubit / 64];
+        *word = (*word & !(1 << (qubit % 64))) | ((value as u64) << (qubit % 64));
+    }
+
+    /// Applies a Clifford gate by conjugating every row.
+    fn apply(&mut self, gate: &CliffordGate) {
+        for row in 0..2 * self.number_qubits {
+            let (mut x, mut z) = (0, 0);
+            for (position, qubit) in gate.qubits.iter().enumerate() {
+                x |= (self.x_bit(row, *qubit) as u64) << position;
+                z |= (self.z_bit(row, *qubit) as u64) << position;
+            }
+            if x == 0 && z == 0 {
+                continue;
+            }
+            let image = gate.image(x, z);
+            // Images of Hermitian operators have a real phase
+            self.r[row] ^= image.phase == 2;
+            for (position, qubit) in gate.qubits.iter().enumerate() {
+                self.set_x(row, *qubit, (image.x >> position) & 1 == 1);
+                self.set_z(row, *qubit, (image.z >> position) & 1 == 1);
+            }
+        }
+    }
+
+    /// Multiplies row `target` by row `source`, the rowsum operation of Aaronson and Gottesman.
+    fn rowsum(&mut self, target: usize, source: usize) {
+        let mut phase: i64 = 2 * (self.r[target] as i64 + self.r[source] as i64);
+        for word in 0..self.words {
+            let (source_index, target_index) =
+                (source * self.words + word, target * self.words + word);
+            let (plus, minus) = product_phase_words(
+                self.x[source_index],
+                self.z[source_index],
+                self.x[target_index],
+                self.z[target_index],
+            );
+            phase += plus.count_ones() as i64 - minus.count_ones() as i64;
+            self.x[target_index] ^= self.x[source_index];
+            self.z[target_index] ^= self.z[source_index];
+        }
+        self.r[target] = phase.rem_euclid(4) == 2;
+    }
+
+    /// Measures a qubit in the Z basis, returns true for outcome 1.
+    fn measure(&mut self, qubit: usize, rng: &mut StdRng) -> bool {
+        let number_qubits = self.number_qubits;
+        let random_row = (number_qubits..2 * number_qubits).find(|row| self.x_bit(*row, qubit));
+        match random_row {
+            Some(pivot) => {
+                for row in 0..2 * number_qubits {
+                    if row != pivot && self.x_bit(row, qubit) {
+                        self.rowsum(row, pivot);
+                    }
+                }
+                // The destabilizer takes over the stabilizer, the stabilizer becomes +-Z
+                let destabilizer = pivot - number_qubits;
+                let words = self.words;
+                self.x
+                    .copy_within(pivot * words..(pivot + 1) * words, destabilizer * words);
+                self.z
+                    .copy_within(pivot * words..(pivot + 1) * words, destabilizer * words);
+                self.r[destabilizer] = self.r[pivot];
+                self.x[pivot * words..(pivot + 1) * words].fill(0);
+                self.z[pivot * words..(pivot + 1) * words].fill(0);
+                self.set_z(pivot, qubit, true);
+                let outcome = rng.gen::<bool>();
+                self.r[pivot] = outcome;
+                outcome
+            }
+            None => {
+                let scratch = 2 * number_qubits;
+                let words = self.words;
+                self.x[scratch * words..(scratch + 1) * words].fill(0);
+                self.z[scratch * words..(scratch + 1) * words].fill(0);
+                self.r[scratch] = false;
+                for row in 0..number_qubits {
+                    if self.x_bit(row, qubit) {
+                        self.rowsum(scratch, row + number_qubits);
+                    }
+                }
+                self.r[scratch]
+            }
+        }
+    }
+
+    /// Applies a Pauli X to a qubit, flipping the sign of the rows anticommuting with it.
+    fn flip(&mut self, qubit: usize) {
+        for row in 0..2 * self.number_qubits {
+            if self.z_bit(row, qubit) {
+                self.r[row] ^= true;
+            }
+        }
+    }
+}
+
+/// Operation of a circuit run by the stabilizer simulation.
+#[derive(Debug, Clone)]
+enum StabilizerStep {
+    /// A Clifford gate.
+    Gate(CliffordGate),
+    /// Measurement of a qubit into a bit of a register.
+    Measure {
+        qubit: usize,
+        readout: String,
+        index: usize,
+    },
+    /// Measurement of all qubits into a register, with the index of each qubit.
+    MeasureAll {
+        readout: String,
+        mapping: Option<HashMap<usize, usize>>,
+    },
+    /// Reset of a qubit to state |0>.
+    Reset(usize),
+}
+
+/// Circuit compiled for the stabilizer simulation
+#[derive(Debug, Clone)]
+pub(crate) struct StabilizerCircuit {
+    /// The number of qubits of the circuit.
+    number_qubits: usize,
+    /// The bit registers of the circuit with their length and whether they are returned.
+    bit_registers: Vec<(String, usize, bool)>,
+    /// The float registers returned by the circuit with their length.
+    ///
+    /// No operation of a compiled circuit writes to float or complex registers, every shot returns
+    /// them with their initial value 0.
+    float_registers: Vec<(String, usize)>,
+    /// The complex registers returned by the circuit with their length.
+    complex_registers: Vec<(String, usize)>,
+    /// The number of times the circuit is run.
+    shots: usize,
+    /// The steps of the circuit.
+    steps: Vec<StabilizerStep>,
+}
+
+impl StabilizerCircuit {
+    /// Compiles a sequence of operations for the stabilizer simulation.
+    ///
+    /// # Arguments
+    ///
+    /// * `operations` - The operations of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StabilizerCircuit)` - The compiled circuit.
+    /// * `Err(RoqoqoBackendError)` - An operation is not a Clifford gate or not supported by the stabilizer simulation, or there is more than one repeated measurement.
+    pub(crate) fn compile(operations: &[&Operation]) -> Result<Self, RoqoqoBackendError> {
+        let mut circuit = StabilizerCircuit {
+            number_qubits: 0,
+            bit_registers: Vec::new(),
+            float_registers: Vec::new(),
+            complex_registers: Vec::new(),
+            shots: 1,
+            steps: Vec::new(),
+        };
+        let mut repeated_readout: Option<String> = None;
+        let mut set_repetitions = |readout: &str, number_measurements: usize| {
+            if let Some(previous) = repeated_readout.replace(readout.to_string()) {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!("Only one repeated measurement allowed, trying to run repeated measurement for {} but already used for {}", readout, previous),
+                });
+            }
+            Ok(number_measurements)
+        };
+        for (position, operation) in operations.iter().enumerate() {
+            if let InvolvedQubits::Set(qubits) = operation.involved_qubits() {
+                if let Some(qubit) = qubits.into_iter().max() {
+                    circuit.number_qubits = circuit.number_qubits.max(qubit + 1);
+                }
+            }
+            let unsupported = |reason: &str| {
+                RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The stabilizer simulation does not support the {} operation at position {}: {}",
+                    operation.hqslang(),
+                    position,
+                    reason
+                ),
+            }
+            };
+            match operation {
+                Operation::DefinitionBit(definition) => circuit.bit_registers.push((
+                    definition.name().clone(),
+                    *definition.length(),
+                    *definition.is_output(),
+                )),
+                Operation::DefinitionFloat(definition) => {
+                    if *definition.is_output() {
+                        circuit
+                            .float_registers
+                            .push((definition.name().clone(), *definition.length()));
+                    }
+                }
+                Operation::DefinitionComplex(definition) => {
+                    if *definition.is_output() {
+                        circuit
+                            .complex_registers
+                            .push((definition.name().clone(), *definition.length()));
+                    }
+                }
+                Operation::DefinitionUsize(_) => (),
+                Operation::MeasureQubit(measure) => circuit.steps.push(StabilizerStep::Measure {
+                    qubit: *measure.qubit(),
+                    readout: measure.readout().clone(),
+                    index: *measure.readout_index(),
+                }),
+                Operation::PragmaRepeatedMeasurement(measure) => {
+                    circuit.shots =
+                        set_repetitions(measure.readout(), *measure.number_measurements())?;
+                    circuit.steps.push(StabilizerStep::MeasureAll {
+                        readout: measure.readout().clone(),
+                        mapping: measure.qubit_mapping().clone(),
+                    });
+                }
+                Operation::PragmaSetNumberOfMeasurements(pragma) => {
+                    circuit.shots =
+                        set_repetitions(pragma.readout(), *pragma.number_measurements())?;
+                }
+                Operation::PragmaActiveReset(reset) => {
+                    circuit.steps.push(StabilizerStep::Reset(*reset.qubit()))
+                }
+                Operation::PragmaGlobalPhase(_)
+                | Operation::PragmaStopParallelBlock(_)
+                | Operation::PragmaStartDecompositionBlock(_)
+                | Operation::PragmaStopDecompositionBlock(_)
+                | Operation::PragmaSleep(_)
+                | Operation::PragmaChangeDevice(_) => (),
+                Operation::PragmaGetStateVector(_)
+                | Operation::PragmaGetDensityMatrix(_)
+                | Operation::PragmaGetOccupationProbability(_)
+                | Operation::PragmaGetPauliProduct(_) => {
+                    return Err(unsupported(
+                        "float and complex registers are not computed, use the state_vector simulation mode.",
+                    ))
+                }
+                _ => {
+                    let gate = GateOperation::try_from((*operation).clone()).map_err(|_| {
+                        unsupported(
+                            "only gates, measurements, resets and definitions are supported.",
+                        )
+                    })?;
+                    let qubits = gate_qubits(operation)
+                        .ok_or_else(|| unsupported("the qubits of the gate are unknown."))?;
+                    if qubits.len() > MAX_STABILIZER_GATE_QUBITS {
+                        return Err(unsupported(&format!(
+                            "gates on more than {} qubits are not supported.",
+                            MAX_STABILIZER_GATE_QUBITS
+                        )));
+                    }
+                    let unitary = gate
+                        .unitary_matrix()
+                        .map_err(|err| unsupported(&format!("{}", err)))?;
+                    let clifford = CliffordGate::from_unitary(qubits, &unitary)
+                        .ok_or_else(|| unsupported("the gate is not a Clifford gate."))?;
+                    circuit.steps.push(StabilizerStep::Gate(clifford));
+                }
+            }
+        }
+        Ok(circuit)
+    }
+
+    /// Runs the circuit once per shot.
+    ///
+    /// The gates before the first measurement or reset are applied once for all shots. Every
+    /// shot adds one entry to each output register.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator of the measurement outcomes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Registers)` - The output registers of the runs.
+    /// * `Err(RoqoqoBackendError)` - A measurement writes to a missing register or beyond its end.
+    pub(crate) fn run(&self, rng: &mut StdRng) -> RegisterResult {
+        let mut initial = Tableau::new(self.number_qubits);
+        let prefix = self
+            .steps
+            .iter()
+            .take_while(|step| matches!(step, StabilizerStep::Gate(_)))
+            .count();
+        for step in self.steps[..prefix].iter() {
+            if let StabilizerStep::Gate(gate) = step {
+                initial.apply(gate);
+            }
+        }
+
+        let mut bit_output: HashMap<String, BitOutputRegister> = self
+            .bit_registers
+            .iter()
+            .filter(|(_, _, is_output)| *is_output)
+            .map(|(name, _, _)| (name.clone(), Vec::with_capacity(self.shots)))
+            .collect();
+        for _ in 0..self.shots {
+            let mut tableau = initial.clone();
+            let mut bits: HashMap<&str, Vec<bool>> = self
+                .bit_registers
+                .iter()
+                .map(|(name, length, _)| (name.as_str(), vec![false; *length]))
+                .collect();
+            for step in self.steps[prefix..].iter() {
+                match step {
+                    StabilizerStep::Gate(gate) => tableau.apply(gate),
+                    StabilizerStep::Measure {
+                        qubit,
+                        readout,
+                        index,
+                    } => {
+                        let outcome = tableau.measure(*qubit, rng);
+                        write_bit(&mut bits, readout, *index, outcome)?;
+                    }
+                    StabilizerStep::MeasureAll { readout, mapping } => {
+                        for qubit in 0..self.number_qubits {
+                            let outcome = tableau.measure(qubit, rng);
+                            let index = mapping
+                                .as_ref()
+                                .and_then(|mapping| mapping.get(&qubit))
+                                .copied()
+                                .unwrap_or(qubit);
+                            write_bit(&mut bits, readout, index, outcome)?;
+                        }
+                    }
+                    StabilizerStep::Reset(qubit) => {
+                        if tableau.measure(*qubit, rng) {
+                            tableau.flip(*qubit);
+                        }
+                    }
+                }
+            }
+            for (name, register) in bit_output.iter_mut() {
+                if let Some(shot) = bits.remove(name.as_str()) {
+                    register.push(shot);
+                }
+            }
+        }
+
+        let float_output: HashMap<String, FloatOutputRegister> = self
+            .float_registers
+            .iter()
+            .map(|(name, length)| (name.clone(), vec![vec![0.0; *length]; self.shots]))
+            .collect();
+        let complex_output: HashMap<String, ComplexOutputRegister> = self
+            .complex_registers
+            .iter()
+            .map(|(name, length)| {
+                (
+                    name.clone(),
+                    vec![vec![Complex64::new(0.0, 0.0); *length]; self.shots],
+                )
+            })
+            .collect();
+        Ok((bit_output, float_output, complex_output))
+    }
+}
+
+/// Writes a measured bit to a register.
+fn write_bit(
+    bits: &mut HashMap<&str, Vec<bool>>,
+    readout: &str,
+    index: usize,
+    outcome: bool,
+) -> Result<(), RoqoqoBackendError> {
+    let register = bits
+        .get_mut(readout)
+        .ok_or_else(|| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Trying to write readout to non-existent register {}",
+                readout
+            ),
+        })?;
+    let length = register.len();
+    let bit = register
+        .get_mut(index)
+        .ok_or_else(|| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Trying to write bit {} of register {} of length {}",
+                index, readout, length
+            ),
+        })?;
+    *bit = outcome;
+    Ok(())
+}
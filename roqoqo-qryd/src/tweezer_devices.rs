@@ -17,23 +17,31 @@
 //! QRyd devices can be physical hardware or simulators.
 
 use bincode::deserialize;
-use itertools::{iproduct, Itertools};
+use itertools::Itertools;
 use ndarray::Array2;
 use std::{
     collections::{HashMap, HashSet},
-    env,
     str::FromStr,
+    sync::Arc,
 };
 
+#[cfg(feature = "web-api")]
+use crate::device_cache::{download_device, DownloadOptions};
+use crate::layout_indices::{LayoutIndex, LayoutIndexCache};
 use crate::{
-    phi_theta_relation, PragmaDeactivateQRydQubit, PragmaShiftQubitsTweezers,
-    PragmaSwitchDeviceLayout,
+    lattice_layout, phi_theta_relation, DeviceSnapshot, InteractionModel,
+    PragmaDeactivateQRydQubit, PragmaShiftQubitsTweezers, PragmaSwitchDeviceLayout,
+    QubitTweezerMapping, ReadoutConfusionMatrix, TweezerLattice,
 };
 
 use image::DynamicImage;
 use roqollage::render_typst_str;
 use roqoqo::{
     devices::{Device, GenericDevice},
+    operations::{
+        Operate, OperateSingleQubit, OperateThreeQubit, OperateTwoQubit, Operation,
+        SingleQubitGateOperation, ThreeQubitGateOperation, TwoQubitGateOperation,
+    },
     RoqoqoBackendError, RoqoqoError,
 };
 
@@ -65,12 +73,15 @@ pub static ALLOWED_NATIVE_MULTI_QUBIT_GATES: [&str; 0] = [];
 
 /// Tweezer Device
 ///
-#[derive(Debug, PartialEq, Default, Clone, serde::Serialize, serde::Deserialize)]
+/// Serialized with the fields below, except the skipped ones. Binary formats like bincode have
+/// no field names and are prefixed with a format version instead, so that devices serialized by
+/// releases without tweezer positions, decoherence rates and error rates can still be read.
+#[derive(Debug, Default, Clone)]
 pub struct TweezerDevice {
-    /// Mapping from qubit to tweezer.
-    pub qubit_to_tweezer: Option<HashMap<usize, usize>>,
-    /// Register of Layouts.
-    pub layout_register: Option<HashMap<String, TweezerLayoutInfo>>,
+    /// Mapping from qubit to tweezer, see `qubit_to_tweezer()` and `set_qubit_to_tweezer()`.
+    pub(crate) qubit_to_tweezer: Option<QubitTweezerMapping>,
+    /// Register of Layouts, see `layout_register()` and `layout_register_mut()`.
+    pub(crate) layout_register: Option<HashMap<String, TweezerLayoutInfo>>,
     /// Current Layout.
     pub current_layout: Option<String>,
     /// The specific PhaseShiftedControlledZ relation to use.
@@ -86,8 +97,48 @@ pub struct TweezerDevice {
     /// Device name.
     pub device_name: String,
     /// Available gates (EmulatorDevice).
-    #[serde(default)]
     pub available_gates: Option<Vec<String>>,
+    /// Physical positions of the tweezers, by Layout name.
+    pub(crate) tweezer_positions: HashMap<String, TweezerPositions>,
+    /// Whether the device is a cached copy, used because the QRyd WebAPI was unreachable.
+    /// Not serialized.
+    pub(crate) offline_fallback: bool,
+    /// Lookup indices of the Layouts, built on first use and dropped when a Layout changes.
+    /// Not serialized.
+    pub(crate) layout_indices: LayoutIndexCache,
+}
+
+// Whether the device is an offline fallback and its cached lookup indices are not part of the
+// device definition and are left out of the comparison.
+impl PartialEq for TweezerDevice {
+    fn eq(&self, other: &Self) -> bool {
+        let TweezerDevice {
+            qubit_to_tweezer,
+            layout_register,
+            current_layout,
+            controlled_z_phase_relation,
+            controlled_phase_phase_relation,
+            default_layout,
+            seed,
+            allow_reset,
+            device_name,
+            available_gates,
+            tweezer_positions,
+            offline_fallback: _,
+            layout_indices: _,
+        } = self;
+        *qubit_to_tweezer == other.qubit_to_tweezer
+            && *layout_register == other.layout_register
+            && *current_layout == other.current_layout
+            && *controlled_z_phase_relation == other.controlled_z_phase_relation
+            && *controlled_phase_phase_relation == other.controlled_phase_phase_relation
+            && *default_layout == other.default_layout
+            && *seed == other.seed
+            && *allow_reset == other.allow_reset
+            && *device_name == other.device_name
+            && *available_gates == other.available_gates
+            && *tweezer_positions == other.tweezer_positions
+    }
 }
 
 /// Tweezers information relative to a Layout
@@ -114,6 +165,109 @@ pub struct TweezerLayoutInfo {
     /// Specifies how many tweezers per row are present. Dynamic layout switching is only allowed between layouts
     /// having the same number of tweezers per row.
     pub tweezers_per_row: Option<Vec<usize>>,
+    /// Maps a tweezer to the decoherence rates of a qubit held in it.
+    /// Tweezers without rates are noise free.
+    pub tweezer_decoherence_rates: HashMap<usize, TweezerDecoherenceRates>,
+    /// Maps a single-qubit gate name to a tweezer -> error rate mapping
+    pub tweezer_single_qubit_gate_errors: HashMap<String, HashMap<usize, f64>>,
+    /// Maps a two-qubit gate name to a (tweezer, tweezer) -> error rate mapping
+    pub tweezer_two_qubit_gate_errors: HashMap<String, HashMap<(usize, usize), f64>>,
+    /// Maps a three-qubit gate name to a (tweezer, tweezer, tweezer) -> error rate mapping
+    pub tweezer_three_qubit_gate_errors: HashMap<String, HashMap<(usize, usize, usize), f64>>,
+    /// Maps a tweezer to the readout confusion matrix of the qubit held in it.
+    /// Gates and tweezers without error rates are error free.
+    pub tweezer_readout_errors: HashMap<usize, ReadoutConfusionMatrix>,
+}
+
+/// Physical positions of the tweezers of a Layout
+///
+#[derive(Debug, Default, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TweezerPositions {
+    /// The number of coordinates given for each tweezer, 2 or 3.
+    pub number_dimensions: usize,
+    /// Maps a tweezer to its (x, y, z) position in micrometers.
+    /// Two-dimensional positions have z = 0.
+    pub positions: HashMap<usize, (f64, f64, f64)>,
+}
+
+impl TweezerPositions {
+    /// Returns the position of a tweezer.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((f64, f64, f64))` - The (x, y, z) coordinates of the tweezer in micrometers.
+    /// * `Err(RoqoqoBackendError)` - No position is set for the tweezer.
+    pub fn position(&self, tweezer: usize) -> Result<(f64, f64, f64), RoqoqoBackendError> {
+        self.positions
+            .get(&tweezer)
+            .copied()
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: format!("No position is set for tweezer {}.", tweezer),
+            })
+    }
+}
+
+/// Decoherence rates of a qubit held in a tweezer
+///
+/// The rates follow the conventions of the roqoqo GenericDevice `add_damping`, `add_dephasing`
+/// and `add_depolarising` methods.
+#[derive(Debug, Default, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct TweezerDecoherenceRates {
+    /// The damping rate, 1/T1.
+    pub damping: f64,
+    /// The pure dephasing rate.
+    pub dephasing: f64,
+    /// The depolarising rate.
+    pub depolarising: f64,
+}
+
+impl TweezerDecoherenceRates {
+    /// Creates the decoherence rates from the T1 and T2 times.
+    ///
+    /// The damping rate is 1/T1. The coherences decay as exp(-t/(2 T1)) under damping and as
+    /// exp(-2 rate t) under dephasing in roqoqo, so the dephasing rate is (1/T2 - 1/(2 T1)) / 2.
+    ///
+    /// # Arguments
+    ///
+    /// * `t1` - The relaxation time T1.
+    /// * `t2` - The coherence time T2, at most 2 T1.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TweezerDecoherenceRates)` - The decoherence rates without depolarisation.
+    /// * `Err(RoqoqoBackendError)` - The times are not positive or T2 is larger than 2 T1.
+    pub fn from_t1_t2(t1: f64, t2: f64) -> Result<Self, RoqoqoBackendError> {
+        if !(t1 > 0.0 && t2 > 0.0) || t2 > 2.0 * t1 {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The times T1 = {} and T2 = {} need to be positive with T2 <= 2 T1.",
+                    t1, t2
+                ),
+            });
+        }
+        Ok(Self {
+            damping: 1.0 / t1,
+            dephasing: (1.0 / t2 - 0.5 / t1) / 2.0,
+            depolarising: 0.0,
+        })
+    }
+
+    /// Returns the decoherence rates matrix of the roqoqo Device trait.
+    ///
+    /// # Returns
+    ///
+    /// * `Array2<f64>` - The 3x3 decoherence rates matrix.
+    pub fn rates_matrix(&self) -> Array2<f64> {
+        let mut rates = Array2::zeros((3, 3));
+        rates[[0, 0]] = self.damping + self.depolarising / 2.0;
+        rates[[1, 1]] = self.depolarising / 2.0;
+        rates[[2, 2]] = self.dephasing + self.depolarising / 4.0;
+        rates
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
@@ -130,6 +284,21 @@ struct TweezerLayoutInfoSerialize {
     allowed_tweezer_shifts: Vec<(usize, Vec<Vec<usize>>)>,
     /// Specifies how many tweezers per row are present.
     tweezers_per_row: Option<Vec<usize>>,
+    /// Decoherence rates of the tweezers
+    #[serde(default)]
+    tweezer_decoherence_rates: Vec<(usize, TweezerDecoherenceRates)>,
+    /// Maps a single-qubit gate name to a tweezer -> error rate mapping
+    #[serde(default)]
+    tweezer_single_qubit_gate_errors: Vec<(String, SingleTweezerTimes)>,
+    /// Maps a two-qubit gate name to a (tweezer, tweezer) -> error rate mapping
+    #[serde(default)]
+    tweezer_two_qubit_gate_errors: Vec<(String, TwoTweezersTimes)>,
+    /// Maps a three-qubit gate name to a (tweezer, tweezer, tweezer) -> error rate mapping
+    #[serde(default)]
+    tweezer_three_qubit_gate_errors: Vec<(String, ThreeTweezersTimes)>,
+    /// Readout confusion matrices of the tweezers
+    #[serde(default)]
+    tweezer_readout_errors: Vec<(usize, ReadoutConfusionMatrix)>,
 }
 type SingleTweezerTimes = Vec<(usize, f64)>;
 type TwoTweezersTimes = Vec<((usize, usize), f64)>;
@@ -161,6 +330,25 @@ impl From<TweezerLayoutInfoSerialize> for TweezerLayoutInfo {
         let allowed_tweezer_shifts: HashMap<usize, Vec<Vec<usize>>> =
             info.allowed_tweezer_shifts.into_iter().collect();
         let tweezers_per_row = info.tweezers_per_row;
+        let tweezer_decoherence_rates: HashMap<usize, TweezerDecoherenceRates> =
+            info.tweezer_decoherence_rates.into_iter().collect();
+        let tweezer_single_qubit_gate_errors: HashMap<String, HashMap<usize, f64>> = info
+            .tweezer_single_qubit_gate_errors
+            .into_iter()
+            .map(|(k, v)| (k, v.into_iter().collect()))
+            .collect();
+        let tweezer_two_qubit_gate_errors: HashMap<String, HashMap<(usize, usize), f64>> = info
+            .tweezer_two_qubit_gate_errors
+            .into_iter()
+            .map(|(k, v)| (k, v.into_iter().collect()))
+            .collect();
+        let tweezer_three_qubit_gate_errors: HashMap<String, HashMap<(usize, usize, usize), f64>> =
+            info.tweezer_three_qubit_gate_errors
+                .into_iter()
+                .map(|(k, v)| (k, v.into_iter().collect()))
+                .collect();
+        let tweezer_readout_errors: HashMap<usize, ReadoutConfusionMatrix> =
+            info.tweezer_readout_errors.into_iter().collect();
 
         Self {
             tweezer_single_qubit_gate_times,
@@ -169,6 +357,11 @@ impl From<TweezerLayoutInfoSerialize> for TweezerLayoutInfo {
             tweezer_multi_qubit_gate_times,
             allowed_tweezer_shifts,
             tweezers_per_row,
+            tweezer_decoherence_rates,
+            tweezer_single_qubit_gate_errors,
+            tweezer_two_qubit_gate_errors,
+            tweezer_three_qubit_gate_errors,
+            tweezer_readout_errors,
         }
     }
 }
@@ -198,6 +391,25 @@ impl From<TweezerLayoutInfo> for TweezerLayoutInfoSerialize {
         let allowed_tweezer_shifts: Vec<(usize, Vec<Vec<usize>>)> =
             info.allowed_tweezer_shifts.into_iter().collect();
         let tweezers_per_row = info.tweezers_per_row;
+        let tweezer_decoherence_rates: Vec<(usize, TweezerDecoherenceRates)> =
+            info.tweezer_decoherence_rates.into_iter().collect();
+        let tweezer_single_qubit_gate_errors: Vec<(String, SingleTweezerTimes)> = info
+            .tweezer_single_qubit_gate_errors
+            .into_iter()
+            .map(|(k, v)| (k, v.into_iter().collect()))
+            .collect();
+        let tweezer_two_qubit_gate_errors: Vec<(String, TwoTweezersTimes)> = info
+            .tweezer_two_qubit_gate_errors
+            .into_iter()
+            .map(|(k, v)| (k, v.into_iter().collect()))
+            .collect();
+        let tweezer_three_qubit_gate_errors: Vec<(String, ThreeTweezersTimes)> = info
+            .tweezer_three_qubit_gate_errors
+            .into_iter()
+            .map(|(k, v)| (k, v.into_iter().collect()))
+            .collect();
+        let tweezer_readout_errors: Vec<(usize, ReadoutConfusionMatrix)> =
+            info.tweezer_readout_errors.into_iter().collect();
 
         Self {
             tweezer_single_qubit_gate_times,
@@ -206,6 +418,231 @@ impl From<TweezerLayoutInfo> for TweezerLayoutInfoSerialize {
             tweezer_multi_qubit_gate_times,
             allowed_tweezer_shifts,
             tweezers_per_row,
+            tweezer_decoherence_rates,
+            tweezer_single_qubit_gate_errors,
+            tweezer_two_qubit_gate_errors,
+            tweezer_three_qubit_gate_errors,
+            tweezer_readout_errors,
+        }
+    }
+}
+
+/// Serialized form of a TweezerLayoutInfo from before the decoherence rates were added.
+#[derive(serde::Deserialize)]
+struct TweezerLayoutInfoWithoutDecoherence {
+    tweezer_single_qubit_gate_times: Vec<(String, SingleTweezerTimes)>,
+    tweezer_two_qubit_gate_times: Vec<(String, TwoTweezersTimes)>,
+    tweezer_three_qubit_gate_times: Vec<(String, ThreeTweezersTimes)>,
+    tweezer_multi_qubit_gate_times: Vec<(String, MultiTweezersTimes)>,
+    allowed_tweezer_shifts: Vec<(usize, Vec<Vec<usize>>)>,
+    tweezers_per_row: Option<Vec<usize>>,
+}
+
+impl From<TweezerLayoutInfoWithoutDecoherence> for TweezerLayoutInfo {
+    fn from(info: TweezerLayoutInfoWithoutDecoherence) -> Self {
+        TweezerLayoutInfoSerialize {
+            tweezer_single_qubit_gate_times: info.tweezer_single_qubit_gate_times,
+            tweezer_two_qubit_gate_times: info.tweezer_two_qubit_gate_times,
+            tweezer_three_qubit_gate_times: info.tweezer_three_qubit_gate_times,
+            tweezer_multi_qubit_gate_times: info.tweezer_multi_qubit_gate_times,
+            allowed_tweezer_shifts: info.allowed_tweezer_shifts,
+            tweezers_per_row: info.tweezers_per_row,
+            tweezer_decoherence_rates: Vec::new(),
+            tweezer_single_qubit_gate_errors: Vec::new(),
+            tweezer_two_qubit_gate_errors: Vec::new(),
+            tweezer_three_qubit_gate_errors: Vec::new(),
+            tweezer_readout_errors: Vec::new(),
+        }
+        .into()
+    }
+}
+
+/// Version of the binary serialized form of a TweezerDevice, serialized before its fields.
+///
+/// The bincode form of the released TweezerDevice, without version, starts with the Option tag
+/// of the qubit -> tweezer mapping, 0 or 1.
+const BINARY_FORMAT_VERSION: u8 = 2;
+
+/// Serialized fields of a TweezerDevice.
+#[derive(serde::Serialize)]
+struct TweezerDeviceSerializeRef<'a> {
+    qubit_to_tweezer: &'a Option<QubitTweezerMapping>,
+    layout_register: &'a Option<HashMap<String, TweezerLayoutInfo>>,
+    current_layout: &'a Option<String>,
+    controlled_z_phase_relation: &'a String,
+    controlled_phase_phase_relation: &'a String,
+    default_layout: &'a Option<String>,
+    seed: &'a Option<usize>,
+    allow_reset: &'a bool,
+    device_name: &'a String,
+    available_gates: &'a Option<Vec<String>>,
+    tweezer_positions: &'a HashMap<String, TweezerPositions>,
+}
+
+/// Deserialized fields of a TweezerDevice.
+///
+/// Human readable definitions without available gates or tweezer positions stay loadable.
+#[derive(serde::Deserialize)]
+struct TweezerDeviceSerialize {
+    qubit_to_tweezer: Option<QubitTweezerMapping>,
+    layout_register: Option<HashMap<String, TweezerLayoutInfo>>,
+    current_layout: Option<String>,
+    controlled_z_phase_relation: String,
+    controlled_phase_phase_relation: String,
+    default_layout: Option<String>,
+    seed: Option<usize>,
+    allow_reset: bool,
+    device_name: String,
+    #[serde(default)]
+    available_gates: Option<Vec<String>>,
+    #[serde(default)]
+    tweezer_positions: HashMap<String, TweezerPositions>,
+}
+
+impl From<TweezerDeviceSerialize> for TweezerDevice {
+    fn from(device: TweezerDeviceSerialize) -> Self {
+        TweezerDevice {
+            qubit_to_tweezer: device.qubit_to_tweezer,
+            layout_register: device.layout_register,
+            current_layout: device.current_layout,
+            controlled_z_phase_relation: device.controlled_z_phase_relation,
+            controlled_phase_phase_relation: device.controlled_phase_phase_relation,
+            default_layout: device.default_layout,
+            seed: device.seed,
+            allow_reset: device.allow_reset,
+            device_name: device.device_name,
+            available_gates: device.available_gates,
+            tweezer_positions: device.tweezer_positions,
+            offline_fallback: false,
+            layout_indices: LayoutIndexCache::default(),
+        }
+    }
+}
+
+/// Binary serialized fields of the released TweezerDevice, following its qubit -> tweezer mapping.
+///
+/// bincode has no field names, so the missing positions cannot be filled in by `#[serde(default)]`.
+#[derive(serde::Deserialize)]
+struct TweezerDeviceWithoutPositions {
+    layout_register: Option<HashMap<String, TweezerLayoutInfoWithoutDecoherence>>,
+    current_layout: Option<String>,
+    controlled_z_phase_relation: String,
+    controlled_phase_phase_relation: String,
+    default_layout: Option<String>,
+    seed: Option<usize>,
+    allow_reset: bool,
+    device_name: String,
+    available_gates: Option<Vec<String>>,
+}
+
+impl TweezerDeviceWithoutPositions {
+    /// Returns the TweezerDevice with the given qubit -> tweezer mapping.
+    fn into_device(self, qubit_to_tweezer: Option<QubitTweezerMapping>) -> TweezerDevice {
+        TweezerDevice {
+            qubit_to_tweezer,
+            layout_register: self.layout_register.map(|register| {
+                register
+                    .into_iter()
+                    .map(|(name, info)| (name, info.into()))
+                    .collect()
+            }),
+            current_layout: self.current_layout,
+            controlled_z_phase_relation: self.controlled_z_phase_relation,
+            controlled_phase_phase_relation: self.controlled_phase_phase_relation,
+            default_layout: self.default_layout,
+            seed: self.seed,
+            allow_reset: self.allow_reset,
+            device_name: self.device_name,
+            available_gates: self.available_gates,
+            tweezer_positions: HashMap::new(),
+            offline_fallback: false,
+            layout_indices: LayoutIndexCache::default(),
+        }
+    }
+}
+
+impl serde::Serialize for TweezerDevice {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let fields = TweezerDeviceSerializeRef {
+            qubit_to_tweezer: &self.qubit_to_tweezer,
+            layout_register: &self.layout_register,
+            current_layout: &self.current_layout,
+            controlled_z_phase_relation: &self.controlled_z_phase_relation,
+            controlled_phase_phase_relation: &self.controlled_phase_phase_relation,
+            default_layout: &self.default_layout,
+            seed: &self.seed,
+            allow_reset: &self.allow_reset,
+            device_name: &self.device_name,
+            available_gates: &self.available_gates,
+            tweezer_positions: &self.tweezer_positions,
+        };
+        if serializer.is_human_readable() {
+            fields.serialize(serializer)
+        } else {
+            (BINARY_FORMAT_VERSION, fields).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TweezerDevice {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            TweezerDeviceSerialize::deserialize(deserializer).map(TweezerDevice::from)
+        } else {
+            // The released form reads the mapping and the remaining fields after its Option tag
+            deserializer.deserialize_tuple(3, BinaryTweezerDeviceVisitor)
+        }
+    }
+}
+
+/// Visitor of the binary serialized forms of a TweezerDevice.
+struct BinaryTweezerDeviceVisitor;
+
+impl<'de> serde::de::Visitor<'de> for BinaryTweezerDeviceVisitor {
+    type Value = TweezerDevice;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a binary serialized TweezerDevice")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        use serde::de::Error;
+
+        let version: u8 = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        match version {
+            BINARY_FORMAT_VERSION => seq
+                .next_element::<TweezerDeviceSerialize>()?
+                .map(TweezerDevice::from)
+                .ok_or_else(|| A::Error::invalid_length(1, &self)),
+            // Released form, the version is the Option tag of the qubit -> tweezer mapping
+            0 | 1 => {
+                let qubit_to_tweezer = if version == 1 {
+                    Some(
+                        seq.next_element::<QubitTweezerMapping>()?
+                            .ok_or_else(|| A::Error::invalid_length(1, &self))?,
+                    )
+                } else {
+                    None
+                };
+                seq.next_element::<TweezerDeviceWithoutPositions>()?
+                    .map(|device| device.into_device(qubit_to_tweezer))
+                    .ok_or_else(|| A::Error::invalid_length(2, &self))
+            }
+            _ => Err(A::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(version.into()),
+                &self,
+            )),
         }
     }
 }
@@ -245,13 +682,38 @@ impl TweezerDevice {
             allow_reset: false,
             device_name: String::from("qryd_tweezer_device"),
             available_gates: None,
+            tweezer_positions: HashMap::new(),
+            offline_fallback: false,
+            layout_indices: LayoutIndexCache::default(),
         }
     }
 
+    /// Deserializes a TweezerDevice from its bincode representation.
+    ///
+    /// Devices serialized by releases without tweezer positions are read without positions,
+    /// decoherence rates and error rates, as by `bincode::deserialize`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bincode representation of the TweezerDevice.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TweezerDevice)` - The deserialized TweezerDevice.
+    /// * `Err(RoqoqoBackendError)` - The bytes are not a serialized TweezerDevice.
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, RoqoqoBackendError> {
+        deserialize::<TweezerDevice>(bytes).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!("Could not deserialize the TweezerDevice: {}", err),
+        })
+    }
+
     /// Creates a new TweezerDevice instance containing populated tweezer data.
     ///
     /// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
     ///
+    /// Uses the `DownloadOptions` configured via environmental variables, see
+    /// `from_api_with_options` to set them explicitly.
+    ///
     /// # Arguments
     ///
     /// * `device_name` - The name of the device to instantiate. Defaults to "qryd_emulator".
@@ -279,118 +741,132 @@ impl TweezerDevice {
         dev: Option<bool>,
         api_version: Option<String>,
     ) -> Result<Self, RoqoqoBackendError> {
-        // Preparing variables
+        TweezerDevice::from_api_with_options(
+            device_name,
+            access_token,
+            mock_port,
+            seed,
+            dev,
+            api_version,
+            &DownloadOptions::from_env(),
+        )
+    }
+
+    /// Creates a new TweezerDevice instance containing populated tweezer data, with explicit options.
+    ///
+    /// This requires a valid QRYD_API_TOKEN. Visit `https://thequantumlaend.de/get-access/` to get one.
+    ///
+    /// If `options.validate` is set, the Layouts of the downloaded device are checked with
+    /// `validate()` and any issue is returned as an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_name` - The name of the device to instantiate. Defaults to "qryd_emulator".
+    /// * `access_token` - An access_token is required to access QRYD hardware and emulators.
+    ///                    The access_token can either be given as an argument here
+    ///                         or set via the environmental variable `$QRYD_API_TOKEN`.
+    /// * `mock_port` - The address of the Mock server, used for testing purposes.
+    /// * `seed` - Optionally overwrite seed value from downloaded device instance.
+    /// * `dev` - The boolean to set the dev header to.
+    /// * `api_version` - The version of the QRYD API to use. Defaults to "v1_1".
+    /// * `options` - The options of the download, e.g. the device cache to use.
+    ///
+    /// # Returns
+    ///
+    /// * `TweezerDevice` - The new TweezerDevice instance with populated tweezer data.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError`
+    #[cfg(feature = "web-api")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            name = "from_api",
+            skip_all,
+            fields(
+                device_name = ?device_name,
+                api_version = ?api_version,
+                dev = ?dev,
+                mock = mock_port.is_some(),
+                cache = options.cache.is_some()
+            )
+        )
+    )]
+    pub fn from_api_with_options(
+        device_name: Option<String>,
+        access_token: Option<String>,
+        mock_port: Option<String>,
+        seed: Option<usize>,
+        dev: Option<bool>,
+        api_version: Option<String>,
+        options: &DownloadOptions,
+    ) -> Result<Self, RoqoqoBackendError> {
         let device_name_internal = device_name.unwrap_or_else(|| String::from("qryd_emulator"));
         let api_version = api_version.unwrap_or_else(|| String::from("v1_1"));
-        let dev = dev.unwrap_or(false);
-        let hqs_env_var = env::var("QRYD_API_HQS").is_ok();
-        let access_token_internal: String = if mock_port.is_some() {
-            "".to_string()
-        } else {
-            match access_token {
-                Some(s) => s,
-                None => env::var("QRYD_API_TOKEN").map_err(|_| {
-                    RoqoqoBackendError::MissingAuthentication {
-                        msg: "QRYD access token is missing.".to_string(),
-                    }
-                })?,
-            }
-        };
-
-        // Client setup
-        let client = if mock_port.is_some() {
-            reqwest::blocking::Client::builder().build().map_err(|x| {
-                RoqoqoBackendError::NetworkError {
-                    msg: format!("Could not create test client {:?}.", x),
-                }
-            })?
-        } else {
-            reqwest::blocking::Client::builder()
-                .https_only(true)
-                .build()
-                .map_err(|x| RoqoqoBackendError::NetworkError {
-                    msg: format!("Could not create https client {:?}.", x),
-                })?
-        };
+        let mut device = download_device(
+            &device_name_internal,
+            access_token,
+            mock_port,
+            dev.unwrap_or(false),
+            &api_version,
+            options.cache.as_ref(),
+        )?;
+        device.prepare_downloaded(device_name_internal, seed, options.validate)?;
+        Ok(device)
+    }
 
-        // Response gathering
-        let resp = if let Some(port) = mock_port {
-            client
-                .get(format!("http://127.0.0.1:{}", port))
-                .body(device_name_internal.clone())
-                .send()
-                .map_err(|e| RoqoqoBackendError::NetworkError {
-                    msg: format!("{:?}", e),
-                })?
-        } else {
-            match (dev, hqs_env_var) {
-                (true, true) => client
-                    .get(format!(
-                        "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                        api_version, device_name_internal
-                    ))
-                    .header("X-API-KEY", access_token_internal)
-                    .header("X-DEV", "?1")
-                    .header("X-HQS", "?1")
-                    .send()
-                    .map_err(|e| RoqoqoBackendError::NetworkError {
-                        msg: format!("{:?}", e),
-                    })?,
-                (true, false) => client
-                    .get(format!(
-                        "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                        api_version, device_name_internal
-                    ))
-                    .header("X-API-KEY", access_token_internal)
-                    .header("X-DEV", "?1")
-                    .send()
-                    .map_err(|e| RoqoqoBackendError::NetworkError {
-                        msg: format!("{:?}", e),
-                    })?,
-                (false, true) => client
-                    .get(format!(
-                        "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                        api_version, device_name_internal
-                    ))
-                    .header("X-API-KEY", access_token_internal)
-                    .header("X-HQS", "?1")
-                    .send()
-                    .map_err(|e| RoqoqoBackendError::NetworkError {
-                        msg: format!("{:?}", e),
-                    })?,
-                (false, false) => client
-                    .get(format!(
-                        "https://api.qryddemo.itp3.uni-stuttgart.de/{}/devices/{}",
-                        api_version, device_name_internal
-                    ))
-                    .header("X-API-KEY", access_token_internal)
-                    .send()
-                    .map_err(|e| RoqoqoBackendError::NetworkError {
-                        msg: format!("{:?}", e),
-                    })?,
-            }
-        };
+    /// Creates a new TweezerDevice instance from a device snapshot file.
+    ///
+    /// The offline counterpart of `from_api`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the snapshot file, as written by `DeviceSnapshot::save`.
+    /// * `seed` - Optionally overwrite seed value from the stored device instance.
+    ///
+    /// # Returns
+    ///
+    /// * `TweezerDevice` - The new TweezerDevice instance with populated tweezer data.
+    ///
+    /// # Errors
+    ///
+    /// * `RoqoqoBackendError`
+    pub fn from_snapshot<P: AsRef<std::path::Path>>(
+        path: P,
+        seed: Option<usize>,
+    ) -> Result<Self, RoqoqoBackendError> {
+        DeviceSnapshot::load(path)?.to_tweezer_device(seed)
+    }
 
-        // Response handling
-        let status_code = resp.status();
-        if status_code == reqwest::StatusCode::OK {
-            let mut device = resp.json::<TweezerDevice>().unwrap();
-            if let Some(default) = device.default_layout.clone() {
-                device.switch_layout(&default, None).unwrap();
-            }
-            if let Some(new_seed) = seed {
-                device.seed = Some(new_seed);
-            }
-            device.device_name = device_name_internal;
-            Ok(device)
-        } else {
-            Err(RoqoqoBackendError::NetworkError {
-                msg: format!(
-                    "Request to server failed with HTTP status code {:?}.",
-                    status_code
-                ),
-            })
+    /// Applies the post-processing to a device definition downloaded from the QRyd WebAPI.
+    ///
+    /// Validates the Layouts if requested, switches to the default layout, if any, and sets
+    /// the device name and the seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_name` - The name of the device on the QRyd WebAPI.
+    /// * `seed` - Optionally overwrite seed value from downloaded device instance.
+    /// * `validate` - Whether to return the issues found by `validate()` as an error.
+    pub(crate) fn prepare_downloaded(
+        &mut self,
+        device_name: String,
+        seed: Option<usize>,
+        validate: bool,
+    ) -> Result<(), RoqoqoBackendError> {
+        if validate {
+            self.validate().into_result()?;
+        }
+        if let Some(default) = self.default_layout.clone() {
+            self.switch_layout(&default, None)?;
         }
+        if let Some(new_seed) = seed {
+            self.seed = Some(new_seed);
+        }
+        self.device_name = device_name;
+        Ok(())
     }
 
     /// Adds a new empty Layout to the device's register.
@@ -412,6 +888,53 @@ impl TweezerDevice {
                 .as_mut()
                 .unwrap()
                 .insert(name.to_string(), TweezerLayoutInfo::default());
+            self.layout_indices.clear();
+        }
+        Ok(())
+    }
+
+    /// Add a new Layout generated from a lattice of tweezers to the device.
+    ///
+    /// See `lattice_layout` for the generated gate times and shifts.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name that is assigned to the new Layout.
+    /// * `lattice` - The geometry of the tweezers.
+    /// * `gate_times` - The gate time of each native gate supported by the Layout, by hqslang name.
+    /// * `interaction_radius` - The maximal distance of interacting tweezers in micrometers.
+    ///   Defaults to the spacing of the lattice, required for arbitrary positions.
+    /// * `row_shifts` - Whether to allow shifting qubits along the rows of the lattice.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The Layout has been added.
+    /// * `Err(RoqoqoBackendError)` - The name is already in use or the Layout could not be generated.
+    pub fn add_lattice_layout(
+        &mut self,
+        name: &str,
+        lattice: &TweezerLattice,
+        gate_times: &HashMap<String, f64>,
+        interaction_radius: Option<f64>,
+        row_shifts: bool,
+    ) -> Result<(), RoqoqoBackendError> {
+        if let Some(int_register) = &self.layout_register {
+            if int_register.contains_key(name) {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Error adding layout to TweezerDevice. Layout name {} is already in use in the Layout register.",
+                        name,
+                    ),
+                });
+            }
+            let info = lattice_layout(lattice, gate_times, interaction_radius, row_shifts)?;
+            let positions = lattice.tweezer_positions()?;
+            self.layout_register
+                .as_mut()
+                .unwrap()
+                .insert(name.to_string(), info);
+            self.tweezer_positions.insert(name.to_string(), positions);
+            self.layout_indices.clear();
         }
         Ok(())
     }
@@ -426,6 +949,10 @@ impl TweezerDevice {
     ///
     /// * `name` - The name of the new Layout.
     /// * `with_trivial_map` - Whether the qubit -> tweezer mapping should be trivially populated. Defaults to true.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self), fields(current_layout = ?self.current_layout))
+    )]
     pub fn switch_layout(
         &mut self,
         name: &str,
@@ -448,6 +975,28 @@ impl TweezerDevice {
         Ok(())
     }
 
+    /// Returns the register of Layouts.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&HashMap<String, TweezerLayoutInfo>>` - The Layouts by name, None for an EmulatorDevice.
+    pub fn layout_register(&self) -> Option<&HashMap<String, TweezerLayoutInfo>> {
+        self.layout_register.as_ref()
+    }
+
+    /// Returns the register of Layouts for direct modification.
+    ///
+    /// Drops the cached lookup indices of all Layouts, which are rebuilt on next use.
+    /// Prefer the setters of the device, which also check the modifications.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&mut HashMap<String, TweezerLayoutInfo>>` - The Layouts by name, None for an EmulatorDevice.
+    pub fn layout_register_mut(&mut self) -> Option<&mut HashMap<String, TweezerLayoutInfo>> {
+        self.layout_indices.clear();
+        self.layout_register.as_mut()
+    }
+
     /// Returns a vector of all available Layout names.
     ///
     /// # Returns:
@@ -467,7 +1016,9 @@ impl TweezerDevice {
 
     /// Modifies the qubit -> tweezer mapping of the device.
     ///
-    /// If a qubit -> tweezer mapping is already present, it is overwritten.
+    /// If a qubit -> tweezer mapping is already present, it is overwritten and a qubit
+    /// previously occupying the tweezer is removed from the mapping. Use `insert_qubit`,
+    /// `move_qubit` and `remove_qubit` to report such conflicts instead.
     /// Returns an error in the the tweezer does not exist.
     ///
     /// # Arguments
@@ -489,23 +1040,80 @@ impl TweezerDevice {
                 msg: "The given tweezer is not present in the device Tweezer data.".to_string(),
             });
         }
-        if let Some(map) = &mut self.qubit_to_tweezer {
-            // Remove the previous qubit present in the tweezer
-            if let Some(qubit_to_remove) =
-                map.iter()
-                    .find_map(|(&qbt, &twz)| if twz == tweezer { Some(qbt) } else { None })
-            {
-                map.remove(&qubit_to_remove);
-            }
-            map.insert(qubit, tweezer);
-        } else {
-            self.qubit_to_tweezer = Some(HashMap::from([(qubit, tweezer)]));
-        }
-        Ok(self
+        let map = self
             .qubit_to_tweezer
-            .as_ref()
-            .expect("Internal error: qubit_to_tweezer mapping supposed to be Some().")
-            .clone())
+            .get_or_insert_with(QubitTweezerMapping::new);
+        // Remove the previous qubit present in the tweezer
+        let _evicted = map.assign(qubit, tweezer);
+        #[cfg(feature = "tracing")]
+        if let Some(evicted) = _evicted {
+            tracing::debug!(qubit, tweezer, evicted, "Evicted qubit from tweezer");
+        }
+        Ok(map.as_map().clone())
+    }
+
+    /// Places an unmapped qubit in a free tweezer of the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The index of the qubit.
+    /// * `tweezer` - The index of the tweezer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The qubit has been placed.
+    /// * `Err(RoqoqoBackendError)` - The tweezer does not exist or is occupied, or the qubit is already mapped.
+    pub fn insert_qubit(&mut self, qubit: usize, tweezer: usize) -> Result<(), RoqoqoBackendError> {
+        if !self.is_tweezer_present(tweezer, None) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The given tweezer is not present in the device Tweezer data.".to_string(),
+            });
+        }
+        self.qubit_to_tweezer
+            .get_or_insert_with(QubitTweezerMapping::new)
+            .insert(qubit, tweezer)
+    }
+
+    /// Moves a mapped qubit into a free tweezer of the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The index of the qubit.
+    /// * `tweezer` - The index of the target tweezer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The tweezer the qubit has been moved out of.
+    /// * `Err(RoqoqoBackendError)` - The tweezer does not exist or is occupied, or the qubit is not mapped.
+    pub fn move_qubit(
+        &mut self,
+        qubit: usize,
+        tweezer: usize,
+    ) -> Result<usize, RoqoqoBackendError> {
+        if !self.is_tweezer_present(tweezer, None) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The given tweezer is not present in the device Tweezer data.".to_string(),
+            });
+        }
+        self.qubit_to_tweezer
+            .get_or_insert_with(QubitTweezerMapping::new)
+            .move_qubit(qubit, tweezer)
+    }
+
+    /// Removes a qubit from the qubit -> tweezer mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The index of the qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The tweezer the qubit occupied.
+    /// * `Err(RoqoqoBackendError)` - The qubit is not mapped.
+    pub fn remove_qubit(&mut self, qubit: usize) -> Result<usize, RoqoqoBackendError> {
+        self.qubit_to_tweezer
+            .get_or_insert_with(QubitTweezerMapping::new)
+            .remove(qubit)
     }
 
     /// Set the time of a single-qubit gate for a tweezer in a given Layout.
@@ -537,6 +1145,7 @@ impl TweezerDevice {
                 msg: "No layout name provided and no current layout set.".to_string(),
             })?;
         self.qubit_to_tweezer = None;
+        self.layout_indices.clear();
 
         if let Some(info) = self.layout_register.as_mut().unwrap().get_mut(&layout_name) {
             let sqt = &mut info.tweezer_single_qubit_gate_times;
@@ -582,6 +1191,7 @@ impl TweezerDevice {
                 msg: "No layout name provided and no current layout set.".to_string(),
             })?;
         self.qubit_to_tweezer = None;
+        self.layout_indices.clear();
 
         if let Some(info) = self.layout_register.as_mut().unwrap().get_mut(&layout_name) {
             let sqt = &mut info.tweezer_two_qubit_gate_times;
@@ -629,6 +1239,7 @@ impl TweezerDevice {
                 msg: "No layout name provided and no current layout set.".to_string(),
             })?;
         self.qubit_to_tweezer = None;
+        self.layout_indices.clear();
 
         if let Some(info) = self.layout_register.as_mut().unwrap().get_mut(&layout_name) {
             let sqt = &mut info.tweezer_three_qubit_gate_times;
@@ -672,6 +1283,7 @@ impl TweezerDevice {
                 msg: "No layout name provided and no current layout set.".to_string(),
             })?;
         self.qubit_to_tweezer = None;
+        self.layout_indices.clear();
 
         if let Some(info) = self.layout_register.as_mut().unwrap().get_mut(&layout_name) {
             let sqt = &mut info.tweezer_multi_qubit_gate_times;
@@ -781,63 +1393,930 @@ impl TweezerDevice {
             }
         }
 
-        let allowed_shifts = &mut self
-            .layout_register
-            .as_mut()
-            .unwrap()
-            .get_mut(&layout_name)
-            .unwrap()
-            .allowed_tweezer_shifts;
+        insert_row_shifts(
+            &mut self
+                .layout_register
+                .as_mut()
+                .unwrap()
+                .get_mut(&layout_name)
+                .unwrap()
+                .allowed_tweezer_shifts,
+            row_shifts,
+        );
 
-        // For each row in the input..
-        row_shifts.iter().for_each(|row| {
-            // ... divide in left, mid (the tweezer) and right parts
-            for i in 0..row.len() {
-                let (left_slice, mid) = row.split_at(i);
-                let mid = mid.first().unwrap_or(&0);
-                let mut vec_left = left_slice.to_vec();
-                vec_left.reverse();
-                let vec_right = &row[i + 1..].to_vec();
-
-                // Insert the left and right side
-                let val = allowed_shifts.entry(*mid).or_default();
-                if !vec_left.is_empty() && !val.contains(&vec_left) {
-                    val.push(vec_left.to_vec());
-                }
-                if !vec_right.is_empty() && !val.contains(vec_right) {
-                    val.push(vec_right.to_vec());
-                }
+        Ok(())
+    }
+
+    /// Set the tweezer per row value for a given Layout.
+    ///
+    /// This is needed for dynamically switching layouts during circuit execution.
+    /// Only switching between layouts having the same tweezer per row value is supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezers_per_row` - Vector containing the number of tweezers per row to set.
+    /// * `layout_name` - The name of the Layout to set the tweezer per row for. Defaults to the current Layout.
+    pub fn set_tweezers_per_row(
+        &mut self,
+        tweezers_per_row: Vec<usize>,
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        let layout_name = layout_name
+            .or_else(|| self.current_layout.as_ref().map(|s| s.to_string()))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "No layout name provided and no current layout set.".to_string(),
+            })?;
+
+        if let Some(info) = self.layout_register.as_mut().unwrap().get_mut(&layout_name) {
+            info.tweezers_per_row = Some(tweezers_per_row);
+        }
+
+        Ok(())
+    }
+
+    /// Set the position of a tweezer in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `position` - The (x, y) or (x, y, z) coordinates of the tweezer in micrometers.
+    /// * `layout_name` - The name of the Layout to set the position in. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The position has been set.
+    /// * `Err(RoqoqoBackendError)` - The tweezer is not present, the position is not valid or
+    ///   has a different number of coordinates than the positions already set in the Layout.
+    pub fn set_tweezer_position(
+        &mut self,
+        tweezer: usize,
+        coordinates: &[f64],
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        let layout_name = layout_name
+            .or_else(|| self.current_layout.as_ref().map(|s| s.to_string()))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "No layout name provided and no current layout set.".to_string(),
+            })?;
+        let position = match coordinates {
+            [x, y] => (*x, *y, 0.0),
+            [x, y, z] => (*x, *y, *z),
+            _ => {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "A tweezer position needs 2 or 3 coordinates, {} were given.",
+                        coordinates.len()
+                    ),
+                })
             }
-        });
+        };
+        if ![position.0, position.1, position.2]
+            .iter()
+            .all(|coordinate| coordinate.is_finite())
+        {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The given tweezer position is not finite.".to_string(),
+            });
+        }
+        self.get_layout_info(Some(layout_name.clone()))?;
+        if !self.is_tweezer_present(tweezer, Some(layout_name.clone())) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The given tweezer is not present in the device Tweezer data.".to_string(),
+            });
+        }
+        let positions = self
+            .tweezer_positions
+            .entry(layout_name)
+            .or_insert_with(|| TweezerPositions {
+                number_dimensions: coordinates.len(),
+                positions: HashMap::new(),
+            });
+        if positions.number_dimensions != coordinates.len() {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "The positions of the Layout have {} coordinates, {} were given.",
+                    positions.number_dimensions,
+                    coordinates.len()
+                ),
+            });
+        }
+        positions.positions.insert(tweezer, position);
+        Ok(())
+    }
+
+    /// Returns the physical positions of the tweezers of a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `layout_name` - The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(&TweezerPositions)` - The positions of the tweezers and their number of dimensions.
+    /// * `Err(RoqoqoBackendError)` - The layout does not exist or has no tweezer positions.
+    pub fn get_tweezer_positions(
+        &self,
+        layout_name: Option<String>,
+    ) -> Result<&TweezerPositions, RoqoqoBackendError> {
+        let layout_name = layout_name
+            .or_else(|| self.current_layout.as_ref().map(|s| s.to_string()))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "No layout name provided and no current layout set.".to_string(),
+            })?;
+        self.get_layout_info(Some(layout_name.clone()))?;
+        self.tweezer_positions
+            .get(&layout_name)
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: format!("No tweezer positions are set in Layout {}.", layout_name),
+            })
+    }
+
+    /// Get the position of a tweezer in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `layout_name` - The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((f64, f64, f64))` - The (x, y, z) coordinates of the tweezer in micrometers.
+    /// * `Err(RoqoqoBackendError)` - The layout does not exist or has no position for the tweezer.
+    pub fn get_tweezer_position(
+        &self,
+        tweezer: usize,
+        layout_name: Option<String>,
+    ) -> Result<(f64, f64, f64), RoqoqoBackendError> {
+        self.get_tweezer_positions(layout_name)?.position(tweezer)
+    }
+
+    /// Set the decoherence rates of a qubit held in a tweezer in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `rates` - The damping, dephasing and depolarising rates of the tweezer.
+    /// * `layout_name` - The name of the Layout to set the rates in. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The rates have been set.
+    /// * `Err(RoqoqoBackendError)` - The tweezer is not present or a rate is negative or not finite.
+    pub fn set_tweezer_decoherence_rates(
+        &mut self,
+        tweezer: usize,
+        rates: TweezerDecoherenceRates,
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        let layout_name = layout_name
+            .or_else(|| self.current_layout.as_ref().map(|s| s.to_string()))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "No layout name provided and no current layout set.".to_string(),
+            })?;
+        if ![rates.damping, rates.dephasing, rates.depolarising]
+            .iter()
+            .all(|rate| rate.is_finite() && *rate >= 0.0)
+        {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The decoherence rates need to be finite and non-negative.".to_string(),
+            });
+        }
+        self.get_layout_info(Some(layout_name.clone()))?;
+        if !self.is_tweezer_present(tweezer, Some(layout_name.clone())) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The given tweezer is not present in the device Tweezer data.".to_string(),
+            });
+        }
+        if let Some(info) = self.layout_register.as_mut().unwrap().get_mut(&layout_name) {
+            info.tweezer_decoherence_rates.insert(tweezer, rates);
+        }
+        Ok(())
+    }
+
+    /// Get the decoherence rates of a qubit held in a tweezer in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `layout_name` - The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TweezerDecoherenceRates)` - The rates of the tweezer, zero if none are set.
+    /// * `Err(RoqoqoBackendError)` - The layout does not exist.
+    pub fn get_tweezer_decoherence_rates(
+        &self,
+        tweezer: usize,
+        layout_name: Option<String>,
+    ) -> Result<TweezerDecoherenceRates, RoqoqoBackendError> {
+        Ok(self
+            .get_layout_info(layout_name)?
+            .tweezer_decoherence_rates
+            .get(&tweezer)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    /// Set the error rate of a single-qubit gate for a tweezer in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of a single-qubit gate.
+    /// * `tweezer` - The index of the tweezer.
+    /// * `error_rate` - The probability of an error during the gate.
+    /// * `layout_name` - The name of the Layout to set the error rate in. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The error rate has been set.
+    /// * `Err(RoqoqoBackendError)` - The gate has no gate time for the tweezer or the error rate is not in [0, 1].
+    pub fn set_tweezer_single_qubit_gate_error(
+        &mut self,
+        hqslang: &str,
+        tweezer: usize,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        let info = self.error_rate_layout_info(error_rate, layout_name)?;
+        if !info
+            .tweezer_single_qubit_gate_times
+            .get(hqslang)
+            .is_some_and(|times| times.contains_key(&tweezer))
+        {
+            return Err(gate_not_available_error(hqslang, &[tweezer]));
+        }
+        info.tweezer_single_qubit_gate_errors
+            .entry(hqslang.to_string())
+            .or_default()
+            .insert(tweezer, error_rate);
+        Ok(())
+    }
 
+    /// Set the error rate of a two-qubit gate for a tweezer couple in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of a two-qubit gate.
+    /// * `tweezer0` - The index of the first tweezer.
+    /// * `tweezer1` - The index of the second tweezer.
+    /// * `error_rate` - The probability of an error during the gate.
+    /// * `layout_name` - The name of the Layout to set the error rate in. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The error rate has been set.
+    /// * `Err(RoqoqoBackendError)` - The gate has no gate time for the tweezers or the error rate is not in [0, 1].
+    pub fn set_tweezer_two_qubit_gate_error(
+        &mut self,
+        hqslang: &str,
+        tweezer0: usize,
+        tweezer1: usize,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        let info = self.error_rate_layout_info(error_rate, layout_name)?;
+        if !info
+            .tweezer_two_qubit_gate_times
+            .get(hqslang)
+            .is_some_and(|times| times.contains_key(&(tweezer0, tweezer1)))
+        {
+            return Err(gate_not_available_error(hqslang, &[tweezer0, tweezer1]));
+        }
+        info.tweezer_two_qubit_gate_errors
+            .entry(hqslang.to_string())
+            .or_default()
+            .insert((tweezer0, tweezer1), error_rate);
         Ok(())
     }
 
-    /// Set the tweezer per row value for a given Layout.
+    /// Set the error rate of a three-qubit gate for a tweezer trio in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of a three-qubit gate.
+    /// * `tweezer0` - The index of the first tweezer.
+    /// * `tweezer1` - The index of the second tweezer.
+    /// * `tweezer2` - The index of the third tweezer.
+    /// * `error_rate` - The probability of an error during the gate.
+    /// * `layout_name` - The name of the Layout to set the error rate in. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The error rate has been set.
+    /// * `Err(RoqoqoBackendError)` - The gate has no gate time for the tweezers or the error rate is not in [0, 1].
+    pub fn set_tweezer_three_qubit_gate_error(
+        &mut self,
+        hqslang: &str,
+        tweezer0: usize,
+        tweezer1: usize,
+        tweezer2: usize,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        let info = self.error_rate_layout_info(error_rate, layout_name)?;
+        if !info
+            .tweezer_three_qubit_gate_times
+            .get(hqslang)
+            .is_some_and(|times| times.contains_key(&(tweezer0, tweezer1, tweezer2)))
+        {
+            return Err(gate_not_available_error(
+                hqslang,
+                &[tweezer0, tweezer1, tweezer2],
+            ));
+        }
+        info.tweezer_three_qubit_gate_errors
+            .entry(hqslang.to_string())
+            .or_default()
+            .insert((tweezer0, tweezer1, tweezer2), error_rate);
+        Ok(())
+    }
+
+    /// Set the readout error rate of a tweezer in a given Layout, the same for both states.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `error_rate` - The probability of reading out the wrong state of the qubit held in the tweezer.
+    /// * `layout_name` - The name of the Layout to set the error rate in. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The error rate has been set.
+    /// * `Err(RoqoqoBackendError)` - The tweezer is not present or the error rate is not in [0, 1].
+    pub fn set_tweezer_readout_error(
+        &mut self,
+        tweezer: usize,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        self.set_tweezer_readout_confusion_matrix(
+            tweezer,
+            ReadoutConfusionMatrix::symmetric(error_rate)?,
+            layout_name,
+        )
+    }
+
+    /// Set the readout confusion matrix of a tweezer in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `confusion_matrix` - The readout confusion matrix of the qubit held in the tweezer.
+    /// * `layout_name` - The name of the Layout to set the confusion matrix in. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The confusion matrix has been set.
+    /// * `Err(RoqoqoBackendError)` - The tweezer is not present or a probability is not in [0, 1].
+    pub fn set_tweezer_readout_confusion_matrix(
+        &mut self,
+        tweezer: usize,
+        confusion_matrix: ReadoutConfusionMatrix,
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        confusion_matrix.validate()?;
+        let layout_name = layout_name
+            .or_else(|| self.current_layout.as_ref().map(|s| s.to_string()))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "No layout name provided and no current layout set.".to_string(),
+            })?;
+        self.get_layout_info(Some(layout_name.clone()))?;
+        if !self.is_tweezer_present(tweezer, Some(layout_name.clone())) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "The given tweezer is not present in the device Tweezer data.".to_string(),
+            });
+        }
+        self.error_rate_layout_info(confusion_matrix.error_rate(), Some(layout_name))?
+            .tweezer_readout_errors
+            .insert(tweezer, confusion_matrix);
+        Ok(())
+    }
+
+    /// Returns the error rate of a single-qubit gate on a qubit in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the single-qubit gate.
+    /// * `qubit` - The qubit the gate acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The error rate, zero if none is set.
+    /// * `None` - The gate is not available on the qubit.
+    pub fn single_qubit_gate_error(&self, hqslang: &str, qubit: &usize) -> Option<f64> {
+        self.single_qubit_gate_time(hqslang, qubit)?;
+        let tweezer = self.get_tweezer_from_qubit(qubit).ok()?;
+        Some(
+            self.get_current_layout_info()
+                .ok()?
+                .tweezer_single_qubit_gate_errors
+                .get(hqslang)
+                .and_then(|errors| errors.get(&tweezer))
+                .copied()
+                .unwrap_or(0.0),
+        )
+    }
+
+    /// Returns the error rate of a two-qubit gate on a qubit couple in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the two-qubit gate.
+    /// * `control` - The control qubit the gate acts on.
+    /// * `target` - The target qubit the gate acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The error rate, zero if none is set.
+    /// * `None` - The gate is not available on the qubits.
+    pub fn two_qubit_gate_error(
+        &self,
+        hqslang: &str,
+        control: &usize,
+        target: &usize,
+    ) -> Option<f64> {
+        self.two_qubit_gate_time(hqslang, control, target)?;
+        let tweezers = (
+            self.get_tweezer_from_qubit(control).ok()?,
+            self.get_tweezer_from_qubit(target).ok()?,
+        );
+        Some(
+            self.get_current_layout_info()
+                .ok()?
+                .tweezer_two_qubit_gate_errors
+                .get(hqslang)
+                .and_then(|errors| errors.get(&tweezers))
+                .copied()
+                .unwrap_or(0.0),
+        )
+    }
+
+    /// Returns the error rate of a three-qubit gate on a qubit trio in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the three-qubit gate.
+    /// * `control_0` - The first control qubit the gate acts on.
+    /// * `control_1` - The second control qubit the gate acts on.
+    /// * `target` - The target qubit the gate acts on.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The error rate, zero if none is set.
+    /// * `None` - The gate is not available on the qubits.
+    pub fn three_qubit_gate_error(
+        &self,
+        hqslang: &str,
+        control_0: &usize,
+        control_1: &usize,
+        target: &usize,
+    ) -> Option<f64> {
+        self.three_qubit_gate_time(hqslang, control_0, control_1, target)?;
+        let tweezers = (
+            self.get_tweezer_from_qubit(control_0).ok()?,
+            self.get_tweezer_from_qubit(control_1).ok()?,
+            self.get_tweezer_from_qubit(target).ok()?,
+        );
+        Some(
+            self.get_current_layout_info()
+                .ok()?
+                .tweezer_three_qubit_gate_errors
+                .get(hqslang)
+                .and_then(|errors| errors.get(&tweezers))
+                .copied()
+                .unwrap_or(0.0),
+        )
+    }
+
+    /// Returns the readout error rate of a qubit in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The measured qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The mean probability of reading out the wrong state in the tweezer the qubit occupies, zero if none is set.
+    /// * `None` - The qubit is not mapped to a tweezer or no current Layout is set.
+    pub fn readout_error(&self, qubit: &usize) -> Option<f64> {
+        self.readout_confusion_matrix(qubit)
+            .map(|confusion_matrix| confusion_matrix.error_rate())
+    }
+
+    /// Returns the readout confusion matrix of a qubit in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The measured qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<ReadoutConfusionMatrix>` - The confusion matrix of the tweezer the qubit occupies, error free if none is set.
+    /// * `None` - The qubit is not mapped to a tweezer or no current Layout is set.
+    pub fn readout_confusion_matrix(&self, qubit: &usize) -> Option<ReadoutConfusionMatrix> {
+        let tweezer = self.get_tweezer_from_qubit(qubit).ok()?;
+        Some(
+            self.get_current_layout_info()
+                .ok()?
+                .tweezer_readout_errors
+                .get(&tweezer)
+                .copied()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Returns the readout confusion matrices of the qubits measured into a register, in register order.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The qubit measured into each bit of the register.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<ReadoutConfusionMatrix>)` - The confusion matrix of each qubit.
+    /// * `Err(RoqoqoBackendError)` - A qubit is not mapped to a tweezer or no current Layout is set.
+    pub fn readout_confusion_matrices(
+        &self,
+        qubits: &[usize],
+    ) -> Result<Vec<ReadoutConfusionMatrix>, RoqoqoBackendError> {
+        qubits
+            .iter()
+            .map(|qubit| {
+                self.readout_confusion_matrix(qubit).ok_or_else(|| {
+                    RoqoqoBackendError::GenericError {
+                        msg: format!(
+                            "Qubit {} is not mapped to a tweezer or no current Layout is set.",
+                            qubit
+                        ),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the error rate of a gate operation in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - The single-, two- or three-qubit gate operation.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The error rate of the gate on its qubits, zero if none is set.
+    /// * `None` - The operation is not a single-, two- or three-qubit gate or is not available.
+    pub fn gate_error(&self, operation: &Operation) -> Option<f64> {
+        if let Ok(gate) = SingleQubitGateOperation::try_from(operation.clone()) {
+            self.single_qubit_gate_error(gate.hqslang(), gate.qubit())
+        } else if let Ok(gate) = TwoQubitGateOperation::try_from(operation.clone()) {
+            self.two_qubit_gate_error(gate.hqslang(), gate.control(), gate.target())
+        } else if let Ok(gate) = ThreeQubitGateOperation::try_from(operation.clone()) {
+            self.three_qubit_gate_error(
+                gate.hqslang(),
+                gate.control_0(),
+                gate.control_1(),
+                gate.target(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Returns the fidelity of a gate operation in the current Layout, one minus its error rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - The single-, two- or three-qubit gate operation.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The fidelity of the gate on its qubits.
+    /// * `None` - The operation is not a single-, two- or three-qubit gate or is not available.
+    pub fn gate_fidelity(&self, operation: &Operation) -> Option<f64> {
+        self.gate_error(operation)
+            .map(|error_rate| 1.0 - error_rate)
+    }
+
+    /// Returns the error score of a tweezer in a given Layout, used to prefer high-fidelity tweezers.
+    ///
+    /// The score is the sum of the largest single-qubit gate error rate of the tweezer, the
+    /// largest two-qubit gate error rate over the pairs of tweezers it belongs to and its
+    /// readout error rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `layout_name` - The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - The error score of the tweezer, zero if no error rates are set.
+    /// * `Err(RoqoqoBackendError)` - The layout does not exist.
+    pub fn tweezer_error_score(
+        &self,
+        tweezer: usize,
+        layout_name: Option<String>,
+    ) -> Result<f64, RoqoqoBackendError> {
+        let info = self.get_layout_info(layout_name)?;
+        let gate_error = info
+            .tweezer_single_qubit_gate_errors
+            .values()
+            .filter_map(|errors| errors.get(&tweezer))
+            .fold(0.0, |max: f64, error_rate| max.max(*error_rate));
+        let two_qubit_gate_error = info
+            .tweezer_two_qubit_gate_errors
+            .values()
+            .flat_map(|errors| errors.iter())
+            .filter(|((tweezer0, tweezer1), _)| *tweezer0 == tweezer || *tweezer1 == tweezer)
+            .fold(0.0, |max: f64, (_, error_rate)| max.max(*error_rate));
+        let readout_error = info
+            .tweezer_readout_errors
+            .get(&tweezer)
+            .map(|confusion_matrix| confusion_matrix.error_rate())
+            .unwrap_or(0.0);
+        Ok(gate_error + two_qubit_gate_error + readout_error)
+    }
+
+    /// Returns the Layout to set an error rate in, after checking the error rate.
+    fn error_rate_layout_info(
+        &mut self,
+        error_rate: f64,
+        layout_name: Option<String>,
+    ) -> Result<&mut TweezerLayoutInfo, RoqoqoBackendError> {
+        if !(0.0..=1.0).contains(&error_rate) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!("The error rate {} is not between 0 and 1.", error_rate),
+            });
+        }
+        let layout_name = layout_name
+            .or_else(|| self.current_layout.as_ref().map(|s| s.to_string()))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "No layout name provided and no current layout set.".to_string(),
+            })?;
+        self.layout_register
+            .as_mut()
+            .and_then(|register| register.get_mut(&layout_name))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "The given layout name is not present in the layout register.".to_string(),
+            })
+    }
+
+    /// Returns the distance between two tweezers in a given Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer0` - The index of the first tweezer.
+    /// * `tweezer1` - The index of the second tweezer.
+    /// * `layout_name` - The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - The distance between the tweezers in micrometers.
+    /// * `Err(RoqoqoBackendError)` - The position of one of the tweezers is not set.
+    pub fn tweezer_distance(
+        &self,
+        tweezer0: usize,
+        tweezer1: usize,
+        layout_name: Option<String>,
+    ) -> Result<f64, RoqoqoBackendError> {
+        let positions = self.get_tweezer_positions(layout_name)?;
+        Ok(distance(
+            positions.position(tweezer0)?,
+            positions.position(tweezer1)?,
+        ))
+    }
+
+    /// Returns the distance between the tweezers two qubits are mapped to in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit0` - The index of the first qubit.
+    /// * `qubit1` - The index of the second qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - The distance between the qubits in micrometers.
+    /// * `Err(RoqoqoBackendError)` - A qubit is not mapped or the position of its tweezer is not set.
+    pub fn qubit_distance(&self, qubit0: usize, qubit1: usize) -> Result<f64, RoqoqoBackendError> {
+        self.tweezer_distance(
+            self.get_tweezer_from_qubit(&qubit0)?,
+            self.get_tweezer_from_qubit(&qubit1)?,
+            None,
+        )
+    }
+
+    /// Returns the tweezers closest to a given tweezer in a given Layout.
+    ///
+    /// Only tweezers with a set position are considered.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `number` - The maximal number of tweezers to return.
+    /// * `layout_name` - The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(usize, f64)>)` - The closest tweezers with their distances, sorted by distance.
+    /// * `Err(RoqoqoBackendError)` - The position of the given tweezer is not set.
+    pub fn nearest_tweezers(
+        &self,
+        tweezer: usize,
+        number: usize,
+        layout_name: Option<String>,
+    ) -> Result<Vec<(usize, f64)>, RoqoqoBackendError> {
+        let mut neighbours = self.sorted_tweezer_distances(tweezer, layout_name)?;
+        neighbours.truncate(number);
+        Ok(neighbours)
+    }
+
+    /// Returns the tweezers within a given distance of a given tweezer in a given Layout.
+    ///
+    /// Only tweezers with a set position are considered.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The index of the tweezer.
+    /// * `max_distance` - The maximal distance in micrometers, inclusive.
+    /// * `layout_name` - The name of the Layout to reference. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(usize, f64)>)` - The tweezers in range with their distances, sorted by distance.
+    /// * `Err(RoqoqoBackendError)` - The position of the given tweezer is not set.
+    pub fn tweezers_within_distance(
+        &self,
+        tweezer: usize,
+        max_distance: f64,
+        layout_name: Option<String>,
+    ) -> Result<Vec<(usize, f64)>, RoqoqoBackendError> {
+        Ok(self
+            .sorted_tweezer_distances(tweezer, layout_name)?
+            .into_iter()
+            .take_while(|(_, dist)| *dist <= max_distance)
+            .collect())
+    }
+
+    /// Derive the two- and three-qubit gate times of a Layout from an interaction model.
+    ///
+    /// The model is evaluated for every pair and trio of tweezers with a set position and
+    /// every native two- and three-qubit gate. Two-qubit gate times are set for both orders
+    /// of each pair. The existing two- and three-qubit gate times of the Layout are replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The interaction model to evaluate.
+    /// * `layout_name` - The name of the Layout to set the gate times in. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The gate times have been set.
+    /// * `Err(RoqoqoBackendError)` - The layout does not exist.
+    pub fn apply_interaction_model<M: InteractionModel + ?Sized>(
+        &mut self,
+        model: &M,
+        layout_name: Option<String>,
+    ) -> Result<(), RoqoqoBackendError> {
+        let layout_name = layout_name
+            .or_else(|| self.current_layout.as_ref().map(|s| s.to_string()))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "No layout name provided and no current layout set.".to_string(),
+            })?;
+        let info = self
+            .layout_register
+            .as_mut()
+            .and_then(|register| register.get_mut(&layout_name))
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "The given layout name is not present in the layout register.".to_string(),
+            })?;
+        let positions: Vec<(usize, (f64, f64, f64))> = self
+            .tweezer_positions
+            .get(&layout_name)
+            .into_iter()
+            .flat_map(|positions| positions.positions.iter())
+            .map(|(&tweezer, &position)| (tweezer, position))
+            .sorted_by_key(|(tweezer, _)| *tweezer)
+            .collect();
+
+        let mut two_qubit_gate_times: HashMap<String, HashMap<(usize, usize), f64>> =
+            HashMap::new();
+        for ((tweezer0, position0), (tweezer1, position1)) in positions.iter().tuple_combinations()
+        {
+            let dist = distance(*position0, *position1);
+            for hqslang in ALLOWED_NATIVE_TWO_QUBIT_GATES {
+                if let Some(gate_time) = model.two_qubit_gate_time(hqslang, dist) {
+                    let times = two_qubit_gate_times.entry(hqslang.to_string()).or_default();
+                    times.insert((*tweezer0, *tweezer1), gate_time);
+                    times.insert((*tweezer1, *tweezer0), gate_time);
+                }
+            }
+        }
+        let mut three_qubit_gate_times: HashMap<String, HashMap<(usize, usize, usize), f64>> =
+            HashMap::new();
+        for ((tweezer0, position0), (tweezer1, position1), (tweezer2, position2)) in
+            positions.iter().tuple_combinations()
+        {
+            let distances = [
+                distance(*position0, *position1),
+                distance(*position0, *position2),
+                distance(*position1, *position2),
+            ];
+            for hqslang in ALLOWED_NATIVE_THREE_QUBIT_GATES {
+                if let Some(gate_time) = model.three_qubit_gate_time(hqslang, distances) {
+                    three_qubit_gate_times
+                        .entry(hqslang.to_string())
+                        .or_default()
+                        .insert((*tweezer0, *tweezer1, *tweezer2), gate_time);
+                }
+            }
+        }
+        info.tweezer_two_qubit_gate_times = two_qubit_gate_times;
+        info.tweezer_three_qubit_gate_times = three_qubit_gate_times;
+        self.layout_indices.clear();
+        Ok(())
+    }
+
+    /// Returns the gate time of a two-qubit gate evaluated from an interaction model.
+    ///
+    /// Uses the positions of the tweezers the qubits are mapped to in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The interaction model to evaluate.
+    /// * `hqslang` - The hqslang name of the two-qubit gate.
+    /// * `control` - The control qubit.
+    /// * `target` - The target qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The gate time.
+    /// * `None` - The gate is not available or a tweezer position is not set.
+    pub fn model_two_qubit_gate_time<M: InteractionModel + ?Sized>(
+        &self,
+        model: &M,
+        hqslang: &str,
+        control: &usize,
+        target: &usize,
+    ) -> Option<f64> {
+        let dist = self.qubit_distance(*control, *target).ok()?;
+        model.two_qubit_gate_time(hqslang, dist)
+    }
+
+    /// Returns the fidelity of a two-qubit gate evaluated from an interaction model.
+    ///
+    /// Uses the positions of the tweezers the qubits are mapped to in the current Layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The interaction model to evaluate.
+    /// * `hqslang` - The hqslang name of the two-qubit gate.
+    /// * `control` - The control qubit.
+    /// * `target` - The target qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The gate fidelity.
+    /// * `None` - The gate is not available or a tweezer position is not set.
+    pub fn model_two_qubit_gate_fidelity<M: InteractionModel + ?Sized>(
+        &self,
+        model: &M,
+        hqslang: &str,
+        control: &usize,
+        target: &usize,
+    ) -> Option<f64> {
+        let dist = self.qubit_distance(*control, *target).ok()?;
+        model.two_qubit_gate_fidelity(hqslang, dist)
+    }
+
+    /// Returns the gate time of a three-qubit gate evaluated from an interaction model.
     ///
-    /// This is needed for dynamically switching layouts during circuit execution.
-    /// Only switching between layouts having the same tweezer per row value is supported.
+    /// Uses the positions of the tweezers the qubits are mapped to in the current Layout.
     ///
     /// # Arguments
     ///
-    /// * `tweezers_per_row` - Vector containing the number of tweezers per row to set.
-    /// * `layout_name` - The name of the Layout to set the tweezer per row for. Defaults to the current Layout.
-    pub fn set_tweezers_per_row(
-        &mut self,
-        tweezers_per_row: Vec<usize>,
-        layout_name: Option<String>,
-    ) -> Result<(), RoqoqoBackendError> {
-        let layout_name = layout_name
-            .or_else(|| self.current_layout.as_ref().map(|s| s.to_string()))
-            .ok_or_else(|| RoqoqoBackendError::GenericError {
-                msg: "No layout name provided and no current layout set.".to_string(),
-            })?;
-
-        if let Some(info) = self.layout_register.as_mut().unwrap().get_mut(&layout_name) {
-            info.tweezers_per_row = Some(tweezers_per_row);
-        }
-
-        Ok(())
+    /// * `model` - The interaction model to evaluate.
+    /// * `hqslang` - The hqslang name of the three-qubit gate.
+    /// * `control_0` - The first control qubit.
+    /// * `control_1` - The second control qubit.
+    /// * `target` - The target qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The gate time.
+    /// * `None` - The gate is not available or a tweezer position is not set.
+    pub fn model_three_qubit_gate_time<M: InteractionModel + ?Sized>(
+        &self,
+        model: &M,
+        hqslang: &str,
+        control_0: &usize,
+        control_1: &usize,
+        target: &usize,
+    ) -> Option<f64> {
+        let distances = [
+            self.qubit_distance(*control_0, *control_1).ok()?,
+            self.qubit_distance(*control_0, *target).ok()?,
+            self.qubit_distance(*control_1, *target).ok()?,
+        ];
+        model.three_qubit_gate_time(hqslang, distances)
     }
 
     /// Set whether the device allows PragmaActiveReset operations or not.
@@ -888,11 +2367,10 @@ impl TweezerDevice {
     /// * `Err(RoqoqoBackendError)` - If the qubit identifier is not related to any tweezer.
     pub fn get_tweezer_from_qubit(&self, qubit: &usize) -> Result<usize, RoqoqoBackendError> {
         if let Some(map) = &self.qubit_to_tweezer {
-            map.get(qubit)
+            map.tweezer_of(*qubit)
                 .ok_or(RoqoqoBackendError::GenericError {
                     msg: "The given qubit is not present in the Layout.".to_string(),
                 })
-                .copied()
         } else {
             Err(RoqoqoBackendError::GenericError {
                 msg: "The device qubit -> tweezer mapping is empty.".to_string(),
@@ -900,6 +2378,94 @@ impl TweezerDevice {
         }
     }
 
+    /// Returns the qubit -> tweezer mapping of the device.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&QubitTweezerMapping>` - The mapping, None if no mapping is set.
+    pub fn qubit_to_tweezer(&self) -> Option<&QubitTweezerMapping> {
+        self.qubit_to_tweezer.as_ref()
+    }
+
+    /// Replaces the qubit -> tweezer mapping of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `mapping` - The new mapping, None to remove the mapping.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The mapping has been set.
+    /// * `Err(RoqoqoBackendError)` - No current Layout is set or a tweezer of the mapping is not present in it.
+    pub fn set_qubit_to_tweezer(
+        &mut self,
+        mapping: Option<QubitTweezerMapping>,
+    ) -> Result<(), RoqoqoBackendError> {
+        // For the EmulatorDevice, the tweezer check must not be performed
+        if let (Some(map), Some(_)) = (&mapping, &self.layout_register) {
+            let tweezers = &self.get_layout_index(None)?.tweezers;
+            if let Some((_, tweezer)) = map.iter().find(|(_, tweezer)| !tweezers.contains(tweezer))
+            {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Tweezer {} is not present in the device Tweezer data.",
+                        tweezer
+                    ),
+                });
+            }
+        }
+        self.qubit_to_tweezer = mapping;
+        Ok(())
+    }
+
+    /// Get the qubit occupying the given tweezer.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweezer` - The input tweezer identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The qubit in the tweezer, None if the tweezer is free.
+    pub fn qubit_at(&self, tweezer: usize) -> Option<usize> {
+        self.qubit_to_tweezer
+            .as_ref()
+            .and_then(|map| map.qubit_at(tweezer))
+    }
+
+    /// Returns the tweezers occupied by a qubit, in ascending order.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The occupied tweezers.
+    pub fn occupied_tweezers(&self) -> Vec<usize> {
+        self.qubit_to_tweezer
+            .as_ref()
+            .map(|map| map.occupied_tweezers())
+            .unwrap_or_default()
+    }
+
+    /// Returns the tweezers of a Layout not occupied by a qubit, in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `layout_name` - The name of the Layout. Defaults to the current Layout.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<usize>)` - The free tweezers.
+    /// * `Err(RoqoqoBackendError)` - The layout does not exist or no current layout is set.
+    pub fn free_tweezers(
+        &self,
+        layout_name: Option<String>,
+    ) -> Result<Vec<usize>, RoqoqoBackendError> {
+        let tweezers = self.get_layout_index(layout_name)?.tweezers.clone();
+        Ok(match &self.qubit_to_tweezer {
+            Some(map) => map.free_tweezers(tweezers),
+            None => tweezers.into_iter().collect(),
+        })
+    }
+
     /// Get the names of the available gates in the given layout.
     ///
     /// # Arguments
@@ -960,12 +2526,12 @@ impl TweezerDevice {
         qubit: usize,
     ) -> Result<HashMap<usize, usize>, RoqoqoBackendError> {
         if let Some(map) = &mut self.qubit_to_tweezer {
-            if map.remove(&qubit).is_none() {
+            if map.remove(qubit).is_err() {
                 Err(RoqoqoBackendError::GenericError {
                     msg: "The given qubit is not present in the Layout.".to_string(),
                 })
             } else {
-                Ok(map.clone())
+                Ok(map.as_map().clone())
             }
         } else {
             Err(RoqoqoBackendError::GenericError {
@@ -1092,49 +2658,11 @@ impl TweezerDevice {
         &self,
         layout_name: Option<String>,
     ) -> Result<usize, RoqoqoBackendError> {
-        let mut set_tweezer_indices: HashSet<usize> = HashSet::new();
-        let tweezer_info = if let Some(layout_name) = layout_name {
-            if let Some(tw) = self._extract_layout_register()?.get(&layout_name) {
-                tw
-            } else {
-                return Err(RoqoqoBackendError::GenericError {
-                    msg: "The given layout name is not present in the layout register.".to_string(),
-                });
-            }
-        } else {
-            self.get_current_layout_info()?
-        };
-        for single_qubit_gate_struct in &tweezer_info.tweezer_single_qubit_gate_times {
-            for tw_id in single_qubit_gate_struct.1.keys() {
-                set_tweezer_indices.insert(*tw_id);
-            }
-        }
-        for two_qubit_gate_struct in &tweezer_info.tweezer_two_qubit_gate_times {
-            for tw_id in two_qubit_gate_struct.1.keys() {
-                set_tweezer_indices.insert(tw_id.0);
-                set_tweezer_indices.insert(tw_id.1);
-            }
-        }
-        for three_qubit_gate_struct in &tweezer_info.tweezer_three_qubit_gate_times {
-            for tw_id in three_qubit_gate_struct.1.keys() {
-                set_tweezer_indices.insert(tw_id.0);
-                set_tweezer_indices.insert(tw_id.1);
-                set_tweezer_indices.insert(tw_id.2);
-            }
-        }
-        for multi_qubit_gate_struct in &tweezer_info.tweezer_multi_qubit_gate_times {
-            for tw_ids in multi_qubit_gate_struct.1.keys() {
-                for id in tw_ids.iter() {
-                    set_tweezer_indices.insert(*id);
-                }
-            }
-        }
-
-        Ok(set_tweezer_indices.len())
+        Ok(self.get_layout_index(layout_name)?.tweezers.len())
     }
 
     #[inline]
-    fn get_current_layout_info(&self) -> Result<&TweezerLayoutInfo, RoqoqoBackendError> {
+    pub(crate) fn get_current_layout_info(&self) -> Result<&TweezerLayoutInfo, RoqoqoBackendError> {
         if let Some(current) = &self.current_layout {
             Ok(self
                 .layout_register
@@ -1150,116 +2678,72 @@ impl TweezerDevice {
         }
     }
 
-    fn is_tweezer_present(&self, tweezer: usize, layout_name: Option<String>) -> bool {
+    fn get_layout_info(
+        &self,
+        layout_name: Option<String>,
+    ) -> Result<&TweezerLayoutInfo, RoqoqoBackendError> {
+        if let Some(layout_name) = layout_name {
+            self._extract_layout_register()?
+                .get(&layout_name)
+                .ok_or_else(|| RoqoqoBackendError::GenericError {
+                    msg: "The given layout name is not present in the layout register.".to_string(),
+                })
+        } else {
+            self.get_current_layout_info()
+        }
+    }
+
+    pub(crate) fn get_layout_index(
+        &self,
+        layout_name: Option<String>,
+    ) -> Result<Arc<LayoutIndex>, RoqoqoBackendError> {
+        let layout_name = layout_name
+            .or_else(|| self.current_layout.clone())
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "Tried to access current layout info but no current layout is set."
+                    .to_string(),
+            })?;
+        let info = self.get_layout_info(Some(layout_name.clone()))?;
+        Ok(self.layout_indices.get_or_build(&layout_name, info))
+    }
+
+    fn sorted_tweezer_distances(
+        &self,
+        tweezer: usize,
+        layout_name: Option<String>,
+    ) -> Result<Vec<(usize, f64)>, RoqoqoBackendError> {
+        let positions = self.get_tweezer_positions(layout_name)?;
+        let origin = positions.position(tweezer)?;
+        let mut distances: Vec<(usize, f64)> = positions
+            .positions
+            .iter()
+            .filter(|(&other, _)| other != tweezer)
+            .map(|(&other, &position)| (other, distance(origin, position)))
+            .collect();
+        distances.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        Ok(distances)
+    }
+
+    pub(crate) fn is_tweezer_present(&self, tweezer: usize, layout_name: Option<String>) -> bool {
         // For the EmulatorDevice, the tweezer check must not be performed
         if self.layout_register.is_none() {
             return true;
         }
-        let tweezer_info = if let Some(x) = layout_name {
-            self.layout_register
-                .as_ref()
-                .unwrap()
-                .get(&x)
-                .expect("The specified layout does not exist.")
-        } else {
-            self.get_current_layout_info().unwrap()
-        };
-        let mut present: bool = false;
-        for single_qubit_gate_struct in &tweezer_info.tweezer_single_qubit_gate_times {
-            if single_qubit_gate_struct.1.contains_key(&tweezer) {
-                present = true;
-            }
-        }
-        for two_qubit_gate_struct in &tweezer_info.tweezer_two_qubit_gate_times {
-            if two_qubit_gate_struct
-                .1
-                .keys()
-                .any(|k| k.0 == tweezer || k.1 == tweezer)
-            {
-                present = true;
-            }
-        }
-        for three_qubit_gate_struct in &tweezer_info.tweezer_three_qubit_gate_times {
-            if three_qubit_gate_struct
-                .1
-                .keys()
-                .any(|k| k.0 == tweezer || k.1 == tweezer || k.2 == tweezer)
-            {
-                present = true;
-            }
-        }
-        for multi_qubit_gate_struct in &tweezer_info.tweezer_multi_qubit_gate_times {
-            if multi_qubit_gate_struct
-                .1
-                .keys()
-                .any(|k| k.contains(&tweezer))
-            {
-                present = true;
-            }
-        }
-        present
+        self.get_layout_index(layout_name)
+            .expect("The specified layout does not exist.")
+            .tweezers
+            .contains(&tweezer)
     }
 
     fn max_tweezer(&self) -> Result<Option<usize>, RoqoqoBackendError> {
-        let tweezer_info = self.get_current_layout_info()?;
-        let mut max_tweezer_id: Option<usize> = None;
-
-        for single_qubit_struct in &tweezer_info.tweezer_single_qubit_gate_times {
-            if let Some(max) = single_qubit_struct.1.keys().max() {
-                if let Some(current_max) = max_tweezer_id {
-                    max_tweezer_id = Some(*max.max(&current_max));
-                } else {
-                    max_tweezer_id = Some(*max);
-                }
-            }
-        }
-        for two_qubit_struct in &tweezer_info.tweezer_two_qubit_gate_times {
-            if let Some(max) = two_qubit_struct
-                .1
-                .keys()
-                .flat_map(|&(a, b)| vec![a, b])
-                .max()
-            {
-                if let Some(current_max) = max_tweezer_id {
-                    max_tweezer_id = Some(max.max(current_max));
-                } else {
-                    max_tweezer_id = Some(max);
-                }
-            }
-        }
-        for three_qubit_struct in &tweezer_info.tweezer_three_qubit_gate_times {
-            if let Some(max) = three_qubit_struct
-                .1
-                .keys()
-                .flat_map(|&(a, b, c)| vec![a, b, c])
-                .max()
-            {
-                if let Some(current_max) = max_tweezer_id {
-                    max_tweezer_id = Some(max.max(current_max));
-                } else {
-                    max_tweezer_id = Some(max);
-                }
-            }
-        }
-        for multi_qubit_struct in &tweezer_info.tweezer_multi_qubit_gate_times {
-            if let Some(max) = multi_qubit_struct.1.keys().flatten().max() {
-                if let Some(current_max) = max_tweezer_id {
-                    max_tweezer_id = Some(*max.max(&current_max));
-                } else {
-                    max_tweezer_id = Some(*max);
-                };
-            }
-        }
-        Ok(max_tweezer_id)
+        Ok(self.get_layout_index(None)?.max_tweezer())
     }
 
-    fn new_trivial_mapping(&self) -> HashMap<usize, usize> {
+    fn new_trivial_mapping(&self) -> QubitTweezerMapping {
         if let Some(max_tweezer_id) = self.max_tweezer().unwrap() {
-            (0..=max_tweezer_id)
-                .map(|i| (i, i))
-                .collect::<HashMap<usize, usize>>()
+            QubitTweezerMapping::trivial(0..=max_tweezer_id)
         } else {
-            HashMap::new()
+            QubitTweezerMapping::new()
         }
     }
 
@@ -1274,70 +2758,42 @@ impl TweezerDevice {
         }
     }
 
-    fn _are_all_shifts_valid(&mut self, pragma: &PragmaShiftQubitsTweezers) -> bool {
+    fn _are_all_shifts_valid(&self, pragma: &PragmaShiftQubitsTweezers) -> bool {
         #[inline]
         fn _is_tweezer_in_shift_lists(tweezer_id: &usize, shift_lists: &[Vec<usize>]) -> bool {
             shift_lists.iter().any(|list| list.contains(tweezer_id))
         }
         #[inline]
-        fn _is_tweezer_occupied(qbt_to_twz: &HashMap<usize, usize>, tweezer_id: &usize) -> bool {
-            qbt_to_twz.iter().any(|(_, twz)| twz == tweezer_id)
-        }
-        #[inline]
         fn _is_path_free(
-            qbt_to_twz: &HashMap<usize, usize>,
+            mapping: &QubitTweezerMapping,
             end_tweezer: &usize,
             shift_lists: &[Vec<usize>],
         ) -> bool {
-            let correct_shift_list = shift_lists
-                .iter()
-                .find(|list| list.contains(end_tweezer))
-                .unwrap();
-            // Check the path up to the target tweezer
-            for el in correct_shift_list
-                .iter()
-                .take_while(|tw| *tw != end_tweezer)
-            {
-                if _is_tweezer_occupied(qbt_to_twz, el) {
-                    return false;
-                }
-            }
-            // Check the target tweezer itself
-            if _is_tweezer_occupied(qbt_to_twz, end_tweezer) {
-                return false;
-            }
-            true
+            // Check the path up to the target tweezer, and the target tweezer itself
+            shift_path(*end_tweezer, shift_lists)
+                .is_some_and(|path| path.iter().all(|tw| !mapping.is_occupied(*tw)))
         }
         // Temporary clone: pretending the shift of the qubits in order to understand
         //  if the whole row can indeed be shifted or not
-        let mut tmp_qubit_to_tweezer = self.qubit_to_tweezer.clone();
+        let mut tmp_qubit_to_tweezer = self
+            .qubit_to_tweezer
+            .clone()
+            .expect("Internal error: qubit_to_tweezer mapping supposed to be Some().");
+        let allowed_tweezer_shifts = &self
+            .get_current_layout_info()
+            .unwrap()
+            .allowed_tweezer_shifts;
         // Checks for all shifts from pragma:
         // - if the starting tweezer has any valid shifts associated with it in the device
         // - if the ending tweezer is contained in the associated valid shifts
         // - if the device in the starting tweezer position is already occupied
         // - if any tweezer in between the starting and ending tweezers is free (ending included)
         for (shift_start, shift_end) in &pragma.shifts {
-            match self
-                .get_current_layout_info()
-                .unwrap()
-                .allowed_tweezer_shifts
-                .get(shift_start)
-            {
+            match allowed_tweezer_shifts.get(shift_start) {
                 Some(allowed_shifts) => {
                     if !_is_tweezer_in_shift_lists(shift_end, allowed_shifts)
-                        || !_is_tweezer_occupied(
-                            tmp_qubit_to_tweezer.as_ref().expect(
-                                "Internal error: qubit_to_tweezer mapping supposed to be Some().",
-                            ),
-                            shift_start,
-                        )
-                        || !_is_path_free(
-                            tmp_qubit_to_tweezer.as_ref().expect(
-                                "Internal error: qubit_to_tweezer mapping supposed to be Some().",
-                            ),
-                            shift_end,
-                            allowed_shifts,
-                        )
+                        || !tmp_qubit_to_tweezer.is_occupied(*shift_start)
+                        || !_is_path_free(&tmp_qubit_to_tweezer, shift_end, allowed_shifts)
                     {
                         return false;
                     }
@@ -1346,18 +2802,10 @@ impl TweezerDevice {
                 None => return false,
             }
             // "Faking" the movement of the qubit
-            if let Some((key, _)) = tmp_qubit_to_tweezer
-                .as_ref()
-                .unwrap()
-                .iter()
-                .find(|&(_, &value)| value == *shift_start)
-                .map(|(&key, &value)| (key, value))
-            {
-                tmp_qubit_to_tweezer.as_mut().unwrap().remove(&key);
-                tmp_qubit_to_tweezer
-                    .as_mut()
-                    .unwrap()
-                    .insert(key, *shift_end);
+            if let Some(qubit) = tmp_qubit_to_tweezer.qubit_at(*shift_start) {
+                if tmp_qubit_to_tweezer.move_qubit(qubit, *shift_end).is_err() {
+                    return false;
+                }
             }
         }
 
@@ -1369,6 +2817,17 @@ impl TweezerDevice {
         self.seed
     }
 
+    /// Returns whether the device is a cached copy, used because the QRyd WebAPI was unreachable.
+    ///
+    /// Only set by `from_api` and `device_from_api` with the device cache enabled.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the device definition may be outdated.
+    pub fn is_offline_fallback(&self) -> bool {
+        self.offline_fallback
+    }
+
     /// Returns the backend associated with the device.
     pub fn qrydbackend(&self) -> String {
         self.device_name.clone()
@@ -1391,14 +2850,13 @@ impl TweezerDevice {
         draw_shifts: bool,
         file_save_path: &Option<String>,
     ) -> Result<DynamicImage, RoqoqoBackendError> {
+        let layout_name = self
+            .current_layout
+            .clone()
+            .or_else(|| self.default_layout.clone())
+            .unwrap_or_default();
         let layout = match &self.layout_register {
-            Some(x) => x.get(
-                &self
-                    .current_layout
-                    .clone()
-                    .or_else(|| self.default_layout.clone())
-                    .unwrap_or_default(),
-            ),
+            Some(x) => x.get(&layout_name),
             None => {
                 return Err(RoqoqoBackendError::GenericError {
                     msg: "Draw method not available for EmulatorDevice.".to_owned(),
@@ -1430,12 +2888,13 @@ impl TweezerDevice {
             .unwrap_or(&0_usize)
             .to_owned()
             + 1;
-        let mut tweezers_positions = Vec::new();
+        let mut node_positions = Vec::new();
         let mut edges_map = HashMap::new();
         let nodes = create_nodes(
             nb_tweezers,
             current_layout.tweezers_per_row.clone(),
-            &mut tweezers_positions,
+            self.tweezer_positions.get(&layout_name),
+            &mut node_positions,
             &self.qubit_to_tweezer,
         )?;
         map_edges(
@@ -1449,7 +2908,7 @@ impl TweezerDevice {
                 &mut edges_map,
             )?
         }
-        let edges = create_edges(&edges_map, &tweezers_positions)?;
+        let edges = create_edges(&edges_map, &node_positions)?;
         let mut typst_str = r#"#import "@preview/fletcher:0.5.0" as fletcher: diagram, node, edge
 #set page(width: auto, height: auto, margin: 5mm, fill: white)
 #show math.equation: set text(font: "Fira Math")
@@ -1556,10 +3015,24 @@ impl Device for TweezerDevice {
         None
     }
 
-    #[allow(unused_variables)]
+    /// Returns the decoherence rates matrix of the tweezer the qubit occupies in the current
+    /// Layout.
+    ///
+    /// Qubits that are not mapped to a tweezer, or whose tweezer has no rates set, are noise free.
     fn qubit_decoherence_rates(&self, qubit: &usize) -> Option<Array2<f64>> {
-        // At the moment we hard-code a noise free model
-        Some(Array2::zeros((3, 3).to_owned()))
+        let rates = self
+            .qubit_to_tweezer
+            .as_ref()
+            .and_then(|mapping| mapping.tweezer_of(*qubit))
+            .and_then(|tweezer| {
+                self.get_current_layout_info()
+                    .ok()?
+                    .tweezer_decoherence_rates
+                    .get(&tweezer)
+            })
+            .copied()
+            .unwrap_or_default();
+        Some(rates.rates_matrix())
     }
 
     fn number_qubits(&self) -> usize {
@@ -1567,36 +3040,37 @@ impl Device for TweezerDevice {
             if map.is_empty() {
                 return 0;
             }
-            return *map.keys().max().unwrap_or(&0) + 1;
+            return map.qubits().last().map_or(0, |qubit| qubit + 1);
         }
         0
     }
 
     fn two_qubit_edges(&self) -> Vec<(usize, usize)> {
-        let tw_two_qubit_gate_times = &self
-            .get_current_layout_info()
-            .expect("Tried to access current layout info but no current layout is set.")
-            .tweezer_two_qubit_gate_times;
+        let layout_index = self
+            .get_layout_index(None)
+            .expect("Tried to access current layout info but no current layout is set.");
         if let Some(map) = &self.qubit_to_tweezer {
             let mut edges: Vec<(usize, usize)> = Vec::new();
-            for (qbt0, qbt1) in iproduct!(map.keys(), map.keys()) {
-                if let (Some(mapped_qbt0), Some(mapped_qbt1)) = (
-                    self.get_tweezer_from_qubit(qbt0).ok(),
-                    self.get_tweezer_from_qubit(qbt1).ok(),
-                ) {
-                    if tw_two_qubit_gate_times
-                        .values()
-                        .any(|times| times.get(&(mapped_qbt0, mapped_qbt1)).is_some())
-                    {
-                        edges.push((*qbt0, *qbt1));
-                    }
+            for (qbt0, twz0) in map.iter() {
+                if let Some(neighbours) = layout_index.two_qubit_neighbours.get(&twz0) {
+                    edges.extend(
+                        neighbours
+                            .iter()
+                            .filter_map(|twz1| map.qubit_at(*twz1))
+                            .map(|qbt1| (qbt0, qbt1)),
+                    );
                 }
             }
+            edges.sort_unstable();
             return edges;
         }
         vec![]
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self, operation))
+    )]
     fn change_device(&mut self, hqslang: &str, operation: &[u8]) -> Result<(), RoqoqoBackendError> {
         match hqslang {
             "PragmaChangeQRydLayout" => Err(RoqoqoBackendError::GenericError {
@@ -1615,6 +3089,12 @@ impl Device for TweezerDevice {
                                     (Some(current_tweezers_per_row), Some(new_tweezers_per_row)) => {
                                         // Switch if the number of tweezers per row is the same
                                         if current_tweezers_per_row == new_tweezers_per_row {
+                                            #[cfg(feature = "tracing")]
+                                            tracing::debug!(
+                                                from = ?self.current_layout,
+                                                to = %pragma.new_layout(),
+                                                "Switching device layout"
+                                            );
                                             self.current_layout = Some(pragma.new_layout().to_string());
                                             Ok(())
                                         } else {
@@ -1678,23 +3158,26 @@ impl Device for TweezerDevice {
                         }
                         // Check if the shifts in the operation are valid on the device
                         if !self._are_all_shifts_valid(&pragma) {
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!(
+                                shifts = ?pragma.shifts,
+                                layout = ?self.current_layout,
+                                "Rejected invalid PragmaShiftQubitsTweezers"
+                            );
                             return Err(RoqoqoBackendError::GenericError {
                                 msg: "The PragmaShiftQubitsTweezers operation is not valid on this device."
                                     .to_string(),
                             });
                         }
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(
+                            shifts = ?pragma.shifts,
+                            layout = ?self.current_layout,
+                            "Applying PragmaShiftQubitsTweezers"
+                        );
                         // Start applying the shifts
                         if let Some(map) = &mut self.qubit_to_tweezer {
-                            for (shift_start, shift_end) in &pragma.shifts {
-                                if let Some(qubit_to_move) =
-                                    map.iter()
-                                        .find_map(|(&qbt, &twz)| if twz == *shift_start { Some(qbt) } else { None })
-                                {
-                                    // Move the qubit into the new tweezer
-                                    map.remove(&qubit_to_move);
-                                    map.insert(qubit_to_move, *shift_end);
-                                }
-                            }
+                            map.apply_shifts(&pragma.shifts)?;
                         }
                         Ok(())
                     }
@@ -1747,6 +3230,96 @@ impl Device for TweezerDevice {
     }
 }
 
+/// Adds the shifts along each row to the allowed tweezer shifts.
+///
+/// Every tweezer of a row can be shifted towards both ends of the row.
+pub(crate) fn insert_row_shifts(
+    allowed_shifts: &mut HashMap<usize, Vec<Vec<usize>>>,
+    row_shifts: &[&[usize]],
+) {
+    // For each row in the input..
+    row_shifts.iter().for_each(|row| {
+        // ... divide in left, mid (the tweezer) and right parts
+        for i in 0..row.len() {
+            let (left_slice, mid) = row.split_at(i);
+            let mid = mid.first().unwrap_or(&0);
+            let mut vec_left = left_slice.to_vec();
+            vec_left.reverse();
+            let vec_right = &row[i + 1..].to_vec();
+
+            // Insert the left and right side
+            let val = allowed_shifts.entry(*mid).or_default();
+            if !vec_left.is_empty() && !val.contains(&vec_left) {
+                val.push(vec_left.to_vec());
+            }
+            if !vec_right.is_empty() && !val.contains(vec_right) {
+                val.push(vec_right.to_vec());
+            }
+        }
+    });
+}
+
+/// Returns the tweezers a shift passes through, the end tweezer included.
+///
+/// The path follows the first allowed shift list of the start tweezer containing the end tweezer.
+///
+/// # Arguments
+///
+/// * `end_tweezer` - The tweezer the shift ends in.
+/// * `shift_lists` - The allowed shift lists of the start tweezer.
+///
+/// # Returns
+///
+/// * `Option<&[usize]>` - The path of the shift, None if the shift is not allowed.
+pub(crate) fn shift_path(end_tweezer: usize, shift_lists: &[Vec<usize>]) -> Option<&[usize]> {
+    shift_lists.iter().find_map(|list| {
+        list.iter()
+            .position(|tweezer| *tweezer == end_tweezer)
+            .map(|position| &list[..=position])
+    })
+}
+
+#[inline]
+/// Returns the error of a gate without gate time on the given tweezers.
+fn gate_not_available_error(hqslang: &str, tweezers: &[usize]) -> RoqoqoBackendError {
+    RoqoqoBackendError::GenericError {
+        msg: format!(
+            "The {} gate has no gate time on the tweezers {:?}, an error rate cannot be set.",
+            hqslang, tweezers
+        ),
+    }
+}
+
+fn distance(position0: (f64, f64, f64), position1: (f64, f64, f64)) -> f64 {
+    ((position0.0 - position1.0).powi(2)
+        + (position0.1 - position1.1).powi(2)
+        + (position0.2 - position1.2).powi(2))
+    .sqrt()
+}
+
+/// Returns the smallest non-zero distance between two points, None for fewer than two distinct points.
+///
+/// The points are swept in order of their x coordinate and a point is only compared with the
+/// following points that are closer in x than the smallest distance found so far. For the
+/// tweezer lattices drawn by the device this avoids comparing every pair of tweezers.
+fn minimum_distance(points: &[(f64, f64)]) -> Option<f64> {
+    let mut sorted_points = points.to_vec();
+    sorted_points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut minimum = f64::INFINITY;
+    for (index, &(x0, y0)) in sorted_points.iter().enumerate() {
+        for &(x1, y1) in sorted_points[index + 1..].iter() {
+            if x1 - x0 >= minimum {
+                break;
+            }
+            let dist = (x1 - x0).hypot(y1 - y0);
+            if dist > 0.0 && dist < minimum {
+                minimum = dist;
+            }
+        }
+    }
+    minimum.is_finite().then_some(minimum)
+}
+
 enum ShiftType {
     None,
     LeftToRight,
@@ -1757,11 +3330,25 @@ enum ShiftType {
 fn create_nodes(
     nb_tweezers: usize,
     tweezers_per_row: Option<Vec<usize>>,
-    tweezers_positions: &mut Vec<(usize, usize)>,
-    qubit_to_tweezer: &Option<HashMap<usize, usize>>,
+    physical_positions: Option<&TweezerPositions>,
+    node_positions: &mut Vec<(f64, f64)>,
+    qubit_to_tweezer: &Option<QubitTweezerMapping>,
 ) -> Result<String, RoqoqoBackendError> {
-    let mut nodes = "".to_owned();
-    if tweezers_per_row.is_some()
+    let physical_positions = physical_positions.filter(|physical_positions| {
+        (0..nb_tweezers).all(|tweezer| physical_positions.positions.contains_key(&tweezer))
+    });
+    if let Some(physical_positions) = physical_positions {
+        // Physical positions are projected on the xy-plane and scaled so that the closest
+        // tweezers are one unit apart
+        let points: Vec<(f64, f64)> = (0..nb_tweezers)
+            .map(|tweezer| {
+                let (x, y, _) = physical_positions.positions[&tweezer];
+                (x, y)
+            })
+            .collect();
+        let scale = minimum_distance(&points).unwrap_or(1.0);
+        node_positions.extend(points.iter().map(|&(x, y)| (x / scale, y / scale)));
+    } else if tweezers_per_row.is_some()
         && tweezers_per_row
             .clone()
             .unwrap()
@@ -1773,21 +3360,7 @@ fn create_nodes(
         let mut x = 0;
         let mut y = 0;
         for tweezer in 0..nb_tweezers {
-            tweezers_positions.insert(tweezer, (x, y));
-            nodes.push_str(&format!(
-                "node(({x},{y}), ${tweezer}_t{}, shape: circle),\n",
-                qubit_to_tweezer
-                    .clone()
-                    .map(|qubit_map| {
-                        for (qubit, tweez) in qubit_map {
-                            if tweez == tweezer {
-                                return format!("|{qubit}_q$, radius: 2.3em");
-                            }
-                        }
-                        "$, radius: 1.3em".to_owned()
-                    })
-                    .unwrap_or("$, radius: 1.3em".to_owned())
-            ));
+            node_positions.insert(tweezer, (x as f64, y as f64));
             x += 1;
             if x == nb_tweezers_per_row[y] {
                 x = 0;
@@ -1804,6 +3377,17 @@ fn create_nodes(
             },
         ));
     }
+    let mut nodes = "".to_owned();
+    for (tweezer, (x, y)) in node_positions.iter().enumerate() {
+        nodes.push_str(&format!(
+            "node(({x},{y}), ${tweezer}_t{}, shape: circle),\n",
+            qubit_to_tweezer
+                .as_ref()
+                .and_then(|qubit_map| qubit_map.qubit_at(tweezer))
+                .map(|qubit| format!("|{qubit}_q$, radius: 2.3em"))
+                .unwrap_or("$, radius: 1.3em".to_owned())
+        ));
+    }
     Ok(nodes)
 }
 
@@ -1827,16 +3411,16 @@ fn map_edges(
 
 fn create_edges(
     edges_map: &HashMap<(usize, usize), ShiftType>,
-    tweezers_positions: &[(usize, usize)],
+    node_positions: &[(f64, f64)],
 ) -> Result<String, RoqoqoBackendError> {
     let mut edges = "".to_owned();
     for (&(qb1, qb2), shift_type) in edges_map.iter() {
         edges.push_str(&format!(
             "   edge(({},{}), ({},{}){})\n",
-            tweezers_positions[qb1].0,
-            tweezers_positions[qb1].1,
-            tweezers_positions[qb2].0,
-            tweezers_positions[qb2].1,
+            node_positions[qb1].0,
+            node_positions[qb1].1,
+            node_positions[qb2].0,
+            node_positions[qb2].1,
             match shift_type {
                 ShiftType::None => "",
                 ShiftType::Both => ", \"<|-|>\"",
@@ -1893,3 +3477,72 @@ fn map_shifts(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn minimum_distance_of_points() {
+        assert_eq!(minimum_distance(&[]), None);
+        assert_eq!(minimum_distance(&[(1.0, 1.0)]), None);
+        assert_eq!(minimum_distance(&[(1.0, 1.0), (1.0, 1.0)]), None);
+        assert_eq!(
+            minimum_distance(&[(0.0, 0.0), (10.0, 0.0), (3.0, 4.0), (0.0, 10.0)]),
+            Some(5.0)
+        );
+        // Points with the same x coordinate are compared as well
+        assert_eq!(
+            minimum_distance(&[(0.0, 0.0), (0.0, 9.0), (0.0, 2.0), (5.0, 0.0)]),
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn nodes_from_physical_positions() {
+        let physical_positions = TweezerPositions {
+            number_dimensions: 3,
+            positions: HashMap::from([
+                (0, (0.0, 0.0, 0.0)),
+                (1, (4.0, 0.0, 1.0)),
+                (2, (0.0, 8.0, 0.0)),
+            ]),
+        };
+        let mut node_positions = Vec::new();
+        let nodes = create_nodes(
+            3,
+            Some(vec![1]),
+            Some(&physical_positions),
+            &mut node_positions,
+            &None,
+        )
+        .unwrap();
+        // Projected on the xy-plane and scaled by the smallest distance of 4 micrometers
+        assert_eq!(node_positions, vec![(0.0, 0.0), (1.0, 0.0), (0.0, 2.0)]);
+        assert!(nodes.contains("node((0,2), $2_t$, radius: 1.3em, shape: circle)"));
+    }
+
+    #[test]
+    fn nodes_from_rows_without_all_positions() {
+        let physical_positions = TweezerPositions {
+            number_dimensions: 2,
+            positions: HashMap::from([(0, (0.0, 0.0, 0.0))]),
+        };
+        let mut qubit_to_tweezer = QubitTweezerMapping::default();
+        qubit_to_tweezer.insert(0, 2).unwrap();
+        let mut node_positions = Vec::new();
+        let nodes = create_nodes(
+            3,
+            Some(vec![2, 1]),
+            Some(&physical_positions),
+            &mut node_positions,
+            &Some(qubit_to_tweezer),
+        )
+        .unwrap();
+        assert_eq!(node_positions, vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]);
+        assert!(nodes.contains("node((0,1), $2_t|0_q$, radius: 2.3em, shape: circle)"));
+
+        let mut node_positions = Vec::new();
+        assert!(create_nodes(3, Some(vec![1]), None, &mut node_positions, &None).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright © 2021-2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Tweezer layout generators
+//!
+//! Generates complete TweezerLayoutInfo instances and the matching TweezerPositions for
+//! regular lattices and arbitrary tweezer coordinates.
+
+use std::collections::HashMap;
+
+use roqoqo::RoqoqoBackendError;
+
+use crate::{
+    tweezer_devices::insert_row_shifts, TweezerLayoutInfo, TweezerPositions,
+    ALLOWED_NATIVE_SINGLE_QUBIT_GATES, ALLOWED_NATIVE_THREE_QUBIT_GATES,
+    ALLOWED_NATIVE_TWO_QUBIT_GATES,
+};
+
+/// Relative tolerance used when comparing tweezer distances with the interaction radius.
+const DISTANCE_TOLERANCE: f64 = 1e-9;
+
+/// The (x, y, z) coordinates of tweezers in micrometers, row by row.
+pub type TweezerRows = Vec<Vec<(f64, f64, f64)>>;
+
+/// Geometry of the tweezers of a generated Layout.
+///
+/// Tweezers are numbered row by row, starting with the row with the lowest y coordinate.
+/// The `spacing` is the distance between nearest neighbours in micrometers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TweezerLattice {
+    /// Square lattice with `rows` rows of `columns` tweezers.
+    Square {
+        /// Number of rows.
+        rows: usize,
+        /// Number of tweezers per row.
+        columns: usize,
+        /// Distance between nearest neighbours.
+        spacing: f64,
+    },
+    /// Triangular lattice with `rows` rows of `columns` tweezers, every other row offset by half a spacing.
+    Triangular {
+        /// Number of rows.
+        rows: usize,
+        /// Number of tweezers per row.
+        columns: usize,
+        /// Distance between nearest neighbours.
+        spacing: f64,
+    },
+    /// Hexagonal (honeycomb) lattice with `rows` zigzag rows of `columns` tweezers.
+    Hexagonal {
+        /// Number of rows.
+        rows: usize,
+        /// Number of tweezers per row.
+        columns: usize,
+        /// Distance between nearest neighbours.
+        spacing: f64,
+    },
+    /// Kagome lattice with `rows` rows, alternating between full rows of `columns` tweezers
+    /// and rows with a tweezer above every other gap.
+    Kagome {
+        /// Number of rows.
+        rows: usize,
+        /// Number of tweezers in the full rows.
+        columns: usize,
+        /// Distance between nearest neighbours.
+        spacing: f64,
+    },
+    /// Arbitrary tweezer positions, given as rows of (x, y, z) coordinates in micrometers.
+    Arbitrary {
+        /// The positions of the tweezers, row by row.
+        rows: TweezerRows,
+    },
+}
+
+impl TweezerLattice {
+    /// Returns the positions of the tweezers of the lattice.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TweezerRows)` - The (x, y, z) coordinates of the tweezers, row by row.
+    /// * `Err(RoqoqoBackendError)` - The spacing is not positive, a coordinate is not finite or
+    ///   two arbitrary positions coincide.
+    pub fn tweezer_rows(&self) -> Result<TweezerRows, RoqoqoBackendError> {
+        if let Some(spacing) = self.spacing() {
+            if !(spacing.is_finite() && spacing > 0.0) {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!("The lattice spacing must be positive, got {}.", spacing),
+                });
+            }
+        }
+        let row_height = 3.0_f64.sqrt() / 2.0;
+        let rows = match *self {
+            TweezerLattice::Square {
+                rows,
+                columns,
+                spacing,
+            } => (0..rows)
+                .map(|row| {
+                    (0..columns)
+                        .map(|column| (column as f64 * spacing, row as f64 * spacing, 0.0))
+                        .collect()
+                })
+                .collect(),
+            TweezerLattice::Triangular {
+                rows,
+                columns,
+                spacing,
+            } => (0..rows)
+                .map(|row| {
+                    let offset = if row % 2 == 1 { 0.5 } else { 0.0 };
+                    (0..columns)
+                        .map(|column| {
+                            (
+                                (column as f64 + offset) * spacing,
+                                row as f64 * row_height * spacing,
+                                0.0,
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+            TweezerLattice::Hexagonal {
+                rows,
+                columns,
+                spacing,
+            } => (0..rows)
+                .map(|row| {
+                    (0..columns)
+                        .map(|column| {
+                            let offset = if (row + column) % 2 == 1 { 0.5 } else { 0.0 };
+                            (
+                                column as f64 * row_height * spacing,
+                                (row as f64 * 1.5 + offset) * spacing,
+                                0.0,
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+            TweezerLattice::Kagome {
+                rows,
+                columns,
+                spacing,
+            } => (0..rows)
+                .map(|row| {
+                    let y = row as f64 * row_height * spacing;
+                    if row % 2 == 0 {
+                        (0..columns)
+                            .map(|column| (column as f64 * spacing, y, 0.0))
+                            .collect()
+                    } else {
+                        let offset = if row % 4 == 3 { 1.5 } else { 0.5 };
+                        (0..)
+                            .map(|column| offset + 2.0 * column as f64)
+                            .take_while(|&x| x + 0.5 <= columns as f64 - 1.0)
+                            .map(|x| (x * spacing, y, 0.0))
+                            .collect()
+                    }
+                })
+                .collect(),
+            TweezerLattice::Arbitrary { ref rows } => {
+                if rows
+                    .iter()
+                    .flatten()
+                    .any(|&(x, y, z)| !(x.is_finite() && y.is_finite() && z.is_finite()))
+                {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: "The given tweezer positions are not finite.".to_string(),
+                    });
+                }
+                // Adding zero maps -0.0 to 0.0, so that equal positions are sorted next to each other
+                let mut positions: Vec<(f64, f64, f64)> = rows
+                    .iter()
+                    .flatten()
+                    .map(|&(x, y, z)| (x + 0.0, y + 0.0, z + 0.0))
+                    .collect();
+                positions.sort_by(|a, b| {
+                    a.0.total_cmp(&b.0)
+                        .then(a.1.total_cmp(&b.1))
+                        .then(a.2.total_cmp(&b.2))
+                });
+                if let Some(duplicate) = positions
+                    .windows(2)
+                    .find(|pair| pair[0] == pair[1])
+                    .map(|pair| pair[0])
+                {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: format!(
+                            "The given tweezer positions contain {:?} more than once.",
+                            duplicate
+                        ),
+                    });
+                }
+                rows.clone()
+            }
+        };
+        Ok(rows)
+    }
+
+    /// Returns the positions of the tweezers of the lattice, numbered row by row.
+    ///
+    /// Regular lattices are two-dimensional. Arbitrary positions are three-dimensional
+    /// unless all z coordinates are zero.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TweezerPositions)` - The positions of the tweezers and their number of dimensions.
+    /// * `Err(RoqoqoBackendError)` - The spacing is not positive or a coordinate is not finite.
+    pub fn tweezer_positions(&self) -> Result<TweezerPositions, RoqoqoBackendError> {
+        let positions: Vec<(f64, f64, f64)> = self.tweezer_rows()?.into_iter().flatten().collect();
+        let number_dimensions = if positions.iter().all(|&(_, _, z)| z == 0.0) {
+            2
+        } else {
+            3
+        };
+        Ok(TweezerPositions {
+            number_dimensions,
+            positions: positions.into_iter().enumerate().collect(),
+        })
+    }
+
+    /// Returns the distance between nearest neighbours of the lattice.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The spacing of the lattice, None for arbitrary positions.
+    pub fn spacing(&self) -> Option<f64> {
+        match *self {
+            TweezerLattice::Square { spacing, .. }
+            | TweezerLattice::Triangular { spacing, .. }
+            | TweezerLattice::Hexagonal { spacing, .. }
+            | TweezerLattice::Kagome { spacing, .. } => Some(spacing),
+            TweezerLattice::Arbitrary { .. } => None,
+        }
+    }
+}
+
+/// Generates a complete TweezerLayoutInfo for a lattice of tweezers.
+///
+/// Every tweezer supports the given single-qubit gates. Two-qubit gates are set in both
+/// orders for every pair of tweezers within the interaction radius, three-qubit gates in all
+/// orders for every trio of tweezers pairwise within the interaction radius.
+///
+/// # Arguments
+///
+/// * `lattice` - The geometry of the tweezers.
+/// * `gate_times` - The gate time of each native gate supported by the Layout, by hqslang name.
+/// * `interaction_radius` - The maximal distance of interacting tweezers in micrometers.
+///   Defaults to the spacing of the lattice, required for arbitrary positions.
+/// * `row_shifts` - Whether to allow shifting qubits along the rows of the lattice.
+///
+/// # Returns
+///
+/// * `Ok(TweezerLayoutInfo)` - The generated Layout, including tweezers per row. The positions of
+///   the tweezers are given by `TweezerLattice::tweezer_positions`.
+/// * `Err(RoqoqoBackendError)` - A gate is not a native gate or the geometry is invalid.
+pub fn lattice_layout(
+    lattice: &TweezerLattice,
+    gate_times: &HashMap<String, f64>,
+    interaction_radius: Option<f64>,
+    row_shifts: bool,
+) -> Result<TweezerLayoutInfo, RoqoqoBackendError> {
+    let interaction_radius = interaction_radius
+        .or_else(|| lattice.spacing())
+        .ok_or_else(|| RoqoqoBackendError::GenericError {
+            msg: "An interaction radius is required for arbitrary tweezer positions.".to_string(),
+        })?;
+    if !(interaction_radius.is_finite() && interaction_radius >= 0.0) {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "The interaction radius must not be negative, got {}.",
+                interaction_radius
+            ),
+        });
+    }
+    for hqslang in gate_times.keys() {
+        if !ALLOWED_NATIVE_SINGLE_QUBIT_GATES.contains(&hqslang.as_str())
+            && !ALLOWED_NATIVE_TWO_QUBIT_GATES.contains(&hqslang.as_str())
+            && !ALLOWED_NATIVE_THREE_QUBIT_GATES.contains(&hqslang.as_str())
+        {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Error generating the Layout. Gate {} is not supported.",
+                    hqslang
+                ),
+            });
+        }
+    }
+
+    let rows = lattice.tweezer_rows()?;
+    let positions: Vec<(f64, f64, f64)> = rows.iter().flatten().copied().collect();
+    let max_distance = interaction_radius * (1.0 + DISTANCE_TOLERANCE);
+    let interacting = |tweezer0: usize, tweezer1: usize| {
+        let (x0, y0, z0) = positions[tweezer0];
+        let (x1, y1, z1) = positions[tweezer1];
+        ((x0 - x1).powi(2) + (y0 - y1).powi(2) + (z0 - z1).powi(2)).sqrt() <= max_distance
+    };
+    let pairs: Vec<(usize, usize)> = (0..positions.len())
+        .flat_map(|tweezer0| ((tweezer0 + 1)..positions.len()).map(move |t1| (tweezer0, t1)))
+        .filter(|&(tweezer0, tweezer1)| interacting(tweezer0, tweezer1))
+        .collect();
+
+    let mut info = TweezerLayoutInfo::default();
+    for (hqslang, &gate_time) in gate_times.iter() {
+        if ALLOWED_NATIVE_SINGLE_QUBIT_GATES.contains(&hqslang.as_str()) {
+            info.tweezer_single_qubit_gate_times.insert(
+                hqslang.clone(),
+                (0..positions.len())
+                    .map(|tweezer| (tweezer, gate_time))
+                    .collect(),
+            );
+        } else if ALLOWED_NATIVE_TWO_QUBIT_GATES.contains(&hqslang.as_str()) {
+            info.tweezer_two_qubit_gate_times.insert(
+                hqslang.clone(),
+                pairs
+                    .iter()
+                    .flat_map(|&(tweezer0, tweezer1)| {
+                        [
+                            ((tweezer0, tweezer1), gate_time),
+                            ((tweezer1, tweezer0), gate_time),
+                        ]
+                    })
+                    .collect(),
+            );
+        } else {
+            let trios: HashMap<(usize, usize, usize), f64> = pairs
+                .iter()
+                .flat_map(|&(tweezer0, tweezer1)| {
+                    ((tweezer1 + 1)..positions.len())
+                        .filter(move |&tweezer2| {
+                            interacting(tweezer0, tweezer2) && interacting(tweezer1, tweezer2)
+                        })
+                        .flat_map(move |tweezer2| {
+                            // The gate times are looked up for the tweezers in gate order
+                            [
+                                (tweezer0, tweezer1, tweezer2),
+                                (tweezer0, tweezer2, tweezer1),
+                                (tweezer1, tweezer0, tweezer2),
+                                (tweezer1, tweezer2, tweezer0),
+                                (tweezer2, tweezer0, tweezer1),
+                                (tweezer2, tweezer1, tweezer0),
+                            ]
+                            .map(|trio| (trio, gate_time))
+                        })
+                })
+                .collect();
+            info.tweezer_three_qubit_gate_times
+                .insert(hqslang.clone(), trios);
+        }
+    }
+
+    let mut first_tweezer = 0;
+    let tweezer_indices: Vec<Vec<usize>> = rows
+        .iter()
+        .map(|row| {
+            let indices = (first_tweezer..first_tweezer + row.len()).collect();
+            first_tweezer += row.len();
+            indices
+        })
+        .collect();
+    if row_shifts {
+        let row_slices: Vec<&[usize]> = tweezer_indices.iter().map(|row| row.as_slice()).collect();
+        insert_row_shifts(&mut info.allowed_tweezer_shifts, &row_slices);
+    }
+    info.tweezers_per_row = Some(rows.iter().map(|row| row.len()).collect());
+    Ok(info)
+}
@@ -19,7 +19,10 @@ use roqoqo::Circuit;
 use roqoqo::QuantumProgram;
 use roqoqo::RoqoqoBackendError;
 use roqoqo_qryd::api_devices::{QRydAPIDevice, QrydEmuSquareDevice, QrydEmuTriangularDevice};
-use roqoqo_qryd::{APIBackend, QRydJobResult, QRydJobStatus, ResultCounts, TweezerDevice};
+use roqoqo_qryd::{
+    APIBackend, QRydJobResult, QRydJobStatus, ReadoutConfusionMatrix, ReadoutMitigationMethod,
+    ResultCounts, TweezerDevice,
+};
 
 use qoqo_calculator::CalculatorFloat;
 
@@ -1732,6 +1735,40 @@ fn test_unknown_device_error() {
     }
 }
 
+/// Test the mitigation of the readout errors of WebAPI counts
+#[test]
+fn test_mitigate_counts() {
+    let counts = ResultCounts {
+        counts: [("0x1".to_string(), 80), ("0x0".to_string(), 20)]
+            .into_iter()
+            .collect(),
+    };
+    let confusion_matrices = vec![
+        ReadoutConfusionMatrix::new(0.1, 0.2).unwrap(),
+        ReadoutConfusionMatrix::default(),
+    ];
+    let mitigated = APIBackend::mitigate_counts(
+        &counts,
+        2,
+        &confusion_matrices,
+        ReadoutMitigationMethod::Inversion,
+    )
+    .unwrap();
+    assert_eq!(mitigated.len(), 1);
+    assert!((mitigated["10"] - 1.0).abs() < 1e-12);
+
+    let counts = ResultCounts {
+        counts: [("1".to_string(), 1)].into_iter().collect(),
+    };
+    assert!(APIBackend::mitigate_counts(
+        &counts,
+        2,
+        &confusion_matrices,
+        ReadoutMitigationMethod::Inversion
+    )
+    .is_err());
+}
+
 // /// Test downcovert_roqoqo_version function
 // #[test]
 // fn test_downconvert_roqoqo_version() {
@@ -1854,3 +1891,117 @@ fn test_unknown_device_error() {
 //     let correct_downconverted_quantum_program = downconvert_roqoqo_version(program);
 //     assert!(correct_downconverted_quantum_program.is_ok());
 // }
+
+/// Buffer collecting the formatted output of a tracing subscriber.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Default)]
+struct TraceBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+#[cfg(feature = "tracing")]
+impl std::io::Write for TraceBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for TraceBuffer {
+    type Writer = TraceBuffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+// Test that posting a job and querying its status emits tracing events (mocked)
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn api_backend_tracing() {
+    use wiremock::matchers::header;
+
+    let server_wiremock = MockServer::start().await;
+    let qryd_job_status_completed = QRydJobStatus {
+        status: "completed".to_string(),
+        msg: "the job has been completed".to_string(),
+    };
+    let access_token = "tracing-secret-token-4711";
+    let _mock_post = Mock::given(method("POST"))
+        .and(header("X-API-KEY", access_token))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .insert_header(
+                    "Location",
+                    &format!("{}/DummyLocation", server_wiremock.uri()),
+                )
+                .insert_header("x-request-id", "abc-123"),
+        )
+        .expect(1)
+        .mount(&server_wiremock)
+        .await;
+    let _mock_status = Mock::given(method("GET"))
+        .and(path("/DummyLocation/status"))
+        .and(header("X-API-KEY", access_token))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&qryd_job_status_completed))
+        .expect(1)
+        .mount(&server_wiremock)
+        .await;
+
+    let device = QrydEmuSquareDevice::new(Some(2), None, None);
+    let qryd_device: QRydAPIDevice = QRydAPIDevice::from(&device);
+    let api_backend_new = APIBackend::new(
+        qryd_device,
+        Some(access_token.to_string()),
+        None,
+        Some(server_wiremock.address().port().to_string()),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += operations::PauliX::new(0);
+    circuit += operations::MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += operations::PragmaSetNumberOfMeasurements::new(10, "ro".to_string());
+    let program = QuantumProgram::ClassicalRegister {
+        measurement: ClassicalRegister {
+            constant_circuit: None,
+            circuits: vec![circuit],
+        },
+        input_parameter_names: vec![],
+    };
+
+    let buffer = TraceBuffer::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(buffer.clone())
+        .finish();
+    let job_status = tokio::task::spawn_blocking(move || {
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(backend = ?api_backend_new, "Backend created");
+            let job_loc = api_backend_new.post_job(program).unwrap();
+            api_backend_new.get_job_status(job_loc).unwrap()
+        })
+    })
+    .await
+    .unwrap();
+    assert_eq!(job_status.status, "completed");
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("post_job"));
+    assert!(output.contains("status=201"));
+    assert!(output.contains("request_id=\"abc-123\""));
+    assert!(output.contains("Job posted to QRyd WebAPI"));
+    assert!(output.contains("get_job_status"));
+    assert!(output.contains("status=200"));
+    assert!(output.contains("Backend created"));
+    assert!(!output.contains(access_token));
+
+    server_wiremock.verify().await;
+}
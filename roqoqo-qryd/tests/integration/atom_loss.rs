@@ -0,0 +1,78 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo::registers::BitOutputRegister;
+use roqoqo_qryd::{post_select_shots, AtomLossModel, DEFAULT_LOST_QUBITS_REGISTER};
+
+/// Test AtomLossModel builder, validation and loss probabilities
+#[test]
+fn test_atom_loss_model() {
+    let model = AtomLossModel::new();
+    assert_eq!(model.lost_register, DEFAULT_LOST_QUBITS_REGISTER);
+    assert_eq!(model.shift_loss_probability(10.0), 0.0);
+    assert_eq!(model.idle_loss_probability(10.0), 0.0);
+    assert!(model.validate().is_ok());
+
+    let model = AtomLossModel::new()
+        .with_shift_loss_rate(0.5)
+        .with_measurement_loss_probability(0.1)
+        .with_idle_loss_rate(2.0)
+        .with_lost_register("lost");
+    assert_eq!(model.lost_register, "lost");
+    assert_eq!(model.measurement_loss_probability, 0.1);
+    assert!((model.shift_loss_probability(2.0) - (1.0 - (-1.0f64).exp())).abs() < 1e-12);
+    assert!((model.idle_loss_probability(0.5) - (1.0 - (-1.0f64).exp())).abs() < 1e-12);
+    assert!(model.validate().is_ok());
+
+    assert!(AtomLossModel::new()
+        .with_shift_loss_rate(-1.0)
+        .validate()
+        .is_err());
+    assert!(AtomLossModel::new()
+        .with_idle_loss_rate(f64::NAN)
+        .validate()
+        .is_err());
+    assert!(AtomLossModel::new()
+        .with_measurement_loss_probability(1.5)
+        .validate()
+        .is_err());
+}
+
+/// Test post_select_shots() keeping the shots without lost qubits
+#[test]
+fn test_post_select_shots() {
+    let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
+    bit_registers.insert(
+        "ro".to_string(),
+        vec![vec![true, true], vec![false, true], vec![true, false]],
+    );
+    bit_registers.insert(
+        "lost".to_string(),
+        vec![vec![false, false], vec![true, false], vec![false, true]],
+    );
+
+    let selected = post_select_shots(&bit_registers, "lost", None).unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected["ro"], vec![vec![true, true]]);
+
+    let selected = post_select_shots(&bit_registers, "lost", Some(&[1])).unwrap();
+    assert_eq!(selected["ro"], vec![vec![true, true], vec![false, true]]);
+    let selected = post_select_shots(&bit_registers, "lost", Some(&[])).unwrap();
+    assert_eq!(selected["ro"], bit_registers["ro"]);
+
+    // Errors
+    assert!(post_select_shots(&bit_registers, "missing", None).is_err());
+    bit_registers.insert("other".to_string(), vec![vec![true]]);
+    assert!(post_select_shots(&bit_registers, "lost", None).is_err());
+}
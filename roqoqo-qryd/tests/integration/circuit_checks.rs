@@ -0,0 +1,122 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations;
+use roqoqo::Circuit;
+use roqoqo_qryd::{
+    violations_into_result, DeviceViolation, PragmaShiftQubitsTweezers, QubitTweezerMapping,
+    TweezerDevice, ViolationReason,
+};
+
+/// Returns a device with four tweezers holding qubits 0, 1 and 2 in tweezers 0, 1 and 2.
+///
+/// RotateX is available on all tweezers, PhaseShiftedControlledZ between tweezers 0 and 1 and
+/// qubits can be shifted from tweezer 2 to tweezer 3.
+fn test_device() -> TweezerDevice {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("test").unwrap();
+    for tweezer in 0..4 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 1.0, Some("test".to_string()))
+            .unwrap();
+    }
+    for (control, target) in [(0, 1), (1, 0)] {
+        device
+            .set_tweezer_two_qubit_gate_time(
+                "PhaseShiftedControlledZ",
+                control,
+                target,
+                1.0,
+                Some("test".to_string()),
+            )
+            .unwrap();
+    }
+    device
+        .set_allowed_tweezer_shifts(&2, &[&[3]], Some("test".to_string()))
+        .unwrap();
+    device.switch_layout("test", None).unwrap();
+    device
+        .set_qubit_to_tweezer(Some(QubitTweezerMapping::trivial(0..3)))
+        .unwrap();
+    device
+}
+
+/// Test that a circuit respecting the device has no violations
+#[test]
+fn test_check_valid_circuit() {
+    let device = test_device();
+    let mut circuit = Circuit::new();
+    circuit += operations::RotateX::new(2, 1.0.into());
+    circuit += operations::PhaseShiftedControlledZ::new(0, 1, 0.0.into());
+    circuit += PragmaShiftQubitsTweezers::new(vec![(2, 3)])
+        .to_pragma_change_device()
+        .unwrap();
+    circuit += operations::RotateX::new(2, 1.0.into());
+
+    let violations = device.check_circuit(&circuit);
+    assert!(violations.is_empty());
+    assert!(violations_into_result(&violations).is_ok());
+    // The device itself is not changed by the check
+    assert_eq!(device.get_tweezer_from_qubit(&2).unwrap(), 2);
+}
+
+/// Test that all violations of a circuit are reported in order
+#[test]
+fn test_check_circuit_violations() {
+    let device = test_device();
+    let mut circuit = Circuit::new();
+    circuit += operations::PhaseShiftedControlledZ::new(1, 2, 0.0.into());
+    circuit += operations::RotateX::new(5, 1.0.into());
+    circuit += PragmaShiftQubitsTweezers::new(vec![(0, 3)])
+        .to_pragma_change_device()
+        .unwrap();
+    circuit += operations::Hadamard::new(0);
+    circuit += PragmaShiftQubitsTweezers::new(vec![(2, 3)])
+        .to_pragma_change_device()
+        .unwrap();
+    circuit += operations::PhaseShiftedControlledZ::new(2, 0, 0.0.into());
+
+    let violations = device.check_circuit(&circuit);
+    assert_eq!(
+        violations
+            .iter()
+            .map(|violation| (violation.index, violation.reason.rule()))
+            .collect::<Vec<(usize, &str)>>(),
+        vec![
+            (0, "UnavailableGate"),
+            (1, "QubitNotInTweezer"),
+            (2, "FailedDeviceChange"),
+            (3, "UnavailableGate"),
+            (5, "UnavailableGate"),
+        ]
+    );
+    assert_eq!(
+        violations[0],
+        DeviceViolation {
+            index: 0,
+            hqslang: "PhaseShiftedControlledZ".to_string(),
+            qubits: vec![1, 2],
+            tweezers: vec![Some(1), Some(2)],
+            reason: ViolationReason::UnavailableGate,
+        }
+    );
+    assert_eq!(violations[1].tweezers, vec![None]);
+    assert_eq!(violations[2].hqslang, "PragmaShiftQubitsTweezers");
+    // The qubit -> tweezer mapping follows the successful shift
+    assert_eq!(violations[4].qubits, vec![2, 0]);
+    assert_eq!(violations[4].tweezers, vec![Some(3), Some(0)]);
+
+    let err = violations_into_result(&violations).unwrap_err();
+    let msg = format!("{}", err);
+    assert!(msg.contains("Operation 1 (RotateX on the qubits [5], tweezers [None])"));
+    assert!(msg.contains("Operation 5"));
+}
@@ -0,0 +1,144 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo_qryd::{CrosstalkModel, QubitTweezerMapping, TweezerDevice};
+
+/// Returns a device with a chain of four tweezers at x = 0, 1, 2 and 5 and trivial mapping.
+///
+/// Neighbouring tweezers are connected by a PhaseShiftedControlledZ gate.
+fn chain_device(positions: bool) -> TweezerDevice {
+    chain_device_with_directions(positions, true)
+}
+
+/// Returns the chain device, with gate times from each tweezer to the next one only unless
+/// `both_directions` is set.
+fn chain_device_with_directions(positions: bool, both_directions: bool) -> TweezerDevice {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("test").unwrap();
+    for (tweezer0, tweezer1) in [(0, 1), (1, 2), (2, 3)] {
+        let directions = if both_directions {
+            vec![(tweezer0, tweezer1), (tweezer1, tweezer0)]
+        } else {
+            vec![(tweezer0, tweezer1)]
+        };
+        for (control, target) in directions {
+            device
+                .set_tweezer_two_qubit_gate_time(
+                    "PhaseShiftedControlledZ",
+                    control,
+                    target,
+                    1.0,
+                    Some("test".to_string()),
+                )
+                .unwrap();
+        }
+    }
+    device.switch_layout("test", None).unwrap();
+    if positions {
+        for (tweezer, x) in [(0, 0.0), (1, 1.0), (2, 2.0), (3, 5.0)] {
+            device
+                .set_tweezer_position(tweezer, &[x, 0.0], None)
+                .unwrap();
+        }
+    }
+    device
+        .set_qubit_to_tweezer(Some(QubitTweezerMapping::trivial(0..4)))
+        .unwrap();
+    device
+}
+
+/// Test CrosstalkModel builder and validation
+#[test]
+fn test_crosstalk_model() {
+    let model = CrosstalkModel::new(0.1);
+    assert_eq!(model.strength, 0.1);
+    assert_eq!(model.range, None);
+    assert!(model.validate().is_ok());
+
+    let model = model.with_range(2.5);
+    assert_eq!(model.range, Some(2.5));
+    assert!(model.validate().is_ok());
+
+    assert!(CrosstalkModel::new(f64::NAN).validate().is_err());
+    assert!(CrosstalkModel::new(0.1).with_range(0.0).validate().is_err());
+    assert!(CrosstalkModel::new(0.1)
+        .with_range(f64::INFINITY)
+        .validate()
+        .is_err());
+}
+
+/// Test the spectators given by the two-qubit connectivity of the device
+#[test]
+fn test_spectator_phases_from_connectivity() {
+    let device = chain_device(false);
+    let model = CrosstalkModel::new(0.5);
+    assert_eq!(
+        model.spectator_phases(&device, &[0, 1]).unwrap(),
+        vec![(1, 2, 0.5)]
+    );
+    assert_eq!(
+        model.spectator_phases(&device, &[2, 1]).unwrap(),
+        vec![(1, 0, 0.5), (2, 3, 0.5)]
+    );
+
+    // Connections set in one direction only act on the spectators in both directions
+    let device = chain_device_with_directions(false, false);
+    assert_eq!(
+        model.spectator_phases(&device, &[2, 1]).unwrap(),
+        vec![(1, 0, 0.5), (2, 3, 0.5)]
+    );
+    assert_eq!(
+        model.spectator_phases(&device, &[3, 2]).unwrap(),
+        vec![(2, 1, 0.5)]
+    );
+
+    // Without strength there is no crosstalk
+    assert!(CrosstalkModel::new(0.0)
+        .spectator_phases(&device, &[0, 1])
+        .unwrap()
+        .is_empty());
+
+    // Unmapped qubits
+    assert!(model.spectator_phases(&device, &[4, 5]).is_err());
+}
+
+/// Test the spectators given by the distance between the tweezers
+#[test]
+fn test_spectator_phases_within_range() {
+    let device = chain_device(true);
+    let model = CrosstalkModel::new(0.5).with_range(1.5);
+    assert_eq!(
+        model.spectator_phases(&device, &[0, 1]).unwrap(),
+        vec![(1, 2, 0.5)]
+    );
+
+    let model = CrosstalkModel::new(0.5).with_range(3.0);
+    assert_eq!(
+        model.spectator_phases(&device, &[1, 2]).unwrap(),
+        vec![(1, 0, 0.5), (2, 0, 0.5), (2, 3, 0.5)]
+    );
+
+    // Free tweezers hold no spectators
+    let mut device = chain_device(true);
+    device
+        .set_qubit_to_tweezer(Some(QubitTweezerMapping::trivial(0..3)))
+        .unwrap();
+    assert_eq!(
+        model.spectator_phases(&device, &[1, 2]).unwrap(),
+        vec![(1, 0, 0.5), (2, 0, 0.5)]
+    );
+
+    // The range needs the tweezer positions
+    assert!(model
+        .spectator_phases(&chain_device(false), &[0, 1])
+        .is_err());
+}
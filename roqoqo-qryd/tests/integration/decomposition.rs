@@ -0,0 +1,299 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_complex::Complex64;
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::backends::EvaluatingBackend;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_qryd::{decompose_to_native_gates, QubitTweezerMapping, TweezerDevice};
+use test_case::test_case;
+
+/// Returns a device with four tweezers supporting the given gates between all tweezers.
+fn native_device(single: &[&str], two: &[&str], three: &[&str]) -> TweezerDevice {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..4 {
+        for hqslang in single {
+            device
+                .set_tweezer_single_qubit_gate_time(hqslang, tweezer, 0.1, None)
+                .unwrap();
+        }
+        for other in (0..4).filter(|other| *other != tweezer) {
+            for hqslang in two {
+                device
+                    .set_tweezer_two_qubit_gate_time(hqslang, tweezer, other, 0.5, None)
+                    .unwrap();
+            }
+        }
+    }
+    for hqslang in three {
+        for (tweezer0, tweezer1, tweezer2) in [(0, 1, 2), (1, 0, 2), (0, 2, 1), (2, 0, 1)] {
+            device
+                .set_tweezer_three_qubit_gate_time(hqslang, tweezer0, tweezer1, tweezer2, 1.0, None)
+                .unwrap();
+        }
+    }
+    device
+        .set_qubit_to_tweezer(Some(QubitTweezerMapping::trivial(0..4)))
+        .unwrap();
+    device
+}
+
+/// Device with phase-shifted two-qubit gates, as the QRyd emulators.
+fn phase_shifted_device() -> TweezerDevice {
+    native_device(
+        &["RotateZ", "RotateXY"],
+        &["PhaseShiftedControlledZ", "PhaseShiftedControlledPhase"],
+        &[],
+    )
+}
+
+/// Device with RotateX, PhaseShiftState1 and ControlledPauliZ gates.
+fn controlled_z_device() -> TweezerDevice {
+    native_device(
+        &["RotateX", "PhaseShiftState1"],
+        &["ControlledPauliZ"],
+        &["ControlledControlledPauliZ"],
+    )
+}
+
+/// Device with PhaseShiftedControlledPhase as the only two-qubit gate.
+fn controlled_phase_device() -> TweezerDevice {
+    native_device(
+        &["RotateX", "RotateZ"],
+        &["PhaseShiftedControlledPhase"],
+        &[],
+    )
+}
+
+/// Returns the unitary of a circuit on three qubits, column by column.
+fn unitary(circuit: &Circuit) -> Vec<Vec<Complex64>> {
+    let backend = roqoqo_quest::Backend::new(3, None);
+    (0..8)
+        .map(|basis_state| {
+            let mut full = Circuit::new();
+            full += DefinitionComplex::new("state".to_string(), 8, true);
+            for qubit in 0..3 {
+                if basis_state & (1 << qubit) != 0 {
+                    full += PauliX::new(qubit);
+                }
+            }
+            full += circuit.clone();
+            full += PragmaGetStateVector::new("state".to_string(), None);
+            let (_, _, complex_registers) = backend.run_circuit(&full).unwrap();
+            complex_registers["state"][0].clone()
+        })
+        .collect()
+}
+
+/// Asserts that two circuits have the same unitary up to a global phase.
+fn assert_equivalent(circuit: &Circuit, decomposed: &Circuit) {
+    let expected = unitary(circuit);
+    let found = unitary(decomposed);
+    let (column, row) = (0..8)
+        .flat_map(|column| (0..8).map(move |row| (column, row)))
+        .max_by(|a, b| {
+            expected[a.0][a.1]
+                .norm()
+                .total_cmp(&expected[b.0][b.1].norm())
+        })
+        .unwrap();
+    let phase = found[column][row] / expected[column][row];
+    assert!((phase.norm() - 1.0).abs() < 1e-8, "{:?}", decomposed);
+    for column in 0..8 {
+        for row in 0..8 {
+            assert!(
+                (found[column][row] - phase * expected[column][row]).norm() < 1e-8,
+                "Circuit {:?} decomposed into {:?}",
+                circuit,
+                decomposed
+            );
+        }
+    }
+}
+
+/// Asserts that every gate of a circuit is available on the device.
+fn assert_native(device: &TweezerDevice, decomposed: &Circuit) {
+    let names = device.get_available_gates_names(None).unwrap();
+    for operation in decomposed.iter() {
+        match operation {
+            Operation::PhaseShiftedControlledZ(gate) => {
+                assert!(device
+                    .gate_time_controlled_z(
+                        gate.control(),
+                        gate.target(),
+                        *gate.phi().float().unwrap()
+                    )
+                    .is_some());
+            }
+            Operation::PhaseShiftedControlledPhase(gate) => {
+                assert!(device
+                    .gate_time_controlled_phase(
+                        gate.control(),
+                        gate.target(),
+                        *gate.phi().float().unwrap(),
+                        *gate.theta().float().unwrap()
+                    )
+                    .is_some());
+            }
+            _ => assert!(
+                names.contains(&operation.hqslang()),
+                "{} is not native",
+                operation.hqslang()
+            ),
+        }
+    }
+}
+
+/// Returns a circuit equal to the operation for the unitary comparison.
+///
+/// The Toffoli gate is written with ControlledControlledPauliZ, whose unitary does not depend
+/// on the qubit order in the simulator.
+fn reference(operation: &Operation) -> Circuit {
+    let mut circuit = Circuit::new();
+    if let Operation::Toffoli(gate) = operation {
+        circuit += Hadamard::new(*gate.target());
+        circuit +=
+            ControlledControlledPauliZ::new(*gate.control_0(), *gate.control_1(), *gate.target());
+        circuit += Hadamard::new(*gate.target());
+    } else {
+        circuit.add_operation(operation.clone());
+    }
+    circuit
+}
+
+/// Returns the gates to decompose.
+fn gates() -> Vec<Operation> {
+    vec![
+        Hadamard::new(0).into(),
+        PauliY::new(1).into(),
+        SGate::new(2).into(),
+        RotateY::new(0, 0.3.into()).into(),
+        RotateZ::new(1, 0.7.into()).into(),
+        SingleQubitGate::new(
+            2,
+            0.5.into(),
+            0.5.into(),
+            0.5.into(),
+            (-0.5).into(),
+            0.2.into(),
+        )
+        .into(),
+        CNOT::new(0, 1).into(),
+        CNOT::new(2, 0).into(),
+        SWAP::new(1, 2).into(),
+        ISwap::new(0, 2).into(),
+        ControlledPauliY::new(1, 0).into(),
+        ControlledPhaseShift::new(0, 1, 0.4.into()).into(),
+        PhaseShiftedControlledZ::new(1, 2, 0.3.into()).into(),
+        PhaseShiftedControlledPhase::new(2, 1, 1.2.into(), 0.1.into()).into(),
+        XY::new(0, 1, 0.8.into()).into(),
+        Fsim::new(1, 2, 0.3.into(), 0.5.into(), 0.7.into()).into(),
+        Toffoli::new(0, 1, 2).into(),
+        ControlledControlledPhaseShift::new(2, 0, 1, 0.6.into()).into(),
+        MultiQubitMS::new(vec![0, 1, 2], 0.9.into()).into(),
+    ]
+}
+
+/// Test decompose_to_native_gates() on single gates and on a circuit of all gates
+#[test_case(phase_shifted_device(); "phase shifted")]
+#[test_case(controlled_z_device(); "controlled z")]
+#[test_case(controlled_phase_device(); "controlled phase")]
+fn test_decompose_to_native_gates(device: TweezerDevice) {
+    let mut full_circuit = Circuit::new();
+    let mut full_reference = Circuit::new();
+    for gate in gates() {
+        let mut circuit = Circuit::new();
+        circuit.add_operation(gate.clone());
+        let decomposed = decompose_to_native_gates(&circuit, &device).unwrap();
+        assert_native(&device, &decomposed);
+        assert_equivalent(&reference(&gate), &decomposed);
+        full_circuit.add_operation(gate.clone());
+        full_reference += reference(&gate);
+    }
+    let decomposed = decompose_to_native_gates(&full_circuit, &device).unwrap();
+    assert_native(&device, &decomposed);
+    assert_equivalent(&full_reference, &decomposed);
+}
+
+/// Test that native gates and other operations are kept
+#[test]
+fn test_decompose_keeps_native_operations() {
+    let device = phase_shifted_device();
+    let phi = device.phase_shift_controlled_z().unwrap();
+    let theta = 0.5;
+    let phi_theta = device.phase_shift_controlled_phase(theta).unwrap();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateZ::new(0, CalculatorFloat::from("symbolic"));
+    circuit += RotateXY::new(1, 0.2.into(), 0.3.into());
+    circuit += PhaseShiftedControlledZ::new(0, 1, phi.into());
+    circuit += PhaseShiftedControlledPhase::new(1, 0, theta.into(), phi_theta.into());
+    circuit += PragmaGlobalPhase::new(0.1.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert_eq!(
+        decompose_to_native_gates(&circuit, &device).unwrap(),
+        circuit
+    );
+
+    // The phase of PhaseShiftedControlledZ is compensated
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliZ::new(0, 1);
+    let decomposed = decompose_to_native_gates(&circuit, &device).unwrap();
+    assert_eq!(decomposed.len(), 3);
+    assert_eq!(
+        decomposed[0],
+        Operation::from(PhaseShiftedControlledZ::new(0, 1, phi.into()))
+    );
+    assert_eq!(
+        decomposed[1],
+        Operation::from(RotateZ::new(0, (-phi).into()))
+    );
+    assert_equivalent(&circuit, &decomposed);
+}
+
+/// Test decompose_to_native_gates() errors
+#[test]
+fn test_decompose_to_native_gates_errors() {
+    let device = phase_shifted_device();
+
+    // Symbolic parameters of rewritten gates
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, CalculatorFloat::from("theta"));
+    assert!(decompose_to_native_gates(&circuit, &device)
+        .unwrap_err()
+        .to_string()
+        .contains("The RotateX gate has the symbolic parameter"));
+
+    // Missing native gates
+    let device = native_device(&["RotateX"], &[], &[]);
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    assert!(decompose_to_native_gates(&circuit, &device)
+        .unwrap_err()
+        .to_string()
+        .contains("no z-rotation"));
+    let device = native_device(&["RotateX", "RotateZ"], &[], &[]);
+    let mut circuit = Circuit::new();
+    circuit += CNOT::new(0, 1);
+    assert!(decompose_to_native_gates(&circuit, &device)
+        .unwrap_err()
+        .to_string()
+        .contains("no two-qubit gate equivalent to ControlledPauliZ"));
+
+    // No current Layout
+    let mut device = phase_shifted_device();
+    device.current_layout = None;
+    assert!(decompose_to_native_gates(&circuit, &device).is_err());
+}
@@ -0,0 +1,247 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use roqoqo_qryd::{
+    device_from_snapshot, CombinedDevice, DeviceSnapshot, EmulatorDevice, TweezerDevice,
+    SNAPSHOT_FORMAT_VERSION,
+};
+
+/// Returns a path in the temporary directory, unique to the calling test.
+fn snapshot_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "roqoqo_qryd_snapshot_{}_{}.json",
+        name,
+        std::process::id()
+    ))
+}
+
+/// Returns a TweezerDevice as downloaded from the WebAPI, with a default layout not yet switched to.
+fn downloaded_tweezer_device() -> TweezerDevice {
+    let mut device = TweezerDevice::new(Some(1), None, None);
+    device.add_layout("default").unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.23, Some("default".to_string()))
+        .unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 1, 0.23, Some("default".to_string()))
+        .unwrap();
+    device.default_layout = Some("default".to_string());
+    device
+}
+
+/// Test DeviceSnapshot new() metadata
+#[test]
+fn test_new() {
+    let snapshot = DeviceSnapshot::new(
+        downloaded_tweezer_device(),
+        "qryd_tweezer_device".to_string(),
+        "v1_1".to_string(),
+    );
+    assert_eq!(snapshot.metadata.format_version, SNAPSHOT_FORMAT_VERSION);
+    assert_eq!(snapshot.metadata.device_name, "qryd_tweezer_device");
+    assert_eq!(snapshot.metadata.api_version, "v1_1");
+    assert!(snapshot.metadata.downloaded_at > 0);
+    assert_eq!(
+        snapshot.metadata.roqoqo_qryd_version,
+        env!("CARGO_PKG_VERSION")
+    );
+    assert_eq!(snapshot.device, downloaded_tweezer_device());
+}
+
+/// Test DeviceSnapshot save() and load()
+#[test]
+fn test_save_load() {
+    let path = snapshot_path("save_load");
+    let snapshot = DeviceSnapshot::new(
+        downloaded_tweezer_device(),
+        "qryd_tweezer_device".to_string(),
+        "v1_1".to_string(),
+    );
+    snapshot.save(&path).unwrap();
+    let loaded = DeviceSnapshot::load(&path).unwrap();
+    assert_eq!(loaded, snapshot);
+
+    let serialized: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(
+        serialized["metadata"]["format_version"],
+        serde_json::json!(SNAPSHOT_FORMAT_VERSION)
+    );
+    assert_eq!(
+        serialized["metadata"]["device_name"],
+        serde_json::json!("qryd_tweezer_device")
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(DeviceSnapshot::load(&path).is_err());
+}
+
+/// Test DeviceSnapshot load() errors on invalid files
+#[test]
+fn test_load_invalid() {
+    let path = snapshot_path("load_invalid");
+    std::fs::write(&path, "{\"metadata\": {}}").unwrap();
+    assert!(DeviceSnapshot::load(&path).is_err());
+
+    let mut snapshot = DeviceSnapshot::new(
+        downloaded_tweezer_device(),
+        "qryd_tweezer_device".to_string(),
+        "v1_1".to_string(),
+    );
+    snapshot.metadata.format_version = SNAPSHOT_FORMAT_VERSION + 1;
+    snapshot.save(&path).unwrap();
+    let loaded = DeviceSnapshot::load(&path);
+    assert!(loaded.is_err());
+    assert!(format!("{:?}", loaded.unwrap_err()).contains("format version"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Test DeviceSnapshot to_tweezer_device() post-processing
+#[test]
+fn test_to_tweezer_device() {
+    let snapshot = DeviceSnapshot::new(
+        downloaded_tweezer_device(),
+        "qryd_tweezer_device".to_string(),
+        "v1_1".to_string(),
+    );
+    let device = snapshot.to_tweezer_device(None).unwrap();
+    assert_eq!(device.current_layout, Some("default".to_string()));
+    assert_eq!(device.device_name, "qryd_tweezer_device");
+    assert_eq!(device.seed(), Some(1));
+    assert_eq!(device.get_tweezer_from_qubit(&1).unwrap(), 1);
+
+    let device = snapshot.to_tweezer_device(Some(42)).unwrap();
+    assert_eq!(device.seed(), Some(42));
+
+    let mut broken = snapshot.clone();
+    broken.device.default_layout = Some("missing".to_string());
+    assert!(broken.to_tweezer_device(None).is_err());
+}
+
+/// Test DeviceSnapshot to_emulator_device() post-processing
+#[test]
+fn test_to_emulator_device() {
+    let mut emulator = EmulatorDevice::new(Some(1), None, None);
+    emulator.add_available_gate("RotateX").unwrap();
+    let snapshot = DeviceSnapshot::new(
+        emulator.internal.clone(),
+        "qryd_emulator".to_string(),
+        "v1_1".to_string(),
+    );
+    let device = snapshot.to_emulator_device(Some(3)).unwrap();
+    assert_eq!(device.seed(), Some(3));
+    assert_eq!(device.internal.device_name, "qryd_emulator");
+    assert_eq!(device.get_available_gates_names().unwrap(), vec!["RotateX"]);
+
+    let snapshot = DeviceSnapshot::new(
+        downloaded_tweezer_device(),
+        "qryd_tweezer_device".to_string(),
+        "v1_1".to_string(),
+    );
+    assert!(snapshot.to_emulator_device(None).is_err());
+}
+
+/// Test DeviceSnapshot to_combined_device() and device_from_snapshot()
+#[test]
+fn test_device_from_snapshot() {
+    let path = snapshot_path("device_from_snapshot");
+    DeviceSnapshot::new(
+        downloaded_tweezer_device(),
+        "qryd_tweezer_device".to_string(),
+        "v1_1".to_string(),
+    )
+    .save(&path)
+    .unwrap();
+    match device_from_snapshot(&path, Some(5)).unwrap() {
+        CombinedDevice::Tweezer(device) => {
+            assert_eq!(device.current_layout, Some("default".to_string()));
+            assert_eq!(device.seed(), Some(5));
+        }
+        CombinedDevice::Emulator(_) => panic!("Expected a TweezerDevice"),
+    }
+    let device = TweezerDevice::from_snapshot(&path, None).unwrap();
+    assert_eq!(device.device_name, "qryd_tweezer_device");
+    assert!(EmulatorDevice::from_snapshot(&path, None).is_err());
+
+    let mut emulator = EmulatorDevice::new(None, None, None);
+    emulator.add_available_gate("RotateX").unwrap();
+    DeviceSnapshot::new(
+        emulator.internal,
+        "qryd_emulator".to_string(),
+        "v1_1".to_string(),
+    )
+    .save(&path)
+    .unwrap();
+    match device_from_snapshot(&path, None).unwrap() {
+        CombinedDevice::Emulator(device) => {
+            assert_eq!(device.internal.device_name, "qryd_emulator");
+        }
+        CombinedDevice::Tweezer(_) => panic!("Expected an EmulatorDevice"),
+    }
+    let device = EmulatorDevice::from_snapshot(&path, None).unwrap();
+    assert_eq!(device.internal.device_name, "qryd_emulator");
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Test DeviceSnapshot from_api() (mocked)
+#[cfg(feature = "web-api")]
+#[tokio::test]
+async fn test_from_api() {
+    use wiremock::matchers::{body_string, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let wiremock_server = MockServer::start().await;
+    let port = wiremock_server.address().port().to_string();
+    // The same mock serves DeviceSnapshot::from_api and TweezerDevice::from_api
+    let _mock_get = Mock::given(method("GET"))
+        .and(body_string("qryd_tweezer_device"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(downloaded_tweezer_device()))
+        .expect(2)
+        .mount(&wiremock_server)
+        .await;
+
+    let port_cloned = port.clone();
+    let snapshot = tokio::task::spawn_blocking(move || {
+        DeviceSnapshot::from_api(
+            Some("qryd_tweezer_device".to_string()),
+            None,
+            Some(port_cloned),
+            None,
+            None,
+        )
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(snapshot.metadata.device_name, "qryd_tweezer_device");
+    assert_eq!(snapshot.metadata.api_version, "v1_1");
+    assert_eq!(snapshot.device, downloaded_tweezer_device());
+    assert_eq!(snapshot.device.current_layout, None);
+
+    let device = tokio::task::spawn_blocking(move || {
+        TweezerDevice::from_api(
+            Some("qryd_tweezer_device".to_string()),
+            None,
+            Some(port),
+            None,
+            None,
+            None,
+        )
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(device, snapshot.to_tweezer_device(None).unwrap());
+    wiremock_server.verify().await;
+}
@@ -30,8 +30,8 @@ fn test_new() {
     let device = EmulatorDevice::new(Some(2), None, None);
 
     assert!(device.internal.current_layout.is_none());
-    assert!(device.internal.qubit_to_tweezer.is_none());
-    assert!(device.internal.layout_register.is_none());
+    assert!(device.internal.qubit_to_tweezer().is_none());
+    assert!(device.internal.layout_register().is_none());
     assert_eq!(device.internal.seed(), Some(2));
     assert_eq!(device.internal.qrydbackend(), "qryd_tweezer_device");
 
@@ -45,7 +45,7 @@ fn test_new() {
 fn test_qubit_tweezer_mapping() {
     let mut device = EmulatorDevice::new(None, None, None);
 
-    assert!(device.internal.qubit_to_tweezer.is_none());
+    assert!(device.internal.qubit_to_tweezer().is_none());
 
     let res = device.add_qubit_tweezer_mapping(0, 0);
     assert!(res.is_ok());
@@ -93,6 +93,24 @@ fn test_available_gate_names() {
     assert!(gates.contains(&"MultiQubitZZ"));
 }
 
+/// Test EmulatorDevice validate() method
+#[test]
+fn test_validate() {
+    let mut device = EmulatorDevice::new(None, None, None);
+    device.add_available_gate("RotateX").unwrap();
+    assert!(device.validate().is_ok());
+
+    device
+        .internal
+        .available_gates
+        .as_mut()
+        .unwrap()
+        .push("error".to_string());
+    let result = device.validate();
+    assert!(result.is_err());
+    assert!(format!("{:?}", result.unwrap_err()).contains("unknown gates: error."));
+}
+
 /// Test EmulatorDevice allow_reset field
 #[test]
 fn test_allow_reset() {
@@ -281,34 +299,47 @@ fn test_change_device() {
     let pr_deac = PragmaDeactivateQRydQubit::new(3);
     assert!(device
         .internal
-        .qubit_to_tweezer
-        .as_ref()
+        .qubit_to_tweezer()
         .unwrap()
-        .contains_key(&3));
+        .tweezer_of(3)
+        .is_some());
     assert!(device
         .change_device("PragmaDeactivateQRydQubit", &serialize(&pr_deac).unwrap())
         .is_ok());
-    assert!(!device
+    assert!(device
         .internal
-        .qubit_to_tweezer
-        .as_ref()
+        .qubit_to_tweezer()
         .unwrap()
-        .contains_key(&3));
+        .tweezer_of(3)
+        .is_none());
 
     assert!(device
         .change_device("PragmaShiftQubitsTweezers", &serialize(&pr_shift).unwrap())
         .is_ok());
     assert_eq!(
-        device.internal.qubit_to_tweezer.as_ref().unwrap().get(&0),
-        Some(&0)
+        device.internal.qubit_to_tweezer().unwrap().tweezer_of(0),
+        Some(0)
     );
     assert_eq!(
-        device.internal.qubit_to_tweezer.as_ref().unwrap().get(&1),
-        Some(&1)
+        device.internal.qubit_to_tweezer().unwrap().tweezer_of(1),
+        Some(1)
     );
     assert_eq!(
-        device.internal.qubit_to_tweezer.as_ref().unwrap().get(&2),
-        Some(&3)
+        device.internal.qubit_to_tweezer().unwrap().tweezer_of(2),
+        Some(3)
+    );
+
+    // The second shift moves qubit 1 into the occupied tweezer 3: no shift is applied
+    let pr_conflict = PragmaShiftQubitsTweezers::new(vec![(0, 4), (1, 3)]);
+    assert!(device
+        .change_device(
+            "PragmaShiftQubitsTweezers",
+            &serialize(&pr_conflict).unwrap()
+        )
+        .is_err());
+    assert_eq!(
+        device.internal.qubit_to_tweezer().unwrap().as_map(),
+        &HashMap::from([(0, 0), (1, 1), (2, 3)])
     );
 }
 
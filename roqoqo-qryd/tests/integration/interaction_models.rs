@@ -0,0 +1,215 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo::devices::Device;
+use roqoqo_qryd::{
+    BlockadeInteractionModel, CutoffInteractionModel, InteractionModel, TweezerDevice,
+    TweezerLattice,
+};
+
+/// Returns a blockade model with a blockade radius of 2 µm and a leakage of 1/64^2 at 1 µm.
+fn blockade_model() -> BlockadeInteractionModel {
+    BlockadeInteractionModel::new(
+        64.0,
+        1.0,
+        HashMap::from([
+            ("PhaseShiftedControlledPhase".to_string(), 0.5),
+            ("ControlledControlledPauliZ".to_string(), 0.8),
+        ]),
+        0.99,
+    )
+    .unwrap()
+}
+
+/// Test CutoffInteractionModel
+#[test]
+fn test_cutoff_model() {
+    let model = CutoffInteractionModel::new(2.0, 2e-6);
+    assert_eq!(
+        model.two_qubit_gate_time("PhaseShiftedControlledZ", 1.5),
+        Some(2e-6 * 2.25)
+    );
+    assert_eq!(
+        model.two_qubit_gate_fidelity("PhaseShiftedControlledPhase", 2.0),
+        Some(1.0)
+    );
+    assert_eq!(
+        model.two_qubit_gate_time("PhaseShiftedControlledZ", 2.5),
+        None
+    );
+    assert_eq!(model.two_qubit_gate_time("ControlledPauliZ", 1.0), None);
+    assert_eq!(
+        model.three_qubit_gate_time("ControlledControlledPauliZ", [1.0, 1.0, 1.0]),
+        None
+    );
+}
+
+/// Test BlockadeInteractionModel
+#[test]
+fn test_blockade_model() {
+    let mut model = blockade_model();
+    assert!((model.blockade_radius() - 2.0).abs() < 1e-12);
+    assert_eq!(model.interaction_strength(2.0), 1.0);
+
+    let leakage = (1.0_f64 / 64.0).powi(2);
+    assert_eq!(
+        model.two_qubit_gate_time("PhaseShiftedControlledPhase", 1.0),
+        Some(0.5 * (1.0 + leakage))
+    );
+    assert_eq!(
+        model.two_qubit_gate_fidelity("PhaseShiftedControlledPhase", 1.0),
+        Some(0.99 * (1.0 - leakage / 2.0))
+    );
+    assert_eq!(
+        model.two_qubit_gate_time("PhaseShiftedControlledPhase", 1.9),
+        Some(0.5 * (1.0 + (1.9_f64.powi(6) / 64.0).powi(2)))
+    );
+    assert_eq!(
+        model.two_qubit_gate_time("PhaseShiftedControlledPhase", 2.1),
+        None
+    );
+    assert_eq!(
+        model.two_qubit_gate_time("PhaseShiftedControlledZ", 1.0),
+        None
+    );
+    assert_eq!(
+        model.two_qubit_gate_fidelity("PhaseShiftedControlledZ", 1.0),
+        None
+    );
+    assert_eq!(
+        model.three_qubit_gate_time("ControlledControlledPauliZ", [1.0, 1.0, 1.0]),
+        Some(0.8 * (1.0 + leakage))
+    );
+    assert_eq!(
+        model.three_qubit_gate_time("ControlledControlledPauliZ", [1.0, 1.0, 2.5]),
+        None
+    );
+    // Gate times only apply to gates with the matching number of qubits
+    assert_eq!(
+        model.two_qubit_gate_time("ControlledControlledPauliZ", 1.0),
+        None
+    );
+    assert_eq!(
+        model.two_qubit_gate_fidelity("ControlledControlledPauliZ", 1.0),
+        None
+    );
+    assert_eq!(
+        model.three_qubit_gate_time("PhaseShiftedControlledPhase", [1.0, 1.0, 1.0]),
+        None
+    );
+
+    model.set_blockade_radius(3.0).unwrap();
+    assert_eq!(model.blockade_radius(), 3.0);
+    for invalid in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(model.set_blockade_radius(invalid).is_err());
+    }
+    assert_eq!(model.blockade_radius(), 3.0);
+    assert!(model
+        .two_qubit_gate_time("PhaseShiftedControlledPhase", 2.5)
+        .is_some());
+
+    assert!(BlockadeInteractionModel::new(0.0, 1.0, HashMap::new(), 0.99).is_err());
+    assert!(BlockadeInteractionModel::new(1.0, -1.0, HashMap::new(), 0.99).is_err());
+    assert!(BlockadeInteractionModel::new(1.0, 1.0, HashMap::new(), 1.5).is_err());
+}
+
+/// Test TweezerDevice apply_interaction_model() and on-the-fly evaluation
+#[test]
+fn test_apply_interaction_model() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device
+        .add_lattice_layout(
+            "triangular",
+            &TweezerLattice::Triangular {
+                rows: 2,
+                columns: 3,
+                spacing: 1.5,
+            },
+            &HashMap::from([
+                ("RotateX".to_string(), 0.2),
+                ("PhaseShiftedControlledZ".to_string(), 0.3),
+            ]),
+            Some(10.0),
+            false,
+        )
+        .unwrap();
+    assert!(device
+        .apply_interaction_model(&blockade_model(), None)
+        .is_err());
+    device
+        .apply_interaction_model(&blockade_model(), Some("triangular".to_string()))
+        .unwrap();
+    assert!(device
+        .apply_interaction_model(&blockade_model(), Some("error".to_string()))
+        .is_err());
+
+    let info = &device.layout_register().unwrap()["triangular"];
+    // The generated PhaseShiftedControlledZ times are replaced
+    assert!(!info
+        .tweezer_two_qubit_gate_times
+        .contains_key("PhaseShiftedControlledZ"));
+    assert_eq!(
+        info.tweezer_two_qubit_gate_times["PhaseShiftedControlledPhase"].len(),
+        18
+    );
+    assert_eq!(
+        info.tweezer_three_qubit_gate_times["ControlledControlledPauliZ"].len(),
+        4
+    );
+    assert_eq!(info.tweezer_single_qubit_gate_times["RotateX"].len(), 6);
+    assert!(device.validate().is_valid());
+
+    device.switch_layout("triangular", None).unwrap();
+    let model = blockade_model();
+    assert_eq!(
+        device.two_qubit_gate_time("PhaseShiftedControlledPhase", &0, &3),
+        device.model_two_qubit_gate_time(&model, "PhaseShiftedControlledPhase", &0, &3)
+    );
+    assert_eq!(
+        device.two_qubit_gate_time("PhaseShiftedControlledPhase", &3, &0),
+        device.two_qubit_gate_time("PhaseShiftedControlledPhase", &0, &3)
+    );
+    assert!(device
+        .model_two_qubit_gate_time(&model, "PhaseShiftedControlledPhase", &0, &3)
+        .is_some());
+    assert_eq!(
+        device.two_qubit_gate_time("PhaseShiftedControlledPhase", &0, &2),
+        None
+    );
+    assert_eq!(
+        device.model_two_qubit_gate_time(&model, "PhaseShiftedControlledPhase", &0, &2),
+        None
+    );
+    assert_eq!(
+        device.model_two_qubit_gate_fidelity(&model, "PhaseShiftedControlledPhase", &0, &1),
+        model.two_qubit_gate_fidelity("PhaseShiftedControlledPhase", 1.5)
+    );
+    assert_eq!(
+        device.three_qubit_gate_time("ControlledControlledPauliZ", &0, &1, &3),
+        device.model_three_qubit_gate_time(&model, "ControlledControlledPauliZ", &0, &1, &3)
+    );
+    assert!(device
+        .model_three_qubit_gate_time(&model, "ControlledControlledPauliZ", &0, &1, &3)
+        .is_some());
+    assert_eq!(
+        device.model_two_qubit_gate_time(&model, "PhaseShiftedControlledPhase", &0, &17),
+        None
+    );
+
+    // Trait objects are supported
+    let dyn_model: &dyn InteractionModel = &model;
+    assert!(device
+        .model_two_qubit_gate_time(dyn_model, "PhaseShiftedControlledPhase", &0, &1)
+        .is_some());
+}
@@ -0,0 +1,226 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo_qryd::{EmulatorDevice, LayoutIssue, TweezerDevice, ValidationReport};
+
+/// Returns a consistent device with a "default" layout of three fully connected tweezers.
+fn valid_device() -> TweezerDevice {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..3 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.2, None)
+            .unwrap();
+    }
+    for (tweezer0, tweezer1) in [(0, 1), (1, 0), (0, 2), (2, 0), (1, 2), (2, 1)] {
+        device
+            .set_tweezer_two_qubit_gate_time(
+                "PhaseShiftedControlledPhase",
+                tweezer0,
+                tweezer1,
+                0.5,
+                None,
+            )
+            .unwrap();
+    }
+    device
+        .set_tweezer_three_qubit_gate_time("ControlledControlledPauliZ", 0, 1, 2, 0.8, None)
+        .unwrap();
+    device.set_tweezers_per_row(vec![3], None).unwrap();
+    device
+        .set_allowed_tweezer_shifts_from_rows(&[&[0, 1, 2]], None)
+        .unwrap();
+    device.set_tweezer_position(2, &[1.0, 1.0], None).unwrap();
+    device
+}
+
+fn layout_info(device: &mut TweezerDevice) -> &mut roqoqo_qryd::TweezerLayoutInfo {
+    device
+        .layout_register_mut()
+        .unwrap()
+        .get_mut("default")
+        .unwrap()
+}
+
+/// Test validate() on consistent devices
+#[test]
+fn test_valid() {
+    let mut device = valid_device();
+    device.add_layout("empty").unwrap();
+    let report = device.validate();
+    assert!(report.is_valid());
+    assert_eq!(report.layouts.len(), 2);
+    assert!(report.layout_issues("default").is_empty());
+    assert!(report.layout_issues("missing").is_empty());
+    assert!(report.into_result().is_ok());
+
+    let report = EmulatorDevice::new(None, None, None).internal.validate();
+    assert_eq!(report, ValidationReport::default());
+    assert!(report.is_valid());
+}
+
+/// Test the AsymmetricTwoQubitGate rule
+#[test]
+fn test_asymmetric_two_qubit_gate() {
+    let mut device = valid_device();
+    let times = layout_info(&mut device)
+        .tweezer_two_qubit_gate_times
+        .get_mut("PhaseShiftedControlledPhase")
+        .unwrap();
+    times.remove(&(1, 0));
+    times.insert((2, 1), 0.6);
+    let report = device.validate();
+    assert_eq!(
+        report.layout_issues("default"),
+        &[
+            LayoutIssue::AsymmetricTwoQubitGate {
+                hqslang: "PhaseShiftedControlledPhase".to_string(),
+                tweezers: (0, 1),
+            },
+            LayoutIssue::AsymmetricTwoQubitGate {
+                hqslang: "PhaseShiftedControlledPhase".to_string(),
+                tweezers: (1, 2),
+            },
+        ]
+    );
+    assert_eq!(
+        report.layout_issues("default")[0].rule(),
+        "AsymmetricTwoQubitGate"
+    );
+    assert_eq!(report.layout_issues("default")[0].tweezers(), vec![0, 1]);
+}
+
+/// Test the ShiftWithUnknownTweezer rule
+#[test]
+fn test_shift_with_unknown_tweezer() {
+    let mut device = valid_device();
+    let shifts = &mut layout_info(&mut device).allowed_tweezer_shifts;
+    shifts.get_mut(&0).unwrap().push(vec![5, 6]);
+    shifts.insert(7, vec![vec![0]]);
+    let issues = device.validate().layout_issues("default").to_vec();
+    assert_eq!(
+        issues,
+        vec![
+            LayoutIssue::ShiftWithUnknownTweezer {
+                tweezer: 0,
+                unknown_tweezer: 5
+            },
+            LayoutIssue::ShiftWithUnknownTweezer {
+                tweezer: 0,
+                unknown_tweezer: 6
+            },
+            LayoutIssue::ShiftWithUnknownTweezer {
+                tweezer: 7,
+                unknown_tweezer: 7
+            },
+        ]
+    );
+}
+
+/// Test the TweezersPerRowMismatch rule
+#[test]
+fn test_tweezers_per_row_mismatch() {
+    let mut device = valid_device();
+    device.set_tweezers_per_row(vec![2, 2], None).unwrap();
+    let issues = device.validate().layout_issues("default").to_vec();
+    assert_eq!(
+        issues,
+        vec![LayoutIssue::TweezersPerRowMismatch {
+            tweezers_per_row: 4,
+            number_tweezers: 3
+        }]
+    );
+    assert!(issues[0].tweezers().is_empty());
+}
+
+/// Test the UnconnectedThreeQubitGate rule
+#[test]
+fn test_unconnected_three_qubit_gate() {
+    let mut device = valid_device();
+    let info = layout_info(&mut device);
+    let times = info
+        .tweezer_two_qubit_gate_times
+        .get_mut("PhaseShiftedControlledPhase")
+        .unwrap();
+    times.remove(&(1, 2));
+    times.remove(&(2, 1));
+    info.tweezer_three_qubit_gate_times
+        .get_mut("ControlledControlledPauliZ")
+        .unwrap()
+        .insert((0, 2, 1), 0.8);
+    let issues = device.validate().layout_issues("default").to_vec();
+    assert_eq!(
+        issues,
+        vec![
+            LayoutIssue::UnconnectedThreeQubitGate {
+                hqslang: "ControlledControlledPauliZ".to_string(),
+                tweezers: (0, 1, 2),
+                pair: (1, 2),
+            },
+            LayoutIssue::UnconnectedThreeQubitGate {
+                hqslang: "ControlledControlledPauliZ".to_string(),
+                tweezers: (0, 2, 1),
+                pair: (2, 1),
+            },
+        ]
+    );
+    assert_eq!(issues[1].tweezers(), vec![0, 2, 1]);
+}
+
+/// Test the PositionOfUnknownTweezer rule
+#[test]
+fn test_position_of_unknown_tweezer() {
+    let mut device = valid_device();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 4, 0.2, None)
+        .unwrap();
+    device.set_tweezer_position(4, &[0.0, 0.0], None).unwrap();
+    layout_info(&mut device)
+        .tweezer_single_qubit_gate_times
+        .get_mut("RotateX")
+        .unwrap()
+        .remove(&4);
+    let issues = device.validate().layout_issues("default").to_vec();
+    assert_eq!(
+        issues,
+        vec![LayoutIssue::PositionOfUnknownTweezer { tweezer: 4 }]
+    );
+}
+
+/// Test ValidationReport into_result() and issue messages
+#[test]
+fn test_into_result() {
+    let mut device = valid_device();
+    device.add_layout("other").unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.2, Some("other".to_string()))
+        .unwrap();
+    device
+        .set_tweezers_per_row(vec![2], Some("other".to_string()))
+        .unwrap();
+    let report = device.validate();
+    assert!(!report.is_valid());
+    assert!(report.layout_issues("default").is_empty());
+    assert_eq!(report.layout_issues("other").len(), 1);
+    assert_eq!(
+        report.layout_issues("other")[0].to_string(),
+        "The tweezers per row add up to 2, but the Layout has 1 tweezers."
+    );
+
+    let serialized = serde_json::to_string(&report).unwrap();
+    let deserialized: ValidationReport = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, report);
+
+    let error = format!("{:?}", report.into_result().unwrap_err());
+    assert!(error.contains("Layout other: The tweezers per row add up to 2"));
+}
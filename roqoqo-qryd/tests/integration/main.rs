@@ -16,9 +16,37 @@ mod qryd_devices;
 #[cfg(test)]
 mod tweezer_devices;
 
+#[cfg(test)]
+mod tweezer_layouts;
+
+#[cfg(test)]
+mod interaction_models;
+
+#[cfg(test)]
+mod layout_validation;
+
+#[cfg(test)]
+mod qubit_tweezer_mapping;
+
+#[cfg(test)]
+mod shift_planning;
+
+#[cfg(test)]
+mod routing;
+
+#[cfg(test)]
+#[cfg(feature = "simulator")]
+mod decomposition;
+
+#[cfg(test)]
+mod scheduling;
+
 #[cfg(test)]
 mod emulator_devices;
 
+#[cfg(test)]
+mod device_snapshots;
+
 #[cfg(test)]
 mod pragma_operations;
 
@@ -26,12 +54,182 @@ mod pragma_operations;
 #[cfg(feature = "simulator")]
 mod simulator_backend;
 
+#[cfg(test)]
+#[cfg(feature = "simulator")]
+mod simulator_states;
+
+#[cfg(test)]
+#[cfg(feature = "simulator")]
+mod stabilizer;
+
+#[cfg(test)]
+mod atom_loss;
+
+#[cfg(test)]
+mod readout_mitigation;
+
+#[cfg(test)]
+mod crosstalk;
+
+#[cfg(test)]
+mod circuit_checks;
+
 #[cfg(test)]
 #[cfg(feature = "web-api")]
 mod api_backend;
 
 mod api_devices;
 
+/// bincode representation of a TweezerDevice serialized by the released version without tweezer
+/// positions, decoherence rates and error rates.
+///
+/// The device has the Layout "default" with a RotateX gate time of 0.23 on tweezer 0, set as
+/// current Layout, and qubit 0 in tweezer 0.
+pub(crate) const BASELINE_DEVICE_BYTES: &[u8] = &[
+    1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0,
+    0, 0, 7, 0, 0, 0, 0, 0, 0, 0, 100, 101, 102, 97, 117, 108, 116, 1, 0, 0, 0, 0, 0, 0, 0, 7, 0,
+    0, 0, 0, 0, 0, 0, 82, 111, 116, 97, 116, 101, 88, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 113, 61, 10, 215, 163, 112, 205, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 7, 0, 0, 0, 0, 0, 0, 0, 100, 101, 102, 97, 117,
+    108, 116, 15, 0, 0, 0, 0, 0, 0, 0, 68, 101, 102, 97, 117, 108, 116, 82, 101, 108, 97, 116, 105,
+    111, 110, 15, 0, 0, 0, 0, 0, 0, 0, 68, 101, 102, 97, 117, 108, 116, 82, 101, 108, 97, 116, 105,
+    111, 110, 0, 0, 0, 19, 0, 0, 0, 0, 0, 0, 0, 113, 114, 121, 100, 95, 116, 119, 101, 101, 122,
+    101, 114, 95, 100, 101, 118, 105, 99, 101, 0,
+];
+
+/// Description of the single-Layout TweezerDevice used by the integration tests.
+///
+/// Build a device with [TestDevice::build], overriding the fields of [TestDevice::DEFAULT] with
+/// struct update syntax.
+#[derive(Debug, Clone)]
+pub(crate) struct TestDevice<'a> {
+    /// The seed of the device.
+    pub seed: Option<usize>,
+    /// The name of the Layout.
+    pub layout: &'a str,
+    /// The number of tweezers, each supporting the single-qubit gates.
+    pub tweezers: usize,
+    /// The single-qubit gates available on every tweezer.
+    pub single_qubit_gates: &'a [&'a str],
+    /// The gate time of the single-qubit gates.
+    pub single_qubit_gate_time: f64,
+    /// The two-qubit gates available on every pair of tweezers in `two_qubit_edges`.
+    pub two_qubit_gates: &'a [&'a str],
+    /// The (control, target) pairs of tweezers supporting the two-qubit gates.
+    pub two_qubit_edges: &'a [(usize, usize)],
+    /// The gate time of the two-qubit gates.
+    pub two_qubit_gate_time: f64,
+    /// The allowed shifts, as starting tweezer and shift paths.
+    pub shifts: &'a [(usize, &'a [&'a [usize]])],
+    /// The rows of tweezers along which qubits can be shifted.
+    pub shift_rows: &'a [&'a [usize]],
+    /// The number of tweezers per row, not set if empty.
+    pub tweezers_per_row: &'a [usize],
+    /// The decoherence rates of tweezers.
+    pub decoherence_rates: &'a [(usize, roqoqo_qryd::TweezerDecoherenceRates)],
+    /// The qubit -> tweezer mapping, None for the trivial mapping of all tweezers.
+    pub mapping: Option<&'a [(usize, usize)]>,
+}
+
+impl<'a> TestDevice<'a> {
+    /// Two tweezers supporting RotateX gates, each occupied by a qubit.
+    pub const DEFAULT: TestDevice<'static> = TestDevice {
+        seed: None,
+        layout: "test",
+        tweezers: 2,
+        single_qubit_gates: &["RotateX"],
+        single_qubit_gate_time: 1.0,
+        two_qubit_gates: &["PhaseShiftedControlledZ"],
+        two_qubit_edges: &[],
+        two_qubit_gate_time: 1.0,
+        shifts: &[],
+        shift_rows: &[],
+        tweezers_per_row: &[],
+        decoherence_rates: &[],
+        mapping: None,
+    };
+
+    /// Returns a new device with the described Layout as current Layout.
+    pub fn build(&self) -> roqoqo_qryd::TweezerDevice {
+        let mut device = roqoqo_qryd::TweezerDevice::new(self.seed, None, None);
+        self.add_layout_to(&mut device);
+        device.switch_layout(self.layout, None).unwrap();
+        if let Some(mapping) = self.mapping {
+            device
+                .set_qubit_to_tweezer(Some(
+                    roqoqo_qryd::QubitTweezerMapping::try_from(
+                        mapping
+                            .iter()
+                            .copied()
+                            .collect::<std::collections::HashMap<usize, usize>>(),
+                    )
+                    .unwrap(),
+                ))
+                .unwrap();
+        }
+        device
+    }
+
+    /// Adds the described Layout to a device, without switching to it.
+    pub fn add_layout_to(&self, device: &mut roqoqo_qryd::TweezerDevice) {
+        let layout = Some(self.layout.to_string());
+        device.add_layout(self.layout).unwrap();
+        for tweezer in 0..self.tweezers {
+            for hqslang in self.single_qubit_gates {
+                device
+                    .set_tweezer_single_qubit_gate_time(
+                        hqslang,
+                        tweezer,
+                        self.single_qubit_gate_time,
+                        layout.clone(),
+                    )
+                    .unwrap();
+            }
+        }
+        for &(control, target) in self.two_qubit_edges {
+            for hqslang in self.two_qubit_gates {
+                device
+                    .set_tweezer_two_qubit_gate_time(
+                        hqslang,
+                        control,
+                        target,
+                        self.two_qubit_gate_time,
+                        layout.clone(),
+                    )
+                    .unwrap();
+            }
+        }
+        for (tweezer, paths) in self.shifts {
+            device
+                .set_allowed_tweezer_shifts(tweezer, paths, layout.clone())
+                .unwrap();
+        }
+        if !self.shift_rows.is_empty() {
+            device
+                .set_allowed_tweezer_shifts_from_rows(self.shift_rows, layout.clone())
+                .unwrap();
+        }
+        if !self.tweezers_per_row.is_empty() {
+            device
+                .set_tweezers_per_row(self.tweezers_per_row.to_vec(), layout.clone())
+                .unwrap();
+        }
+        for (tweezer, rates) in self.decoherence_rates {
+            device
+                .set_tweezer_decoherence_rates(*tweezer, *rates, layout.clone())
+                .unwrap();
+        }
+    }
+}
+
+/// Returns the pairs of neighbouring tweezers of a line of tweezers, in both directions.
+#[cfg(feature = "simulator")]
+pub(crate) fn line_edges(tweezers: usize) -> Vec<(usize, usize)> {
+    (1..tweezers)
+        .flat_map(|tweezer| [(tweezer - 1, tweezer), (tweezer, tweezer - 1)])
+        .collect()
+}
+
 #[cfg(feature = "web-api")]
 #[test]
 fn test_device_from_api() {
@@ -0,0 +1,231 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo_qryd::{QubitTweezerMapping, TweezerDevice};
+
+/// Test QubitTweezerMapping queries
+#[test]
+fn test_occupancy_queries() {
+    let mut mapping = QubitTweezerMapping::new();
+    assert!(mapping.is_empty());
+    mapping.insert(0, 4).unwrap();
+    mapping.insert(1, 2).unwrap();
+
+    assert_eq!(mapping.len(), 2);
+    assert_eq!(mapping.tweezer_of(0), Some(4));
+    assert_eq!(mapping.tweezer_of(2), None);
+    assert_eq!(mapping.qubit_at(2), Some(1));
+    assert_eq!(mapping.qubit_at(0), None);
+    assert!(mapping.is_occupied(4));
+    assert_eq!(mapping.occupied_tweezers(), vec![2, 4]);
+    assert_eq!(
+        mapping.free_tweezers([5, 4, 3, 2, 1, 0, 0]),
+        vec![0, 1, 3, 5]
+    );
+    assert_eq!(mapping, HashMap::from([(0, 4), (1, 2)]));
+    assert_eq!(mapping.tweezer_of(1), Some(2));
+
+    let trivial = QubitTweezerMapping::trivial(0..3);
+    assert_eq!(trivial, HashMap::from([(0, 0), (1, 1), (2, 2)]));
+    assert_eq!(trivial.qubit_at(2), Some(2));
+}
+
+/// Test the checked operations of QubitTweezerMapping
+#[test]
+fn test_checked_operations() {
+    let mut mapping = QubitTweezerMapping::trivial(0..3);
+
+    let err = mapping.insert(3, 1).unwrap_err();
+    assert!(err.to_string().contains("occupied by qubit 1"));
+    assert!(mapping.insert(1, 5).is_err());
+    mapping.insert(3, 5).unwrap();
+
+    assert!(mapping.move_qubit(0, 1).is_err());
+    assert!(mapping.move_qubit(4, 6).is_err());
+    assert_eq!(mapping.move_qubit(0, 6).unwrap(), 0);
+    assert_eq!(mapping.qubit_at(0), None);
+    assert_eq!(mapping.qubit_at(6), Some(0));
+    // Moving a qubit into its own tweezer is not a conflict
+    assert_eq!(mapping.move_qubit(0, 6).unwrap(), 6);
+
+    assert_eq!(mapping.remove(1).unwrap(), 1);
+    assert!(mapping.remove(1).is_err());
+    assert!(!mapping.is_occupied(1));
+    assert_eq!(mapping, HashMap::from([(0, 6), (2, 2), (3, 5)]));
+
+    // Assigning evicts and reports the previous occupant
+    assert_eq!(mapping.assign(4, 2), Some(2));
+    assert_eq!(mapping.assign(4, 2), None);
+    assert_eq!(mapping.assign(0, 7), None);
+    assert_eq!(mapping.qubit_at(6), None);
+    assert_eq!(mapping, HashMap::from([(0, 7), (3, 5), (4, 2)]));
+    assert_eq!(mapping.occupied_tweezers(), vec![2, 5, 7]);
+}
+
+/// Test that QubitTweezerMapping serializes to the qubit -> tweezer map
+#[test]
+fn test_serialization() {
+    let mapping = QubitTweezerMapping::try_from(HashMap::from([(0, 3), (1, 0)])).unwrap();
+    let serialized = serde_json::to_string(&mapping).unwrap();
+    let as_map: HashMap<usize, usize> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(as_map, HashMap::from([(0, 3), (1, 0)]));
+    let deserialized: QubitTweezerMapping = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, mapping);
+    assert_eq!(deserialized.qubit_at(3), Some(0));
+
+    let bincode_deserialized: QubitTweezerMapping =
+        bincode::deserialize(&bincode::serialize(&mapping).unwrap()).unwrap();
+    assert_eq!(bincode_deserialized, mapping);
+
+    // Two qubits in the same tweezer are rejected
+    assert!(QubitTweezerMapping::try_from(HashMap::from([(0, 1), (1, 1)])).is_err());
+
+    // Devices keep the JSON shape of their qubit -> tweezer mapping
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.1, Some("default".to_string()))
+        .unwrap();
+    device.switch_layout("default", None).unwrap();
+    let value = serde_json::to_value(&device).unwrap();
+    assert_eq!(value["qubit_to_tweezer"], serde_json::json!({"0": 0}));
+}
+
+/// Test that serialized mappings placing several qubits in the same tweezer are rejected
+#[test]
+fn test_deserialization_of_conflicting_mapping() {
+    let error = serde_json::from_str::<QubitTweezerMapping>(r#"{"3": 1, "1": 1, "2": 2}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Tweezer 1 is already occupied"), "{error}");
+
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.1, Some("default".to_string()))
+        .unwrap();
+    device.switch_layout("default", None).unwrap();
+    let mut value = serde_json::to_value(&device).unwrap();
+    value["qubit_to_tweezer"] = serde_json::json!({"0": 0, "1": 0});
+    assert!(serde_json::from_value::<TweezerDevice>(value).is_err());
+
+    let conflicting = HashMap::from([(0usize, 5usize), (1, 5)]);
+    assert!(bincode::deserialize::<QubitTweezerMapping>(
+        &bincode::serialize(&conflicting).unwrap()
+    )
+    .is_err());
+}
+
+/// Test QubitTweezerMapping apply_shifts()
+#[test]
+fn test_apply_shifts() {
+    let mut mapping = QubitTweezerMapping::trivial(0..3);
+    mapping.apply_shifts(&[(2, 3), (1, 2), (5, 6)]).unwrap();
+    assert_eq!(mapping, HashMap::from([(0, 0), (1, 2), (2, 3)]));
+
+    // The second shift conflicts: the first one is not applied either
+    assert!(mapping.apply_shifts(&[(3, 4), (0, 2)]).is_err());
+    assert_eq!(mapping, HashMap::from([(0, 0), (1, 2), (2, 3)]));
+    assert_eq!(mapping.qubit_at(4), None);
+    assert_eq!(mapping.qubit_at(3), Some(2));
+}
+
+/// Test the checked qubit operations of TweezerDevice
+#[test]
+fn test_device_checked_operations() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..3 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.1, None)
+            .unwrap();
+    }
+
+    device.insert_qubit(0, 0).unwrap();
+    device.insert_qubit(1, 1).unwrap();
+    assert!(device.insert_qubit(2, 1).is_err());
+    assert!(device.insert_qubit(0, 2).is_err());
+    assert!(device.insert_qubit(2, 7).is_err());
+
+    assert!(device.move_qubit(0, 1).is_err());
+    assert!(device.move_qubit(0, 7).is_err());
+    assert!(device.move_qubit(2, 2).is_err());
+    assert_eq!(device.move_qubit(0, 2).unwrap(), 0);
+    assert_eq!(device.qubit_at(2), Some(0));
+
+    assert_eq!(device.remove_qubit(1).unwrap(), 1);
+    assert!(device.remove_qubit(1).is_err());
+    assert_eq!(device.occupied_tweezers(), vec![2]);
+}
+
+/// Test setting the qubit -> tweezer mapping of TweezerDevice
+#[test]
+fn test_device_set_qubit_to_tweezer() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    assert!(device
+        .set_qubit_to_tweezer(Some(QubitTweezerMapping::trivial(0..1)))
+        .is_err());
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..3 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.1, None)
+            .unwrap();
+    }
+
+    let mapping = QubitTweezerMapping::try_from(HashMap::from([(0, 2), (1, 0)])).unwrap();
+    device.set_qubit_to_tweezer(Some(mapping.clone())).unwrap();
+    assert_eq!(device.qubit_to_tweezer(), Some(&mapping));
+    assert_eq!(device.qubit_to_tweezer().unwrap().qubits(), vec![0, 1]);
+
+    assert!(device
+        .set_qubit_to_tweezer(Some(QubitTweezerMapping::trivial(2..4)))
+        .is_err());
+    assert_eq!(device.qubit_to_tweezer(), Some(&mapping));
+
+    device.set_qubit_to_tweezer(None).unwrap();
+    assert!(device.qubit_to_tweezer().is_none());
+}
+
+/// Test the occupancy queries of TweezerDevice
+#[test]
+fn test_device_occupancy() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..4 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.1, None)
+            .unwrap();
+    }
+    assert_eq!(device.occupied_tweezers(), Vec::<usize>::new());
+    assert_eq!(device.free_tweezers(None).unwrap(), vec![0, 1, 2, 3]);
+
+    device.add_qubit_tweezer_mapping(0, 2).unwrap();
+    device.add_qubit_tweezer_mapping(1, 3).unwrap();
+    assert_eq!(device.qubit_at(2), Some(0));
+    assert_eq!(device.qubit_at(0), None);
+    assert_eq!(device.occupied_tweezers(), vec![2, 3]);
+    assert_eq!(device.free_tweezers(None).unwrap(), vec![0, 1]);
+    assert!(device.free_tweezers(Some("missing".to_string())).is_err());
+
+    // The replaced qubit is removed from the mapping
+    let mapping = device.add_qubit_tweezer_mapping(2, 3).unwrap();
+    assert_eq!(mapping, HashMap::from([(0, 2), (2, 3)]));
+    assert_eq!(device.qubit_at(3), Some(2));
+
+    device.deactivate_qubit(0).unwrap();
+    assert_eq!(device.free_tweezers(None).unwrap(), vec![0, 1, 2]);
+}
@@ -0,0 +1,161 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use ndarray::{array, Array2};
+use roqoqo::registers::BitOutputRegister;
+use roqoqo_qryd::{
+    mitigate_bit_register, mitigate_frequencies, ReadoutConfusionMatrix, ReadoutMitigationMethod,
+    MAX_MITIGATED_BITS,
+};
+
+/// Test ReadoutConfusionMatrix construction, validation, matrix and inverse
+#[test]
+fn test_readout_confusion_matrix() {
+    let confusion_matrix = ReadoutConfusionMatrix::new(0.1, 0.2).unwrap();
+    assert_eq!(confusion_matrix.flip_probability(false), 0.1);
+    assert_eq!(confusion_matrix.flip_probability(true), 0.2);
+    assert!((confusion_matrix.error_rate() - 0.15).abs() < 1e-12);
+    assert_eq!(confusion_matrix.matrix(), array![[0.9, 0.2], [0.1, 0.8]]);
+    let product = confusion_matrix
+        .matrix()
+        .dot(&confusion_matrix.inverse().unwrap());
+    assert!((product - Array2::<f64>::eye(2))
+        .iter()
+        .all(|value| value.abs() < 1e-12));
+    assert_eq!(
+        ReadoutConfusionMatrix::symmetric(0.05).unwrap(),
+        ReadoutConfusionMatrix {
+            zero_to_one: 0.05,
+            one_to_zero: 0.05
+        }
+    );
+    assert_eq!(
+        ReadoutConfusionMatrix::default().matrix(),
+        Array2::<f64>::eye(2)
+    );
+
+    // Errors
+    assert!(ReadoutConfusionMatrix::new(-0.1, 0.2).is_err());
+    assert!(ReadoutConfusionMatrix::symmetric(1.5).is_err());
+    assert!(ReadoutConfusionMatrix::new(0.5, 0.5)
+        .unwrap()
+        .inverse()
+        .is_err());
+}
+
+/// Test ReadoutMitigationMethod from string
+#[test]
+fn test_readout_mitigation_method() {
+    assert_eq!(
+        ReadoutMitigationMethod::from_str("inversion"),
+        Ok(ReadoutMitigationMethod::Inversion)
+    );
+    assert_eq!(
+        ReadoutMitigationMethod::from_str("least_squares"),
+        Ok(ReadoutMitigationMethod::LeastSquares)
+    );
+    assert_eq!(
+        ReadoutMitigationMethod::default(),
+        ReadoutMitigationMethod::Inversion
+    );
+    assert!(ReadoutMitigationMethod::from_str("unknown").is_err());
+}
+
+/// Test the inversion of the readout errors of a bit register
+#[test]
+fn test_mitigate_bit_register_inversion() {
+    let confusion_matrices = vec![
+        ReadoutConfusionMatrix::new(0.1, 0.2).unwrap(),
+        ReadoutConfusionMatrix::default(),
+    ];
+    // |10> read out with 80 % probability, flipped to |00> with 20 % probability
+    let mut register: BitOutputRegister = vec![vec![true, false]; 8];
+    register.extend(vec![vec![false, false]; 2]);
+    let mitigated = mitigate_bit_register(
+        &register,
+        &confusion_matrices,
+        ReadoutMitigationMethod::Inversion,
+    )
+    .unwrap();
+    assert_eq!(mitigated.len(), 1);
+    assert!((mitigated["10"] - 1.0).abs() < 1e-12);
+
+    // Readout errors without mitigation keep the frequencies
+    let mitigated = mitigate_bit_register(
+        &register,
+        &[ReadoutConfusionMatrix::default(); 2],
+        ReadoutMitigationMethod::Inversion,
+    )
+    .unwrap();
+    assert_eq!(mitigated.len(), 2);
+    assert!((mitigated["10"] - 0.8).abs() < 1e-12);
+    assert!((mitigated["00"] - 0.2).abs() < 1e-12);
+}
+
+/// Test the least-squares mitigation returning a probability distribution
+#[test]
+fn test_mitigate_frequencies_least_squares() {
+    let confusion_matrices = vec![ReadoutConfusionMatrix::new(0.2, 0.2).unwrap()];
+    // Fewer ones than the readout error alone produces give a negative quasi-probability
+    let frequencies: HashMap<Vec<bool>, u64> =
+        [(vec![false], 95), (vec![true], 5)].into_iter().collect();
+    let inverted = mitigate_frequencies(
+        &frequencies,
+        &confusion_matrices,
+        ReadoutMitigationMethod::Inversion,
+    )
+    .unwrap();
+    assert!(inverted["1"] < 0.0);
+    assert!((inverted.values().sum::<f64>() - 1.0).abs() < 1e-12);
+    let mitigated = mitigate_frequencies(
+        &frequencies,
+        &confusion_matrices,
+        ReadoutMitigationMethod::LeastSquares,
+    )
+    .unwrap();
+    assert_eq!(mitigated.len(), 1);
+    assert!((mitigated["0"] - 1.0).abs() < 1e-12);
+}
+
+/// Test the errors of the mitigation of readout errors
+#[test]
+fn test_mitigate_readout_errors() {
+    let confusion_matrices = vec![ReadoutConfusionMatrix::default(); 2];
+    assert!(mitigate_bit_register(
+        &Vec::new(),
+        &confusion_matrices,
+        ReadoutMitigationMethod::Inversion
+    )
+    .is_err());
+    assert!(mitigate_bit_register(
+        &vec![vec![true]],
+        &confusion_matrices,
+        ReadoutMitigationMethod::Inversion
+    )
+    .is_err());
+    assert!(mitigate_bit_register(
+        &vec![vec![true]],
+        &[ReadoutConfusionMatrix::new(0.5, 0.5).unwrap()],
+        ReadoutMitigationMethod::Inversion
+    )
+    .is_err());
+    let number_bits = MAX_MITIGATED_BITS + 1;
+    assert!(mitigate_bit_register(
+        &vec![vec![false; number_bits]],
+        &vec![ReadoutConfusionMatrix::default(); number_bits],
+        ReadoutMitigationMethod::Inversion
+    )
+    .is_err());
+}
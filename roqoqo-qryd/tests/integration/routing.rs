@@ -0,0 +1,316 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo::devices::Device;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_qryd::{
+    check_routed_circuit, route_circuit, PragmaShiftQubitsTweezers, PragmaSwitchDeviceLayout,
+    QubitTweezerMapping, TweezerDevice,
+};
+
+/// Returns a device with two rows of four tweezers, shifts being allowed along the rows.
+///
+/// Tweezers 0-3 form the first row and tweezers 4-7 the second one. In the "columns" Layout,
+/// ControlledPauliZ gates act between the tweezers facing each other in the two rows. In the "rows"
+/// Layout, they act between neighbouring tweezers of a row.
+fn two_row_device(with_rows_layout: bool, with_shifts: bool) -> TweezerDevice {
+    let mut device = TweezerDevice::new(None, None, None);
+    let mut layouts = vec!["columns"];
+    if with_rows_layout {
+        layouts.push("rows");
+    }
+    for layout in layouts {
+        let name = Some(layout.to_string());
+        device.add_layout(layout).unwrap();
+        for tweezer in 0..8 {
+            device
+                .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.1, name.clone())
+                .unwrap();
+        }
+        let pairs: Vec<(usize, usize)> = if layout == "columns" {
+            (0..4).map(|tweezer| (tweezer, tweezer + 4)).collect()
+        } else {
+            [0, 1, 2, 4, 5, 6]
+                .iter()
+                .map(|tweezer| (*tweezer, tweezer + 1))
+                .collect()
+        };
+        for (tweezer0, tweezer1) in pairs {
+            device
+                .set_tweezer_two_qubit_gate_time(
+                    "ControlledPauliZ",
+                    tweezer0,
+                    tweezer1,
+                    0.5,
+                    name.clone(),
+                )
+                .unwrap();
+            device
+                .set_tweezer_two_qubit_gate_time(
+                    "ControlledPauliZ",
+                    tweezer1,
+                    tweezer0,
+                    0.5,
+                    name.clone(),
+                )
+                .unwrap();
+        }
+        if with_shifts {
+            device
+                .set_allowed_tweezer_shifts_from_rows(&[&[0, 1, 2, 3], &[4, 5, 6, 7]], name.clone())
+                .unwrap();
+        }
+        device.set_tweezers_per_row(vec![4, 4], name).unwrap();
+    }
+    device.current_layout = Some("columns".to_string());
+    device
+}
+
+/// Returns the device with the initial mapping of a routed circuit.
+fn mapped_device(device: &TweezerDevice, mapping: &HashMap<usize, usize>) -> TweezerDevice {
+    let mut device = device.clone();
+    device
+        .set_qubit_to_tweezer(Some(
+            QubitTweezerMapping::try_from(mapping.clone()).unwrap(),
+        ))
+        .unwrap();
+    device
+}
+
+/// Test route_circuit() on a circuit executable without moves
+#[test]
+fn test_route_circuit_without_moves() {
+    let device = two_row_device(false, true);
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, 1.0.into());
+    circuit += ControlledPauliZ::new(0, 1);
+    circuit += RotateX::new(2, 1.0.into());
+
+    let routed = route_circuit(&circuit, &device, None).unwrap();
+    assert_eq!(routed.circuit, circuit);
+    assert_eq!(routed.number_shifts, 0);
+    assert_eq!(routed.number_layout_switches, 0);
+    assert_eq!(routed.initial_mapping.len(), 3);
+    // The interacting qubits face each other
+    assert_eq!(
+        (routed.initial_mapping[&0] as i64 - routed.initial_mapping[&1] as i64).abs(),
+        4
+    );
+    assert!(device.qubit_to_tweezer().is_none());
+}
+
+/// Test route_circuit() placing the qubits on the tweezers with the lowest error rates
+#[test]
+fn test_route_circuit_prefers_low_error_tweezers() {
+    let mut device = two_row_device(false, true);
+    device.set_tweezer_readout_error(0, 0.1, None).unwrap();
+    device
+        .set_tweezer_single_qubit_gate_error("RotateX", 4, 0.05, None)
+        .unwrap();
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliZ::new(0, 1);
+
+    let routed = route_circuit(&circuit, &device, None).unwrap();
+    assert_eq!(routed.circuit, circuit);
+    assert_eq!(routed.initial_mapping, HashMap::from([(0, 1), (1, 5)]));
+
+    // Without error rates, the lowest tweezers are used
+    let routed = route_circuit(&circuit, &two_row_device(false, true), None).unwrap();
+    assert_eq!(routed.initial_mapping, HashMap::from([(0, 0), (1, 4)]));
+}
+
+/// Test route_circuit() inserting shifts
+#[test]
+fn test_route_circuit_with_shifts() {
+    let device = two_row_device(false, true);
+    // Qubit 0 interacts with three qubits, but only faces one tweezer
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliZ::new(0, 1);
+    circuit += ControlledPauliZ::new(0, 2);
+    circuit += ControlledPauliZ::new(3, 0);
+    circuit += ControlledPauliZ::new(0, 1);
+
+    let routed = route_circuit(&circuit, &device, None).unwrap();
+    assert!(routed.number_shifts > 0);
+    assert_eq!(routed.number_layout_switches, 0);
+    let pragmas: Vec<&PragmaChangeDevice> = routed
+        .circuit
+        .iter()
+        .filter_map(|operation| match operation {
+            Operation::PragmaChangeDevice(pragma) => Some(pragma),
+            _ => None,
+        })
+        .collect();
+    assert!(!pragmas.is_empty());
+    assert!(pragmas
+        .iter()
+        .all(|pragma| pragma.wrapped_hqslang == "PragmaShiftQubitsTweezers"));
+    // The gates are kept in order after removing the inserted operations
+    let gates: Vec<&Operation> = routed
+        .circuit
+        .iter()
+        .filter(|operation| !matches!(operation, Operation::PragmaChangeDevice(_)))
+        .collect();
+    assert_eq!(gates, circuit.iter().collect::<Vec<_>>());
+
+    // Walking the circuit through change_device makes every gate executable
+    let mut walked = mapped_device(&device, &routed.initial_mapping);
+    for operation in routed.circuit.iter() {
+        match operation {
+            Operation::PragmaChangeDevice(pragma) => walked
+                .change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation)
+                .unwrap(),
+            Operation::ControlledPauliZ(cz) => assert!(walked
+                .two_qubit_gate_time("ControlledPauliZ", cz.control(), cz.target())
+                .is_some()),
+            _ => (),
+        }
+    }
+    check_routed_circuit(
+        &routed.circuit,
+        &mapped_device(&device, &routed.initial_mapping),
+    )
+    .unwrap();
+}
+
+/// Test route_circuit() switching the Layout when no shifts make a gate executable
+#[test]
+fn test_route_circuit_with_layout_switch() {
+    let device = two_row_device(true, true);
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliZ::new(0, 1);
+    circuit += ControlledPauliZ::new(0, 2);
+    circuit += ControlledPauliZ::new(1, 2);
+
+    let routed = route_circuit(&circuit, &device, None).unwrap();
+    check_routed_circuit(
+        &routed.circuit,
+        &mapped_device(&device, &routed.initial_mapping),
+    )
+    .unwrap();
+    // The qubits 0 and 2 are placed in the same row: their gate needs the "rows" Layout, and the
+    // gate of the qubits 1 and 2 a switch back to "columns" with one shift
+    assert_eq!(routed.number_layout_switches, 2);
+    assert_eq!(routed.number_shifts, 1);
+
+    // Without shifts, the gate can only be reached with a layout switch
+    let device = two_row_device(true, false);
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliZ::new(0, 1);
+    circuit += ControlledPauliZ::new(0, 2);
+    let routed = route_circuit(&circuit, &device, None).unwrap();
+    assert_eq!(routed.number_shifts, 0);
+    assert_eq!(routed.number_layout_switches, 1);
+    let switch = PragmaSwitchDeviceLayout::new("rows".to_string())
+        .to_pragma_change_device()
+        .unwrap();
+    assert_eq!(routed.circuit[1], Operation::from(switch));
+}
+
+/// Test route_circuit() errors
+#[test]
+fn test_route_circuit_errors() {
+    let device = two_row_device(false, true);
+
+    // Gate not available in any Layout
+    let mut circuit = Circuit::new();
+    circuit += CNOT::new(0, 1);
+    assert!(route_circuit(&circuit, &device, None)
+        .unwrap_err()
+        .to_string()
+        .contains("CNOT gate on the qubits [0, 1] cannot be made executable"));
+
+    // More qubits than tweezers
+    let mut circuit = Circuit::new();
+    for qubit in 0..9 {
+        circuit += RotateX::new(qubit, 1.0.into());
+    }
+    assert!(route_circuit(&circuit, &device, None)
+        .unwrap_err()
+        .to_string()
+        .contains("only contains 8 tweezers"));
+
+    // Already routed circuits
+    let mut circuit = Circuit::new();
+    circuit += PragmaSwitchDeviceLayout::new("columns".to_string())
+        .to_pragma_change_device()
+        .unwrap();
+    assert!(route_circuit(&circuit, &device, None).is_err());
+
+    // No current Layout
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliZ::new(0, 1);
+    let mut no_layout = device.clone();
+    no_layout.current_layout = None;
+    assert!(route_circuit(&circuit, &no_layout, None).is_err());
+
+    // Unexecutable circuits are detected by check_routed_circuit
+    let mapped = mapped_device(&device, &HashMap::from([(0, 0), (1, 1)]));
+    assert!(check_routed_circuit(&circuit, &mapped)
+        .unwrap_err()
+        .to_string()
+        .contains("ControlledPauliZ gate on the qubits [0, 1] is not executable"));
+}
+
+/// Test route_circuit() and check_routed_circuit() on single-qubit gates
+#[test]
+fn test_route_circuit_single_qubit_gates() {
+    let mut device = two_row_device(false, true);
+    // RotateZ is only available on the last tweezer of the first row
+    device
+        .set_tweezer_single_qubit_gate_time("RotateZ", 3, 0.1, None)
+        .unwrap();
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, 1.0.into());
+    circuit += RotateZ::new(0, 1.0.into());
+
+    let routed = route_circuit(&circuit, &device, None).unwrap();
+    assert_eq!(routed.initial_mapping, HashMap::from([(0, 0)]));
+    assert!(routed.number_shifts > 0);
+    check_routed_circuit(
+        &routed.circuit,
+        &mapped_device(&device, &routed.initial_mapping),
+    )
+    .unwrap();
+
+    // The unrouted circuit is not executable
+    let mapped = mapped_device(&device, &HashMap::from([(0, 0)]));
+    assert!(check_routed_circuit(&circuit, &mapped)
+        .unwrap_err()
+        .to_string()
+        .contains("RotateZ gate on the qubits [0] is not executable"));
+
+    // Gates are checked against the mapping after the shifts
+    let mapped = mapped_device(&device, &HashMap::from([(0, 3)]));
+    let mut shifted = Circuit::new();
+    shifted += RotateZ::new(0, 1.0.into());
+    shifted += PragmaShiftQubitsTweezers::new(vec![(3, 2)])
+        .to_pragma_change_device()
+        .unwrap();
+    check_routed_circuit(&shifted, &mapped).unwrap();
+    shifted += RotateZ::new(0, 1.0.into());
+    assert!(check_routed_circuit(&shifted, &mapped)
+        .unwrap_err()
+        .to_string()
+        .contains("RotateZ gate on the qubits [0] is not executable"));
+
+    // Single-qubit gates not available in any Layout cannot be routed
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    assert!(route_circuit(&circuit, &device, None)
+        .unwrap_err()
+        .to_string()
+        .contains("Hadamard gate on the qubits [0] cannot be made executable"));
+}
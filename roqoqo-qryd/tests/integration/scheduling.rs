@@ -0,0 +1,196 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_qryd::{
+    schedule_circuit, PragmaShiftQubitsTweezers, PragmaSwitchDeviceLayout, QubitTweezerMapping,
+    SchedulingStrategy, TweezerDevice,
+};
+
+use crate::TestDevice;
+
+/// Returns a device with a row of four tweezers and the "default" and "slow" Layouts.
+///
+/// ControlledPauliZ gates act between neighbouring tweezers and the qubit in tweezer 2 can be
+/// shifted to tweezer 1. All gates take twice as long in the "slow" Layout.
+fn row_device() -> TweezerDevice {
+    let default = TestDevice {
+        layout: "default",
+        tweezers: 4,
+        single_qubit_gate_time: 0.1,
+        two_qubit_gates: &["ControlledPauliZ"],
+        two_qubit_edges: &[(0, 1), (1, 2), (2, 3)],
+        two_qubit_gate_time: 0.5,
+        shifts: &[(2, &[&[1]])],
+        tweezers_per_row: &[4],
+        ..TestDevice::DEFAULT
+    };
+    let mut device = TweezerDevice::new(None, None, None);
+    default.add_layout_to(&mut device);
+    TestDevice {
+        layout: "slow",
+        single_qubit_gate_time: 0.2,
+        two_qubit_gate_time: 1.0,
+        ..default
+    }
+    .add_layout_to(&mut device);
+    device.switch_layout("default", None).unwrap();
+    device
+}
+
+/// Asserts that two times are equal up to rounding errors.
+fn assert_time(found: f64, expected: f64) {
+    assert!(
+        (found - expected).abs() < 1e-12,
+        "found {found}, expected {expected}"
+    );
+}
+
+/// Test schedule_circuit() as soon and as late as possible
+#[test]
+fn test_schedule_circuit() {
+    let device = row_device();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 4, true);
+    circuit += RotateX::new(0, 1.0.into());
+    circuit += RotateX::new(0, 1.0.into());
+    circuit += ControlledPauliZ::new(0, 1);
+    circuit += RotateX::new(2, 1.0.into());
+    circuit += MeasureQubit::new(2, "ro".to_string(), 2);
+
+    let schedule = schedule_circuit(&circuit, &device, SchedulingStrategy::Asap, 0.0, 0.0).unwrap();
+    assert_eq!(schedule.operations.len(), 6);
+    assert_time(schedule.duration, 0.7);
+    let starts: Vec<f64> = schedule.operations.iter().map(|op| op.start_time).collect();
+    let durations: Vec<f64> = schedule.operations.iter().map(|op| op.duration).collect();
+    for (found, expected) in starts.iter().zip([0.0, 0.0, 0.1, 0.2, 0.0, 0.1]) {
+        assert_time(*found, expected);
+    }
+    for (found, expected) in durations.iter().zip([0.0, 0.1, 0.1, 0.5, 0.1, 0.0]) {
+        assert_time(*found, expected);
+    }
+    assert_eq!(schedule.critical_path, vec![1, 2, 3]);
+    assert_eq!(schedule.idle_times.len(), 4);
+    assert_time(schedule.idle_times[&0], 0.0);
+    assert_time(schedule.idle_times[&1], 0.2);
+    assert_time(schedule.idle_times[&2], 0.6);
+    assert_time(schedule.idle_times[&3], 0.7);
+
+    let schedule = schedule_circuit(&circuit, &device, SchedulingStrategy::Alap, 0.0, 0.0).unwrap();
+    assert_time(schedule.duration, 0.7);
+    for (operation, expected) in schedule
+        .operations
+        .iter()
+        .zip([0.7, 0.0, 0.1, 0.2, 0.6, 0.7])
+    {
+        assert_time(operation.start_time, expected);
+    }
+    assert_eq!(schedule.critical_path, vec![1, 2, 3]);
+
+    let empty = schedule_circuit(
+        &Circuit::new(),
+        &device,
+        SchedulingStrategy::default(),
+        0.0,
+        0.0,
+    )
+    .unwrap();
+    assert!(empty.operations.is_empty());
+    assert!(empty.critical_path.is_empty());
+    assert_time(empty.duration, 0.0);
+}
+
+/// Test schedule_circuit() following layout switches and tweezer shifts
+#[test]
+fn test_schedule_circuit_with_device_changes() {
+    let device = row_device();
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(1, 1.0.into());
+    circuit += PragmaSwitchDeviceLayout::new("slow".to_string())
+        .to_pragma_change_device()
+        .unwrap();
+    circuit += RotateX::new(0, 1.0.into());
+    let schedule = schedule_circuit(&circuit, &device, SchedulingStrategy::Asap, 0.0, 0.0).unwrap();
+    // The switch waits for all qubits and the gate takes the time of the new Layout
+    assert_time(schedule.operations[1].start_time, 0.1);
+    assert_time(schedule.operations[2].start_time, 0.1);
+    assert_time(schedule.operations[2].duration, 0.2);
+    assert_time(schedule.duration, 0.3);
+    assert_eq!(schedule.critical_path, vec![0, 1, 2]);
+    // The switch takes the given layout switch time, shifts are not charged
+    let schedule =
+        schedule_circuit(&circuit, &device, SchedulingStrategy::Asap, 0.7, 0.05).unwrap();
+    assert_time(schedule.operations[1].duration, 0.05);
+    assert_time(schedule.operations[2].start_time, 0.15);
+    assert_time(schedule.duration, 0.35);
+    assert_eq!(schedule.critical_path, vec![0, 1, 2]);
+    assert_time(schedule.idle_times[&0], 0.15);
+    // The given device is unchanged
+    assert_eq!(device.current_layout, Some("default".to_string()));
+
+    // The gate between the qubits 0 and 2 is only executable after the shift
+    let mut device = row_device();
+    device
+        .set_qubit_to_tweezer(Some(
+            QubitTweezerMapping::try_from(HashMap::from([(0, 0), (2, 2)])).unwrap(),
+        ))
+        .unwrap();
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliZ::new(0, 2);
+    assert!(
+        schedule_circuit(&circuit, &device, SchedulingStrategy::Asap, 0.0, 0.0)
+            .unwrap_err()
+            .to_string()
+            .contains(
+                "The ControlledPauliZ gate on the qubits [0, 2] is not available on the device."
+            )
+    );
+    let mut shifted = Circuit::new();
+    shifted += PragmaShiftQubitsTweezers::new(vec![(2, 1)])
+        .to_pragma_change_device()
+        .unwrap();
+    shifted += circuit;
+    let schedule = schedule_circuit(&shifted, &device, SchedulingStrategy::Asap, 0.0, 0.0).unwrap();
+    assert_time(schedule.duration, 0.5);
+    // The shift takes the given shift time before the gate
+    let schedule = schedule_circuit(&shifted, &device, SchedulingStrategy::Alap, 0.2, 0.7).unwrap();
+    assert_time(schedule.operations[0].start_time, 0.0);
+    assert_time(schedule.operations[0].duration, 0.2);
+    assert_time(schedule.operations[1].start_time, 0.2);
+    assert_time(schedule.duration, 0.7);
+}
+
+/// Test schedule_circuit() errors
+#[test]
+fn test_schedule_circuit_errors() {
+    let device = row_device();
+
+    // Gate not available
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    assert!(schedule_circuit(&circuit, &device, SchedulingStrategy::Asap, 0.0, 0.0).is_err());
+
+    // Qubit not mapped
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(5, 1.0.into());
+    assert!(schedule_circuit(&circuit, &device, SchedulingStrategy::Asap, 0.0, 0.0).is_err());
+
+    // Failing layout switch
+    let mut circuit = Circuit::new();
+    circuit += PragmaSwitchDeviceLayout::new("missing".to_string())
+        .to_pragma_change_device()
+        .unwrap();
+    assert!(schedule_circuit(&circuit, &device, SchedulingStrategy::Alap, 0.0, 0.0).is_err());
+}
@@ -0,0 +1,305 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo::devices::Device;
+use roqoqo_qryd::TweezerDevice;
+
+use crate::TestDevice;
+
+/// Returns a device with two rows of four tweezers, shifts being allowed along the rows.
+///
+/// Tweezers 0-3 form the first row and tweezers 4-7 the second one.
+fn row_device(mapping: &[(usize, usize)]) -> TweezerDevice {
+    TestDevice {
+        layout: "default",
+        tweezers: 8,
+        single_qubit_gate_time: 0.1,
+        shift_rows: &[&[0, 1, 2, 3], &[4, 5, 6, 7]],
+        mapping: Some(mapping),
+        ..TestDevice::DEFAULT
+    }
+    .build()
+}
+
+/// Applies the planned operations to the device and returns the number of operations.
+fn apply_plan(device: &mut TweezerDevice, target: &HashMap<usize, usize>) -> usize {
+    let plan = device.plan_shifts(target, None).unwrap();
+    for pragma in plan.iter() {
+        device
+            .change_device(&pragma.wrapped_hqslang, &pragma.wrapped_operation)
+            .unwrap();
+    }
+    assert_eq!(device.qubit_to_tweezer().unwrap(), target);
+    plan.len()
+}
+
+/// Test plan_shifts() on reachable targets
+#[test]
+fn test_plan_shifts() {
+    // Already at the target
+    let device = row_device(&[(0, 0), (1, 5)]);
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 0), (1, 5)]), None)
+        .unwrap()
+        .is_empty());
+
+    // Shifts of different rows are applied in parallel
+    let mut device = row_device(&[(0, 0), (1, 4)]);
+    let target = HashMap::from([(0, 3), (1, 6)]);
+    let pragmas = device.plan_shift_pragmas(&target, None).unwrap();
+    assert_eq!(pragmas.len(), 1);
+    assert_eq!(pragmas[0].shifts.len(), 2);
+    assert!(pragmas[0].shifts.contains(&(0, 3)));
+    assert!(pragmas[0].shifts.contains(&(4, 6)));
+    assert_eq!(apply_plan(&mut device, &target), 1);
+
+    // The shifts of an operation are applied in order, the leading qubit moving first
+    let mut device = row_device(&[(0, 0), (1, 1)]);
+    let target = HashMap::from([(0, 2), (1, 3)]);
+    let pragmas = device.plan_shift_pragmas(&target, None).unwrap();
+    assert_eq!(
+        pragmas
+            .iter()
+            .map(|pragma| pragma.shifts.clone())
+            .collect::<Vec<_>>(),
+        vec![vec![(1, 3), (0, 2)]]
+    );
+    assert_eq!(apply_plan(&mut device, &target), 1);
+
+    // A whole row shifts in a single operation
+    let mut device = row_device(&[(0, 0), (1, 1), (2, 2)]);
+    let target = HashMap::from([(0, 1), (1, 2), (2, 3)]);
+    let pragmas = device.plan_shift_pragmas(&target, None).unwrap();
+    assert_eq!(
+        pragmas
+            .iter()
+            .map(|pragma| pragma.shifts.clone())
+            .collect::<Vec<_>>(),
+        vec![vec![(2, 3), (1, 2), (0, 1)]]
+    );
+    assert_eq!(apply_plan(&mut device, &target), 1);
+}
+
+/// Test plan_shifts() errors
+#[test]
+fn test_plan_shifts_errors() {
+    let device = row_device(&[(0, 0), (1, 1)]);
+
+    // Qubits cannot overtake each other in a row
+    let err = device
+        .plan_shifts(&HashMap::from([(0, 1), (1, 0)]), None)
+        .unwrap_err();
+    assert!(err.to_string().contains("not reachable"));
+    // No shifts between the rows
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 4), (1, 1)]), None)
+        .unwrap_err()
+        .to_string()
+        .contains("not reachable"));
+
+    // Invalid targets
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 2)]), None)
+        .unwrap_err()
+        .to_string()
+        .contains("contains the qubits [0]"));
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 2), (1, 2)]), None)
+        .is_err());
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 2), (1, 9)]), None)
+        .unwrap_err()
+        .to_string()
+        .contains("Target tweezer 9"));
+
+    // Search budget
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 2), (1, 3)]), Some(1))
+        .unwrap_err()
+        .to_string()
+        .contains("explored 1 qubit arrangements"));
+
+    let mut device = row_device(&[]);
+    device.set_qubit_to_tweezer(None).unwrap();
+    assert!(device.plan_shifts(&HashMap::new(), None).is_err());
+}
+
+/// Returns a device with a chain of four tweezers where qubits can only move one tweezer
+/// forward per shift: 0 -> 1 -> 2 -> 3.
+fn chain_device(mapping: &[(usize, usize)]) -> TweezerDevice {
+    TestDevice {
+        layout: "default",
+        tweezers: 4,
+        single_qubit_gate_time: 0.1,
+        shifts: &[(0, &[&[1]]), (1, &[&[2]]), (2, &[&[3]])],
+        mapping: Some(mapping),
+        ..TestDevice::DEFAULT
+    }
+    .build()
+}
+
+/// Returns the shifts of each planned operation.
+fn planned_shifts(
+    device: &TweezerDevice,
+    target: &HashMap<usize, usize>,
+) -> Vec<Vec<(usize, usize)>> {
+    device
+        .plan_shift_pragmas(target, None)
+        .unwrap()
+        .into_iter()
+        .map(|pragma| pragma.shifts)
+        .collect()
+}
+
+/// Test plan_shifts() on targets matching the current mapping
+#[test]
+fn test_plan_shifts_already_satisfied() {
+    for mapping in [
+        &[(0, 0)][..],
+        &[(0, 3), (1, 4)],
+        &[(0, 0), (1, 1), (2, 2), (3, 3)],
+    ] {
+        let device = row_device(mapping);
+        let target: HashMap<usize, usize> = mapping.iter().copied().collect();
+        assert!(planned_shifts(&device, &target).is_empty());
+        assert!(device.plan_shifts(&target, None).unwrap().is_empty());
+    }
+
+    // Also without any allowed shift, and with a search budget of a single arrangement
+    let device = chain_device(&[(0, 3)]);
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 3)]), Some(1))
+        .unwrap()
+        .is_empty());
+}
+
+/// Test plan_shifts() on targets the allowed shifts cannot reach
+#[test]
+fn test_plan_shifts_unreachable() {
+    // Shifts only go forward along the chain
+    let device = chain_device(&[(0, 2)]);
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 1)]), None)
+        .unwrap_err()
+        .to_string()
+        .contains("not reachable"));
+
+    // The qubit in the last tweezer cannot make room for the qubit behind it
+    let device = chain_device(&[(0, 2), (1, 3)]);
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 3), (1, 2)]), None)
+        .unwrap_err()
+        .to_string()
+        .contains("not reachable"));
+
+    // A single unreachable qubit makes the whole target unreachable
+    let device = row_device(&[(0, 0), (1, 4)]);
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 1), (1, 3)]), None)
+        .unwrap_err()
+        .to_string()
+        .contains("not reachable"));
+
+    // Without allowed shifts, only the current mapping is reachable
+    let device = TestDevice {
+        layout: "default",
+        tweezers: 4,
+        mapping: Some(&[(0, 0)]),
+        ..TestDevice::DEFAULT
+    }
+    .build();
+    assert!(device
+        .plan_shifts(&HashMap::from([(0, 1)]), None)
+        .unwrap_err()
+        .to_string()
+        .contains("not reachable"));
+}
+
+/// Test plan_shifts() moving qubits in several rows
+#[test]
+fn test_plan_shifts_multiple_rows() {
+    let device = TestDevice {
+        layout: "default",
+        tweezers: 9,
+        single_qubit_gate_time: 0.1,
+        shift_rows: &[&[0, 1, 2], &[3, 4, 5], &[6, 7, 8]],
+        mapping: Some(&[(0, 0), (1, 3), (2, 6), (3, 7)]),
+        ..TestDevice::DEFAULT
+    }
+    .build();
+
+    // Every row shifts in the same operation, the leading qubit of the last row moving first
+    let target = HashMap::from([(0, 2), (1, 4), (2, 7), (3, 8)]);
+    let shifts = planned_shifts(&device, &target);
+    assert_eq!(shifts.len(), 1);
+    let mut sorted = shifts[0].clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, vec![(0, 2), (3, 4), (6, 7), (7, 8)]);
+    let position = |shift| shifts[0].iter().position(|found| *found == shift).unwrap();
+    assert!(position((7, 8)) < position((6, 7)));
+    assert_eq!(apply_plan(&mut device.clone(), &target), 1);
+
+    // Rows without moves are left out
+    let target = HashMap::from([(0, 0), (1, 5), (2, 6), (3, 7)]);
+    assert_eq!(planned_shifts(&device, &target), vec![vec![(3, 5)]]);
+    assert_eq!(apply_plan(&mut device.clone(), &target), 1);
+}
+
+/// Test that plan_shifts() returns the fewest operations, then the fewest single shifts
+#[test]
+fn test_plan_shifts_minimality() {
+    // A qubit moves at most once per operation: three steps need three operations
+    let device = chain_device(&[(0, 0)]);
+    let target = HashMap::from([(0, 3)]);
+    assert_eq!(
+        planned_shifts(&device, &target),
+        vec![vec![(0, 1)], vec![(1, 2)], vec![(2, 3)]]
+    );
+    assert_eq!(apply_plan(&mut device.clone(), &target), 3);
+
+    // Two qubits moving two steps each share the two operations
+    let device = chain_device(&[(0, 0), (1, 1)]);
+    let target = HashMap::from([(0, 2), (1, 3)]);
+    assert_eq!(
+        planned_shifts(&device, &target),
+        vec![vec![(1, 2), (0, 1)], vec![(2, 3), (1, 2)]]
+    );
+    assert_eq!(apply_plan(&mut device.clone(), &target), 2);
+
+    // Along a row, a qubit jumps directly to its target instead of stepping through the row
+    let device = row_device(&[(0, 0), (1, 1)]);
+    let target = HashMap::from([(0, 0), (1, 3)]);
+    assert_eq!(planned_shifts(&device, &target), vec![vec![(1, 3)]]);
+
+    // Qubits already at their target are not moved
+    let device = row_device(&[(0, 0), (1, 2), (2, 4)]);
+    let target = HashMap::from([(0, 1), (1, 2), (2, 4)]);
+    assert_eq!(planned_shifts(&device, &target), vec![vec![(0, 1)]]);
+
+    // The shift count of every planned operation sequence is the smallest one
+    let device = row_device(&[(0, 0), (1, 1), (2, 4)]);
+    for target in [
+        HashMap::from([(0, 1), (1, 2), (2, 4)]),
+        HashMap::from([(0, 2), (1, 3), (2, 7)]),
+        HashMap::from([(0, 0), (1, 3), (2, 5)]),
+    ] {
+        let moved = target
+            .iter()
+            .filter(|(qubit, tweezer)| device.get_tweezer_from_qubit(qubit).unwrap() != **tweezer)
+            .count();
+        let shifts = planned_shifts(&device, &target);
+        assert_eq!(shifts.len(), 1);
+        assert_eq!(shifts.iter().map(Vec::len).sum::<usize>(), moved);
+    }
+}
@@ -10,11 +10,20 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use roqoqo::devices::Device;
+use roqoqo::measurements::ClassicalRegister;
 use roqoqo::prelude::*;
 use roqoqo::{operations::*, Circuit};
-use roqoqo_qryd::{SimulatorBackend, TweezerDevice};
+use roqoqo_qryd::{
+    post_select_shots, AtomLossModel, CrosstalkModel, PragmaShiftQubitsTweezers,
+    QubitTweezerMapping, ReadoutConfusionMatrix, SimulationMode, SimulatorBackend,
+    SimulatorNoiseModel, TweezerDecoherenceRates, TweezerDevice, ViolationReason,
+    DEFAULT_LOST_QUBITS_REGISTER,
+};
 use roqoqo_test::prepare_monte_carlo_gate_test;
 
+use crate::{TestDevice, BASELINE_DEVICE_BYTES};
+
 /// Test SimulatorBackend initialization with TweezerDevice.
 #[test]
 fn test_init_backend_tw() {
@@ -76,7 +85,7 @@ fn test_simple_traits() {
     assert_eq!(
         format!("{:?}", backend_tw),
         format!(
-            "SimulatorBackend {{ device: {:?}, number_qubits: 0 }}",
+            "SimulatorBackend {{ device: {:?}, number_qubits: 0, noise_model: None, atom_loss_model: None, readout_errors: false, crosstalk_model: None, number_threads: None, shot_batch_size: None, simulation_mode: StateVector }}",
             device_tw
         )
     );
@@ -205,3 +214,674 @@ fn test_full_simple_gate() {
         assert!((val - measured_exp_vals.get(key).unwrap()).abs() < 1.0);
     }
 }
+
+/// Device with a decaying tweezer 0 and a shift from tweezer 0 to tweezer 1.
+const NOISY_DEVICE: TestDevice<'static> = TestDevice {
+    shifts: &[(0, &[&[1]])],
+    decoherence_rates: &[(
+        0,
+        TweezerDecoherenceRates {
+            damping: 5.0,
+            dephasing: 0.0,
+            depolarising: 0.0,
+        },
+    )],
+    ..TestDevice::DEFAULT
+};
+
+/// Test .noisy_circuit() inserting the noise operations after each gate
+#[test]
+fn test_noisy_circuit() {
+    let device = NOISY_DEVICE.build();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, 1.0.into());
+    circuit += RotateX::new(1, 1.0.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+
+    let backend = SimulatorBackend::new(device.clone(), None);
+    assert_eq!(backend.noise_model, None);
+    assert_eq!(backend.noisy_circuit(&circuit).unwrap(), circuit);
+
+    let noise_model = SimulatorNoiseModel::new().with_gate_error_rate("RotateX", 0.1);
+    let backend = SimulatorBackend::new(device.clone(), None).with_noise_model(noise_model);
+    let noisy = backend.noisy_circuit(&circuit).unwrap();
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("ro".to_string(), 2, true);
+    expected += RotateX::new(0, 1.0.into());
+    expected += PragmaGeneralNoise::new(0, 1.0.into(), device.qubit_decoherence_rates(&0).unwrap());
+    expected += PragmaDepolarising::new(0, 1.0.into(), 0.1.into());
+    expected += RotateX::new(1, 1.0.into());
+    expected += PragmaDepolarising::new(1, 1.0.into(), 0.1.into());
+    expected += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert_eq!(noisy, expected);
+
+    // Qubit positions are followed through shifts
+    let mut circuit = Circuit::new();
+    circuit += PragmaShiftQubitsTweezers::new(vec![(0, 1)])
+        .to_pragma_change_device()
+        .unwrap();
+    circuit += RotateX::new(0, 1.0.into());
+    let mut device = NOISY_DEVICE.build();
+    device
+        .set_qubit_to_tweezer(Some(QubitTweezerMapping::trivial(0..1)))
+        .unwrap();
+    let backend =
+        SimulatorBackend::new(device, Some(1)).with_noise_model(SimulatorNoiseModel::new());
+    assert_eq!(backend.noisy_circuit(&circuit).unwrap(), circuit);
+
+    // Gates not available on the device
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    assert!(backend.noisy_circuit(&circuit).is_err());
+}
+
+/// Test noisy simulations against noise-free simulations of the same device
+#[test]
+fn test_noisy_simulation() {
+    let device = NOISY_DEVICE.build();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, std::f64::consts::PI.into());
+    circuit += RotateX::new(1, std::f64::consts::PI.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += PragmaSetNumberOfMeasurements::new(100, "ro".to_string());
+
+    let ideal = SimulatorBackend::new(device.clone(), None);
+    let (bits, _, _) = ideal.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 100);
+    assert!(bits["ro"].iter().all(|shot| shot[0] && shot[1]));
+
+    // The excitation of qubit 0 decays, qubit 1 is noise free
+    let noisy = SimulatorBackend::new(device, None).with_noise_model(SimulatorNoiseModel::new());
+    let (bits, _, _) = noisy.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 100);
+    assert!(bits["ro"].iter().all(|shot| shot[1]));
+    assert!(bits["ro"].iter().filter(|shot| shot[0]).count() < 50);
+}
+
+/// Test noisy simulations applying the gate error rates of the device
+#[test]
+fn test_noisy_simulation_with_gate_error_rates() {
+    let mut device = NOISY_DEVICE.build();
+    device
+        .set_tweezer_single_qubit_gate_error("RotateX", 1, 0.3, None)
+        .unwrap();
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(1, 1.0.into());
+    let backend =
+        SimulatorBackend::new(device.clone(), None).with_noise_model(SimulatorNoiseModel::new());
+    let noisy = backend.noisy_circuit(&circuit).unwrap();
+    assert_eq!(noisy.len(), 2);
+    match noisy.get(1).unwrap() {
+        Operation::PragmaDepolarising(pragma) => {
+            assert_eq!(*pragma.qubit(), 1);
+            assert!((f64::try_from(pragma.probability()).unwrap() - 0.3).abs() < 1e-12);
+        }
+        operation => panic!("Unexpected operation {:?}", operation),
+    }
+
+    // Error rates of at least 3/4 depolarise the qubit completely
+    device
+        .set_tweezer_single_qubit_gate_error("RotateX", 1, 0.75, None)
+        .unwrap();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(1, std::f64::consts::PI.into());
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += PragmaSetNumberOfMeasurements::new(200, "ro".to_string());
+    let backend = SimulatorBackend::new(device, None).with_noise_model(SimulatorNoiseModel::new());
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    let excited = bits["ro"].iter().filter(|shot| shot[1]).count();
+    assert!(excited > 50 && excited < 150);
+}
+
+/// Device with qubits in tweezers 0 and 1 and a shift from tweezer 0 to the free tweezer 2.
+const ATOM_LOSS_DEVICE: TestDevice<'static> = TestDevice {
+    tweezers: 3,
+    shifts: &[(0, &[&[2]])],
+    mapping: Some(&[(0, 0), (1, 1)]),
+    ..TestDevice::DEFAULT
+};
+
+/// Returns a circuit exciting qubits 0 and 1, shifting qubit 0 and measuring both qubits.
+fn atom_loss_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, std::f64::consts::PI.into());
+    circuit += RotateX::new(1, std::f64::consts::PI.into());
+    circuit += PragmaShiftQubitsTweezers::new(vec![(0, 2)])
+        .to_pragma_change_device()
+        .unwrap();
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += PragmaSetNumberOfMeasurements::new(20, "ro".to_string());
+    circuit
+}
+
+/// Test simulations losing the qubits shifted between tweezers
+#[test]
+fn test_atom_loss_during_shifts() {
+    let circuit = atom_loss_circuit();
+    let backend = SimulatorBackend::new(ATOM_LOSS_DEVICE.build(), None);
+    assert_eq!(backend.atom_loss_model, None);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert!(!bits.contains_key(DEFAULT_LOST_QUBITS_REGISTER));
+
+    // Without losses, no qubit is marked as lost
+    let backend = SimulatorBackend::new(ATOM_LOSS_DEVICE.build(), None)
+        .with_atom_loss_model(AtomLossModel::new());
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"], vec![vec![true, true]; 20]);
+    assert_eq!(
+        bits[DEFAULT_LOST_QUBITS_REGISTER],
+        vec![vec![false, false]; 20]
+    );
+
+    // The shifted qubit is lost and reads out as false
+    let model = AtomLossModel::new().with_shift_loss_rate(100.0);
+    let backend = SimulatorBackend::new(ATOM_LOSS_DEVICE.build(), None).with_atom_loss_model(model);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"], vec![vec![false, true]; 20]);
+    assert_eq!(
+        bits[DEFAULT_LOST_QUBITS_REGISTER],
+        vec![vec![true, false]; 20]
+    );
+    let selected = post_select_shots(&bits, DEFAULT_LOST_QUBITS_REGISTER, None).unwrap();
+    assert!(selected["ro"].is_empty());
+    let selected = post_select_shots(&bits, DEFAULT_LOST_QUBITS_REGISTER, Some(&[1])).unwrap();
+    assert_eq!(selected["ro"].len(), 20);
+}
+
+/// Test simulations losing qubits during measurements and idle times
+#[test]
+fn test_atom_loss_during_measurements_and_idle_times() {
+    let circuit = atom_loss_circuit();
+
+    // Qubits lost during their measurement keep the measured value
+    let model = AtomLossModel::new()
+        .with_measurement_loss_probability(1.0)
+        .with_lost_register("lost");
+    let backend = SimulatorBackend::new(ATOM_LOSS_DEVICE.build(), None).with_atom_loss_model(model);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"], vec![vec![true, true]; 20]);
+    assert_eq!(bits["lost"], vec![vec![true, true]; 20]);
+
+    // Qubit 1 is idle during the rotation of qubit 0 and qubit 0 during the one of qubit 1
+    let model = AtomLossModel::new().with_idle_loss_rate(100.0);
+    let backend = SimulatorBackend::new(ATOM_LOSS_DEVICE.build(), None).with_atom_loss_model(model);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"], vec![vec![false, false]; 20]);
+    assert_eq!(
+        bits[DEFAULT_LOST_QUBITS_REGISTER],
+        vec![vec![true, true]; 20]
+    );
+
+    // Invalid models
+    let model = AtomLossModel::new().with_measurement_loss_probability(2.0);
+    let backend = SimulatorBackend::new(ATOM_LOSS_DEVICE.build(), None).with_atom_loss_model(model);
+    assert!(backend.run_circuit(&circuit).is_err());
+}
+
+/// Test the per-shot statistics and the reproducibility of atom losses with a seeded device
+#[test]
+fn test_atom_loss_statistics() {
+    let device = TestDevice {
+        seed: Some(42),
+        ..ATOM_LOSS_DEVICE
+    }
+    .build();
+    // The circuit of the other atom loss tests with 1000 shots
+    let mut circuit = Circuit::new();
+    for operation in atom_loss_circuit().iter() {
+        if !matches!(operation, Operation::PragmaSetNumberOfMeasurements(_)) {
+            circuit += operation.clone();
+        }
+    }
+    circuit += PragmaSetNumberOfMeasurements::new(1000, "ro".to_string());
+    let count_lost =
+        |lost: &Vec<Vec<bool>>, qubit: usize| lost.iter().filter(|shot| shot[qubit]).count();
+
+    // The shifted qubit is lost in about half of the shots and reads out as false when lost
+    let model = AtomLossModel::new().with_shift_loss_rate(std::f64::consts::LN_2);
+    let backend = SimulatorBackend::new(device.clone(), None).with_atom_loss_model(model);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    let lost = &bits[DEFAULT_LOST_QUBITS_REGISTER];
+    assert_eq!(lost.len(), 1000);
+    assert!((400..600).contains(&count_lost(lost, 0)));
+    assert_eq!(count_lost(lost, 1), 0);
+    for (readout, lost) in bits["ro"].iter().zip(lost.iter()) {
+        assert_eq!(readout, &vec![!lost[0], true]);
+    }
+    // The losses are reproducible with the seed of the device
+    let (repeated, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(repeated, bits);
+
+    // Each measured qubit is lost in about half of the shots, independently of the other one
+    let model = AtomLossModel::new().with_measurement_loss_probability(0.5);
+    let backend = SimulatorBackend::new(device, None).with_atom_loss_model(model);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    let lost = &bits[DEFAULT_LOST_QUBITS_REGISTER];
+    assert!((400..600).contains(&count_lost(lost, 0)));
+    assert!((400..600).contains(&count_lost(lost, 1)));
+    let both = lost.iter().filter(|shot| shot[0] && shot[1]).count();
+    assert!((150..350).contains(&both));
+}
+
+/// Test simulations applying the readout confusion matrices of the tweezers
+#[test]
+fn test_readout_errors() {
+    let mut device = ATOM_LOSS_DEVICE.build();
+    // Qubits in tweezers 0 and 1 always read out 0, tweezer 2 reads out correctly
+    for tweezer in 0..2 {
+        device
+            .set_tweezer_readout_confusion_matrix(
+                tweezer,
+                ReadoutConfusionMatrix::new(0.0, 1.0).unwrap(),
+                None,
+            )
+            .unwrap();
+    }
+    // Qubit 0 is measured in tweezer 2 after the shift
+    let circuit = atom_loss_circuit();
+
+    let backend = SimulatorBackend::new(device, None);
+    assert!(!backend.readout_errors);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert!(bits["ro"].iter().all(|shot| shot == &vec![true, true]));
+
+    let backend = backend.with_readout_errors(true);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 20);
+    assert!(bits["ro"].iter().all(|shot| shot == &vec![true, false]));
+
+    // Repeated measurements use the confusion matrices of all measured qubits
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, std::f64::consts::PI.into());
+    circuit += RotateX::new(1, std::f64::consts::PI.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 20, None);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 20);
+    assert!(bits["ro"].iter().all(|shot| shot == &vec![false, false]));
+}
+
+/// Device with three qubits in a line of tweezers connected by PhaseShiftedControlledZ gates.
+const CROSSTALK_DEVICE: TestDevice<'static> = TestDevice {
+    tweezers: 3,
+    two_qubit_edges: &[(0, 1), (1, 0), (1, 2), (2, 1)],
+    ..TestDevice::DEFAULT
+};
+
+/// Returns a circuit flipping qubit 2 with two RotateX gates around a gate on qubits 0 and 1.
+///
+/// Qubit 1 is excited, a phase of pi on qubit 2 during the gate keeps qubit 2 in state |0>.
+fn crosstalk_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += RotateX::new(1, std::f64::consts::PI.into());
+    circuit += RotateX::new(2, std::f64::consts::FRAC_PI_2.into());
+    circuit += PhaseShiftedControlledZ::new(0, 1, 0.0.into());
+    circuit += RotateX::new(2, std::f64::consts::FRAC_PI_2.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += MeasureQubit::new(2, "ro".to_string(), 2);
+    circuit += PragmaSetNumberOfMeasurements::new(20, "ro".to_string());
+    circuit
+}
+
+/// Test .noisy_circuit() inserting the crosstalk after each entangling gate
+#[test]
+fn test_crosstalk_circuit() {
+    let circuit = crosstalk_circuit();
+    let backend = SimulatorBackend::new(CROSSTALK_DEVICE.build(), None)
+        .with_crosstalk_model(CrosstalkModel::new(0.3));
+    let noisy = backend.noisy_circuit(&circuit).unwrap();
+    let mut expected = Circuit::new();
+    for operation in circuit.iter() {
+        expected += operation.clone();
+        if let Operation::PhaseShiftedControlledZ(_) = operation {
+            expected += ControlledPhaseShift::new(1, 2, 0.3.into());
+        }
+    }
+    assert_eq!(noisy, expected);
+
+    // Crosstalk and noise are combined
+    let backend = backend.with_noise_model(
+        SimulatorNoiseModel::new().with_gate_error_rate("PhaseShiftedControlledZ", 0.1),
+    );
+    let noisy = backend.noisy_circuit(&circuit).unwrap();
+    assert_eq!(noisy.len(), expected.len() + 2);
+
+    // Gates not available on the device
+    let mut circuit = Circuit::new();
+    circuit += PhaseShiftedControlledZ::new(0, 2, 0.0.into());
+    assert!(backend.noisy_circuit(&circuit).is_err());
+    assert!(backend.run_circuit(&circuit).is_err());
+}
+
+/// Test simulations with the crosstalk of an entangling gate on a spectator qubit
+#[test]
+fn test_crosstalk_simulation() {
+    let circuit = crosstalk_circuit();
+
+    let backend = SimulatorBackend::new(CROSSTALK_DEVICE.build(), None);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 20);
+    assert!(bits["ro"]
+        .iter()
+        .all(|shot| shot == &vec![false, true, true]));
+
+    let backend = backend.with_crosstalk_model(CrosstalkModel::new(std::f64::consts::PI));
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 20);
+    assert!(bits["ro"]
+        .iter()
+        .all(|shot| shot == &vec![false, true, false]));
+
+    // Without excitation of the gate qubits, the spectator is not affected
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += RotateX::new(2, std::f64::consts::FRAC_PI_2.into());
+    circuit += PhaseShiftedControlledZ::new(0, 1, 0.0.into());
+    circuit += RotateX::new(2, std::f64::consts::FRAC_PI_2.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 20, None);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert!(bits["ro"]
+        .iter()
+        .all(|shot| shot == &vec![false, false, true]));
+}
+
+/// Returns a circuit measuring two qubits in an equal superposition 100 times.
+fn superposition_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, std::f64::consts::FRAC_PI_2.into());
+    circuit += RotateX::new(1, std::f64::consts::FRAC_PI_2.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+    circuit
+}
+
+/// Test the reproducibility of simulations with a seeded device
+#[test]
+fn test_seeded_simulation() {
+    let circuit = superposition_circuit();
+    let backend = SimulatorBackend::new(
+        TestDevice {
+            seed: Some(42),
+            ..TestDevice::DEFAULT
+        }
+        .build(),
+        None,
+    );
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 100);
+    assert_eq!(backend.run_circuit(&circuit).unwrap().0, bits);
+    assert_eq!(
+        SimulatorBackend::new(
+            TestDevice {
+                seed: Some(42),
+                ..TestDevice::DEFAULT
+            }
+            .build(),
+            None
+        )
+        .run_circuit(&circuit)
+        .unwrap()
+        .0,
+        bits
+    );
+    assert_ne!(
+        SimulatorBackend::new(
+            TestDevice {
+                seed: Some(7),
+                ..TestDevice::DEFAULT
+            }
+            .build(),
+            None
+        )
+        .run_circuit(&circuit)
+        .unwrap()
+        .0,
+        bits
+    );
+
+    // Circuits with measurements before the end are rerun for each shot
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, std::f64::consts::FRAC_PI_2.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += RotateX::new(1, std::f64::consts::FRAC_PI_2.into());
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += PragmaSetNumberOfMeasurements::new(50, "ro".to_string());
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 50);
+    assert_eq!(backend.run_circuit(&circuit).unwrap().0, bits);
+
+    // Noise, atom losses and readout errors are reproducible
+    let mut device = TestDevice {
+        seed: Some(42),
+        ..TestDevice::DEFAULT
+    }
+    .build();
+    device
+        .set_tweezer_readout_confusion_matrix(
+            0,
+            ReadoutConfusionMatrix::symmetric(0.3).unwrap(),
+            None,
+        )
+        .unwrap();
+    device
+        .set_tweezer_decoherence_rates(
+            1,
+            TweezerDecoherenceRates {
+                depolarising: 0.5,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+    let backend = SimulatorBackend::new(device, None)
+        .with_noise_model(SimulatorNoiseModel::new())
+        .with_atom_loss_model(AtomLossModel::new().with_measurement_loss_probability(0.3))
+        .with_readout_errors(true);
+    let circuit = superposition_circuit();
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"].len(), 100);
+    assert_eq!(backend.run_circuit(&circuit).unwrap().0, bits);
+}
+
+/// Test the seeds of the circuits of a measurement run with a seeded device
+#[test]
+fn test_seeded_measurement() {
+    let measurement = ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![superposition_circuit(), superposition_circuit()],
+    };
+    let backend = SimulatorBackend::new(
+        TestDevice {
+            seed: Some(42),
+            ..TestDevice::DEFAULT
+        }
+        .build(),
+        None,
+    );
+    let (bits, _, _) = backend.run_measurement_registers(&measurement).unwrap();
+    assert_eq!(bits["ro"].len(), 200);
+    assert_eq!(
+        backend.run_measurement_registers(&measurement).unwrap().0,
+        bits
+    );
+
+    // The first circuit is run with the seed of single runs, the others with distinct seeds
+    let (single, _, _) = backend.run_circuit(&superposition_circuit()).unwrap();
+    assert_eq!(bits["ro"][..100], single["ro"][..]);
+    assert_ne!(bits["ro"][100..], single["ro"][..]);
+}
+
+/// Test parallel runs of the circuits of a measurement reproducing sequential runs
+#[test]
+fn test_parallel_measurement() {
+    let mut constant_circuit = Circuit::new();
+    constant_circuit += RotateX::new(0, std::f64::consts::FRAC_PI_4.into());
+    let measurement = ClassicalRegister {
+        constant_circuit: Some(constant_circuit),
+        circuits: vec![superposition_circuit(); 5],
+    };
+    let device = TestDevice {
+        seed: Some(7),
+        ..TestDevice::DEFAULT
+    }
+    .build();
+    let sequential = SimulatorBackend::new(device.clone(), None)
+        .with_atom_loss_model(AtomLossModel::new().with_measurement_loss_probability(0.2));
+    assert_eq!(sequential.threads(), 1);
+    let expected = sequential.run_measurement_registers(&measurement).unwrap();
+    assert_eq!(expected.0["ro"].len(), 500);
+
+    for number_threads in [1, 2, 3, 8] {
+        let parallel = sequential.clone().with_number_threads(number_threads);
+        assert_eq!(parallel.threads(), number_threads);
+        assert_eq!(
+            parallel.run_measurement_registers(&measurement).unwrap(),
+            expected
+        );
+    }
+    assert!(sequential.clone().with_number_threads(0).threads() >= 1);
+
+    // Errors are reported as in sequential runs
+    let mut invalid = measurement.clone();
+    invalid.circuits[3] += Hadamard::new(0);
+    assert_eq!(
+        sequential
+            .clone()
+            .with_number_threads(2)
+            .run_measurement_registers(&invalid),
+        sequential.run_measurement_registers(&invalid)
+    );
+}
+
+/// Test the shot batches of the circuits of a measurement reproducing sequential runs
+#[test]
+fn test_shot_batches() {
+    let mut set_number = Circuit::new();
+    set_number += DefinitionBit::new("ro".to_string(), 2, true);
+    set_number += RotateX::new(0, std::f64::consts::FRAC_PI_2.into());
+    set_number += MeasureQubit::new(0, "ro".to_string(), 0);
+    set_number += MeasureQubit::new(1, "ro".to_string(), 1);
+    set_number += PragmaSetNumberOfMeasurements::new(25, "ro".to_string());
+    let measurement = ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![superposition_circuit(), set_number],
+    };
+    let sequential = SimulatorBackend::new(
+        TestDevice {
+            seed: Some(3),
+            ..TestDevice::DEFAULT
+        }
+        .build(),
+        None,
+    )
+    .with_atom_loss_model(AtomLossModel::new().with_measurement_loss_probability(0.2))
+    .with_shot_batch_size(30);
+    assert_eq!(sequential.shot_batch_size, Some(30));
+    let expected = sequential.run_measurement_registers(&measurement).unwrap();
+    assert_eq!(expected.0["ro"].len(), 125);
+    assert!(expected.0["ro"][100..].iter().all(|bits| !bits[1]));
+
+    // The batches are run with distinct seeds
+    let unbatched = SimulatorBackend::new(
+        TestDevice {
+            seed: Some(3),
+            ..TestDevice::DEFAULT
+        }
+        .build(),
+        None,
+    )
+    .with_atom_loss_model(AtomLossModel::new().with_measurement_loss_probability(0.2));
+    assert_eq!(
+        expected.0["ro"][..30],
+        unbatched.run_measurement_registers(&measurement).unwrap().0["ro"][..30]
+    );
+    assert_ne!(expected.0["ro"][30..60], expected.0["ro"][..30]);
+
+    for number_threads in [2, 3, 8] {
+        assert_eq!(
+            sequential
+                .clone()
+                .with_number_threads(number_threads)
+                .run_measurement_registers(&measurement)
+                .unwrap(),
+            expected
+        );
+    }
+
+    // Unseeded batches run concurrently
+    let unseeded = SimulatorBackend::new(TestDevice::DEFAULT.build(), None)
+        .with_shot_batch_size(10)
+        .with_number_threads(4);
+    let (bits, _, _) = unseeded.run_measurement_registers(&measurement).unwrap();
+    assert_eq!(bits["ro"].len(), 125);
+}
+
+/// Test .check_circuit() reporting all violations and runs failing with the full report
+#[test]
+fn test_check_circuit() {
+    let backend = SimulatorBackend::new(CROSSTALK_DEVICE.build(), Some(3));
+    assert!(backend.check_circuit(&crosstalk_circuit()).is_empty());
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 4, true);
+    circuit += PhaseShiftedControlledZ::new(0, 2, 0.0.into());
+    circuit += RotateX::new(1, 1.0.into());
+    circuit += MeasureQubit::new(3, "ro".to_string(), 3);
+    circuit += Hadamard::new(1);
+    let violations = backend.check_circuit(&circuit);
+    assert_eq!(
+        violations
+            .iter()
+            .map(|violation| (violation.index, violation.reason.clone()))
+            .collect::<Vec<(usize, ViolationReason)>>(),
+        vec![
+            (1, ViolationReason::UnavailableGate),
+            (3, ViolationReason::QubitOutOfRange { number_qubits: 3 }),
+            (4, ViolationReason::UnavailableGate),
+        ]
+    );
+    assert_eq!(violations[1].tweezers, vec![None]);
+
+    let msg = format!("{}", backend.run_circuit(&circuit).unwrap_err());
+    for violation in violations.iter() {
+        assert!(msg.contains(&violation.to_string()));
+    }
+}
+
+/// Test SimulatorBackend from_bincode() with backends serialized by the released version
+#[test]
+fn test_from_bincode_without_options() {
+    let device = TestDevice {
+        layout: "default",
+        tweezers: 1,
+        single_qubit_gate_time: 0.23,
+        ..TestDevice::DEFAULT
+    }
+    .build();
+
+    // The released SimulatorBackend serializes its device followed by the number of qubits, 2
+    let legacy_bytes = [BASELINE_DEVICE_BYTES, &[2, 0, 0, 0, 0, 0, 0, 0]].concat();
+    assert!(bincode::deserialize::<SimulatorBackend>(&legacy_bytes).is_err());
+    assert_eq!(
+        SimulatorBackend::from_bincode(&legacy_bytes),
+        Ok(SimulatorBackend::new(device.clone(), Some(2)))
+    );
+
+    let backend = SimulatorBackend::new(device, None)
+        .with_readout_errors(true)
+        .with_number_threads(2)
+        .with_shot_batch_size(10)
+        .with_simulation_mode(SimulationMode::Stabilizer);
+    let bytes = bincode::serialize(&backend).unwrap();
+    assert_eq!(SimulatorBackend::from_bincode(&bytes), Ok(backend));
+    assert!(SimulatorBackend::from_bincode(&[0, 1, 2]).is_err());
+}
@@ -0,0 +1,265 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, PI};
+
+use ndarray::{array, Array2};
+use num_complex::Complex64;
+use roqoqo::{operations::*, Circuit};
+use roqoqo_qryd::{
+    CrosstalkModel, PragmaShiftQubitsTweezers, SimulatedState, SimulatorBackend,
+    SimulatorNoiseModel, StateBasis, TweezerDecoherenceRates,
+};
+
+use crate::TestDevice;
+
+/// Device with qubits in tweezers 0 and 1 and a shift from tweezer 0 to the free tweezer 2.
+const STATE_DEVICE: TestDevice<'static> = TestDevice {
+    tweezers: 3,
+    shifts: &[(0, &[&[2]])],
+    mapping: Some(&[(0, 0), (1, 1)]),
+    ..TestDevice::DEFAULT
+};
+
+/// Returns a circuit flipping qubit 0 and shifting it to tweezer 2.
+fn flip_and_shift_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, PI.into());
+    circuit += RotateX::new(1, 0.0.into());
+    circuit += PragmaShiftQubitsTweezers::new(vec![(0, 2)])
+        .to_pragma_change_device()
+        .unwrap();
+    circuit
+}
+
+/// Asserts that two arrays of complex numbers are equal up to numerical precision.
+fn assert_close<'a>(
+    actual: impl IntoIterator<Item = &'a Complex64>,
+    expected: impl IntoIterator<Item = &'a Complex64>,
+) {
+    let actual: Vec<&Complex64> = actual.into_iter().collect();
+    let expected: Vec<&Complex64> = expected.into_iter().collect();
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((*a - *e).norm() < 1e-10, "{:?} != {:?}", actual, expected);
+    }
+}
+
+/// Test the final state vector and density matrix in the qubit basis
+#[test]
+fn test_simulate_state() {
+    let backend = SimulatorBackend::new(STATE_DEVICE.build(), None);
+    let circuit = flip_and_shift_circuit();
+    let zero = Complex64::new(0.0, 0.0);
+
+    let snapshot = backend
+        .simulate_state(&circuit, false, StateBasis::Qubits)
+        .unwrap();
+    assert_eq!(snapshot.position, 3);
+    assert_eq!(snapshot.labels, vec![0, 1]);
+    let SimulatedState::StateVector(state) = snapshot.state else {
+        panic!("Expected a state vector");
+    };
+    assert_close(
+        state.iter(),
+        [zero, Complex64::new(0.0, -1.0), zero, zero].iter(),
+    );
+
+    let snapshot = backend
+        .simulate_state(&circuit, true, StateBasis::Qubits)
+        .unwrap();
+    let SimulatedState::DensityMatrix(state) = snapshot.state else {
+        panic!("Expected a density matrix");
+    };
+    let mut expected: Array2<Complex64> = Array2::zeros((4, 4));
+    expected[[1, 1]] = Complex64::new(1.0, 0.0);
+    assert_close(state.iter(), expected.iter());
+
+    // A circuit without qubits has no state
+    assert!(backend
+        .simulate_state(&Circuit::new(), false, StateBasis::Qubits)
+        .is_err());
+}
+
+/// Test the final state in the tweezer basis after shifts
+#[test]
+fn test_simulate_state_in_tweezer_basis() {
+    let backend = SimulatorBackend::new(STATE_DEVICE.build(), None);
+    let circuit = flip_and_shift_circuit();
+    let zero = Complex64::new(0.0, 0.0);
+
+    // Qubit 1 in tweezer 1 is bit 0, qubit 0 in tweezer 2 is bit 1
+    let snapshot = backend
+        .simulate_state(&circuit, false, StateBasis::Tweezers)
+        .unwrap();
+    assert_eq!(snapshot.labels, vec![1, 2]);
+    let SimulatedState::StateVector(state) = snapshot.state else {
+        panic!("Expected a state vector");
+    };
+    assert_close(
+        state.iter(),
+        [zero, zero, Complex64::new(0.0, -1.0), zero].iter(),
+    );
+
+    let snapshot = backend
+        .simulate_state(&circuit, true, StateBasis::Tweezers)
+        .unwrap();
+    let SimulatedState::DensityMatrix(state) = snapshot.state else {
+        panic!("Expected a density matrix");
+    };
+    let mut expected: Array2<Complex64> = Array2::zeros((4, 4));
+    expected[[2, 2]] = Complex64::new(1.0, 0.0);
+    assert_close(state.iter(), expected.iter());
+}
+
+/// Test the snapshots of the state after each operation
+#[test]
+fn test_state_snapshots() {
+    let backend = SimulatorBackend::new(STATE_DEVICE.build(), None);
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, FRAC_PI_2.into());
+    circuit += RotateX::new(1, PI.into());
+    circuit += PragmaShiftQubitsTweezers::new(vec![(0, 2)])
+        .to_pragma_change_device()
+        .unwrap();
+    let zero = Complex64::new(0.0, 0.0);
+    let plus = Complex64::new(FRAC_1_SQRT_2, 0.0);
+    let minus_i = Complex64::new(0.0, -FRAC_1_SQRT_2);
+
+    let snapshots = backend
+        .state_snapshots(&circuit, false, StateBasis::Tweezers)
+        .unwrap();
+    assert_eq!(snapshots.len(), 3);
+    assert_eq!(
+        snapshots
+            .iter()
+            .map(|snapshot| snapshot.position)
+            .collect::<Vec<usize>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(snapshots[0].labels, vec![0, 1]);
+    assert_eq!(snapshots[2].labels, vec![1, 2]);
+    let states: Vec<_> = snapshots
+        .iter()
+        .map(|snapshot| match &snapshot.state {
+            SimulatedState::StateVector(state) => state.clone(),
+            _ => panic!("Expected a state vector"),
+        })
+        .collect();
+    assert_close(states[0].iter(), array![plus, minus_i, zero, zero].iter());
+    let flipped = [
+        zero,
+        zero,
+        Complex64::new(0.0, -FRAC_1_SQRT_2),
+        Complex64::new(-FRAC_1_SQRT_2, 0.0),
+    ];
+    assert_close(states[1].iter(), flipped.iter());
+    // After the shift, qubit 1 is bit 0 and qubit 0 is bit 1
+    assert_close(
+        states[2].iter(),
+        [
+            zero,
+            Complex64::new(0.0, -FRAC_1_SQRT_2),
+            zero,
+            Complex64::new(-FRAC_1_SQRT_2, 0.0),
+        ]
+        .iter(),
+    );
+}
+
+/// Test the states of noisy simulations
+#[test]
+fn test_noisy_states() {
+    let mut device = STATE_DEVICE.build();
+    device
+        .set_tweezer_decoherence_rates(
+            0,
+            TweezerDecoherenceRates {
+                damping: 1.0,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+    let backend = SimulatorBackend::new(device, None).with_noise_model(SimulatorNoiseModel::new());
+    let circuit = flip_and_shift_circuit();
+
+    assert!(backend
+        .simulate_state(&circuit, false, StateBasis::Qubits)
+        .is_err());
+    let snapshot = backend
+        .simulate_state(&circuit, true, StateBasis::Qubits)
+        .unwrap();
+    let SimulatedState::DensityMatrix(state) = snapshot.state else {
+        panic!("Expected a density matrix");
+    };
+    // The excitation of qubit 0 decays during the RotateX gate
+    let trace: Complex64 = state.diag().iter().sum();
+    assert!((trace - Complex64::new(1.0, 0.0)).norm() < 1e-10);
+    assert!(state[[1, 1]].re < 0.9);
+    assert!(state[[0, 0]].re > 0.1);
+}
+
+/// Test the phase of a spectator within the crosstalk range on the emitted circuit and the state
+#[test]
+fn test_crosstalk_state_within_range() {
+    // Qubits 0 and 1 interact, qubit 2 is at distance 1.0 and qubit 3 at distance 1.05 of qubit 1
+    let mut device = TestDevice {
+        tweezers: 4,
+        two_qubit_edges: &[(0, 1), (1, 0)],
+        ..TestDevice::DEFAULT
+    }
+    .build();
+    for (tweezer, position) in [
+        (0, [0.0, 0.0]),
+        (1, [1.0, 0.0]),
+        (2, [2.0, 0.0]),
+        (3, [1.0, 1.05]),
+    ] {
+        device
+            .set_tweezer_position(tweezer, &position, None)
+            .unwrap();
+    }
+    let backend = SimulatorBackend::new(device, None)
+        .with_crosstalk_model(CrosstalkModel::new(0.4).with_range(1.0));
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(1, PI.into());
+    circuit += RotateX::new(2, FRAC_PI_2.into());
+    circuit += RotateX::new(3, FRAC_PI_2.into());
+    circuit += PhaseShiftedControlledZ::new(0, 1, 0.0.into());
+
+    // Only the spectator within the range acquires the phase
+    let mut expected = circuit.clone();
+    expected += ControlledPhaseShift::new(1, 2, 0.4.into());
+    assert_eq!(backend.noisy_circuit(&circuit).unwrap(), expected);
+
+    // Qubit 1 is in state -i|1>, the |1> amplitude of qubit 2 is rotated by the phase 0.4
+    let snapshot = backend
+        .simulate_state(&circuit, false, StateBasis::Qubits)
+        .unwrap();
+    let SimulatedState::StateVector(state) = snapshot.state else {
+        panic!("Expected a state vector");
+    };
+    let minus_i = Complex64::new(0.0, -1.0);
+    let spectator = [
+        Complex64::new(FRAC_1_SQRT_2, 0.0),
+        minus_i * Complex64::from_polar(FRAC_1_SQRT_2, 0.4),
+    ];
+    let outside = [Complex64::new(FRAC_1_SQRT_2, 0.0), minus_i * FRAC_1_SQRT_2];
+    let mut expected = vec![Complex64::new(0.0, 0.0); 16];
+    for (bit2, amplitude2) in spectator.iter().enumerate() {
+        for (bit3, amplitude3) in outside.iter().enumerate() {
+            expected[2 + 4 * bit2 + 8 * bit3] = minus_i * amplitude2 * amplitude3;
+        }
+    }
+    assert_close(state.iter(), expected.iter());
+}
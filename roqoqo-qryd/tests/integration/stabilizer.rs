@@ -0,0 +1,360 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use std::str::FromStr;
+
+use num_complex::Complex64;
+use roqoqo::prelude::*;
+use roqoqo::{operations::*, Circuit};
+use roqoqo_qryd::{
+    AtomLossModel, CrosstalkModel, SimulationMode, SimulatorBackend, SimulatorNoiseModel,
+    TweezerDevice, DEFAULT_LOST_QUBITS_REGISTER, MAX_AUTOMATIC_STATE_VECTOR_QUBITS,
+};
+
+use crate::{line_edges, TestDevice};
+
+const SINGLE_QUBIT_GATES: [&str; 4] = ["RotateX", "RotateZ", "RotateXY", "PhaseShiftState1"];
+const TWO_QUBIT_GATES: [&str; 3] = [
+    "ControlledPauliZ",
+    "PhaseShiftedControlledZ",
+    "ControlledPhaseShift",
+];
+
+/// Returns a device with qubits in a line of tweezers, connected to their neighbours.
+fn line_device(number_qubits: usize, seed: Option<usize>) -> TweezerDevice {
+    TestDevice {
+        seed,
+        tweezers: number_qubits,
+        single_qubit_gates: &SINGLE_QUBIT_GATES,
+        two_qubit_gates: &TWO_QUBIT_GATES,
+        two_qubit_edges: &line_edges(number_qubits),
+        ..TestDevice::DEFAULT
+    }
+    .build()
+}
+
+/// Returns a CNOT gate in the native gates of the device.
+fn native_cnot(control: usize, target: usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += RotateXY::new(target, (-FRAC_PI_2).into(), FRAC_PI_2.into());
+    circuit += ControlledPauliZ::new(control, target);
+    circuit += RotateXY::new(target, FRAC_PI_2.into(), FRAC_PI_2.into());
+    circuit
+}
+
+/// Returns a circuit preparing and measuring a GHZ state on a line of qubits.
+fn ghz_circuit(number_qubits: usize, shots: usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), number_qubits, true);
+    circuit += RotateX::new(0, FRAC_PI_2.into());
+    for qubit in 1..number_qubits {
+        circuit += native_cnot(qubit - 1, qubit);
+    }
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), shots, None);
+    circuit
+}
+
+/// Test parsing the simulation modes
+#[test]
+fn test_simulation_mode_from_str() {
+    assert_eq!(
+        SimulationMode::from_str("state_vector").unwrap(),
+        SimulationMode::StateVector
+    );
+    assert_eq!(
+        SimulationMode::from_str("stabilizer").unwrap(),
+        SimulationMode::Stabilizer
+    );
+    assert_eq!(
+        SimulationMode::from_str("automatic").unwrap(),
+        SimulationMode::Automatic
+    );
+    assert!(SimulationMode::from_str("clifford").is_err());
+    assert_eq!(SimulationMode::default(), SimulationMode::StateVector);
+}
+
+/// Test the stabilizer simulation of a GHZ state on more qubits than a state vector can hold
+#[test]
+fn test_large_ghz_state() {
+    let number_qubits = 100;
+    let backend = SimulatorBackend::new(line_device(number_qubits, None), None)
+        .with_simulation_mode(SimulationMode::Stabilizer);
+    let (bits, _, _) = backend
+        .run_circuit(&ghz_circuit(number_qubits, 200))
+        .unwrap();
+    assert_eq!(bits["ro"].len(), 200);
+    let mut outcomes = HashSet::new();
+    for shot in bits["ro"].iter() {
+        assert_eq!(shot.len(), number_qubits);
+        assert!(shot.iter().all(|bit| *bit == shot[0]));
+        outcomes.insert(shot[0]);
+    }
+    assert_eq!(outcomes.len(), 2);
+}
+
+/// Test deterministic outcomes, mid-circuit measurements, resets and output registers
+#[test]
+fn test_stabilizer_circuit() {
+    let backend = SimulatorBackend::new(line_device(3, None), None)
+        .with_simulation_mode(SimulationMode::Stabilizer);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 4, true);
+    circuit += DefinitionFloat::new("floats".to_string(), 2, true);
+    circuit += RotateX::new(0, FRAC_PI_2.into());
+    circuit += RotateX::new(0, FRAC_PI_2.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += native_cnot(0, 1);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += PragmaActiveReset::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 2);
+    circuit += RotateX::new(2, FRAC_PI_2.into());
+    circuit += RotateZ::new(2, PI.into());
+    circuit += RotateX::new(2, FRAC_PI_2.into());
+    circuit += MeasureQubit::new(2, "ro".to_string(), 3);
+    circuit += PragmaSetNumberOfMeasurements::new(20, "ro".to_string());
+    let (bits, floats, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"], vec![vec![true, true, false, false]; 20]);
+    assert_eq!(floats["floats"], vec![vec![0.0; 2]; 20]);
+
+    // A qubit mapping of the repeated measurement is followed
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += RotateX::new(0, PI.into());
+    circuit += PragmaRepeatedMeasurement::new(
+        "ro".to_string(),
+        5,
+        Some([(0, 2), (2, 0)].into_iter().collect()),
+    );
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"], vec![vec![false, false, true]; 5]);
+
+    // Only one repeated measurement is allowed
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += PragmaSetNumberOfMeasurements::new(5, "ro".to_string());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 5, None);
+    assert!(backend.run_circuit(&circuit).is_err());
+}
+
+/// Test that the stabilizer simulation samples the outcomes of the state vector simulation
+#[test]
+fn test_random_clifford_circuits() {
+    let number_qubits = 4;
+    let mut state = 12345u64;
+    let mut random = |bound: usize| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) as usize) % bound
+    };
+    for _ in 0..10 {
+        let mut circuit = Circuit::new();
+        circuit += DefinitionBit::new("ro".to_string(), number_qubits, true);
+        for _ in 0..30 {
+            let qubit = random(number_qubits);
+            let neighbour = if qubit == 0 { 1 } else { qubit - 1 };
+            match random(9) {
+                0 => circuit += RotateX::new(qubit, FRAC_PI_2.into()),
+                1 => circuit += RotateX::new(qubit, (-FRAC_PI_2).into()),
+                2 => circuit += RotateZ::new(qubit, FRAC_PI_2.into()),
+                3 => circuit += RotateXY::new(qubit, FRAC_PI_2.into(), PI.into()),
+                4 => circuit += PhaseShiftState1::new(qubit, (-FRAC_PI_2).into()),
+                5 => circuit += ControlledPauliZ::new(qubit, neighbour),
+                6 => circuit += PhaseShiftedControlledZ::new(qubit, neighbour, FRAC_PI_2.into()),
+                7 => circuit += ControlledPhaseShift::new(qubit, neighbour, PI.into()),
+                _ => circuit += native_cnot(qubit, neighbour),
+            }
+        }
+        circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 1000, None);
+
+        let backend = SimulatorBackend::new(line_device(number_qubits, Some(3)), None);
+        let outcomes = |mode: SimulationMode| -> HashSet<Vec<bool>> {
+            let (bits, _, _) = backend
+                .clone()
+                .with_simulation_mode(mode)
+                .run_circuit(&circuit)
+                .unwrap();
+            assert_eq!(bits["ro"].len(), 1000);
+            bits["ro"].iter().cloned().collect()
+        };
+        assert_eq!(
+            outcomes(SimulationMode::Stabilizer),
+            outcomes(SimulationMode::StateVector),
+            "{:?}",
+            circuit
+        );
+    }
+}
+
+/// Test non-Clifford gates failing in the stabilizer mode and falling back in the automatic mode
+#[test]
+fn test_non_clifford_circuit() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, FRAC_PI_2.into());
+    circuit += PhaseShiftState1::new(1, FRAC_PI_4.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let backend = SimulatorBackend::new(line_device(2, None), None);
+
+    let error = backend
+        .clone()
+        .with_simulation_mode(SimulationMode::Stabilizer)
+        .run_circuit(&circuit)
+        .unwrap_err();
+    assert!(format!("{}", error).contains("PhaseShiftState1 operation at position 2"));
+
+    let (bits, _, _) = backend
+        .clone()
+        .with_simulation_mode(SimulationMode::Automatic)
+        .run_circuit(&circuit)
+        .unwrap();
+    assert_eq!(bits["ro"].len(), 10);
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionComplex::new("state".to_string(), 4, true);
+    circuit += PragmaGetStateVector::new("state".to_string(), None);
+    assert!(backend
+        .clone()
+        .with_simulation_mode(SimulationMode::Stabilizer)
+        .run_circuit(&circuit)
+        .is_err());
+    assert!(backend
+        .with_simulation_mode(SimulationMode::Automatic)
+        .run_circuit(&circuit)
+        .is_ok());
+
+    // Above the state vector limit, the automatic mode reports the stabilizer error
+    let number_qubits = MAX_AUTOMATIC_STATE_VECTOR_QUBITS + 1;
+    let mut circuit = ghz_circuit(number_qubits, 10);
+    circuit += PhaseShiftState1::new(0, FRAC_PI_4.into());
+    let error = SimulatorBackend::new(line_device(number_qubits, None), None)
+        .with_simulation_mode(SimulationMode::Automatic)
+        .run_circuit(&circuit)
+        .unwrap_err();
+    assert!(format!("{}", error).contains("exceed the 30 qubits"));
+    assert!(format!("{}", error).contains("PhaseShiftState1 operation"));
+}
+
+/// Test operations writing float and complex registers failing in the stabilizer mode
+#[test]
+fn test_stabilizer_float_and_complex_registers() {
+    let backend = SimulatorBackend::new(line_device(2, None), None);
+    let stabilizer = backend
+        .clone()
+        .with_simulation_mode(SimulationMode::Stabilizer);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("probabilities".to_string(), 2, true);
+    circuit += RotateX::new(0, PI.into());
+    circuit += PragmaGetOccupationProbability::new("probabilities".to_string(), None);
+    let error = stabilizer.run_circuit(&circuit).unwrap_err();
+    assert!(format!("{}", error).contains("PragmaGetOccupationProbability operation at position 2"));
+    assert!(format!("{}", error).contains("float and complex registers are not computed"));
+    // The automatic mode falls back to the state vector simulation
+    assert_eq!(
+        backend
+            .clone()
+            .with_simulation_mode(SimulationMode::Automatic)
+            .run_circuit(&circuit)
+            .unwrap(),
+        backend.run_circuit(&circuit).unwrap()
+    );
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionComplex::new("density".to_string(), 16, true);
+    circuit += PragmaGetDensityMatrix::new("density".to_string(), None);
+    assert!(format!("{}", stabilizer.run_circuit(&circuit).unwrap_err())
+        .contains("float and complex registers are not computed"));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("pauli".to_string(), 1, true);
+    circuit +=
+        PragmaGetPauliProduct::new(HashMap::from([(0, 3)]), "pauli".to_string(), Circuit::new());
+    assert!(format!("{}", stabilizer.run_circuit(&circuit).unwrap_err())
+        .contains("float and complex registers are not computed"));
+
+    // Registers that are only defined keep their initial value in every shot
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += DefinitionFloat::new("floats".to_string(), 2, true);
+    circuit += DefinitionComplex::new("complexes".to_string(), 2, true);
+    circuit += RotateX::new(0, PI.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    let (bits, floats, complexes) = stabilizer.run_circuit(&circuit).unwrap();
+    assert_eq!(bits["ro"], vec![vec![true, false]; 3]);
+    assert_eq!(floats["floats"], vec![vec![0.0; 2]; 3]);
+    assert_eq!(
+        complexes["complexes"],
+        vec![vec![Complex64::new(0.0, 0.0); 2]; 3]
+    );
+}
+
+/// Test the device checks, seeds, noise and atom losses in the stabilizer mode
+#[test]
+fn test_stabilizer_device_semantics() {
+    let backend = SimulatorBackend::new(line_device(3, Some(11)), None)
+        .with_simulation_mode(SimulationMode::Stabilizer);
+
+    // Gates between unconnected tweezers are rejected
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += ControlledPauliZ::new(0, 2);
+    let error = backend.run_circuit(&circuit).unwrap_err();
+    assert!(format!("{}", error).contains("The circuit violates the device"));
+
+    // Seeded runs are reproducible
+    let circuit = ghz_circuit(3, 100);
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(backend.run_circuit(&circuit).unwrap().0, bits);
+    assert_ne!(
+        SimulatorBackend::new(line_device(3, Some(12)), None)
+            .with_simulation_mode(SimulationMode::Stabilizer)
+            .run_circuit(&circuit)
+            .unwrap()
+            .0,
+        bits
+    );
+
+    // Noise models are only supported by the state vector simulation
+    assert!(backend
+        .clone()
+        .with_noise_model(SimulatorNoiseModel::new())
+        .run_circuit(&circuit)
+        .is_err());
+    assert!(backend
+        .clone()
+        .with_simulation_mode(SimulationMode::Automatic)
+        .with_noise_model(SimulatorNoiseModel::new())
+        .run_circuit(&circuit)
+        .is_ok());
+
+    // Crosstalk with a strength of pi is a Clifford gate
+    assert!(backend
+        .clone()
+        .with_crosstalk_model(CrosstalkModel::new(PI))
+        .run_circuit(&circuit)
+        .is_ok());
+    assert!(backend
+        .clone()
+        .with_crosstalk_model(CrosstalkModel::new(0.1))
+        .run_circuit(&circuit)
+        .is_err());
+
+    // Atom losses are applied to the measured bits, every qubit is idle during a gate
+    let (bits, _, _) = backend
+        .with_atom_loss_model(AtomLossModel::new().with_idle_loss_rate(100.0))
+        .run_circuit(&circuit)
+        .unwrap();
+    assert_eq!(bits["ro"], vec![vec![false; 3]; 100]);
+    assert_eq!(bits[DEFAULT_LOST_QUBITS_REGISTER], vec![vec![true; 3]; 100]);
+}
@@ -15,12 +15,19 @@ use ndarray::Array2;
 use std::collections::HashMap;
 use std::env;
 
+use roqoqo::operations::{
+    MeasureQubit, OperatePragmaNoise, Operation, PhaseShiftedControlledZ, PragmaGeneralNoise,
+    RotateX,
+};
 use roqoqo::{devices::Device, RoqoqoBackendError};
 use roqoqo_qryd::{
-    phi_theta_relation, PragmaChangeQRydLayout, PragmaShiftQRydQubit, PragmaShiftQubitsTweezers,
-    PragmaSwitchDeviceLayout, TweezerDevice,
+    phi_theta_relation, CutoffInteractionModel, EmulatorDevice, PragmaChangeQRydLayout,
+    PragmaShiftQRydQubit, PragmaShiftQubitsTweezers, PragmaSwitchDeviceLayout,
+    ReadoutConfusionMatrix, TweezerDecoherenceRates, TweezerDevice, TweezerLattice,
 };
 
+use crate::BASELINE_DEVICE_BYTES;
+
 #[cfg(feature = "web-api")]
 use wiremock::matchers::method;
 #[cfg(feature = "web-api")]
@@ -32,9 +39,9 @@ fn test_new() {
     let device = TweezerDevice::new(Some(2), None, None);
 
     assert!(device.current_layout.is_none());
-    assert!(device.qubit_to_tweezer.is_none());
-    assert!(device.layout_register.is_some());
-    assert_eq!(device.layout_register.as_ref().unwrap().len(), 0);
+    assert!(device.qubit_to_tweezer().is_none());
+    assert!(device.layout_register().is_some());
+    assert_eq!(device.layout_register().unwrap().len(), 0);
     assert_eq!(device.seed(), Some(2));
     assert_eq!(device.qrydbackend(), "qryd_tweezer_device");
 
@@ -60,17 +67,9 @@ fn test_layouts() {
 
     assert!(device.add_layout("Test").is_err());
 
-    assert_eq!(device.layout_register.as_ref().unwrap().len(), 2);
-    assert!(device
-        .layout_register
-        .as_ref()
-        .unwrap()
-        .contains_key("default"));
-    assert!(device
-        .layout_register
-        .as_ref()
-        .unwrap()
-        .contains_key("Test"));
+    assert_eq!(device.layout_register().unwrap().len(), 2);
+    assert!(device.layout_register().unwrap().contains_key("default"));
+    assert!(device.layout_register().unwrap().contains_key("Test"));
 
     device
         .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.23, None)
@@ -119,18 +118,8 @@ fn test_layouts() {
     //     )
     //     .unwrap();
 
-    let default_layout = device
-        .layout_register
-        .as_ref()
-        .unwrap()
-        .get("default")
-        .unwrap();
-    let test_layout = device
-        .layout_register
-        .as_ref()
-        .unwrap()
-        .get("Test")
-        .unwrap();
+    let default_layout = device.layout_register().unwrap().get("default").unwrap();
+    let test_layout = device.layout_register().unwrap().get("Test").unwrap();
     assert!(default_layout
         .tweezer_single_qubit_gate_times
         .contains_key("RotateX"));
@@ -228,12 +217,12 @@ fn test_layouts() {
     // );
 
     assert_eq!(device.current_layout, Some("default".to_string()));
-    assert!(device.qubit_to_tweezer.is_none());
+    assert!(device.qubit_to_tweezer().is_none());
 
     device.switch_layout("Test", None).unwrap();
     assert_eq!(device.current_layout, Some("Test".to_string()));
-    assert!(device.qubit_to_tweezer.is_some());
-    assert_eq!(device.qubit_to_tweezer.clone().unwrap().len(), 3);
+    assert!(device.qubit_to_tweezer().is_some());
+    assert_eq!(device.qubit_to_tweezer().unwrap().len(), 3);
 
     assert!(device.switch_layout("Error", None).is_err());
 
@@ -260,15 +249,15 @@ fn test_layouts() {
         .switch_layout("test_trivial_population", Some(false))
         .unwrap();
 
-    assert!(device.qubit_to_tweezer.is_none());
+    assert!(device.qubit_to_tweezer().is_none());
 
     device
         .switch_layout("test_trivial_population", Some(true))
         .unwrap();
 
     assert_eq!(
-        device.qubit_to_tweezer.unwrap(),
-        HashMap::from([(0, 0), (1, 1), (2, 2)])
+        device.qubit_to_tweezer().unwrap().as_map(),
+        &HashMap::from([(0, 0), (1, 1), (2, 2)])
     );
 }
 
@@ -333,8 +322,7 @@ fn test_allowed_tweezer_shifts_from_rows() {
         .is_ok());
 
     let saved_shifts = &device
-        .layout_register
-        .as_ref()
+        .layout_register()
         .unwrap()
         .get("triangle")
         .unwrap()
@@ -367,8 +355,7 @@ fn test_allowed_tweezer_shifts_from_rows() {
     assert!(adding_to_already_present_key.is_ok());
 
     let saved_shifts = &device
-        .layout_register
-        .as_ref()
+        .layout_register()
         .unwrap()
         .get("triangle")
         .unwrap()
@@ -416,8 +403,7 @@ fn test_allowed_tweezer_shifts() {
         .is_ok());
 
     let saved_shifts = &device
-        .layout_register
-        .as_ref()
+        .layout_register()
         .unwrap()
         .get("OtherLayout")
         .unwrap()
@@ -464,8 +450,7 @@ fn test_allowed_tweezer_shifts() {
         .is_ok());
 
     let saved_shifts = &device
-        .layout_register
-        .as_ref()
+        .layout_register()
         .unwrap()
         .get("OtherLayout")
         .unwrap()
@@ -616,6 +601,485 @@ fn test_number_tweezer_positions() {
     assert_eq!(device.number_tweezer_positions(None), Ok(6));
 }
 
+/// Test TweezerDevice set_tweezer_position() and get_tweezer_position() methods
+#[test]
+fn test_tweezer_positions() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.add_layout("other").unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.23, Some("default".to_string()))
+        .unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 1, 0.23, Some("default".to_string()))
+        .unwrap();
+
+    assert!(device.set_tweezer_position(0, &[0.0, 0.0], None).is_err());
+    device.switch_layout("default", None).unwrap();
+
+    assert!(device.get_tweezer_positions(None).is_err());
+    device.set_tweezer_position(0, &[1.0, 2.0], None).unwrap();
+    device
+        .set_tweezer_position(1, &[2.0, 1.0], Some("default".to_string()))
+        .unwrap();
+    assert_eq!(device.get_tweezer_position(0, None), Ok((1.0, 2.0, 0.0)));
+    assert_eq!(
+        device.get_tweezer_position(1, Some("default".to_string())),
+        Ok((2.0, 1.0, 0.0))
+    );
+    assert_eq!(
+        device
+            .get_tweezer_positions(None)
+            .unwrap()
+            .number_dimensions,
+        2
+    );
+    // The number of coordinates is fixed by the first position set in a Layout
+    assert!(device
+        .set_tweezer_position(1, &[1.0, 2.0, 3.0], None)
+        .is_err());
+
+    assert!(device.set_tweezer_position(2, &[0.0, 0.0], None).is_err());
+    assert!(device
+        .set_tweezer_position(0, &[0.0, 0.0], Some("other".to_string()))
+        .is_err());
+    assert!(device
+        .set_tweezer_position(0, &[0.0, 0.0], Some("error".to_string()))
+        .is_err());
+    assert!(device.set_tweezer_position(0, &[0.0], None).is_err());
+    assert!(device
+        .set_tweezer_position(0, &[0.0, 0.0, 0.0, 0.0], None)
+        .is_err());
+    assert!(device
+        .set_tweezer_position(0, &[f64::NAN, 0.0], None)
+        .is_err());
+    assert!(device.get_tweezer_position(2, None).is_err());
+    assert!(device
+        .get_tweezer_position(0, Some("other".to_string()))
+        .is_err());
+    assert!(device
+        .get_tweezer_position(0, Some("error".to_string()))
+        .is_err());
+}
+
+/// Test TweezerDevice distance and nearest neighbour queries
+#[test]
+fn test_tweezer_distances() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..5 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.23, None)
+            .unwrap();
+    }
+    device.set_tweezer_position(0, &[0.0, 0.0], None).unwrap();
+    device.set_tweezer_position(1, &[3.0, 0.0], None).unwrap();
+    device.set_tweezer_position(2, &[0.0, 3.0], None).unwrap();
+    device.set_tweezer_position(3, &[3.0, 4.0], None).unwrap();
+
+    assert_eq!(device.tweezer_distance(0, 3, None), Ok(5.0));
+    assert_eq!(
+        device.tweezer_distance(1, 3, Some("default".to_string())),
+        Ok(4.0)
+    );
+    assert!(device.tweezer_distance(0, 4, None).is_err());
+
+    assert_eq!(
+        device.nearest_tweezers(0, 2, None),
+        Ok(vec![(1, 3.0), (2, 3.0)])
+    );
+    assert_eq!(
+        device.nearest_tweezers(0, 10, None),
+        Ok(vec![(1, 3.0), (2, 3.0), (3, 5.0)])
+    );
+    assert_eq!(device.nearest_tweezers(0, 0, None), Ok(vec![]));
+    assert!(device.nearest_tweezers(4, 1, None).is_err());
+
+    assert_eq!(
+        device.tweezers_within_distance(3, 3.5, None),
+        Ok(vec![(2, 10.0_f64.sqrt())])
+    );
+    assert_eq!(
+        device.tweezers_within_distance(3, 4.0, None),
+        Ok(vec![(2, 10.0_f64.sqrt()), (1, 4.0)])
+    );
+    assert_eq!(device.tweezers_within_distance(0, 1.0, None), Ok(vec![]));
+    assert!(device.tweezers_within_distance(4, 1.0, None).is_err());
+
+    device.add_qubit_tweezer_mapping(0, 3).unwrap();
+    device.add_qubit_tweezer_mapping(1, 0).unwrap();
+    assert_eq!(device.qubit_distance(0, 1), Ok(5.0));
+    assert!(device.qubit_distance(0, 7).is_err());
+}
+
+/// Test TweezerDevice serialization of tweezer positions
+#[test]
+fn test_tweezer_positions_serialization() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.23, None)
+        .unwrap();
+    device
+        .set_tweezer_position(0, &[1.5, 2.5, 0.5], None)
+        .unwrap();
+
+    let serialized = serde_json::to_string(&device).unwrap();
+    let deserialized: TweezerDevice = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, device);
+    let deserialized: TweezerDevice = bincode::deserialize(&serialize(&device).unwrap()).unwrap();
+    assert_eq!(deserialized, device);
+
+    assert_eq!(
+        TweezerDevice::from_bincode(&serialize(&device).unwrap()),
+        Ok(device.clone())
+    );
+
+    // Device definitions without positions stay readable
+    let mut value: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+    value
+        .as_object_mut()
+        .unwrap()
+        .remove("tweezer_positions")
+        .unwrap();
+    let deserialized: TweezerDevice = serde_json::from_value(value).unwrap();
+    assert!(deserialized.get_tweezer_position(0, None).is_err());
+    assert_eq!(deserialized.number_tweezer_positions(None), Ok(1));
+    assert!(TweezerDevice::from_bincode(&[0, 1, 2]).is_err());
+}
+
+/// Test deserializing TweezerDevices serialized by the released version without tweezer positions
+#[test]
+fn test_from_bincode_without_positions() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.23, None)
+        .unwrap();
+    device.add_qubit_tweezer_mapping(0, 0).unwrap();
+
+    assert_eq!(
+        bincode::deserialize::<TweezerDevice>(BASELINE_DEVICE_BYTES).unwrap(),
+        device
+    );
+    assert_eq!(
+        TweezerDevice::from_bincode(BASELINE_DEVICE_BYTES),
+        Ok(device.clone())
+    );
+    let emulator: EmulatorDevice = bincode::deserialize(BASELINE_DEVICE_BYTES).unwrap();
+    assert_eq!(emulator.internal, device);
+
+    // Without qubit -> tweezer mapping, the Option tag of the mapping is 0
+    let mut bytes = BASELINE_DEVICE_BYTES.to_vec();
+    bytes.drain(0..25);
+    bytes.insert(0, 0);
+    device.set_qubit_to_tweezer(None).unwrap();
+    assert_eq!(
+        bincode::deserialize::<TweezerDevice>(&bytes).unwrap(),
+        device
+    );
+
+    // Unknown format versions are rejected
+    bytes[0] = 3;
+    assert!(bincode::deserialize::<TweezerDevice>(&bytes).is_err());
+}
+
+/// Test TweezerDevice decoherence rates of tweezers and qubits
+#[test]
+fn test_tweezer_decoherence_rates() {
+    let mut device = TweezerDevice::new(None, None, None);
+    for layout in ["default", "other"] {
+        device.add_layout(layout).unwrap();
+        for tweezer in 0..2 {
+            device
+                .set_tweezer_single_qubit_gate_time(
+                    "RotateX",
+                    tweezer,
+                    0.23,
+                    Some(layout.to_string()),
+                )
+                .unwrap();
+        }
+    }
+    device.current_layout = Some("default".to_string());
+    let rates = TweezerDecoherenceRates {
+        damping: 0.1,
+        dephasing: 0.2,
+        depolarising: 0.4,
+    };
+    device
+        .set_tweezer_decoherence_rates(1, rates, None)
+        .unwrap();
+    let other_rates = TweezerDecoherenceRates::from_t1_t2(10.0, 4.0).unwrap();
+    assert_eq!(other_rates.damping, 0.1);
+    // The coherences of the simulated noise decay with T2
+    for time in [0.5, 2.0, 7.0] {
+        let superoperator = PragmaGeneralNoise::new(0, time.into(), other_rates.rates_matrix())
+            .superoperator()
+            .unwrap();
+        assert!((superoperator[[1, 1]] - (-time / 4.0_f64).exp()).abs() < 1e-9);
+        assert!((superoperator[[2, 2]] - (-time / 4.0_f64).exp()).abs() < 1e-9);
+    }
+    device
+        .set_tweezer_decoherence_rates(0, other_rates, Some("other".to_string()))
+        .unwrap();
+
+    assert_eq!(device.get_tweezer_decoherence_rates(1, None), Ok(rates));
+    assert_eq!(
+        device.get_tweezer_decoherence_rates(0, None),
+        Ok(TweezerDecoherenceRates::default())
+    );
+    assert_eq!(
+        device.get_tweezer_decoherence_rates(0, Some("other".to_string())),
+        Ok(other_rates)
+    );
+    assert!(device
+        .get_tweezer_decoherence_rates(0, Some("missing".to_string()))
+        .is_err());
+
+    // The rates of a qubit are those of the tweezer it occupies in the current Layout
+    device.add_qubit_tweezer_mapping(0, 1).unwrap();
+    device.add_qubit_tweezer_mapping(1, 0).unwrap();
+    let expected =
+        Array2::from_shape_vec((3, 3), vec![0.3, 0.0, 0.0, 0.0, 0.2, 0.0, 0.0, 0.0, 0.3]).unwrap();
+    let found = device.qubit_decoherence_rates(&0).unwrap();
+    assert!(found
+        .iter()
+        .zip(expected.iter())
+        .all(|(found, expected)| (found - expected).abs() < 1e-12));
+    assert_eq!(
+        device.qubit_decoherence_rates(&1),
+        Some(Array2::zeros((3, 3)))
+    );
+    assert_eq!(
+        device.qubit_decoherence_rates(&5),
+        Some(Array2::zeros((3, 3)))
+    );
+    assert_eq!(
+        device.to_generic_device().qubit_decoherence_rates(&0),
+        device.qubit_decoherence_rates(&0)
+    );
+    device.switch_layout("other", None).unwrap();
+    assert_eq!(
+        device.qubit_decoherence_rates(&0),
+        Some(Array2::zeros((3, 3)))
+    );
+    assert_eq!(
+        device.qubit_decoherence_rates(&1),
+        Some(other_rates.rates_matrix())
+    );
+
+    // The rates are serialized with the Layout and optional in JSON definitions
+    let deserialized: TweezerDevice = bincode::deserialize(&serialize(&device).unwrap()).unwrap();
+    assert_eq!(deserialized, device);
+    let mut value = serde_json::to_value(&device).unwrap();
+    assert_eq!(
+        value["layout_register"]["other"]["tweezer_decoherence_rates"],
+        serde_json::json!([[0, {"damping": 0.1, "dephasing": 0.1, "depolarising": 0.0}]])
+    );
+    for layout in ["default", "other"] {
+        value["layout_register"][layout]
+            .as_object_mut()
+            .unwrap()
+            .remove("tweezer_decoherence_rates");
+    }
+    let deserialized: TweezerDevice = serde_json::from_value(value).unwrap();
+    assert_eq!(
+        deserialized.qubit_decoherence_rates(&1),
+        Some(Array2::zeros((3, 3)))
+    );
+
+    // Errors
+    let negative = TweezerDecoherenceRates {
+        damping: -0.1,
+        ..Default::default()
+    };
+    assert!(device
+        .set_tweezer_decoherence_rates(0, negative, None)
+        .is_err());
+    assert!(device
+        .set_tweezer_decoherence_rates(5, rates, None)
+        .is_err());
+    assert!(device
+        .set_tweezer_decoherence_rates(0, rates, Some("missing".to_string()))
+        .is_err());
+    assert!(TweezerDecoherenceRates::from_t1_t2(1.0, 3.0).is_err());
+    assert!(TweezerDecoherenceRates::from_t1_t2(0.0, 1.0).is_err());
+}
+
+/// Test TweezerDevice gate and readout error rates of tweezers and qubits
+#[test]
+fn test_tweezer_error_rates() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..3 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.23, None)
+            .unwrap();
+    }
+    device
+        .set_tweezer_two_qubit_gate_time("PhaseShiftedControlledZ", 0, 1, 0.34, None)
+        .unwrap();
+    device
+        .set_tweezer_three_qubit_gate_time("ControlledControlledPauliZ", 0, 1, 2, 0.45, None)
+        .unwrap();
+    device
+        .set_tweezer_single_qubit_gate_error("RotateX", 1, 0.01, None)
+        .unwrap();
+    device
+        .set_tweezer_two_qubit_gate_error("PhaseShiftedControlledZ", 0, 1, 0.02, None)
+        .unwrap();
+    device
+        .set_tweezer_three_qubit_gate_error("ControlledControlledPauliZ", 0, 1, 2, 0.03, None)
+        .unwrap();
+    device.set_tweezer_readout_error(1, 0.04, None).unwrap();
+    for (qubit, tweezer) in [(0, 1), (1, 0), (2, 2)] {
+        device.add_qubit_tweezer_mapping(qubit, tweezer).unwrap();
+    }
+
+    // The error rates of the qubits are those of the tweezers they occupy
+    assert_eq!(device.single_qubit_gate_error("RotateX", &0), Some(0.01));
+    assert_eq!(device.single_qubit_gate_error("RotateX", &1), Some(0.0));
+    assert_eq!(device.single_qubit_gate_error("RotateZ", &0), None);
+    assert_eq!(
+        device.two_qubit_gate_error("PhaseShiftedControlledZ", &1, &0),
+        Some(0.02)
+    );
+    assert_eq!(
+        device.two_qubit_gate_error("PhaseShiftedControlledZ", &0, &1),
+        None
+    );
+    assert_eq!(
+        device.three_qubit_gate_error("ControlledControlledPauliZ", &1, &0, &2),
+        Some(0.03)
+    );
+    assert_eq!(device.readout_error(&0), Some(0.04));
+    assert_eq!(device.readout_error(&1), Some(0.0));
+    assert_eq!(device.readout_error(&5), None);
+    let operation: Operation = RotateX::new(0, 0.5.into()).into();
+    assert_eq!(device.gate_error(&operation), Some(0.01));
+    assert_eq!(device.gate_fidelity(&operation), Some(0.99));
+    let operation: Operation = PhaseShiftedControlledZ::new(1, 0, 0.0.into()).into();
+    assert_eq!(device.gate_error(&operation), Some(0.02));
+    let operation: Operation = MeasureQubit::new(0, "ro".to_string(), 0).into();
+    assert_eq!(device.gate_error(&operation), None);
+    // Tweezer 1 has all error rates, tweezer 0 only the two-qubit one
+    assert!((device.tweezer_error_score(1, None).unwrap() - 0.07).abs() < 1e-12);
+    assert_eq!(device.tweezer_error_score(0, None), Ok(0.02));
+    assert_eq!(device.tweezer_error_score(2, None), Ok(0.0));
+    assert!(device
+        .tweezer_error_score(0, Some("missing".to_string()))
+        .is_err());
+
+    // The error rates are serialized with the Layout and optional in JSON definitions
+    let deserialized: TweezerDevice = bincode::deserialize(&serialize(&device).unwrap()).unwrap();
+    assert_eq!(deserialized, device);
+    let mut value = serde_json::to_value(&device).unwrap();
+    assert_eq!(
+        value["layout_register"]["default"]["tweezer_readout_errors"],
+        serde_json::json!([[1, {"zero_to_one": 0.04, "one_to_zero": 0.04}]])
+    );
+    for field in [
+        "tweezer_single_qubit_gate_errors",
+        "tweezer_two_qubit_gate_errors",
+        "tweezer_three_qubit_gate_errors",
+        "tweezer_readout_errors",
+    ] {
+        value["layout_register"]["default"]
+            .as_object_mut()
+            .unwrap()
+            .remove(field);
+    }
+    let deserialized: TweezerDevice = serde_json::from_value(value).unwrap();
+    assert_eq!(
+        deserialized.single_qubit_gate_error("RotateX", &0),
+        Some(0.0)
+    );
+    assert_eq!(deserialized.readout_error(&0), Some(0.0));
+
+    // Errors
+    assert!(device
+        .set_tweezer_single_qubit_gate_error("RotateX", 1, 1.5, None)
+        .is_err());
+    assert!(device
+        .set_tweezer_single_qubit_gate_error("RotateX", 1, -0.1, None)
+        .is_err());
+    assert!(device
+        .set_tweezer_single_qubit_gate_error("RotateZ", 1, 0.01, None)
+        .is_err());
+    assert!(device
+        .set_tweezer_two_qubit_gate_error("PhaseShiftedControlledZ", 1, 0, 0.01, None)
+        .is_err());
+    assert!(device
+        .set_tweezer_three_qubit_gate_error("ControlledControlledPauliZ", 2, 1, 0, 0.01, None)
+        .is_err());
+    assert!(device.set_tweezer_readout_error(5, 0.01, None).is_err());
+    assert!(device
+        .set_tweezer_readout_error(0, 0.01, Some("missing".to_string()))
+        .is_err());
+}
+
+/// Test TweezerDevice readout confusion matrices of tweezers and qubits
+#[test]
+fn test_tweezer_readout_confusion_matrices() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.current_layout = Some("default".to_string());
+    for tweezer in 0..3 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.23, None)
+            .unwrap();
+    }
+    let confusion_matrix = ReadoutConfusionMatrix::new(0.02, 0.06).unwrap();
+    device
+        .set_tweezer_readout_confusion_matrix(1, confusion_matrix, None)
+        .unwrap();
+    device.set_tweezer_readout_error(2, 0.01, None).unwrap();
+    for (qubit, tweezer) in [(0, 1), (1, 0), (2, 2)] {
+        device.add_qubit_tweezer_mapping(qubit, tweezer).unwrap();
+    }
+
+    assert_eq!(device.readout_confusion_matrix(&0), Some(confusion_matrix));
+    assert_eq!(
+        device.readout_confusion_matrix(&1),
+        Some(ReadoutConfusionMatrix::default())
+    );
+    assert_eq!(
+        device.readout_confusion_matrix(&2),
+        Some(ReadoutConfusionMatrix::symmetric(0.01).unwrap())
+    );
+    assert_eq!(device.readout_confusion_matrix(&5), None);
+    assert!((device.readout_error(&0).unwrap() - 0.04).abs() < 1e-12);
+    assert!((device.tweezer_error_score(1, None).unwrap() - 0.04).abs() < 1e-12);
+    assert_eq!(
+        device.readout_confusion_matrices(&[2, 0]),
+        Ok(vec![
+            ReadoutConfusionMatrix::symmetric(0.01).unwrap(),
+            confusion_matrix
+        ])
+    );
+    assert!(device.readout_confusion_matrices(&[0, 5]).is_err());
+
+    // Errors
+    let invalid = ReadoutConfusionMatrix {
+        zero_to_one: 1.5,
+        one_to_zero: 0.0,
+    };
+    assert!(device
+        .set_tweezer_readout_confusion_matrix(0, invalid, None)
+        .is_err());
+    assert!(device
+        .set_tweezer_readout_confusion_matrix(5, confusion_matrix, None)
+        .is_err());
+    assert!(device
+        .set_tweezer_readout_confusion_matrix(0, confusion_matrix, Some("missing".to_string()))
+        .is_err());
+}
+
 /// Test TweezerDevice to_generic_device() method
 #[test]
 fn test_to_generic_device() {
@@ -974,15 +1438,9 @@ fn test_change_device_shift() {
 
     let ok = device.change_device("PragmaShiftQubitsTweezers", &serialize(&pragma_s).unwrap());
     assert!(ok.is_ok());
-    assert_eq!(device.qubit_to_tweezer.as_ref().unwrap().len(), 2);
-    assert_eq!(
-        device.qubit_to_tweezer.as_ref().unwrap().get(&0).unwrap(),
-        &1
-    );
-    assert_eq!(
-        device.qubit_to_tweezer.as_ref().unwrap().get(&2).unwrap(),
-        &3
-    );
+    assert_eq!(device.qubit_to_tweezer().unwrap().len(), 2);
+    assert_eq!(device.qubit_to_tweezer().unwrap().tweezer_of(0).unwrap(), 1);
+    assert_eq!(device.qubit_to_tweezer().unwrap().tweezer_of(2).unwrap(), 3);
 
     device.add_qubit_tweezer_mapping(4, 4).unwrap();
 
@@ -1059,8 +1517,8 @@ fn test_change_device_shift_row() {
 
     assert!(res.is_ok());
     assert_eq!(
-        cloned.qubit_to_tweezer.unwrap(),
-        HashMap::from([(2, 3), (1, 2), (0, 1)])
+        cloned.qubit_to_tweezer().unwrap().as_map(),
+        &HashMap::from([(2, 3), (1, 2), (0, 1)])
     );
 
     device
@@ -1142,6 +1600,116 @@ async fn asnyc_test_from_api() {
     wiremock_server.verify().await;
 }
 
+/// Test TweezerDevice from_api_with_options() with an explicit device cache
+#[tokio::test]
+#[cfg(feature = "web-api")]
+async fn async_test_from_api_with_cache() {
+    use roqoqo_qryd::DownloadOptions;
+
+    let mut returned_device = TweezerDevice::new(None, None, None);
+    returned_device.add_layout("default").unwrap();
+    returned_device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.23, Some("default".to_string()))
+        .unwrap();
+    returned_device.default_layout = Some("default".to_string());
+    let cache_dir =
+        env::temp_dir().join(format!("roqoqo_qryd_from_api_cache_{}", std::process::id()));
+    let options = DownloadOptions::default().with_cache_dir(&cache_dir);
+    options.cache.as_ref().unwrap().clear().unwrap();
+
+    let wiremock_server = MockServer::start().await;
+    let port = wiremock_server.address().port().to_string();
+    let _mock = Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount_as_scoped(&wiremock_server)
+        .await;
+    let options_cloned = options.clone();
+    let port_cloned = port.clone();
+    let response = tokio::task::spawn_blocking(move || {
+        TweezerDevice::from_api_with_options(
+            None,
+            None,
+            Some(port_cloned),
+            None,
+            None,
+            None,
+            &options_cloned,
+        )
+    })
+    .await
+    .unwrap();
+    assert!(response.is_err());
+    drop(_mock);
+
+    let _mock = Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&returned_device))
+        .expect(1)
+        .mount_as_scoped(&wiremock_server)
+        .await;
+    let options_cloned = options.clone();
+    let port_cloned = port.clone();
+    let device = tokio::task::spawn_blocking(move || {
+        TweezerDevice::from_api_with_options(
+            None,
+            None,
+            Some(port_cloned),
+            None,
+            None,
+            None,
+            &options_cloned,
+        )
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert!(!device.is_offline_fallback());
+    drop(_mock);
+
+    // The dev definition is not cached, the production one is the offline fallback
+    let _mock = Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(2)
+        .mount_as_scoped(&wiremock_server)
+        .await;
+    let options_cloned = options.clone();
+    let port_cloned = port.clone();
+    let response = tokio::task::spawn_blocking(move || {
+        TweezerDevice::from_api_with_options(
+            None,
+            None,
+            Some(port_cloned),
+            None,
+            Some(true),
+            None,
+            &options_cloned,
+        )
+    })
+    .await
+    .unwrap();
+    assert!(response.is_err());
+    let options_cloned = options.clone();
+    let fallback = tokio::task::spawn_blocking(move || {
+        TweezerDevice::from_api_with_options(
+            None,
+            None,
+            Some(port),
+            None,
+            None,
+            None,
+            &options_cloned,
+        )
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert!(fallback.is_offline_fallback());
+    assert_eq!(fallback, device);
+    drop(_mock);
+
+    options.cache.unwrap().clear().unwrap();
+}
+
 #[test]
 #[cfg(feature = "web-api")]
 fn test_from_api() {
@@ -1452,3 +2020,104 @@ fn test_render_device() {
     assert!(std::path::Path::new("graph_test.png").exists());
     std::fs::remove_file("graph_test.png").unwrap();
 }
+
+/// Test that the cached Layout indices follow the changes of the device
+#[test]
+fn test_layout_indices_consistency() {
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("default").unwrap();
+    device.add_layout("other").unwrap();
+    device.current_layout = Some("default".to_string());
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 0, 0.1, None)
+        .unwrap();
+    device
+        .set_tweezer_two_qubit_gate_time("PhaseShiftedControlledZ", 0, 1, 0.1, None)
+        .unwrap();
+    device
+        .set_tweezer_single_qubit_gate_time("RotateX", 5, 0.1, Some("other".to_string()))
+        .unwrap();
+
+    assert_eq!(device.number_tweezer_positions(None).unwrap(), 2);
+    assert_eq!(
+        device
+            .number_tweezer_positions(Some("other".to_string()))
+            .unwrap(),
+        1
+    );
+    assert!(device.add_qubit_tweezer_mapping(0, 2).is_err());
+
+    // Setters update the indices of already queried Layouts
+    device
+        .set_tweezer_two_qubit_gate_time("PhaseShiftedControlledZ", 1, 2, 0.1, None)
+        .unwrap();
+    assert_eq!(device.number_tweezer_positions(None).unwrap(), 3);
+    device.add_qubit_tweezer_mapping(0, 0).unwrap();
+    device.add_qubit_tweezer_mapping(1, 1).unwrap();
+    device.add_qubit_tweezer_mapping(2, 2).unwrap();
+    assert_eq!(device.two_qubit_edges(), vec![(0, 1), (1, 2)]);
+
+    // Layout switches use the indices of the new Layout
+    device.switch_layout("other", None).unwrap();
+    assert_eq!(device.number_tweezer_positions(None).unwrap(), 1);
+    assert!(device.two_qubit_edges().is_empty());
+    assert!(device.add_qubit_tweezer_mapping(3, 5).is_ok());
+    assert!(device.add_qubit_tweezer_mapping(3, 0).is_err());
+
+    // Generated Layouts and interaction models update the indices
+    let gate_times = HashMap::from([
+        ("RotateX".to_string(), 0.1),
+        ("PhaseShiftedControlledZ".to_string(), 0.2),
+    ]);
+    let lattice = TweezerLattice::Square {
+        rows: 2,
+        columns: 2,
+        spacing: 1.0,
+    };
+    device
+        .add_lattice_layout("lattice", &lattice, &gate_times, None, true)
+        .unwrap();
+    device.set_qubit_to_tweezer(None).unwrap();
+    device.switch_layout("lattice", None).unwrap();
+    assert_eq!(device.number_tweezer_positions(None).unwrap(), 4);
+    assert_eq!(device.two_qubit_edges().len(), 8);
+    device
+        .apply_interaction_model(&CutoffInteractionModel::new(1.5, 1.0), None)
+        .unwrap();
+    assert_eq!(device.two_qubit_edges().len(), 12);
+
+    // Shifts update the qubit -> tweezer mapping used for the edges
+    device.deactivate_qubit(1).unwrap();
+    let pragma = PragmaShiftQubitsTweezers::new(vec![(0, 1)]);
+    device
+        .change_device("PragmaShiftQubitsTweezers", &serialize(&pragma).unwrap())
+        .unwrap();
+    assert_eq!(device.get_tweezer_from_qubit(&0).unwrap(), 1);
+    assert!(device
+        .change_device("PragmaShiftQubitsTweezers", &serialize(&pragma).unwrap())
+        .is_err());
+    assert_eq!(
+        device.two_qubit_edges(),
+        vec![(0, 2), (0, 3), (2, 0), (2, 3), (3, 0), (3, 2)]
+    );
+
+    // Clones and deserialized devices answer the same queries
+    let deserialized: TweezerDevice =
+        serde_json::from_str(&serde_json::to_string(&device).unwrap()).unwrap();
+    assert_eq!(deserialized, device);
+    assert_eq!(deserialized.two_qubit_edges(), device.two_qubit_edges());
+    assert_eq!(device.clone().two_qubit_edges(), device.two_qubit_edges());
+
+    // Changes through layout_register_mut() update the indices
+    device
+        .layout_register_mut()
+        .unwrap()
+        .get_mut("lattice")
+        .unwrap()
+        .tweezer_single_qubit_gate_times
+        .get_mut("RotateX")
+        .unwrap()
+        .insert(7, 0.1);
+    assert_eq!(device.number_tweezer_positions(None).unwrap(), 5);
+    assert!(device.add_qubit_tweezer_mapping(7, 7).is_ok());
+}
@@ -0,0 +1,316 @@
+// Copyright © 2024 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+
+use roqoqo::devices::Device;
+use roqoqo_qryd::{lattice_layout, TweezerDevice, TweezerLattice, TweezerLayoutInfo};
+
+fn gate_times() -> HashMap<String, f64> {
+    HashMap::from([
+        ("RotateX".to_string(), 0.2),
+        ("RotateZ".to_string(), 0.1),
+        ("PhaseShiftedControlledPhase".to_string(), 0.5),
+        ("ControlledControlledPauliZ".to_string(), 0.8),
+    ])
+}
+
+/// Returns the number of tweezer pairs with a two-qubit gate, checking both orders are set.
+fn number_two_qubit_edges(info: &TweezerLayoutInfo) -> usize {
+    let times = match info
+        .tweezer_two_qubit_gate_times
+        .get("PhaseShiftedControlledPhase")
+    {
+        Some(times) => times,
+        None => return 0,
+    };
+    for &(tweezer0, tweezer1) in times.keys() {
+        assert!(times.contains_key(&(tweezer1, tweezer0)));
+    }
+    times.len() / 2
+}
+
+fn number_three_qubit_trios(info: &TweezerLayoutInfo) -> usize {
+    let times = match info
+        .tweezer_three_qubit_gate_times
+        .get("ControlledControlledPauliZ")
+    {
+        Some(times) => times,
+        None => return 0,
+    };
+    for &(tweezer0, tweezer1, tweezer2) in times.keys() {
+        for trio in [
+            (tweezer0, tweezer2, tweezer1),
+            (tweezer1, tweezer0, tweezer2),
+            (tweezer1, tweezer2, tweezer0),
+            (tweezer2, tweezer0, tweezer1),
+            (tweezer2, tweezer1, tweezer0),
+        ] {
+            assert!(times.contains_key(&trio));
+        }
+    }
+    times.len() / 6
+}
+
+/// Test lattice_layout() for a square lattice
+#[test]
+fn test_square() {
+    let lattice = TweezerLattice::Square {
+        rows: 3,
+        columns: 3,
+        spacing: 4.0,
+    };
+    let info = lattice_layout(&lattice, &gate_times(), None, false).unwrap();
+    assert_eq!(info.tweezers_per_row, Some(vec![3, 3, 3]));
+    assert_eq!(lattice.tweezer_positions().unwrap().positions.len(), 9);
+    assert_eq!(lattice.tweezer_positions().unwrap().number_dimensions, 2);
+    assert_eq!(
+        lattice.tweezer_positions().unwrap().positions[&5],
+        (8.0, 4.0, 0.0)
+    );
+    assert_eq!(info.tweezer_single_qubit_gate_times["RotateX"].len(), 9);
+    assert_eq!(info.tweezer_single_qubit_gate_times["RotateZ"][&8], 0.1);
+    assert_eq!(number_two_qubit_edges(&info), 12);
+    assert_eq!(
+        info.tweezer_two_qubit_gate_times["PhaseShiftedControlledPhase"][&(0, 3)],
+        0.5
+    );
+    assert!(
+        !info.tweezer_two_qubit_gate_times["PhaseShiftedControlledPhase"].contains_key(&(0, 4))
+    );
+    assert_eq!(number_three_qubit_trios(&info), 0);
+    assert!(info.allowed_tweezer_shifts.is_empty());
+
+    // Including the diagonals
+    let info = lattice_layout(&lattice, &gate_times(), Some(4.0 * 2.0_f64.sqrt()), false).unwrap();
+    assert_eq!(number_two_qubit_edges(&info), 20);
+    assert_eq!(number_three_qubit_trios(&info), 16);
+}
+
+/// Test lattice_layout() for a triangular lattice
+#[test]
+fn test_triangular() {
+    let lattice = TweezerLattice::Triangular {
+        rows: 3,
+        columns: 3,
+        spacing: 1.0,
+    };
+    let info = lattice_layout(&lattice, &gate_times(), None, false).unwrap();
+    assert_eq!(info.tweezers_per_row, Some(vec![3, 3, 3]));
+    assert_eq!(lattice.tweezer_positions().unwrap().positions[&3].0, 0.5);
+    assert_eq!(number_two_qubit_edges(&info), 16);
+    assert_eq!(number_three_qubit_trios(&info), 8);
+}
+
+/// Test lattice_layout() for a hexagonal lattice
+#[test]
+fn test_hexagonal() {
+    let lattice = TweezerLattice::Hexagonal {
+        rows: 2,
+        columns: 4,
+        spacing: 1.0,
+    };
+    let info = lattice_layout(&lattice, &gate_times(), None, false).unwrap();
+    assert_eq!(info.tweezers_per_row, Some(vec![4, 4]));
+    assert_eq!(number_two_qubit_edges(&info), 8);
+    assert_eq!(number_three_qubit_trios(&info), 0);
+    let edges = &info.tweezer_two_qubit_gate_times["PhaseShiftedControlledPhase"];
+    assert!(edges.contains_key(&(1, 5)));
+    assert!(edges.contains_key(&(3, 7)));
+    assert!(!edges.contains_key(&(0, 4)));
+    for tweezer in 0..8 {
+        let neighbours: HashSet<usize> = edges
+            .keys()
+            .filter_map(|&(t0, t1)| match (t0 == tweezer, t1 == tweezer) {
+                (true, _) => Some(t1),
+                (_, true) => Some(t0),
+                _ => None,
+            })
+            .collect();
+        assert!(neighbours.len() <= 3);
+    }
+}
+
+/// Test lattice_layout() for a kagome lattice
+#[test]
+fn test_kagome() {
+    let lattice = TweezerLattice::Kagome {
+        rows: 3,
+        columns: 5,
+        spacing: 1.0,
+    };
+    let info = lattice_layout(&lattice, &gate_times(), None, false).unwrap();
+    assert_eq!(info.tweezers_per_row, Some(vec![5, 2, 5]));
+    assert_eq!(lattice.tweezer_positions().unwrap().positions[&5].0, 0.5);
+    assert_eq!(lattice.tweezer_positions().unwrap().positions[&6].0, 2.5);
+    assert_eq!(number_two_qubit_edges(&info), 16);
+    assert_eq!(number_three_qubit_trios(&info), 4);
+
+    let lattice = TweezerLattice::Kagome {
+        rows: 5,
+        columns: 5,
+        spacing: 1.0,
+    };
+    let info = lattice_layout(&lattice, &gate_times(), None, false).unwrap();
+    assert_eq!(info.tweezers_per_row, Some(vec![5, 2, 5, 2, 5]));
+    assert_eq!(lattice.tweezer_positions().unwrap().positions[&12].0, 1.5);
+    assert_eq!(number_two_qubit_edges(&info), 28);
+}
+
+/// Test lattice_layout() for arbitrary tweezer positions
+#[test]
+fn test_arbitrary() {
+    let lattice = TweezerLattice::Arbitrary {
+        rows: vec![
+            vec![(0.0, 0.0, 0.0), (3.0, 0.0, 0.0)],
+            vec![(0.0, 0.0, 5.0)],
+        ],
+    };
+    assert!(lattice_layout(&lattice, &gate_times(), None, false).is_err());
+    let info = lattice_layout(&lattice, &gate_times(), Some(5.0), true).unwrap();
+    assert_eq!(info.tweezers_per_row, Some(vec![2, 1]));
+    assert_eq!(
+        lattice.tweezer_positions().unwrap().positions[&2],
+        (0.0, 0.0, 5.0)
+    );
+    assert_eq!(lattice.tweezer_positions().unwrap().number_dimensions, 3);
+    let edges = &info.tweezer_two_qubit_gate_times["PhaseShiftedControlledPhase"];
+    assert_eq!(number_two_qubit_edges(&info), 2);
+    assert!(edges.contains_key(&(0, 1)));
+    assert!(edges.contains_key(&(2, 0)));
+    assert_eq!(info.allowed_tweezer_shifts[&0], vec![vec![1]]);
+    assert_eq!(info.allowed_tweezer_shifts[&1], vec![vec![0]]);
+    assert!(info.allowed_tweezer_shifts[&2].is_empty());
+
+    let lattice = TweezerLattice::Arbitrary {
+        rows: vec![
+            vec![(0.0, 1.0, 0.0), (-0.0, 5.0, 0.0)],
+            vec![(-0.0, 1.0, 0.0)],
+        ],
+    };
+    assert!(lattice.tweezer_rows().is_err());
+    assert!(lattice_layout(&lattice, &gate_times(), Some(5.0), false).is_err());
+
+    let lattice = TweezerLattice::Arbitrary {
+        rows: vec![vec![(0.0, f64::NAN, 0.0)]],
+    };
+    assert!(lattice_layout(&lattice, &gate_times(), Some(1.0), false).is_err());
+}
+
+/// Test lattice_layout() row shifts
+#[test]
+fn test_row_shifts() {
+    let lattice = TweezerLattice::Square {
+        rows: 2,
+        columns: 3,
+        spacing: 1.0,
+    };
+    let info = lattice_layout(&lattice, &gate_times(), None, true).unwrap();
+    assert_eq!(info.allowed_tweezer_shifts[&0], vec![vec![1, 2]]);
+    assert_eq!(info.allowed_tweezer_shifts[&1], vec![vec![0], vec![2]]);
+    assert_eq!(info.allowed_tweezer_shifts[&5], vec![vec![4, 3]]);
+
+    let mut device = TweezerDevice::new(None, None, None);
+    device.add_layout("manual").unwrap();
+    for tweezer in 0..6 {
+        device
+            .set_tweezer_single_qubit_gate_time("RotateX", tweezer, 0.2, Some("manual".into()))
+            .unwrap();
+    }
+    device
+        .set_allowed_tweezer_shifts_from_rows(&[&[0, 1, 2], &[3, 4, 5]], Some("manual".into()))
+        .unwrap();
+    assert_eq!(
+        device.layout_register().unwrap()["manual"].allowed_tweezer_shifts,
+        info.allowed_tweezer_shifts
+    );
+}
+
+/// Test lattice_layout() errors
+#[test]
+fn test_errors() {
+    let lattice = TweezerLattice::Square {
+        rows: 2,
+        columns: 2,
+        spacing: 1.0,
+    };
+    let mut unsupported = gate_times();
+    unsupported.insert("Hadamard".to_string(), 0.1);
+    assert!(lattice_layout(&lattice, &unsupported, None, false).is_err());
+    assert!(lattice_layout(&lattice, &gate_times(), Some(-1.0), false).is_err());
+    assert!(lattice_layout(
+        &TweezerLattice::Square {
+            rows: 2,
+            columns: 2,
+            spacing: 0.0,
+        },
+        &gate_times(),
+        None,
+        false
+    )
+    .is_err());
+}
+
+/// Test TweezerDevice add_lattice_layout()
+#[test]
+fn test_add_lattice_layout() {
+    let lattice = TweezerLattice::Triangular {
+        rows: 2,
+        columns: 2,
+        spacing: 5.0,
+    };
+    let mut device = TweezerDevice::new(None, None, None);
+    device
+        .add_lattice_layout("triangular", &lattice, &gate_times(), None, true)
+        .unwrap();
+    assert!(device
+        .add_lattice_layout("triangular", &lattice, &gate_times(), None, true)
+        .is_err());
+    assert!(device
+        .add_lattice_layout(
+            "other",
+            &lattice,
+            &HashMap::from([("Hadamard".to_string(), 0.1)]),
+            None,
+            true
+        )
+        .is_err());
+    assert!(!device.layout_register().unwrap().contains_key("other"));
+
+    device.switch_layout("triangular", None).unwrap();
+    assert_eq!(device.number_qubits(), 4);
+    assert_eq!(device.number_tweezer_positions(None), Ok(4));
+    assert_eq!(device.tweezer_distance(0, 2, None), Ok(5.0));
+    assert_eq!(device.single_qubit_gate_time("RotateX", &3), Some(0.2));
+    assert_eq!(
+        device.two_qubit_gate_time("PhaseShiftedControlledPhase", &1, &2),
+        Some(0.5)
+    );
+    assert_eq!(
+        device.three_qubit_gate_time("ControlledControlledPauliZ", &0, &1, &2),
+        Some(0.8)
+    );
+    assert_eq!(
+        device.three_qubit_gate_time("ControlledControlledPauliZ", &2, &1, &0),
+        Some(0.8)
+    );
+    assert_eq!(
+        device.three_qubit_gate_time("ControlledControlledPauliZ", &1, &2, &0),
+        Some(0.8)
+    );
+    assert_eq!(device.two_qubit_edges().len(), 10);
+    assert_eq!(
+        device.two_qubit_gate_time("PhaseShiftedControlledPhase", &2, &1),
+        Some(0.5)
+    );
+    assert!(device.validate().is_valid());
+}